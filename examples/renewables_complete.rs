@@ -72,6 +72,9 @@ async fn main() -> Result<()> {
                 println!("   {:<15} {}", "Date:", forecast.base.date);
                 println!("   {:<15} {}", "Descriptor:", forecast.base.descriptor);
             }
+            models::Renewable::Unknown(_) => {
+                bail!("Unknown renewable energy data type");
+            }
             _ => {
                 bail!("Unexpected renewable energy data type");
             }
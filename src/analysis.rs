@@ -0,0 +1,190 @@
+//! # Analysis
+//!
+//! Helpers for deriving insights from data returned by the API, such as
+//! time-of-day profiles. These operate on already-retrieved data and perform no
+//! network requests, so they are available without the `std` feature.
+//!
+//! All times of day are expressed in NEM time (UTC+10), matching the
+//! convention used by the API for interval dates.
+
+use jiff::{
+    Timestamp,
+    civil::Time,
+    tz::{self, Offset},
+};
+
+use crate::models::ChannelType;
+
+pub mod accuracy;
+pub mod aggregate;
+pub mod billing;
+pub mod budget;
+pub mod changepoint;
+pub mod cheapest;
+pub mod correlation;
+pub mod coverage;
+pub mod demand;
+pub mod earnings;
+pub mod heatmap;
+pub mod margin;
+pub mod reconcile;
+pub mod renewables;
+pub mod resample;
+pub mod summary;
+pub mod timeline;
+pub mod whatif;
+
+/// Number of minutes in a day.
+pub(crate) const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// The fixed UTC offset of NEM time.
+pub(crate) const NEM_OFFSET: Offset = tz::offset(10);
+
+/// Returns the NEM time of day of the given timestamp.
+pub(crate) fn nem_time_of_day(timestamp: Timestamp) -> Time {
+    NEM_OFFSET.to_datetime(timestamp).time()
+}
+
+/// Stable ordering of channel types, matching the order used by the API.
+///
+/// Unknown channel types are placed last, ordered by name.
+pub(crate) fn channel_order(channel_type: &ChannelType) -> (u8, &str) {
+    match channel_type {
+        ChannelType::General => (0, ""),
+        ChannelType::ControlledLoad => (1, ""),
+        ChannelType::FeedIn => (2, ""),
+        ChannelType::Other(other) => (3, other.as_str()),
+    }
+}
+
+/// Cost (c) of the energy of a usage record at the given price.
+///
+/// Feed-in prices are negative when exports are paid, so exports are costed
+/// by their magnitude, making credits negative regardless of the sign of the
+/// energy.
+#[expect(clippy::float_arithmetic, reason = "Computing costs")]
+pub(crate) fn energy_cost(channel_type: &ChannelType, kwh: f64, per_kwh: f64) -> f64 {
+    if *channel_type == ChannelType::FeedIn {
+        kwh.abs() * per_kwh
+    } else {
+        kwh * per_kwh
+    }
+}
+
+/// Index of the slot containing the given time of day.
+pub(crate) fn slot_index(time: Time, slot_minutes: u32) -> Option<usize> {
+    let hour = u32::try_from(time.hour()).ok()?;
+    let minute = u32::try_from(time.minute()).ok()?;
+    let minute_of_day = hour.saturating_mul(60).saturating_add(minute);
+    usize::try_from(minute_of_day.checked_div(slot_minutes)?).ok()
+}
+
+/// Start time of the slot with the given index.
+pub(crate) fn slot_start(index: u32, slot_minutes: u32) -> Time {
+    let minute_of_day = index.saturating_mul(slot_minutes);
+    let hour = minute_of_day.checked_div(60).unwrap_or(0);
+    let minute = minute_of_day.checked_rem(60).unwrap_or(0);
+    Time::new(
+        i8::try_from(hour).unwrap_or(0),
+        i8::try_from(minute).unwrap_or(0),
+        0,
+        0,
+    )
+    .unwrap_or(Time::midnight())
+}
+
+/// A running total of cents.
+///
+/// With the `fixed-point` feature, amounts are accumulated as
+/// [`PriceCents`](crate::money::PriceCents), so that summing thousands of
+/// amounts does not drift, and the total is only converted to `f64` when read.
+/// Amounts which cannot be represented (such as NaN) are then counted as zero,
+/// as in [`total_cost`](crate::money::total_cost). Otherwise, amounts are added
+/// as `f64`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct CentsTotal(
+    #[cfg(feature = "fixed-point")] crate::money::PriceCents,
+    #[cfg(not(feature = "fixed-point"))] f64,
+);
+
+impl CentsTotal {
+    /// Add an amount of cents to the total.
+    #[cfg(feature = "fixed-point")]
+    pub(crate) fn add(&mut self, cents: f64) {
+        self.0 += crate::money::PriceCents::from_cents(cents).unwrap_or_default();
+    }
+
+    /// Add an amount of cents to the total.
+    #[cfg(not(feature = "fixed-point"))]
+    #[expect(clippy::float_arithmetic, reason = "Accumulating costs")]
+    pub(crate) fn add(&mut self, cents: f64) {
+        self.0 += cents;
+    }
+
+    /// Returns the total in cents.
+    #[cfg(feature = "fixed-point")]
+    pub(crate) fn cents(self) -> f64 {
+        self.0.to_cents()
+    }
+
+    /// Returns the total in cents.
+    #[cfg(not(feature = "fixed-point"))]
+    pub(crate) const fn cents(self) -> f64 {
+        self.0
+    }
+}
+
+impl core::iter::Sum for CentsTotal {
+    #[cfg_attr(
+        not(feature = "fixed-point"),
+        expect(clippy::float_arithmetic, reason = "Accumulating costs")
+    )]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |total, amount| Self(total.0 + amount.0))
+    }
+}
+
+/// Round to the nearest integer, with halves rounded away from zero.
+///
+/// `f64::round` is not available without `std`. Adding and subtracting 2^52
+/// rounds to the nearest integer (with ties to even), as no fraction can be
+/// represented at that magnitude; ties are then moved away from zero.
+#[expect(clippy::float_arithmetic, reason = "Rounding")]
+#[expect(clippy::float_cmp, reason = "The fraction is computed exactly")]
+pub(crate) fn round(value: f64) -> f64 {
+    /// Magnitude from which every `f64` is an integer.
+    const INTEGRAL: f64 = 4_503_599_627_370_496.0;
+    let magnitude = value.abs();
+    if magnitude.is_nan() || magnitude >= INTEGRAL {
+        return value;
+    }
+    let mut rounded = (magnitude + INTEGRAL) - INTEGRAL;
+    if magnitude - rounded == 0.5_f64 {
+        rounded += 1.0_f64;
+    }
+    if value < 0.0 { -rounded } else { rounded }
+}
+
+/// Square root of a non-negative number.
+///
+/// `f64::sqrt` is not available without `std`, so this uses Newton's method.
+/// Negative and non-finite inputs return `NaN`, except positive infinity which
+/// is returned as is.
+#[expect(clippy::float_arithmetic, reason = "Newton's method")]
+pub(crate) fn sqrt(value: f64) -> f64 {
+    if value.is_nan() || value < 0.0_f64 {
+        return f64::NAN;
+    }
+    if value == 0.0_f64 || value.is_infinite() {
+        return value;
+    }
+    let mut estimate = value.max(1.0);
+    for _ in 0..1100_u16 {
+        let next = 0.5_f64 * (estimate + value / estimate);
+        if next >= estimate {
+            break;
+        }
+        estimate = next;
+    }
+    estimate
+}
@@ -0,0 +1,310 @@
+//! # Forecast Accuracy
+//!
+//! How far ahead can the forecast be trusted? Answering this requires keeping
+//! the forecasts seen at the time, as [`ForecastSnapshot`]s, and comparing
+//! them with the actual prices published later. [`forecast_errors`] matches
+//! each forecast with the actual interval of the same channel and NEM time,
+//! and [`ForecastAccuracy`] summarises the errors: the mean absolute error,
+//! the bias, and how often the actual price fell within the forecast's
+//! [`AdvancedPrice`](crate::models::AdvancedPrice) band.
+//!
+//! Accuracy usually degrades with lead time, so errors can be grouped by how
+//! far ahead the forecast was made with [`accuracy_by_lead`].
+//!
+//! ```
+//! use core::time::Duration;
+//!
+//! use amber_api::analysis::accuracy::{ForecastAccuracy, accuracy_by_lead, forecast_errors};
+//! use amber_api::history::ForecastSnapshot;
+//! use amber_api::models::Interval;
+//!
+//! # fn example(snapshots: &[ForecastSnapshot], actuals: &[Interval]) {
+//! let errors = forecast_errors(snapshots, actuals);
+//! let overall = ForecastAccuracy::from_errors(&errors);
+//! println!("MAE: {:?} c/kWh", overall.mae);
+//!
+//! for (lead, accuracy) in accuracy_by_lead(&errors, Duration::from_secs(3600)) {
+//!     println!("{}h ahead: {:?} c/kWh", lead.as_secs() / 3600, accuracy.mae);
+//! }
+//! # }
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::time::Duration;
+
+use jiff::Timestamp;
+
+use super::channel_order;
+use crate::{
+    history::ForecastSnapshot,
+    models::{ChannelType, Interval},
+};
+
+/// A forecast price matched with the actual price.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ForecastError {
+    /// Channel of the interval.
+    pub channel_type: ChannelType,
+    /// NEM time of the interval.
+    pub nem_time: Timestamp,
+    /// When the forecast was recorded.
+    pub recorded_at: Timestamp,
+    /// How long before the start of the interval the forecast was recorded.
+    pub lead: Duration,
+    /// Forecast price (c/kWh).
+    ///
+    /// This is the advanced price's prediction when available, and the
+    /// forecast retail price otherwise.
+    pub forecast: f64,
+    /// Lower and upper bounds of the advanced price band (c/kWh), if any.
+    pub band: Option<(f64, f64)>,
+    /// Actual price (c/kWh).
+    pub actual: f64,
+}
+
+impl ForecastError {
+    /// Returns the error of the forecast (c/kWh), positive when the forecast
+    /// was too high.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing the forecast error")]
+    pub fn error(&self) -> f64 {
+        self.forecast - self.actual
+    }
+
+    /// Returns whether the actual price fell within the forecast band, or
+    /// `None` if the forecast had no band.
+    #[must_use]
+    #[inline]
+    pub fn within_band(&self) -> Option<bool> {
+        self.band
+            .map(|(low, high)| (low..=high).contains(&self.actual))
+    }
+}
+
+/// Summary of the errors of a set of forecasts.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ForecastAccuracy {
+    /// Number of forecasts evaluated.
+    pub count: usize,
+    /// Mean absolute error (c/kWh), or `None` without forecasts.
+    pub mae: Option<f64>,
+    /// Mean error (c/kWh), positive when forecasts are too high on average,
+    /// or `None` without forecasts.
+    pub bias: Option<f64>,
+    /// Fraction of the forecasts with a band in which the actual price fell
+    /// within the band, or `None` if no forecast had a band.
+    pub band_coverage: Option<f64>,
+}
+
+impl ForecastAccuracy {
+    /// Summarise the given forecast errors.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Averaging errors")]
+    pub fn from_errors(errors: &[ForecastError]) -> Self {
+        let mut absolute = 0.0_f64;
+        let mut signed = 0.0_f64;
+        let mut banded: u32 = 0;
+        let mut covered: u32 = 0;
+        for error in errors {
+            absolute += error.error().abs();
+            signed += error.error();
+            if let Some(within) = error.within_band() {
+                banded = banded.saturating_add(1);
+                covered = covered.saturating_add(u32::from(within));
+            }
+        }
+        let count = u32::try_from(errors.len()).map_or(f64::MAX, f64::from);
+        Self {
+            count: errors.len(),
+            mae: (!errors.is_empty()).then(|| absolute / count),
+            bias: (!errors.is_empty()).then(|| signed / count),
+            band_coverage: (banded > 0).then(|| f64::from(covered) / f64::from(banded)),
+        }
+    }
+}
+
+/// Match the forecasts of each snapshot with the actual intervals of the same
+/// channel and NEM time.
+///
+/// Forecasts without a matching actual interval are skipped. The errors are
+/// returned in the order of the snapshots.
+#[must_use]
+#[inline]
+pub fn forecast_errors(snapshots: &[ForecastSnapshot], actuals: &[Interval]) -> Vec<ForecastError> {
+    let actual_prices: BTreeMap<((u8, &str), Timestamp), f64> = actuals
+        .iter()
+        .filter_map(Interval::as_actual_interval)
+        .map(|actual| {
+            (
+                (channel_order(&actual.channel_type), actual.nem_time),
+                actual.per_kwh,
+            )
+        })
+        .collect();
+
+    let mut errors = Vec::new();
+    for snapshot in snapshots {
+        for forecast in snapshot.forecasts() {
+            let Some(actual) = actual_prices
+                .get(&(channel_order(&forecast.channel_type), forecast.nem_time))
+                .copied()
+            else {
+                continue;
+            };
+            errors.push(ForecastError {
+                lead: Duration::try_from(forecast.start_time.duration_since(snapshot.recorded_at))
+                    .unwrap_or_default(),
+                forecast: forecast
+                    .advanced_price
+                    .as_ref()
+                    .map_or(forecast.per_kwh, |advanced| advanced.predicted),
+                band: forecast
+                    .advanced_price
+                    .as_ref()
+                    .map(|advanced| (advanced.low, advanced.high)),
+                channel_type: forecast.base.channel_type,
+                nem_time: forecast.base.nem_time,
+                recorded_at: snapshot.recorded_at,
+                actual,
+            });
+        }
+    }
+    errors
+}
+
+/// Summarise forecast errors grouped by lead time.
+///
+/// Each group covers lead times from its key up to (but excluding) the key
+/// plus `bucket`. Groups are returned in order of lead time, and empty groups
+/// are omitted.
+#[must_use]
+#[inline]
+pub fn accuracy_by_lead(
+    errors: &[ForecastError],
+    bucket: Duration,
+) -> Vec<(Duration, ForecastAccuracy)> {
+    let bucket_secs = bucket.as_secs().max(1);
+    let mut groups: BTreeMap<u64, Vec<ForecastError>> = BTreeMap::new();
+    for error in errors {
+        let index = error.lead.as_secs().checked_div(bucket_secs).unwrap_or(0);
+        groups.entry(index).or_default().push(error.clone());
+    }
+    groups
+        .into_iter()
+        .map(|(index, group)| {
+            (
+                Duration::from_secs(index.saturating_mul(bucket_secs)),
+                ForecastAccuracy::from_errors(&group),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::SiteId;
+
+    /// Parse an interval of the general channel at the given time.
+    fn interval(kind: &str, time: &str, per_kwh: f64, band: Option<(f64, f64)>) -> Interval {
+        let advanced_price = band.map(
+            |(low, high)| serde_json::json!({ "low": low, "predicted": per_kwh, "high": high }),
+        );
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": kind,
+            "perKwh": per_kwh,
+            "nemTime": time,
+            "startTime": time,
+            "endTime": time,
+            "advancedPrice": advanced_price
+        }))
+    }
+
+    /// Create a snapshot of the given intervals.
+    fn snapshot(recorded_at: &str, intervals: &[Interval]) -> ForecastSnapshot {
+        let site_id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+        ForecastSnapshot::new(site_id, recorded_at.parse().expect("valid time"), intervals)
+    }
+
+    #[test]
+    fn evaluates_forecasts() {
+        let snapshots = vec![
+            snapshot(
+                "2021-05-05T01:00:00Z",
+                &[
+                    interval(
+                        "ForecastInterval",
+                        "2021-05-05T02:00:00Z",
+                        30.0,
+                        Some((20.0_f64, 40.0_f64)),
+                    ),
+                    interval("ForecastInterval", "2021-05-05T04:00:00Z", 30.0, None),
+                    // No actual price yet
+                    interval("ForecastInterval", "2021-05-05T09:00:00Z", 30.0, None),
+                ],
+            ),
+            snapshot(
+                "2021-05-05T03:30:00Z",
+                &[interval(
+                    "ForecastInterval",
+                    "2021-05-05T04:00:00Z",
+                    22.0,
+                    Some((21.0_f64, 23.0_f64)),
+                )],
+            ),
+        ];
+        let actuals = vec![
+            interval("ActualInterval", "2021-05-05T02:00:00Z", 25.0, None),
+            interval("ActualInterval", "2021-05-05T04:00:00Z", 20.0, None),
+        ];
+
+        let errors = forecast_errors(&snapshots, &actuals);
+        let summary: Vec<_> = errors
+            .iter()
+            .map(|error| (error.lead.as_secs(), error.error(), error.within_band()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (3600, 5.0_f64, Some(true)),
+                (3 * 3600, 10.0_f64, None),
+                (1800, 2.0_f64, Some(false)),
+            ]
+        );
+
+        let accuracy = ForecastAccuracy::from_errors(&errors);
+        assert_eq!(accuracy.count, 3);
+        assert_eq!(accuracy.band_coverage, Some(0.5_f64));
+        assert!(
+            accuracy
+                .mae
+                .is_some_and(|mae| (5.66_f64..5.67_f64).contains(&mae))
+        );
+
+        let by_lead: Vec<_> = accuracy_by_lead(&errors, Duration::from_hours(1))
+            .into_iter()
+            .map(|(lead, stats)| (lead.as_secs(), stats.count, stats.mae))
+            .collect();
+        assert_eq!(
+            by_lead,
+            vec![
+                (0, 1, Some(2.0_f64)),
+                (3600, 1, Some(5.0_f64)),
+                (3 * 3600, 1, Some(10.0_f64)),
+            ]
+        );
+
+        assert_eq!(
+            ForecastAccuracy::from_errors(&[]),
+            ForecastAccuracy::default()
+        );
+    }
+}
@@ -0,0 +1,347 @@
+//! # Usage Aggregation
+//!
+//! Rolls usage records up into daily, weekly or monthly totals for each
+//! channel: the energy used, what it cost, the average price paid and the
+//! interval with the most energy. Each [`UsageAggregate`] renders as a
+//! one-line report such as
+//! `2021-05-03 to 2021-05-09 general: $3.52 from 12.34 kWh (avg 28.53c/kWh)`.
+//!
+//! ```
+//! use amber_api::analysis::aggregate::{Period, aggregate};
+//! use amber_api::models::Usage;
+//!
+//! # fn example(usage: &[Usage]) {
+//! for month in aggregate(usage, Period::Month) {
+//!     println!("{month}");
+//! }
+//! # }
+//! ```
+//!
+//! Periods are based on the NEM date of each record. Weeks start on Monday.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+use jiff::{Span, Timestamp, civil::Date};
+use serde::{Deserialize, Serialize};
+
+use super::{CentsTotal, channel_order};
+use crate::{
+    models::{ChannelType, PriceView, Usage},
+    precision::{DisplayPrecision, PrecisionPolicy},
+    units::{CentsPerKwh, KilowattHours},
+};
+
+/// Length of the periods usage is aggregated over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Period {
+    /// A single day.
+    Day,
+    /// A week, from Monday to Sunday.
+    Week,
+    /// A calendar month.
+    Month,
+}
+
+impl Period {
+    /// Returns the first day of the period containing the date.
+    #[must_use]
+    #[inline]
+    pub fn start(self, date: Date) -> Date {
+        match self {
+            Self::Day => date,
+            Self::Week => date
+                .checked_sub(Span::new().days(date.weekday().to_monday_zero_offset()))
+                .unwrap_or(date),
+            Self::Month => date.first_of_month(),
+        }
+    }
+
+    /// Returns the last day of the period containing the date.
+    #[must_use]
+    #[inline]
+    pub fn end(self, date: Date) -> Date {
+        match self {
+            Self::Day => date,
+            Self::Week => {
+                let start = self.start(date);
+                start.checked_add(Span::new().days(6)).unwrap_or(start)
+            }
+            Self::Month => date.last_of_month(),
+        }
+    }
+}
+
+/// The interval of an aggregate with the most energy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PeakInterval {
+    /// Start of the interval.
+    pub start_time: Timestamp,
+    /// Energy used during the interval, negative for exports.
+    pub kwh: KilowattHours,
+    /// Cost of the energy (c).
+    pub cost: f64,
+}
+
+/// Usage totals of a channel over a period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct UsageAggregate {
+    /// Length of the period.
+    pub period: Period,
+    /// First day of the period.
+    pub start: Date,
+    /// Last day of the period.
+    pub end: Date,
+    /// Type of the channel.
+    pub channel_type: ChannelType,
+    /// Total energy, negative for exports.
+    pub kwh: KilowattHours,
+    /// Total cost in cents, including GST.
+    ///
+    /// Feed-in credits are negative.
+    pub cost: f64,
+    /// The interval with the most energy (imported or exported).
+    pub peak: Option<PeakInterval>,
+    /// Number of usage records aggregated.
+    pub records: usize,
+}
+
+impl UsageAggregate {
+    /// Create an empty aggregate.
+    fn new(period: Period, date: Date, channel_type: ChannelType) -> Self {
+        Self {
+            period,
+            start: period.start(date),
+            end: period.end(date),
+            channel_type,
+            kwh: KilowattHours::ZERO,
+            cost: 0.0,
+            peak: None,
+            records: 0,
+        }
+    }
+
+    /// Add a usage record to the totals, except for its cost which is
+    /// accumulated separately.
+    fn record(&mut self, usage: &Usage) {
+        let energy = usage.energy();
+        self.kwh += energy;
+        self.records = self.records.saturating_add(1);
+        if self.peak.is_none_or(|peak| energy.abs() > peak.kwh.abs()) {
+            self.peak = Some(PeakInterval {
+                start_time: usage.base.start_time,
+                kwh: energy,
+                cost: usage.cost,
+            });
+        }
+    }
+
+    /// Returns the average price paid, or `None` if no energy was used.
+    #[must_use]
+    #[inline]
+    pub fn average_price(&self) -> Option<CentsPerKwh> {
+        CentsPerKwh::from_cost(self.cost, self.kwh)
+    }
+
+    /// Returns the aggregate with its costs in the given view, such as
+    /// excluding GST.
+    #[must_use]
+    #[inline]
+    pub fn in_view(&self, view: PriceView) -> Self {
+        Self {
+            cost: view.apply(self.cost),
+            peak: self.peak.map(|peak| PeakInterval {
+                cost: view.apply(peak.cost),
+                ..peak
+            }),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the total cost in dollars.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Converting cents to dollars")]
+    pub fn cost_dollars(&self) -> f64 {
+        self.cost / 100.0
+    }
+}
+
+impl DisplayPrecision for UsageAggregate {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)?;
+        } else {
+            write!(f, "{} to {}", self.start, self.end)?;
+        }
+        let dollars = self.cost_dollars();
+        let sign = if dollars.is_sign_negative() { "-" } else { "" };
+        write!(
+            f,
+            " {}: {sign}${} from {}",
+            self.channel_type,
+            policy.cost(dollars.abs()),
+            self.kwh.display_with(policy)
+        )?;
+        if let Some(price) = self.average_price() {
+            write!(f, " (avg {})", price.display_with(policy))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for UsageAggregate {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+/// Aggregate usage records into totals for each period and channel.
+///
+/// The aggregates are returned in chronological order, and by channel within
+/// each period.
+#[must_use]
+#[inline]
+pub fn aggregate(usage: &[Usage], period: Period) -> Vec<UsageAggregate> {
+    /// Start of the period and order of the channel.
+    type Key<'a> = (Date, (u8, &'a str));
+
+    let mut aggregates: BTreeMap<Key<'_>, (UsageAggregate, CentsTotal)> = BTreeMap::new();
+    for record in usage {
+        let channel_type = &record.base.channel_type;
+        let (aggregate, cost) = aggregates
+            .entry((period.start(record.base.date), channel_order(channel_type)))
+            .or_insert_with(|| {
+                (
+                    UsageAggregate::new(period, record.base.date, channel_type.clone()),
+                    CentsTotal::default(),
+                )
+            });
+        aggregate.record(record);
+        cost.add(record.cost);
+    }
+    aggregates
+        .into_values()
+        .map(|(aggregate, cost)| UsageAggregate {
+            cost: cost.cents(),
+            ..aggregate
+        })
+        .collect()
+}
+
+/// Aggregate usage records into daily totals for each channel.
+#[must_use]
+#[inline]
+pub fn daily(usage: &[Usage]) -> Vec<UsageAggregate> {
+    aggregate(usage, Period::Day)
+}
+
+/// Aggregate usage records into weekly totals for each channel.
+#[must_use]
+#[inline]
+pub fn weekly(usage: &[Usage]) -> Vec<UsageAggregate> {
+    aggregate(usage, Period::Week)
+}
+
+/// Aggregate usage records into monthly totals for each channel.
+#[must_use]
+#[inline]
+pub fn monthly(usage: &[Usage]) -> Vec<UsageAggregate> {
+    aggregate(usage, Period::Month)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a usage record.
+    fn usage(day: &str, start: &str, channel_type: &str, kwh: f64, cost: f64) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "date": day,
+            "nemTime": start,
+            "startTime": start,
+            "endTime": start,
+            "channelType": channel_type,
+            "kwh": kwh,
+            "cost": cost
+        }))
+    }
+
+    /// Usage over two weeks of May 2021.
+    fn records() -> Vec<Usage> {
+        vec![
+            usage("2021-05-04", "2021-05-04T02:00:00Z", "general", 10.5, 300.0),
+            usage("2021-05-04", "2021-05-04T02:30:00Z", "general", 1.84, 52.0),
+            usage("2021-05-04", "2021-05-04T02:00:00Z", "feedIn", -5.6, -25.0),
+            usage("2021-05-09", "2021-05-09T02:00:00Z", "general", 2.0, 40.0),
+            usage("2021-05-10", "2021-05-10T02:00:00Z", "general", 4.0, 80.0),
+        ]
+    }
+
+    #[test]
+    fn periods() {
+        let sunday = date(2021, 5, 9);
+        assert_eq!(Period::Day.start(sunday), sunday);
+        assert_eq!(Period::Week.start(sunday), date(2021, 5, 3));
+        assert_eq!(Period::Week.end(sunday), sunday);
+        assert_eq!(Period::Month.start(sunday), date(2021, 5, 1));
+        assert_eq!(Period::Month.end(sunday), date(2021, 5, 31));
+    }
+
+    #[test]
+    fn aggregates_by_period_and_channel() {
+        let records = records();
+
+        let days = daily(&records);
+        assert_eq!(days.len(), 4);
+        insta::assert_snapshot!(
+            days.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"),
+            @"
+        2021-05-04 general: $3.52 from 12.34 kWh (avg 28.53c/kWh)
+        2021-05-04 feed-in: -$0.25 from -5.60 kWh (avg 4.46c/kWh)
+        2021-05-09 general: $0.40 from 2.00 kWh (avg 20.00c/kWh)
+        2021-05-10 general: $0.80 from 4.00 kWh (avg 20.00c/kWh)
+        "
+        );
+
+        let weeks = weekly(&records);
+        let totals: Vec<_> = weeks
+            .iter()
+            .map(|week| (week.start, week.channel_type.clone(), week.records))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![
+                (date(2021, 5, 3), ChannelType::General, 3),
+                (date(2021, 5, 3), ChannelType::FeedIn, 1),
+                (date(2021, 5, 10), ChannelType::General, 1),
+            ]
+        );
+        let peak = weeks.first().and_then(|week| week.peak).expect("a peak");
+        assert_eq!(
+            peak.start_time,
+            "2021-05-04T02:00:00Z".parse().expect("valid time")
+        );
+        assert!((10.49_f64..10.51_f64).contains(&peak.kwh.value()));
+
+        let months = monthly(&records);
+        assert_eq!(months.len(), 2);
+        insta::assert_snapshot!(
+            months.first().expect("a month").to_string(),
+            @"2021-05-01 to 2021-05-31 general: $4.72 from 18.34 kWh (avg 25.74c/kWh)"
+        );
+    }
+}
@@ -0,0 +1,393 @@
+//! # Billing Periods
+//!
+//! Bills rarely follow calendar months: a cycle might run from the 14th of
+//! each month, or every 91 days from the date the account was opened. A
+//! [`BillingPeriod`] describes one such cycle by its first day (the anchor)
+//! and its [`CycleLength`], and can step to the neighbouring cycles or find
+//! the cycle containing a date.
+//!
+//! A [`BillingSummary`] totals the usage of a billing period into what a bill
+//! shows: the cost of imports, the credit for exports, the net cost and a
+//! breakdown per channel. With the `std` feature,
+//! [`Amber::summarise_billing_period()`](crate::Amber::summarise_billing_period)
+//! fetches the usage and summarises it in one call.
+//!
+//! ```
+//! use amber_api::analysis::billing::{BillingPeriod, CycleLength};
+//! use jiff::civil::date;
+//!
+//! // Monthly bills starting on the 14th
+//! let period = BillingPeriod::new(date(2025, 1, 14), CycleLength::Months(1));
+//! let current = period.containing(date(2025, 3, 2));
+//! assert_eq!(current.start(), date(2025, 2, 14));
+//! assert_eq!(current.end(), date(2025, 3, 13));
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use jiff::{Span, civil::Date};
+use serde::{Deserialize, Serialize};
+
+use super::{CentsTotal, channel_order};
+use crate::{
+    models::{ChannelType, Usage},
+    precision::{DisplayPrecision, PrecisionPolicy},
+    units::KilowattHours,
+};
+
+/// Length of a billing cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum CycleLength {
+    /// A fixed number of days.
+    Days(u16),
+    /// A number of calendar months.
+    ///
+    /// Cycles anchored on the 29th to the 31st end early in shorter months,
+    /// and later cycles keep the earlier day.
+    Months(u16),
+}
+
+impl CycleLength {
+    /// Returns the span of a number of cycles.
+    fn span(self, cycles: i32) -> Span {
+        match self {
+            Self::Days(days) => Span::new().days(i64::from(days).saturating_mul(cycles.into())),
+            Self::Months(months) => {
+                Span::new().months(i64::from(months).saturating_mul(cycles.into()))
+            }
+        }
+    }
+}
+
+/// A single billing cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BillingPeriod {
+    /// First day of the cycle.
+    pub anchor: Date,
+    /// Length of the cycle.
+    pub length: CycleLength,
+}
+
+impl BillingPeriod {
+    /// Create the billing period starting on `anchor`.
+    #[must_use]
+    #[inline]
+    pub const fn new(anchor: Date, length: CycleLength) -> Self {
+        Self { anchor, length }
+    }
+
+    /// Returns the first day of the period.
+    #[must_use]
+    #[inline]
+    pub fn start(&self) -> Date {
+        self.anchor
+    }
+
+    /// Returns the last day of the period.
+    #[must_use]
+    #[inline]
+    pub fn end(&self) -> Date {
+        self.next().anchor.yesterday().unwrap_or(self.anchor)
+    }
+
+    /// Returns the number of days in the period.
+    #[must_use]
+    #[inline]
+    pub fn days(&self) -> i32 {
+        self.start()
+            .until(self.next().start())
+            .map_or(0, |span| span.get_days())
+    }
+
+    /// Returns `true` if the date falls within the period.
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, date: Date) -> bool {
+        self.start() <= date && date <= self.end()
+    }
+
+    /// Returns the following billing period.
+    #[must_use]
+    #[inline]
+    pub fn next(&self) -> Self {
+        self.shifted(1)
+    }
+
+    /// Returns the preceding billing period.
+    #[must_use]
+    #[inline]
+    pub fn previous(&self) -> Self {
+        self.shifted(-1)
+    }
+
+    /// Returns the billing period of the same cycle containing the date.
+    #[must_use]
+    #[inline]
+    pub fn containing(&self, date: Date) -> Self {
+        let mut period = *self;
+        while date < period.start() {
+            let previous = period.previous();
+            if previous == period {
+                break;
+            }
+            period = previous;
+        }
+        while date > period.end() {
+            let next = period.next();
+            if next == period {
+                break;
+            }
+            period = next;
+        }
+        period
+    }
+
+    /// Returns the period a number of cycles away.
+    fn shifted(self, cycles: i32) -> Self {
+        Self {
+            anchor: self
+                .anchor
+                .checked_add(self.length.span(cycles))
+                .unwrap_or(self.anchor),
+            length: self.length,
+        }
+    }
+}
+
+impl fmt::Display for BillingPeriod {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} to {}", self.start(), self.end())
+    }
+}
+
+/// Usage totals of a channel over a billing period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ChannelTotals {
+    /// Type of the channel.
+    pub channel_type: ChannelType,
+    /// Total energy, negative for exports.
+    pub kwh: KilowattHours,
+    /// Total cost in cents, including GST, negative for credits.
+    pub cost: f64,
+}
+
+/// What a bill for a billing period shows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BillingSummary {
+    /// The billing period summarised.
+    pub period: BillingPeriod,
+    /// Cost of the energy imported (c), including GST.
+    pub import_cost: f64,
+    /// Credit for the energy exported (c), as a positive number when paid.
+    pub export_credit: f64,
+    /// Totals of each channel, in the order used by the API.
+    pub channels: Vec<ChannelTotals>,
+    /// Number of usage records summarised.
+    pub records: usize,
+}
+
+impl BillingSummary {
+    /// Summarise the usage records falling within a billing period.
+    ///
+    /// Records of other dates are ignored, so a longer usage history can be
+    /// passed directly.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Accumulating totals")]
+    pub fn from_usage(period: BillingPeriod, usage: &[Usage]) -> Self {
+        let mut summary = Self {
+            period,
+            import_cost: 0.0,
+            export_credit: 0.0,
+            channels: Vec::new(),
+            records: 0,
+        };
+        let mut import_cost = CentsTotal::default();
+        let mut export_credit = CentsTotal::default();
+        // Costs of each channel, in the same order as `summary.channels`.
+        let mut channel_costs: Vec<CentsTotal> = Vec::new();
+        for record in usage
+            .iter()
+            .filter(|record| period.contains(record.base.date))
+        {
+            let channel_type = &record.base.channel_type;
+            if *channel_type == ChannelType::FeedIn {
+                export_credit.add(-record.cost);
+            } else {
+                import_cost.add(record.cost);
+            }
+            summary.records = summary.records.saturating_add(1);
+
+            let index = match summary
+                .channels
+                .binary_search_by_key(&channel_order(channel_type), |totals| {
+                    channel_order(&totals.channel_type)
+                }) {
+                Ok(index) => index,
+                Err(index) => {
+                    summary.channels.insert(
+                        index,
+                        ChannelTotals {
+                            channel_type: channel_type.clone(),
+                            kwh: KilowattHours::ZERO,
+                            cost: 0.0,
+                        },
+                    );
+                    channel_costs.insert(index, CentsTotal::default());
+                    index
+                }
+            };
+            if let Some(totals) = summary.channels.get_mut(index) {
+                totals.kwh += record.energy();
+            }
+            if let Some(cost) = channel_costs.get_mut(index) {
+                cost.add(record.cost);
+            }
+        }
+        summary.import_cost = import_cost.cents();
+        summary.export_credit = export_credit.cents();
+        for (totals, cost) in summary.channels.iter_mut().zip(channel_costs) {
+            totals.cost = cost.cents();
+        }
+        summary
+    }
+
+    /// Returns the net cost of the period (c): the import cost less the
+    /// export credit.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Netting costs")]
+    pub fn net_cost(&self) -> f64 {
+        self.import_cost - self.export_credit
+    }
+}
+
+impl DisplayPrecision for BillingSummary {
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Converting cents to dollars")]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        let net = self.net_cost() / 100.0_f64;
+        let sign = if net.is_sign_negative() { "-" } else { "" };
+        write!(
+            f,
+            "{}: {sign}${} (${} imports, ${} export credit)",
+            self.period,
+            policy.cost(net.abs()),
+            policy.cost(self.import_cost / 100.0),
+            policy.cost(self.export_credit / 100.0)
+        )
+    }
+}
+
+impl fmt::Display for BillingSummary {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec};
+
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a usage record.
+    fn usage(day: &str, channel_type: &str, kwh: f64, cost: f64) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "date": day,
+            "channelType": channel_type,
+            "kwh": kwh,
+            "cost": cost
+        }))
+    }
+
+    #[test]
+    fn periods() {
+        let monthly = BillingPeriod::new(date(2025, 1, 14), CycleLength::Months(1));
+        assert_eq!(monthly.end(), date(2025, 2, 13));
+        assert_eq!(monthly.next().start(), date(2025, 2, 14));
+        assert_eq!(monthly.previous().start(), date(2024, 12, 14));
+        assert_eq!(
+            monthly.containing(date(2024, 11, 1)).start(),
+            date(2024, 10, 14)
+        );
+        assert!(monthly.contains(date(2025, 2, 13)));
+        assert!(!monthly.contains(date(2025, 2, 14)));
+        assert_eq!(monthly.days(), 31_i32);
+        assert_eq!(monthly.next().days(), 28_i32);
+
+        let quarterly = BillingPeriod::new(date(2025, 1, 1), CycleLength::Days(91));
+        let current = quarterly.containing(date(2025, 7, 1));
+        assert_eq!(current.start(), date(2025, 4, 2));
+        assert_eq!(current.end(), date(2025, 7, 1));
+        assert_eq!(current.to_string(), "2025-04-02 to 2025-07-01");
+        assert_eq!(current.next().start(), date(2025, 7, 2));
+        assert_eq!(current.next().end(), date(2025, 9, 30));
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "The totals are sums of whole cents")]
+    fn summarises_period() {
+        let period = BillingPeriod::new(date(2021, 5, 1), CycleLength::Days(7));
+        let records = vec![
+            usage("2021-05-01", "general", 10.0, 300.0),
+            usage("2021-05-03", "feedIn", -5.0, -40.0),
+            usage("2021-05-04", "controlledLoad", 4.0, 60.0),
+            usage("2021-05-07", "general", 2.0, 52.0),
+            usage("2021-05-08", "general", 100.0, 1000.0),
+        ];
+        let summary = BillingSummary::from_usage(period, &records);
+        assert_eq!(summary.records, 4);
+        assert_eq!(summary.import_cost, 412.0_f64);
+        assert_eq!(summary.export_credit, 40.0_f64);
+        assert_eq!(summary.net_cost(), 372.0_f64);
+        assert_eq!(
+            summary
+                .channels
+                .iter()
+                .map(|totals| (totals.channel_type.clone(), totals.kwh, totals.cost))
+                .collect::<Vec<_>>(),
+            vec![
+                (ChannelType::General, KilowattHours::new(12.0), 352.0_f64),
+                (
+                    ChannelType::ControlledLoad,
+                    KilowattHours::new(4.0),
+                    60.0_f64
+                ),
+                (ChannelType::FeedIn, KilowattHours::new(-5.0), -40.0_f64),
+            ]
+        );
+        insta::assert_snapshot!(
+            summary.to_string(),
+            @"2021-05-01 to 2021-05-07: $3.72 ($4.12 imports, $0.40 export credit)"
+        );
+    }
+
+    #[cfg(feature = "fixed-point")]
+    #[test]
+    #[expect(clippy::float_cmp, reason = "The sums are exact")]
+    fn sums_costs_exactly() {
+        let period = BillingPeriod::new(date(2021, 5, 1), CycleLength::Days(7));
+        let records = vec![usage("2021-05-01", "general", 0.1, 0.1); 10_000];
+        let summary = BillingSummary::from_usage(period, &records);
+        assert_eq!(summary.import_cost, 1000.0_f64);
+        assert_eq!(
+            summary.channels.first().map(|totals| totals.cost),
+            Some(1000.0_f64)
+        );
+    }
+}
@@ -0,0 +1,412 @@
+//! # Budget Tracking
+//!
+//! A [`BudgetTracker`] accumulates the month-to-date cost of a site from its
+//! usage, projects the month-end total, and reports a [`BudgetEvent`] when the
+//! projection (or the actual cost) goes over a configured budget.
+//!
+//! ## Projection
+//!
+//! The remaining days of the month are assumed to cost the same as the average
+//! of the most recent recorded days (the last 7 by default). When price
+//! forecasts are provided, the days they cover are instead costed at the
+//! trailing average daily consumption multiplied by the average forecast
+//! general price, so an upcoming run of expensive (or cheap) prices is
+//! reflected in the projection.
+//!
+//! All costs are in cents, including GST, matching [`Usage::cost`].
+//!
+//! ## Example
+//!
+//! ```
+//! use amber_api::analysis::budget::{BudgetEvent, BudgetTracker};
+//! use amber_api::models::{Interval, Usage};
+//! use jiff::civil::date;
+//!
+//! # fn example(usage: &[Usage], forecast: &[Interval]) {
+//! // A $150 budget for May
+//! let mut tracker = BudgetTracker::new(date(2021, 5, 1), 15_000.0);
+//! tracker.record(usage);
+//! if let Some(event) = tracker.check(forecast) {
+//!     println!("{event}");
+//! }
+//! # }
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+use jiff::civil::Date;
+
+use super::CentsTotal;
+use crate::{
+    models::{ChannelType, Interval, Usage},
+    units::{CentsPerKwh, KilowattHours},
+};
+
+/// Default number of recent days averaged to project the rest of the month.
+const DEFAULT_TRAILING_DAYS: usize = 7;
+
+/// Number of minutes in a day.
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+/// Totals of a single recorded day.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct DayTotal {
+    /// Net cost of the day (c).
+    cost: CentsTotal,
+    /// Energy imported on the general and controlled load channels.
+    kwh: KilowattHours,
+}
+
+/// Month-end projection of a [`BudgetTracker`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct Projection {
+    /// Cost of the recorded days of the month (c).
+    pub month_to_date: f64,
+    /// Projected cost of the whole month (c).
+    pub projected: f64,
+    /// The configured budget (c).
+    pub budget: f64,
+    /// Number of days of the month after the last recorded day.
+    pub remaining_days: i32,
+}
+
+impl Projection {
+    /// Returns `true` if the cost so far exceeds the budget.
+    #[must_use]
+    #[inline]
+    pub fn is_exceeded(&self) -> bool {
+        self.month_to_date > self.budget
+    }
+
+    /// Returns `true` if the projected month-end cost exceeds the budget.
+    #[must_use]
+    #[inline]
+    pub fn is_projected_over(&self) -> bool {
+        self.projected > self.budget
+    }
+
+    /// Returns the status of the budget.
+    fn status(&self) -> Status {
+        if self.is_exceeded() {
+            Status::Exceeded
+        } else if self.is_projected_over() {
+            Status::ProjectedOver
+        } else {
+            Status::OnTrack
+        }
+    }
+}
+
+/// Budget alert raised by [`BudgetTracker::check`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum BudgetEvent {
+    /// The projected month-end cost has gone over the budget.
+    ProjectedOver(Projection),
+    /// The month-to-date cost has gone over the budget.
+    Exceeded(Projection),
+}
+
+impl BudgetEvent {
+    /// Returns the projection which raised the event.
+    #[must_use]
+    #[inline]
+    pub fn projection(&self) -> &Projection {
+        match self {
+            Self::ProjectedOver(projection) | Self::Exceeded(projection) => projection,
+        }
+    }
+}
+
+impl fmt::Display for BudgetEvent {
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Converting cents to dollars")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ProjectedOver(projection) => write!(
+                f,
+                "Projected month-end cost of ${:.2} exceeds the budget of ${:.2}",
+                projection.projected / 100.0,
+                projection.budget / 100.0
+            ),
+            Self::Exceeded(projection) => write!(
+                f,
+                "Month-to-date cost of ${:.2} exceeds the budget of ${:.2}",
+                projection.month_to_date / 100.0,
+                projection.budget / 100.0
+            ),
+        }
+    }
+}
+
+/// Severity of the budget status, in increasing order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+    /// Neither the projection nor the actual cost exceeds the budget.
+    #[default]
+    OnTrack,
+    /// The projection exceeds the budget.
+    ProjectedOver,
+    /// The actual cost exceeds the budget.
+    Exceeded,
+}
+
+/// Tracks the cost of a month against a budget.
+///
+/// See the [module documentation](self) for how the month-end cost is
+/// projected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetTracker {
+    /// First day of the tracked month.
+    month_start: Date,
+    /// Budget for the month (c).
+    budget: f64,
+    /// Number of recent days averaged for the projection.
+    trailing_days: usize,
+    /// Totals of each recorded day.
+    days: BTreeMap<Date, DayTotal>,
+    /// Status when last checked.
+    status: Status,
+}
+
+impl BudgetTracker {
+    /// Track the month containing `month` against a budget in cents.
+    #[must_use]
+    #[inline]
+    pub fn new(month: Date, budget: f64) -> Self {
+        Self {
+            month_start: month.first_of_month(),
+            budget,
+            trailing_days: DEFAULT_TRAILING_DAYS,
+            days: BTreeMap::new(),
+            status: Status::OnTrack,
+        }
+    }
+
+    /// Returns the tracker with the number of recent days averaged for the
+    /// projection set.
+    ///
+    /// Values below one are treated as one.
+    #[must_use]
+    #[inline]
+    pub fn with_trailing_days(mut self, days: usize) -> Self {
+        self.trailing_days = days.max(1);
+        self
+    }
+
+    /// Record usage.
+    ///
+    /// Records outside the tracked month are ignored. Days are replaced as a
+    /// whole, so usage can be re-recorded as it is revised (for example, when
+    /// estimated data becomes billable) without being double-counted; each
+    /// call should therefore contain complete days.
+    #[inline]
+    pub fn record(&mut self, usage: &[Usage]) {
+        let month_end = self.month_start.last_of_month();
+        let mut totals: BTreeMap<Date, DayTotal> = BTreeMap::new();
+        for record in usage {
+            let date = record.base.date;
+            if date < self.month_start || date > month_end {
+                continue;
+            }
+            let total = totals.entry(date).or_default();
+            total.cost.add(record.cost);
+            if record.base.channel_type != ChannelType::FeedIn && record.kwh > 0.0_f64 {
+                total.kwh += record.energy();
+            }
+        }
+        self.days.extend(totals);
+    }
+
+    /// Returns the cost of the recorded days of the month, in cents.
+    #[must_use]
+    #[inline]
+    pub fn month_to_date(&self) -> f64 {
+        self.days
+            .values()
+            .map(|day| day.cost)
+            .sum::<CentsTotal>()
+            .cents()
+    }
+
+    /// Project the month-end cost, using price forecasts where available.
+    ///
+    /// Only forecast intervals of the general channel are used; pass an
+    /// empty slice to project from the trailing average alone.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Projecting costs")]
+    pub fn projection(&self, forecast: &[Interval]) -> Projection {
+        let month_to_date = self.month_to_date();
+        let last_recorded = self
+            .days
+            .keys()
+            .next_back()
+            .copied()
+            .unwrap_or_else(|| self.month_start.yesterday().unwrap_or(self.month_start));
+        let remaining_days = self
+            .month_start
+            .last_of_month()
+            .since(last_recorded)
+            .map_or(0_i32, |span| span.get_days())
+            .max(0_i32);
+
+        let trailing: Vec<&DayTotal> = self.days.values().rev().take(self.trailing_days).collect();
+        let (cost_per_day, kwh_per_day) = if trailing.is_empty() {
+            (0.0_f64, KilowattHours::ZERO)
+        } else {
+            let count = f64::from(u32::try_from(trailing.len()).unwrap_or(u32::MAX));
+            let cost = trailing
+                .iter()
+                .map(|day| day.cost)
+                .sum::<CentsTotal>()
+                .cents();
+            let kwh: KilowattHours = trailing.iter().map(|day| day.kwh).sum();
+            (cost / count, kwh / count)
+        };
+
+        let remaining = f64::from(remaining_days);
+        let (covered_days, forecast_price) = forecast_coverage(forecast);
+        let forecast_days = covered_days.min(remaining);
+        let projected = month_to_date
+            + kwh_per_day * forecast_days * forecast_price
+            + (remaining - forecast_days) * cost_per_day;
+
+        Projection {
+            month_to_date,
+            projected,
+            budget: self.budget,
+            remaining_days,
+        }
+    }
+
+    /// Check the budget, returning an event if its status has worsened since
+    /// the last check.
+    ///
+    /// An event is only raised once per change: going over the projection
+    /// raises [`BudgetEvent::ProjectedOver`], and later exceeding the budget
+    /// raises [`BudgetEvent::Exceeded`]. If the projection drops back under
+    /// the budget, a later rise raises a new event.
+    #[inline]
+    pub fn check(&mut self, forecast: &[Interval]) -> Option<BudgetEvent> {
+        let projection = self.projection(forecast);
+        let status = projection.status();
+        let previous = core::mem::replace(&mut self.status, status);
+        if status <= previous {
+            return None;
+        }
+        match status {
+            Status::OnTrack => None,
+            Status::ProjectedOver => Some(BudgetEvent::ProjectedOver(projection)),
+            Status::Exceeded => Some(BudgetEvent::Exceeded(projection)),
+        }
+    }
+}
+
+/// Returns the number of days covered by general channel forecasts, and
+/// their average price.
+#[expect(clippy::float_arithmetic, reason = "Averaging forecast prices")]
+fn forecast_coverage(forecast: &[Interval]) -> (f64, CentsPerKwh) {
+    let mut minutes = 0.0_f64;
+    let mut weighted_price = CentsPerKwh::ZERO;
+    for interval in forecast {
+        let Some(forecast_interval) = interval.as_forecast_interval() else {
+            continue;
+        };
+        let base = &forecast_interval.base;
+        if base.channel_type == ChannelType::General {
+            let duration = f64::from(base.duration);
+            minutes += duration;
+            weighted_price += base.price() * duration;
+        }
+    }
+    if minutes > 0.0 {
+        (minutes / MINUTES_PER_DAY, weighted_price / minutes)
+    } else {
+        (0.0, CentsPerKwh::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec::Vec};
+
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a general channel usage record for the given date.
+    fn usage(date: &str, kwh: f64, cost: f64) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "date": date,
+            "kwh": kwh,
+            "cost": cost
+        }))
+    }
+
+    /// Parse a day-long general channel forecast at the given price.
+    fn forecast(per_kwh: f64) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": "ForecastInterval",
+            "duration": 1440,
+            "perKwh": per_kwh,
+            "date": "2021-05-11",
+            "nemTime": "2021-05-11T12:30:00+10:00",
+            "startTime": "2021-05-11T02:00:00Z",
+            "endTime": "2021-05-12T02:00:00Z"
+        }))
+    }
+
+    /// Ten days of usage at 10 kWh and $5 per day.
+    fn ten_days() -> Vec<Usage> {
+        (1..=10)
+            .map(|day| usage(&date(2021, 5, day).to_string(), 10.0, 500.0))
+            .collect()
+    }
+
+    #[test]
+    fn projects_from_trailing_average() {
+        let mut tracker = BudgetTracker::new(date(2021, 5, 17), 20_000.0);
+        tracker.record(&ten_days());
+        tracker.record(&[usage("2021-04-30", 10.0, 500.0)]);
+
+        let projection = tracker.projection(&[]);
+        assert_eq!(projection.remaining_days, 21_i32);
+        assert!(4_999.0_f64 < projection.month_to_date && projection.month_to_date < 5_001.0_f64);
+        // $50 so far, and 21 more days at $5
+        assert!(15_499.0_f64 < projection.projected && projection.projected < 15_501.0_f64);
+        assert!(!projection.is_projected_over());
+
+        // A day forecast at 80c/kWh costs $8 rather than $5
+        let with_forecast = tracker.projection(&[forecast(80.0)]);
+        assert!(15_799.0_f64 < with_forecast.projected && with_forecast.projected < 15_801.0_f64);
+    }
+
+    #[test]
+    fn re_recording_replaces_days() {
+        let mut tracker = BudgetTracker::new(date(2021, 5, 1), 20_000.0);
+        tracker.record(&ten_days());
+        tracker.record(&ten_days());
+        let month_to_date = tracker.month_to_date();
+        assert!(4_999.0_f64 < month_to_date && month_to_date < 5_001.0_f64);
+    }
+
+    #[test]
+    fn events_raised_once() {
+        let mut tracker = BudgetTracker::new(date(2021, 5, 1), 10_000.0);
+        assert_eq!(tracker.check(&[]), None);
+
+        tracker.record(&ten_days());
+        let event = tracker.check(&[]).expect("projected over budget");
+        assert!(matches!(event, BudgetEvent::ProjectedOver(_)));
+        insta::assert_snapshot!(event.to_string(), @"Projected month-end cost of $155.00 exceeds the budget of $100.00");
+        assert_eq!(tracker.check(&[]), None);
+
+        tracker.record(&[usage("2021-05-11", 100.0, 6_000.0)]);
+        let exceeded = tracker.check(&[]).expect("over budget");
+        assert!(matches!(exceeded, BudgetEvent::Exceeded(_)));
+    }
+}
@@ -0,0 +1,325 @@
+//! # Change-Point Detection
+//!
+//! Wholesale prices move every five minutes, but the rest of the retail price
+//! only changes when a network tariff, a plan or a fee changes. Such changes
+//! are easy to miss in a noisy history. A [`ChangePointDetector`] averages the
+//! price of each day and looks for dates where the average shifts and stays
+//! shifted, reporting each as a [`ChangePoint`].
+//!
+//! Looking at the [margin](super::margin) rather than the retail price removes
+//! most of the wholesale noise, making tariff changes stand out.
+//!
+//! ```
+//! use amber_api::analysis::changepoint::{ChangePointDetector, Measure};
+//! use amber_api::models::Interval;
+//!
+//! # fn example(history: &[Interval]) {
+//! let detector = ChangePointDetector::builder()
+//!     .measure(Measure::Margin)
+//!     .min_days(14)
+//!     .min_shift(1.0)
+//!     .build();
+//! for change in detector.detect(history) {
+//!     println!(
+//!         "{} {}: {:.2} -> {:.2}c/kWh",
+//!         change.date, change.channel_type, change.before, change.after
+//!     );
+//! }
+//! # }
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use jiff::civil::Date;
+
+use super::{channel_order, margin::PriceDecomposition};
+use crate::models::{ChannelType, Interval};
+
+/// Channel of a series, with the sum and count of its prices on each day.
+type DailySums<'a> = (&'a ChannelType, BTreeMap<Date, (f64, u32)>);
+
+/// Which part of the price to look for changes in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Measure {
+    /// The retail price.
+    #[default]
+    Retail,
+    /// The retail price less the spot price, made up of network, market and
+    /// retail fees.
+    Margin,
+}
+
+/// A sustained change in the average daily price of a channel.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ChangePoint {
+    /// First day of the new regime.
+    pub date: Date,
+    /// Channel whose price changed.
+    pub channel_type: ChannelType,
+    /// Average price (c/kWh) from the previous change point up to this one.
+    pub before: f64,
+    /// Average price (c/kWh) from this change point up to the next one.
+    pub after: f64,
+}
+
+impl ChangePoint {
+    /// Returns the change in average price (c/kWh).
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing the price change")]
+    pub fn shift(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+/// Finds sustained shifts in the average daily price of historical intervals.
+///
+/// Each channel's history is split recursively at the date which best
+/// separates the daily averages into two regimes (binary segmentation), as
+/// long as both regimes last at least [`min_days`](Self::min_days) and their
+/// averages differ by at least [`min_shift`](Self::min_shift).
+#[derive(Debug, Clone, Copy, PartialEq, bon::Builder)]
+#[non_exhaustive]
+pub struct ChangePointDetector {
+    /// Part of the price to analyse.
+    ///
+    /// Defaults to [`Measure::Retail`].
+    #[builder(default)]
+    pub measure: Measure,
+    /// Minimum number of days with data in each regime.
+    ///
+    /// Defaults to 7.
+    #[builder(default = 7)]
+    pub min_days: usize,
+    /// Minimum difference between the averages of consecutive regimes (c/kWh).
+    ///
+    /// Defaults to 1.
+    #[builder(default = 1.0_f64)]
+    pub min_shift: f64,
+}
+
+impl Default for ChangePointDetector {
+    #[inline]
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Average price of a day.
+#[derive(Debug, Clone, Copy)]
+struct Day {
+    /// The day.
+    date: Date,
+    /// Average price (c/kWh).
+    mean: f64,
+}
+
+impl ChangePointDetector {
+    /// Returns the change points in the given history, by channel and in
+    /// chronological order.
+    ///
+    /// Only actual intervals are considered.
+    #[must_use]
+    #[inline]
+    pub fn detect(&self, intervals: &[Interval]) -> Vec<ChangePoint> {
+        let mut channels: BTreeMap<(u8, &str), DailySums<'_>> = BTreeMap::new();
+        for base in intervals
+            .iter()
+            .filter(|interval| interval.is_actual_interval())
+            .filter_map(Interval::as_base_interval)
+        {
+            let price = PriceDecomposition::of(base);
+            let value = match self.measure {
+                Measure::Retail => price.retail,
+                Measure::Margin => price.margin,
+            };
+            let (_, days) = channels
+                .entry(channel_order(&base.channel_type))
+                .or_insert_with(|| (&base.channel_type, BTreeMap::new()));
+            let (sum, count) = days.entry(base.date).or_insert((0.0_f64, 0));
+            accumulate(sum, value);
+            *count = count.saturating_add(1);
+        }
+
+        let mut changes = Vec::new();
+        for (channel_type, daily) in channels.into_values() {
+            let days: Vec<Day> = daily
+                .into_iter()
+                .map(|(date, (sum, count))| Day {
+                    date,
+                    mean: mean_of(sum, count),
+                })
+                .collect();
+            let mut splits = Vec::new();
+            self.segment(&days, 0, &mut splits);
+            splits.sort_unstable();
+
+            let mut bounds = Vec::with_capacity(splits.len().saturating_add(2));
+            bounds.push(0);
+            bounds.extend(splits);
+            bounds.push(days.len());
+            for window in bounds.windows(3) {
+                if let [start, split, end] = *window
+                    && let Some(date) = days.get(split).map(|day| day.date)
+                {
+                    changes.push(ChangePoint {
+                        date,
+                        channel_type: channel_type.clone(),
+                        before: average(days.get(start..split).unwrap_or_default()),
+                        after: average(days.get(split..end).unwrap_or_default()),
+                    });
+                }
+            }
+        }
+        changes
+    }
+
+    /// Find the change points within a run of days, recording the index of
+    /// the first day of each new regime (offset by `offset`).
+    fn segment(&self, days: &[Day], offset: usize, splits: &mut Vec<usize>) {
+        let min_days = self.min_days.max(1);
+        let Some(last) = days.len().checked_sub(min_days) else {
+            return;
+        };
+        let best = (min_days..=last)
+            .filter_map(|split| {
+                let (before, after) = days.split_at_checked(split)?;
+                Some((split, separation(before, after)))
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+        let Some((split, _)) = best else {
+            return;
+        };
+        let Some((before, after)) = days.split_at_checked(split) else {
+            return;
+        };
+        if !shifted(average(before), average(after), self.min_shift) {
+            return;
+        }
+        splits.push(offset.saturating_add(split));
+        self.segment(before, offset, splits);
+        self.segment(after, offset.saturating_add(split), splits);
+    }
+}
+
+/// Add a value to a running sum.
+#[expect(clippy::float_arithmetic, reason = "Accumulating prices")]
+fn accumulate(sum: &mut f64, value: f64) {
+    *sum += value;
+}
+
+/// Returns the mean of `count` values summing to `sum`.
+#[expect(clippy::float_arithmetic, reason = "Averaging prices")]
+fn mean_of(sum: f64, count: u32) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        sum / f64::from(count)
+    }
+}
+
+/// Returns the average of the daily averages.
+fn average(days: &[Day]) -> f64 {
+    mean_of(
+        days.iter().map(|day| day.mean).sum(),
+        u32::try_from(days.len()).unwrap_or(u32::MAX),
+    )
+}
+
+/// Returns how well a split separates two runs of days: the reduction in the
+/// sum of squared deviations from splitting them.
+#[expect(clippy::float_arithmetic, reason = "Comparing averages")]
+fn separation(before: &[Day], after: &[Day]) -> f64 {
+    let n1 = u32::try_from(before.len()).map_or(f64::MAX, f64::from);
+    let n2 = u32::try_from(after.len()).map_or(f64::MAX, f64::from);
+    let difference = average(before) - average(after);
+    n1 * n2 / (n1 + n2) * difference * difference
+}
+
+/// Returns `true` if two averages differ by at least `min_shift`.
+#[expect(clippy::float_arithmetic, reason = "Comparing averages")]
+fn shifted(before: f64, after: f64, min_shift: f64) -> bool {
+    (after - before).abs() >= min_shift
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use jiff::ToSpan as _;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::{ActualInterval, BaseInterval, PriceDescriptor, SpikeStatus};
+
+    /// Create an actual interval on the given day of May 2025.
+    fn interval(day: i8, channel_type: ChannelType, spot: f64, retail: f64) -> Interval {
+        let date = Date::constant(2025, 5, 1)
+            .checked_add(i32::from(day).saturating_sub(1).days())
+            .expect("valid date");
+        let start_time = date
+            .to_zoned(jiff::tz::TimeZone::UTC)
+            .expect("valid time")
+            .timestamp();
+        Interval::ActualInterval(ActualInterval {
+            base: BaseInterval {
+                duration: 30,
+                spot_per_kwh: spot,
+                per_kwh: retail,
+                date,
+                nem_time: start_time,
+                start_time,
+                end_time: start_time,
+                renewables: 50.0,
+                channel_type,
+                tariff_information: None,
+                spike_status: SpikeStatus::None,
+                descriptor: PriceDescriptor::Neutral,
+            },
+        })
+    }
+
+    #[test]
+    fn detects_sustained_shift() {
+        // Noisy spot prices, with the margin rising by 5c from the 11th
+        let intervals: Vec<_> = (1_i8..=20)
+            .flat_map(|day| {
+                let spot = if day.rem_euclid(2) == 0 {
+                    5.0_f64
+                } else {
+                    15.0_f64
+                };
+                let margin = if day > 10 { 20.0_f64 } else { 15.0_f64 };
+                vec![
+                    interval(day, ChannelType::General, spot, spot + margin),
+                    interval(day, ChannelType::FeedIn, -spot, -spot + 2.0),
+                ]
+            })
+            .collect();
+
+        let detector = ChangePointDetector::builder()
+            .measure(Measure::Margin)
+            .min_days(3)
+            .build();
+        let changes = detector.detect(&intervals);
+        assert_eq!(
+            changes,
+            vec![ChangePoint {
+                date: Date::constant(2025, 5, 11),
+                channel_type: ChannelType::General,
+                before: 15.0,
+                after: 20.0,
+            }]
+        );
+        assert_eq!(changes.first().map(ChangePoint::shift), Some(5.0_f64));
+
+        // Too short a regime is ignored
+        let strict = ChangePointDetector {
+            min_days: 11,
+            ..detector
+        };
+        assert!(strict.detect(&intervals).is_empty());
+    }
+}
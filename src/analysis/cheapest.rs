@@ -0,0 +1,331 @@
+//! # Cheapest Intervals
+//!
+//! Flexible loads which only need to run for a total amount of time, such as a
+//! pool pump or a hot water system, do not need a single contiguous window:
+//! they can run in whichever forecast intervals are cheapest.
+//! [`cheapest_intervals`] selects these intervals.
+//!
+//! Switching a device on and off every five minutes is hard on most hardware,
+//! so [`cheapest_runs`] additionally requires every run of consecutive selected
+//! intervals to be at least a minimum length, finding the cheapest selection
+//! which satisfies it.
+//!
+//! ```
+//! use amber_api::analysis::cheapest::{cheapest_intervals, cheapest_runs};
+//! use amber_api::models::{ChannelType, Interval};
+//!
+//! # fn example(forecast: &[Interval]) {
+//! // The four cheapest intervals, wherever they are
+//! for interval in cheapest_intervals(forecast, 4, &ChannelType::General) {
+//!     println!("Run the pump during {interval}");
+//! }
+//!
+//! // Four intervals, in runs of at least two
+//! if let Some(selected) = cheapest_runs(forecast, 4, &ChannelType::General, 2) {
+//!     println!("Selected {} intervals", selected.len());
+//! }
+//! # }
+//! ```
+
+use alloc::{vec, vec::Vec};
+
+use jiff::Timestamp;
+
+use crate::models::{BaseInterval, ChannelType, Interval};
+
+/// Returns the `n` cheapest current or forecast intervals of a channel, in
+/// chronological order.
+///
+/// Intervals are ranked by their retail price. If the channel has fewer than
+/// `n` such intervals, all of them are returned.
+#[must_use]
+#[inline]
+pub fn cheapest_intervals<'a>(
+    intervals: &'a [Interval],
+    n: usize,
+    channel_type: &ChannelType,
+) -> Vec<&'a Interval> {
+    cheapest_runs(intervals, n, channel_type, 1).unwrap_or_default()
+}
+
+/// Returns the `n` cheapest current or forecast intervals of a channel, in
+/// chronological order, such that every run of consecutive selected
+/// intervals is at least `min_run` intervals long.
+///
+/// Intervals are consecutive if one ends when the next starts. If the channel
+/// has fewer than `n` such intervals, all of them are considered.
+///
+/// Returns `None` if no selection satisfies the minimum run length, such as
+/// when `n` is less than `min_run`. Selecting no intervals always succeeds.
+#[must_use]
+#[inline]
+pub fn cheapest_runs<'a>(
+    intervals: &'a [Interval],
+    n: usize,
+    channel_type: &ChannelType,
+    min_run: usize,
+) -> Option<Vec<&'a Interval>> {
+    let mut slots: Vec<(&BaseInterval, &Interval)> = intervals
+        .iter()
+        .filter(|interval| interval.is_current_interval() || interval.is_forecast_interval())
+        .filter_map(|interval| Some((interval.as_base_interval()?, interval)))
+        .filter(|(base, _)| base.channel_type == *channel_type)
+        .collect();
+    slots.sort_by_key(|(base, _)| base.start_time);
+
+    let selected = Table::solve(
+        &slots
+            .iter()
+            .map(|(base, _)| Slot {
+                start: base.start_time,
+                end: base.end_time,
+                per_kwh: base.per_kwh,
+            })
+            .collect::<Vec<_>>(),
+        n.min(slots.len()),
+        min_run.max(1),
+    )?;
+    Some(
+        selected
+            .into_iter()
+            .filter_map(|index| slots.get(index).map(|(_, interval)| *interval))
+            .collect(),
+    )
+}
+
+/// A candidate interval.
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    /// Start of the interval.
+    start: Timestamp,
+    /// End of the interval.
+    end: Timestamp,
+    /// Retail price of the interval (c/kWh).
+    per_kwh: f64,
+}
+
+/// Dynamic programming table for selecting the cheapest intervals.
+///
+/// The state after deciding on the first `i` slots is the number `k` of slots
+/// selected so far and the length `r` of the run of selected slots ending at
+/// the last slot (with `r = 0` if it was not selected). Run lengths are capped
+/// at the minimum run length, as longer runs are all equally acceptable.
+struct Table {
+    /// Number of slots to select, plus one.
+    counts: usize,
+    /// Number of run lengths tracked, the minimum run length plus one.
+    runs: usize,
+    /// Lowest total price reaching each state.
+    cost: Vec<f64>,
+    /// Run length before the last slot, and whether it was selected, for the
+    /// cheapest path to each state.
+    parent: Vec<Option<(usize, bool)>>,
+}
+
+impl Table {
+    /// Returns the indices of the cheapest selection of `n` slots whose runs
+    /// are at least `min_run` long, in chronological order.
+    #[expect(clippy::float_arithmetic, reason = "summing prices")]
+    fn solve(slots: &[Slot], n: usize, min_run: usize) -> Option<Vec<usize>> {
+        if n == 0 {
+            return Some(Vec::new());
+        }
+        // A run longer than the selection (and so than the slots) can never
+        // be completed
+        if min_run > n || min_run > slots.len() {
+            return None;
+        }
+        let counts = n.checked_add(1)?;
+        let runs = min_run.checked_add(1)?;
+        let size = slots
+            .len()
+            .checked_add(1)?
+            .checked_mul(counts)?
+            .checked_mul(runs)?;
+        let mut table = Self {
+            counts,
+            runs,
+            cost: vec![f64::INFINITY; size],
+            parent: vec![None; size],
+        };
+        table.relax(0, 0, 0, 0.0, None);
+
+        let mut previous: Option<&Slot> = None;
+        for (i, slot) in slots.iter().enumerate() {
+            let contiguous = previous.is_some_and(|last| last.end == slot.start);
+            for k in 0..=n {
+                for r in 0..=min_run {
+                    let cost = table.cost(i, k, r);
+                    if cost.is_infinite() {
+                        continue;
+                    }
+                    // A run can only end once it is long enough
+                    let closed = r == 0 || r == min_run;
+                    if closed {
+                        table.relax(i.saturating_add(1), k, 0, cost, Some((r, false)));
+                    }
+                    let next_run = if contiguous {
+                        r.saturating_add(1).min(min_run)
+                    } else if closed {
+                        1
+                    } else {
+                        continue;
+                    };
+                    if k < n {
+                        table.relax(
+                            i.saturating_add(1),
+                            k.saturating_add(1),
+                            next_run,
+                            cost + slot.per_kwh,
+                            Some((r, true)),
+                        );
+                    }
+                }
+            }
+            previous = Some(slot);
+        }
+
+        let mut r = [0, min_run]
+            .into_iter()
+            .filter(|run| table.cost(slots.len(), n, *run).is_finite())
+            .min_by(|a, b| {
+                table
+                    .cost(slots.len(), n, *a)
+                    .total_cmp(&table.cost(slots.len(), n, *b))
+            })?;
+        let mut k = n;
+        let mut selected = Vec::with_capacity(n);
+        for i in (0..slots.len()).rev() {
+            let (previous_run, chosen) = table.parent(i.saturating_add(1), k, r)?;
+            if chosen {
+                selected.push(i);
+                k = k.saturating_sub(1);
+            }
+            r = previous_run;
+        }
+        selected.reverse();
+        Some(selected)
+    }
+
+    /// Returns the index of a state in the table.
+    fn index(&self, i: usize, k: usize, r: usize) -> usize {
+        i.saturating_mul(self.counts)
+            .saturating_add(k)
+            .saturating_mul(self.runs)
+            .saturating_add(r)
+    }
+
+    /// Returns the lowest total price reaching a state.
+    fn cost(&self, i: usize, k: usize, r: usize) -> f64 {
+        self.cost
+            .get(self.index(i, k, r))
+            .copied()
+            .unwrap_or(f64::INFINITY)
+    }
+
+    /// Returns how the cheapest path reached a state.
+    fn parent(&self, i: usize, k: usize, r: usize) -> Option<(usize, bool)> {
+        self.parent.get(self.index(i, k, r)).copied().flatten()
+    }
+
+    /// Record a path to a state, if it is cheaper than the known ones.
+    fn relax(&mut self, i: usize, k: usize, r: usize, cost: f64, parent: Option<(usize, bool)>) {
+        let index = self.index(i, k, r);
+        if let (Some(best), Some(best_parent)) =
+            (self.cost.get_mut(index), self.parent.get_mut(index))
+            && cost < *best
+        {
+            *best = cost;
+            *best_parent = parent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a general channel forecast interval starting at the given minute
+    /// past 02:00 UTC.
+    fn interval(minute: u8, per_kwh: f64) -> Interval {
+        let start = format!("2021-05-05T02:{minute:02}:00Z");
+        let end = format!("2021-05-05T02:{:02}:00Z", minute.saturating_add(5));
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": "ForecastInterval",
+            "duration": 5,
+            "perKwh": per_kwh,
+            "nemTime": end,
+            "startTime": start,
+            "endTime": end
+        }))
+    }
+
+    /// Returns the prices of the selected intervals.
+    fn prices(selected: &[&Interval]) -> Vec<f64> {
+        selected
+            .iter()
+            .filter_map(|interval| interval.as_base_interval())
+            .map(|base| base.per_kwh)
+            .collect()
+    }
+
+    #[test]
+    fn selects_cheapest_in_order() {
+        let forecast = vec![
+            interval(20, 5.0),
+            interval(0, 10.0),
+            interval(5, 30.0),
+            interval(10, 1.0),
+            interval(15, 40.0),
+        ];
+        assert_eq!(
+            prices(&cheapest_intervals(&forecast, 3, &ChannelType::General)),
+            vec![10.0_f64, 1.0_f64, 5.0_f64]
+        );
+        assert_eq!(
+            cheapest_intervals(&forecast, 10, &ChannelType::General).len(),
+            5
+        );
+        assert!(cheapest_intervals(&forecast, 3, &ChannelType::FeedIn).is_empty());
+    }
+
+    #[test]
+    fn respects_minimum_run_length() {
+        let forecast = vec![
+            interval(0, 10.0),
+            interval(5, 30.0),
+            interval(10, 1.0),
+            interval(15, 40.0),
+            interval(20, 5.0),
+            interval(25, 6.0),
+        ];
+        // The cheapest interval can only be used together with a neighbour
+        let selected = cheapest_runs(&forecast, 4, &ChannelType::General, 2).expect("feasible");
+        assert_eq!(prices(&selected), vec![30.0_f64, 1.0_f64, 5.0_f64, 6.0_f64]);
+
+        assert_eq!(cheapest_runs(&forecast, 1, &ChannelType::General, 2), None);
+        let all = cheapest_runs(&forecast, 6, &ChannelType::General, 3).expect("feasible");
+        assert_eq!(all.len(), 6);
+    }
+
+    #[test]
+    fn rejects_unsatisfiable_run_lengths() {
+        let forecast = vec![interval(0, 10.0), interval(5, 30.0), interval(10, 1.0)];
+        assert_eq!(
+            cheapest_runs(&forecast, 3, &ChannelType::General, usize::MAX),
+            None
+        );
+        assert_eq!(
+            cheapest_runs(&forecast, usize::MAX, &ChannelType::General, 4),
+            None
+        );
+        assert_eq!(
+            cheapest_runs(&forecast, 0, &ChannelType::General, usize::MAX),
+            Some(Vec::new())
+        );
+    }
+}
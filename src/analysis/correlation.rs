@@ -0,0 +1,231 @@
+//! # Renewables and Price Correlation
+//!
+//! Wholesale prices tend to fall when renewables make up a larger share of the
+//! grid, but how strongly depends on the region and season. The
+//! [`RenewablesPriceCorrelation`] quantifies this relationship over a period of
+//! intervals, answering whether waiting for "green power" also saves money.
+
+use alloc::vec::Vec;
+
+use super::sqrt;
+use crate::models::{ChannelType, Interval};
+
+/// Relationship between the renewables percentage and the retail price of a
+/// channel.
+///
+/// # Example
+///
+/// ```
+/// use amber_api::analysis::correlation::RenewablesPriceCorrelation;
+/// use amber_api::models::{ChannelType, Interval};
+///
+/// # fn example(intervals: &[Interval]) {
+/// let correlation = RenewablesPriceCorrelation::new(intervals, &ChannelType::General, 10);
+///
+/// if let Some(coefficient) = correlation.coefficient() {
+///     println!("Correlation: {coefficient:.2}");
+/// }
+/// if let Some(saving) = correlation.green_saving() {
+///     println!("Greenest intervals are {saving:.1} c/kWh cheaper");
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenewablesPriceCorrelation {
+    /// Number of intervals analysed.
+    samples: u32,
+    /// Pearson correlation coefficient.
+    coefficient: Option<f64>,
+    /// Least squares slope of the price against the renewables percentage.
+    slope: Option<f64>,
+    /// Mean price by renewables percentage, in increasing order.
+    bins: Vec<RenewablesBin>,
+}
+
+/// Prices of the intervals within a range of renewables percentages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct RenewablesBin {
+    /// Lowest renewables percentage of the bin (inclusive).
+    pub lower: f64,
+    /// Highest renewables percentage of the bin (exclusive, except for the
+    /// last bin).
+    pub upper: f64,
+    /// Mean retail price (c/kWh), or `None` if no interval fell in this bin.
+    pub mean_price: Option<f64>,
+    /// Number of intervals in the bin.
+    pub samples: u32,
+}
+
+impl RenewablesPriceCorrelation {
+    /// Analyse the intervals of the given channel.
+    ///
+    /// The renewables range from 0% to 100% is divided into `bins` bins of
+    /// equal width (at least one).
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing correlation statistics")]
+    pub fn new(intervals: &[Interval], channel_type: &ChannelType, bins: u8) -> Self {
+        let points: Vec<(f64, f64)> = intervals
+            .iter()
+            .filter_map(Interval::as_base_interval)
+            .filter(|base| base.channel_type == *channel_type)
+            .map(|base| (base.renewables, base.per_kwh))
+            .collect();
+
+        let bin_count = bins.max(1);
+        let width = 100.0_f64 / f64::from(bin_count);
+        let mut sums: Vec<(f64, u32)> =
+            core::iter::repeat_n((0.0_f64, 0_u32), usize::from(bin_count)).collect();
+        let mut samples = 0_u32;
+        let (mut sum_x, mut sum_y) = (0.0_f64, 0.0_f64);
+        for &(renewables, price) in &points {
+            samples = samples.saturating_add(1);
+            sum_x += renewables;
+            sum_y += price;
+            let bin = (1..bin_count)
+                .take_while(|&index| renewables >= f64::from(index) * width)
+                .count();
+            if let Some((sum, count)) = sums.get_mut(bin) {
+                *sum += price;
+                *count = count.saturating_add(1);
+            }
+        }
+
+        let (mut coefficient, mut slope) = (None, None);
+        if samples > 1 {
+            let mean_x = sum_x / f64::from(samples);
+            let mean_y = sum_y / f64::from(samples);
+            let (mut covariance, mut variance_x, mut variance_y) = (0.0_f64, 0.0_f64, 0.0_f64);
+            for &(renewables, price) in &points {
+                let dx = renewables - mean_x;
+                let dy = price - mean_y;
+                covariance += dx * dy;
+                variance_x += dx * dx;
+                variance_y += dy * dy;
+            }
+            if variance_x > 0.0_f64 {
+                slope = Some(covariance / variance_x);
+                if variance_y > 0.0_f64 {
+                    coefficient = Some(covariance / sqrt(variance_x * variance_y));
+                }
+            }
+        }
+
+        let ranges = sums
+            .into_iter()
+            .zip(0_u8..)
+            .map(|((sum, count), index)| RenewablesBin {
+                lower: f64::from(index) * width,
+                upper: f64::from(index.saturating_add(1)) * width,
+                mean_price: (count > 0).then(|| sum / f64::from(count)),
+                samples: count,
+            })
+            .collect();
+
+        Self {
+            samples,
+            coefficient,
+            slope,
+            bins: ranges,
+        }
+    }
+
+    /// Returns the number of intervals analysed.
+    #[must_use]
+    #[inline]
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Returns the Pearson correlation coefficient between the renewables
+    /// percentage and the price, between -1 and 1.
+    ///
+    /// Negative values mean prices fall as renewables rise. Returns `None` if
+    /// there are fewer than two intervals, or either quantity is constant.
+    #[must_use]
+    #[inline]
+    pub fn coefficient(&self) -> Option<f64> {
+        self.coefficient
+    }
+
+    /// Returns the average change in price (c/kWh) per percentage point of
+    /// renewables, from a least squares fit.
+    ///
+    /// Returns `None` if there are fewer than two intervals, or the renewables
+    /// percentage is constant.
+    #[must_use]
+    #[inline]
+    pub fn slope(&self) -> Option<f64> {
+        self.slope
+    }
+
+    /// Returns the bins in increasing order of renewables.
+    #[must_use]
+    #[inline]
+    pub fn bins(&self) -> &[RenewablesBin] {
+        &self.bins
+    }
+
+    /// Returns how much cheaper (c/kWh) the greenest intervals are than the
+    /// least green ones.
+    ///
+    /// This is the mean price of the lowest non-empty bin less that of the
+    /// highest non-empty bin, and is negative if green power is more
+    /// expensive. Returns `None` if fewer than two bins have data.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Comparing bin prices")]
+    pub fn green_saving(&self) -> Option<f64> {
+        let mut populated = self.bins.iter().filter_map(|bin| bin.mean_price);
+        let least_green = populated.next()?;
+        let greenest = populated.next_back()?;
+        Some(least_green - greenest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a general channel interval.
+    fn interval(renewables: f64, per_kwh: f64) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "perKwh": per_kwh,
+            "renewables": renewables
+        }))
+    }
+
+    #[test]
+    fn prices_fall_with_renewables() {
+        let intervals = vec![
+            interval(10.0, 40.0),
+            interval(30.0, 30.0),
+            interval(70.0, 10.0),
+            interval(100.0, -5.0),
+        ];
+        let correlation = RenewablesPriceCorrelation::new(&intervals, &ChannelType::General, 4);
+        assert_eq!(correlation.samples(), 4);
+
+        let coefficient = correlation.coefficient().expect("varying data");
+        assert!(
+            (-1.01_f64..-0.95_f64).contains(&coefficient),
+            "{coefficient}"
+        );
+        let slope = correlation.slope().expect("varying data");
+        assert!((-0.55_f64..-0.45_f64).contains(&slope), "{slope}");
+
+        let samples: Vec<u32> = correlation.bins().iter().map(|bin| bin.samples).collect();
+        assert_eq!(samples, vec![1, 1, 1, 1]);
+        assert_eq!(correlation.green_saving(), Some(45.0_f64));
+
+        let feed_in = RenewablesPriceCorrelation::new(&intervals, &ChannelType::FeedIn, 4);
+        assert_eq!(feed_in.samples(), 0);
+        assert_eq!(feed_in.coefficient(), None);
+        assert_eq!(feed_in.green_saving(), None);
+    }
+}
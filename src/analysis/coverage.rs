@@ -0,0 +1,298 @@
+//! # Coverage Checks
+//!
+//! Stitching together chunked fetches, or merging archives from several
+//! sources, can silently leave holes or repeat intervals. [`check_intervals`]
+//! and [`check_usage`] scan a set of price intervals or usage records and
+//! return a [`CoverageReport`] listing the missing periods, the duplicated
+//! intervals and the intervals overlapping each other, so the data can be
+//! validated before it is analysed or stored.
+//!
+//! Prices are checked per channel type, and usage per meter channel, as a site
+//! may have several meters of the same type.
+//!
+//! ```
+//! use amber_api::analysis::coverage::check_usage;
+//! use amber_api::models::Usage;
+//!
+//! # fn example(usage: &[Usage]) {
+//! let report = check_usage(usage);
+//! if !report.is_complete() {
+//!     for gap in &report.gaps {
+//!         println!("{} missing from {} to {}", gap.channel.channel_type, gap.start, gap.end);
+//!     }
+//! }
+//! # }
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use jiff::Timestamp;
+
+use super::channel_order;
+use crate::models::{BaseInterval, ChannelId, ChannelType, Interval, Usage};
+
+/// The channel a finding applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CoverageChannel {
+    /// Type of the channel.
+    pub channel_type: ChannelType,
+    /// Identifier of the meter channel, for usage records.
+    pub channel_identifier: Option<ChannelId>,
+}
+
+/// A period missing between two consecutive intervals of a channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CoverageGap {
+    /// Channel with the missing period.
+    pub channel: CoverageChannel,
+    /// End of the interval before the gap.
+    pub start: Timestamp,
+    /// Start of the interval after the gap.
+    pub end: Timestamp,
+}
+
+/// An interval appearing more than once for the same channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Duplicate {
+    /// Channel of the interval.
+    pub channel: CoverageChannel,
+    /// Start of the interval.
+    pub start: Timestamp,
+    /// End of the interval.
+    pub end: Timestamp,
+    /// Number of times the interval appears.
+    pub count: usize,
+}
+
+/// Two different intervals of the same channel covering a common period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Overlap {
+    /// Channel of the intervals.
+    pub channel: CoverageChannel,
+    /// Start and end of the earlier interval.
+    pub first: (Timestamp, Timestamp),
+    /// Start and end of the later interval.
+    pub second: (Timestamp, Timestamp),
+}
+
+/// Findings of a coverage check.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CoverageReport {
+    /// Number of intervals or records checked.
+    pub checked: usize,
+    /// Earliest start of the checked intervals.
+    pub start: Option<Timestamp>,
+    /// Latest end of the checked intervals.
+    pub end: Option<Timestamp>,
+    /// Periods missing within each channel, in order of channel and time.
+    pub gaps: Vec<CoverageGap>,
+    /// Intervals appearing more than once.
+    pub duplicates: Vec<Duplicate>,
+    /// Different intervals overlapping each other.
+    pub overlaps: Vec<Overlap>,
+}
+
+impl CoverageReport {
+    /// Returns `true` if nothing is missing, duplicated or overlapping.
+    #[must_use]
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.gaps.is_empty() && self.duplicates.is_empty() && self.overlaps.is_empty()
+    }
+}
+
+/// Check the coverage of price intervals, per channel type.
+///
+/// Intervals of an unknown type are not checked.
+#[must_use]
+#[inline]
+pub fn check_intervals(intervals: &[Interval]) -> CoverageReport {
+    check(
+        intervals
+            .iter()
+            .filter_map(Interval::as_base_interval)
+            .map(|base| (base, None)),
+    )
+}
+
+/// Check the coverage of usage records, per meter channel.
+#[must_use]
+#[inline]
+pub fn check_usage(usage: &[Usage]) -> CoverageReport {
+    check(
+        usage
+            .iter()
+            .map(|record| (&record.base, Some(&record.channel_identifier))),
+    )
+}
+
+/// Intervals of a channel, keyed by channel order and identifier.
+type Channels<'a> =
+    BTreeMap<((u8, &'a str), Option<&'a str>), (CoverageChannel, Vec<(Timestamp, Timestamp)>)>;
+
+/// Check the coverage of intervals, grouped by channel type and identifier.
+fn check<'a>(
+    intervals: impl Iterator<Item = (&'a BaseInterval, Option<&'a ChannelId>)>,
+) -> CoverageReport {
+    let mut report = CoverageReport::default();
+    let mut channels: Channels<'a> = BTreeMap::new();
+    for (base, identifier) in intervals {
+        report.checked = report.checked.saturating_add(1);
+        report.start = Some(
+            report
+                .start
+                .map_or(base.start_time, |start| start.min(base.start_time)),
+        );
+        report.end = Some(
+            report
+                .end
+                .map_or(base.end_time, |end| end.max(base.end_time)),
+        );
+        channels
+            .entry((
+                channel_order(&base.channel_type),
+                identifier.map(ChannelId::as_str),
+            ))
+            .or_insert_with(|| {
+                (
+                    CoverageChannel {
+                        channel_type: base.channel_type.clone(),
+                        channel_identifier: identifier.cloned(),
+                    },
+                    Vec::new(),
+                )
+            })
+            .1
+            .push((base.start_time, base.end_time));
+    }
+
+    for (channel, mut bounds) in channels.into_values() {
+        bounds.sort_unstable();
+        let mut previous: Option<(Timestamp, Timestamp)> = None;
+        let mut covered_until: Option<Timestamp> = None;
+        for bound in bounds {
+            if previous == Some(bound) {
+                if let Some(duplicate) = report.duplicates.last_mut()
+                    && duplicate.channel == channel
+                    && (duplicate.start, duplicate.end) == bound
+                {
+                    duplicate.count = duplicate.count.saturating_add(1);
+                } else {
+                    report.duplicates.push(Duplicate {
+                        channel: channel.clone(),
+                        start: bound.0,
+                        end: bound.1,
+                        count: 2,
+                    });
+                }
+                continue;
+            }
+            if let Some(until) = covered_until {
+                if until < bound.0 {
+                    report.gaps.push(CoverageGap {
+                        channel: channel.clone(),
+                        start: until,
+                        end: bound.0,
+                    });
+                } else if let Some(earlier) = previous
+                    && bound.0 < until
+                {
+                    report.overlaps.push(Overlap {
+                        channel: channel.clone(),
+                        first: earlier,
+                        second: bound,
+                    });
+                }
+            }
+            covered_until = Some(covered_until.map_or(bound.1, |until| until.max(bound.1)));
+            previous = Some(bound);
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a timestamp at the given minute past 02:00 UTC.
+    fn at(minute: u8) -> Timestamp {
+        format!("2021-05-05T02:{minute:02}:00Z")
+            .parse()
+            .expect("valid timestamp")
+    }
+
+    /// Parse a usage record of the given meter channel.
+    fn usage(identifier: &str, start: u8, end: u8) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "duration": 5,
+            "nemTime": at(end),
+            "startTime": at(start),
+            "endTime": at(end),
+            "channelIdentifier": identifier,
+            "kwh": 0.5,
+            "cost": 12.0
+        }))
+    }
+
+    #[test]
+    fn reports_findings() {
+        let records = vec![
+            usage("E1", 0, 5),
+            usage("E1", 5, 10),
+            usage("E1", 5, 10),
+            usage("E1", 5, 10),
+            usage("E1", 20, 25),
+            usage("E1", 22, 27),
+            // A second meter is checked separately
+            usage("E2", 10, 15),
+        ];
+        let report = check_usage(&records);
+        assert_eq!(report.checked, 7);
+        assert_eq!((report.start, report.end), (Some(at(0)), Some(at(27))));
+        assert!(!report.is_complete());
+
+        let e1 = CoverageChannel {
+            channel_type: ChannelType::General,
+            channel_identifier: Some(ChannelId::new("E1").expect("valid ID")),
+        };
+        assert_eq!(
+            report.gaps,
+            vec![CoverageGap {
+                channel: e1.clone(),
+                start: at(10),
+                end: at(20),
+            }]
+        );
+        assert_eq!(
+            report.duplicates,
+            vec![Duplicate {
+                channel: e1.clone(),
+                start: at(5),
+                end: at(10),
+                count: 3,
+            }]
+        );
+        assert_eq!(
+            report.overlaps,
+            vec![Overlap {
+                channel: e1,
+                first: (at(20), at(25)),
+                second: (at(22), at(27)),
+            }]
+        );
+
+        let clean = check_usage(records.get(..2).expect("two records"));
+        assert!(clean.is_complete());
+        assert!(check_intervals(&[]).is_complete());
+    }
+}
@@ -0,0 +1,482 @@
+//! # Demand Window Exposure
+//!
+//! Demand tariffs add a charge based on the highest power drawn from the grid
+//! during the demand window (typically weekday afternoons and evenings). A
+//! single load running in the window, such as charging an electric vehicle,
+//! can set the peak for the whole billing period, so it pays to know in
+//! advance which intervals fall in the window.
+//!
+//! The API flags each interval that is inside the demand window through its
+//! [`TariffInformation`](crate::models::TariffInformation). A
+//! [`DemandForecast`] collects these flags from the price forecast, and
+//! estimates the exposure of [`PlannedLoad`]s to the window along with their
+//! energy cost at the forecast prices.
+//!
+//! ```
+//! use core::time::Duration;
+//!
+//! use amber_api::analysis::demand::{DemandForecast, PlannedLoad};
+//! use amber_api::models::Interval;
+//!
+//! # fn example(forecast: &[Interval]) -> Option<()> {
+//! let demand = DemandForecast::new(forecast);
+//! for window in demand.windows() {
+//!     println!("Demand window from {} to {}", window.start, window.end);
+//! }
+//!
+//! let start = demand.windows().first()?.start;
+//! let charge_car = PlannedLoad::new(start, Duration::from_secs(2 * 60 * 60), 7.2);
+//! let exposure = demand.exposure(&charge_car);
+//! if exposure.is_exposed() {
+//!     println!(
+//!         "Charging now adds {:.1} kW to the demand peak, costing an extra {:.0}c/day",
+//!         exposure.peak_window_kw,
+//!         exposure.demand_charge(0.0, 45.0),
+//!     );
+//! }
+//! # Some(())
+//! # }
+//! ```
+//!
+//! Looking back, [`demand_peaks`] finds the peak demand reached within the
+//! window in each [`BillingPeriod`] of past usage, from which the demand
+//! charge of each period can be estimated.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::time::Duration;
+
+use jiff::{Timestamp, civil::Date};
+
+use super::billing::BillingPeriod;
+use crate::{
+    models::{ChannelType, Interval, Usage},
+    units::{CentsPerKwh, Kilowatts},
+};
+
+/// A load planned to run at a constant power.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct PlannedLoad {
+    /// When the load starts.
+    pub start: Timestamp,
+    /// How long the load runs for.
+    pub duration: Duration,
+    /// Power drawn by the load (kW).
+    pub power_kw: f64,
+}
+
+impl PlannedLoad {
+    /// Create a planned load.
+    #[must_use]
+    #[inline]
+    pub fn new(start: Timestamp, duration: Duration, power_kw: f64) -> Self {
+        Self {
+            start,
+            duration,
+            power_kw,
+        }
+    }
+
+    /// Returns the power drawn by the load.
+    #[must_use]
+    #[inline]
+    pub fn power(&self) -> Kilowatts {
+        Kilowatts::new(self.power_kw)
+    }
+
+    /// Returns when the load ends.
+    #[must_use]
+    #[inline]
+    pub fn end(&self) -> Timestamp {
+        jiff::SignedDuration::try_from(self.duration)
+            .ok()
+            .and_then(|duration| self.start.checked_add(duration).ok())
+            .unwrap_or(Timestamp::MAX)
+    }
+}
+
+/// A contiguous period inside the demand window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DemandWindow {
+    /// Start of the window.
+    pub start: Timestamp,
+    /// End of the window.
+    pub end: Timestamp,
+}
+
+/// A forecast interval relevant to demand exposure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Slot {
+    /// Start of the interval.
+    start: Timestamp,
+    /// End of the interval.
+    end: Timestamp,
+    /// Whether the interval is inside the demand window, if known.
+    demand_window: Option<bool>,
+    /// Retail price of the interval (c/kWh).
+    per_kwh: f64,
+}
+
+/// Exposure of a planned load to the demand window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct LoadExposure {
+    /// Energy drawn by the load within the forecast (kWh).
+    pub energy_kwh: f64,
+    /// Energy drawn by the load inside the demand window (kWh).
+    pub window_energy_kwh: f64,
+    /// Power added to the demand peak by the load (kW), or zero if the load
+    /// does not run inside the demand window.
+    pub peak_window_kw: f64,
+    /// Energy cost of the load at the forecast prices (c), including GST.
+    pub energy_cost: f64,
+    /// Time the load runs outside of the forecast, for which no estimate
+    /// could be made.
+    pub uncovered: Duration,
+}
+
+impl LoadExposure {
+    /// Returns `true` if the load runs inside the demand window.
+    #[must_use]
+    #[inline]
+    pub fn is_exposed(&self) -> bool {
+        self.window_energy_kwh > 0.0
+    }
+
+    /// Estimate the increase of the demand charge (c/day) caused by the load.
+    ///
+    /// Demand charges are billed on the highest demand within the window over
+    /// the billing period, at a rate in c/kW/day. The load only increases the
+    /// charge if the demand it adds on top of the household's usual load
+    /// exceeds the peak already reached this period (`current_peak_kw`).
+    /// Household usage other than the load is not included, so this is a
+    /// lower bound.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing demand charges")]
+    pub fn demand_charge(&self, current_peak_kw: f64, rate_per_kw_day: f64) -> f64 {
+        (self.peak_window_kw - current_peak_kw).max(0.0) * rate_per_kw_day
+    }
+}
+
+/// Upcoming demand windows, derived from a price forecast.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DemandForecast {
+    /// General channel intervals in chronological order.
+    slots: Vec<Slot>,
+    /// Contiguous periods inside the demand window.
+    windows: Vec<DemandWindow>,
+}
+
+impl DemandForecast {
+    /// Collect the demand window flags of the general channel intervals.
+    ///
+    /// Sites which are not on a demand tariff have no demand window flags, and
+    /// therefore no windows.
+    #[must_use]
+    #[inline]
+    pub fn new(intervals: &[Interval]) -> Self {
+        let mut slots: Vec<Slot> = intervals
+            .iter()
+            .filter_map(Interval::as_base_interval)
+            .filter(|base| base.channel_type == ChannelType::General)
+            .map(|base| Slot {
+                start: base.start_time,
+                end: base.end_time,
+                demand_window: base
+                    .tariff_information
+                    .as_ref()
+                    .and_then(|tariff| tariff.demand_window),
+                per_kwh: base.per_kwh,
+            })
+            .collect();
+        slots.sort_by_key(|slot| slot.start);
+        slots.dedup_by_key(|slot| slot.start);
+
+        let mut windows: Vec<DemandWindow> = Vec::new();
+        for slot in slots.iter().filter(|slot| slot.demand_window == Some(true)) {
+            match windows.last_mut() {
+                Some(window) if window.end >= slot.start => window.end = window.end.max(slot.end),
+                Some(_) | None => windows.push(DemandWindow {
+                    start: slot.start,
+                    end: slot.end,
+                }),
+            }
+        }
+
+        Self { slots, windows }
+    }
+
+    /// Returns the upcoming demand windows, in chronological order.
+    #[must_use]
+    #[inline]
+    pub fn windows(&self) -> &[DemandWindow] {
+        &self.windows
+    }
+
+    /// Returns whether the given time is inside the demand window.
+    ///
+    /// Returns `None` if the time is outside the forecast, or the interval has
+    /// no demand window information.
+    #[must_use]
+    #[inline]
+    pub fn in_window(&self, at: Timestamp) -> Option<bool> {
+        self.slots
+            .iter()
+            .find(|slot| slot.start <= at && at < slot.end)
+            .and_then(|slot| slot.demand_window)
+    }
+
+    /// Estimate the exposure of a planned load to the demand window, and its
+    /// energy cost at the forecast prices.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing energy and cost")]
+    pub fn exposure(&self, load: &PlannedLoad) -> LoadExposure {
+        let end = load.end();
+        let mut exposure = LoadExposure {
+            energy_kwh: 0.0,
+            window_energy_kwh: 0.0,
+            peak_window_kw: 0.0,
+            energy_cost: 0.0,
+            uncovered: load.duration,
+        };
+        for slot in &self.slots {
+            let overlap = overlap_seconds(load.start, end, slot.start, slot.end);
+            if overlap == 0 {
+                continue;
+            }
+            let energy = load.power().energy_over(Duration::from_secs(overlap));
+            exposure.energy_kwh += energy.value();
+            exposure.energy_cost += energy * CentsPerKwh::new(slot.per_kwh);
+            if slot.demand_window == Some(true) {
+                exposure.window_energy_kwh += energy.value();
+                exposure.peak_window_kw = load.power_kw;
+            }
+            exposure.uncovered = exposure
+                .uncovered
+                .saturating_sub(Duration::from_secs(overlap));
+        }
+        exposure
+    }
+}
+
+/// Peak demand reached within the demand window over a billing period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct DemandPeak {
+    /// The billing period.
+    pub period: BillingPeriod,
+    /// Highest average demand of an interval inside the demand window (kW).
+    pub peak_kw: f64,
+    /// Start of the interval setting the peak.
+    pub peak_at: Timestamp,
+    /// Number of intervals inside the demand window.
+    pub intervals: usize,
+}
+
+impl DemandPeak {
+    /// Returns the peak demand.
+    #[must_use]
+    #[inline]
+    pub fn peak(&self) -> Kilowatts {
+        Kilowatts::new(self.peak_kw)
+    }
+
+    /// Estimate the demand charge of the period at a rate per kW of peak
+    /// demand, such as $/kW.
+    ///
+    /// The charge is in the same unit as the rate.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing demand charges")]
+    pub fn charge(&self, rate_per_kw: f64) -> f64 {
+        self.peak_kw * rate_per_kw
+    }
+
+    /// Estimate the demand charge of the period at a daily rate per kW of
+    /// peak demand, such as c/kW/day, over every day of the period.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing demand charges")]
+    pub fn charge_per_day(&self, rate_per_kw_day: f64) -> f64 {
+        self.charge(rate_per_kw_day) * f64::from(self.period.days())
+    }
+}
+
+/// Find the peak demand within the demand window in each billing period of
+/// the cycle covered by the usage.
+///
+/// Only general channel records flagged as inside the demand window are
+/// considered. Records of the same interval on different meters are added
+/// together, and the demand of an interval is its average power (kW).
+/// Exports do not reduce the peak below zero. Periods are returned in
+/// chronological order, omitting those without any demand window interval.
+#[must_use]
+#[inline]
+#[expect(clippy::float_arithmetic, reason = "Computing demand")]
+pub fn demand_peaks(usage: &[Usage], cycle: BillingPeriod) -> Vec<DemandPeak> {
+    let mut intervals: BTreeMap<Timestamp, (Date, u32, f64)> = BTreeMap::new();
+    for record in usage.iter().filter(|record| {
+        record.base.channel_type == ChannelType::General
+            && record
+                .base
+                .tariff_information
+                .as_ref()
+                .and_then(|tariff| tariff.demand_window)
+                == Some(true)
+    }) {
+        let entry = intervals.entry(record.base.start_time).or_insert((
+            record.base.date,
+            record.base.duration,
+            0.0_f64,
+        ));
+        entry.2 += record.kwh;
+    }
+
+    let mut peaks: BTreeMap<Date, DemandPeak> = BTreeMap::new();
+    for (start_time, (date, duration, kwh)) in intervals {
+        if duration == 0 {
+            continue;
+        }
+        let demand = (kwh * 60.0 / f64::from(duration)).max(0.0);
+        let period = cycle.containing(date);
+        let peak = peaks.entry(period.start()).or_insert(DemandPeak {
+            period,
+            peak_kw: demand,
+            peak_at: start_time,
+            intervals: 0,
+        });
+        peak.intervals = peak.intervals.saturating_add(1);
+        if demand > peak.peak_kw {
+            peak.peak_kw = demand;
+            peak.peak_at = start_time;
+        }
+    }
+    peaks.into_values().collect()
+}
+
+/// Number of whole seconds shared by two time ranges.
+fn overlap_seconds(
+    start: Timestamp,
+    end: Timestamp,
+    other_start: Timestamp,
+    other_end: Timestamp,
+) -> u64 {
+    let from = start.max(other_start).as_second();
+    let to = end.min(other_end).as_second();
+    u64::try_from(to.saturating_sub(from)).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::analysis::billing::CycleLength;
+
+    /// Parse a general channel forecast interval.
+    fn interval(start: &str, end: &str, per_kwh: f64, demand_window: bool) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": "ForecastInterval",
+            "perKwh": per_kwh,
+            "nemTime": end,
+            "startTime": start,
+            "endTime": end,
+            "tariffInformation": {"demandWindow": demand_window}
+        }))
+    }
+
+    /// Parse a general channel usage record.
+    fn usage(start: &str, day: &str, kwh: f64, demand_window: bool) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "date": day,
+            "nemTime": start,
+            "startTime": start,
+            "endTime": start,
+            "tariffInformation": {"demandWindow": demand_window},
+            "kwh": kwh,
+            "cost": 0.0
+        }))
+    }
+
+    /// Parse a timestamp.
+    fn at(timestamp: &str) -> Timestamp {
+        timestamp.parse().expect("valid timestamp")
+    }
+
+    #[test]
+    fn finds_windows_and_exposure() {
+        let forecast = vec![
+            interval("2021-05-05T06:00:00Z", "2021-05-05T06:30:00Z", 20.0, false),
+            interval("2021-05-05T06:30:00Z", "2021-05-05T07:00:00Z", 30.0, true),
+            interval("2021-05-05T07:00:00Z", "2021-05-05T07:30:00Z", 40.0, true),
+        ];
+        let demand = DemandForecast::new(&forecast);
+        assert_eq!(
+            demand.windows(),
+            &[DemandWindow {
+                start: at("2021-05-05T06:30:00Z"),
+                end: at("2021-05-05T07:30:00Z"),
+            }]
+        );
+        assert_eq!(demand.in_window(at("2021-05-05T06:15:00Z")), Some(false));
+        assert_eq!(demand.in_window(at("2021-05-05T07:15:00Z")), Some(true));
+        assert_eq!(demand.in_window(at("2021-05-05T08:00:00Z")), None);
+
+        // One hour at 2 kW, half of it inside the window
+        let load = PlannedLoad::new(at("2021-05-05T06:00:00Z"), Duration::from_hours(1), 2.0);
+        let exposure = demand.exposure(&load);
+        assert!(exposure.is_exposed());
+        assert!((1.99_f64..2.01_f64).contains(&exposure.energy_kwh));
+        assert!((0.99_f64..1.01_f64).contains(&exposure.window_energy_kwh));
+        assert!((49.9_f64..50.1_f64).contains(&exposure.energy_cost));
+        assert_eq!(exposure.uncovered, Duration::ZERO);
+        assert!((59.9_f64..60.1_f64).contains(&exposure.demand_charge(0.5, 40.0)));
+
+        // Starting late enough to run past the end of the forecast
+        let late = PlannedLoad::new(at("2021-05-05T07:15:00Z"), Duration::from_mins(30), 2.0);
+        assert_eq!(demand.exposure(&late).uncovered, Duration::from_mins(15));
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "Peaks and charges are exact in binary")]
+    fn finds_peaks_per_billing_period() {
+        let records = vec![
+            usage("2021-05-05T06:00:00Z", "2021-05-05", 4.0, false),
+            usage("2021-05-05T07:00:00Z", "2021-05-05", 1.5, true),
+            usage("2021-05-05T07:30:00Z", "2021-05-05", 2.5, true),
+            usage("2021-05-20T07:00:00Z", "2021-05-20", 3.0, true),
+            usage("2021-06-02T07:00:00Z", "2021-06-02", 1.0, true),
+            usage("2021-06-02T07:00:00Z", "2021-06-02", 0.5, true),
+        ];
+        let cycle = BillingPeriod::new(date(2021, 5, 1), CycleLength::Months(1));
+
+        let peaks = demand_peaks(&records, cycle);
+        let summary: Vec<_> = peaks
+            .iter()
+            .map(|peak| {
+                (
+                    peak.period.start(),
+                    peak.peak_kw,
+                    peak.peak_at,
+                    peak.intervals,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (date(2021, 5, 1), 6.0_f64, at("2021-05-20T07:00:00Z"), 3),
+                (date(2021, 6, 1), 3.0_f64, at("2021-06-02T07:00:00Z"), 1),
+            ]
+        );
+        let may = peaks.first().expect("peak in May");
+        assert_eq!(may.charge(12.5), 75.0_f64);
+        assert_eq!(may.charge_per_day(0.5), 93.0_f64);
+    }
+}
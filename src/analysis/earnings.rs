@@ -0,0 +1,214 @@
+//! # Feed-In Earnings
+//!
+//! Exports on a wholesale-linked plan earn whatever the feed-in price was at
+//! the time, which can be far more (or less) than a retailer's flat feed-in
+//! tariff. [`feed_in_earnings`] isolates the feed-in records of usage and
+//! totals the energy exported and the revenue earned each day, so that it can
+//! be compared with what a flat tariff would have paid for the same energy.
+//!
+//! Unlike the API, which reports feed-in energy and credits as negative
+//! numbers, everything here is positive when energy is exported and paid for.
+//!
+//! ```
+//! use amber_api::analysis::earnings::feed_in_earnings;
+//! use amber_api::models::Usage;
+//! use amber_api::units::CentsPerKwh;
+//!
+//! # fn example(usage: &[Usage]) {
+//! let earnings = feed_in_earnings(usage);
+//! for day in &earnings.days {
+//!     println!(
+//!         "{}: {:.2} earned {:.2}c ({:+.2}c against 5c/kWh flat)",
+//!         day.date,
+//!         day.kwh,
+//!         day.revenue,
+//!         day.difference(CentsPerKwh::new(5.0))
+//!     );
+//! }
+//! # }
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use jiff::civil::Date;
+
+use crate::{
+    models::{ChannelType, Usage},
+    units::{CentsPerKwh, KilowattHours},
+};
+
+/// Energy exported and revenue earned on a single day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct DailyEarnings {
+    /// Date of the records (in NEM time).
+    pub date: Date,
+    /// Energy exported.
+    pub kwh: KilowattHours,
+    /// Revenue earned for the exports (c), including GST, negative if
+    /// exporting cost money.
+    pub revenue: f64,
+}
+
+impl DailyEarnings {
+    /// Returns the revenue (c) the same exports would have earned at a flat
+    /// feed-in tariff.
+    #[must_use]
+    #[inline]
+    pub fn flat_revenue(&self, rate: CentsPerKwh) -> f64 {
+        self.kwh * rate
+    }
+
+    /// Returns how much more was earned (c) than at a flat feed-in tariff.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Comparing revenue")]
+    pub fn difference(&self, rate: CentsPerKwh) -> f64 {
+        self.revenue - self.flat_revenue(rate)
+    }
+
+    /// Returns the average feed-in rate earned, or `None` if nothing was
+    /// exported.
+    #[must_use]
+    #[inline]
+    pub fn average_rate(&self) -> Option<CentsPerKwh> {
+        if self.kwh > KilowattHours::ZERO {
+            CentsPerKwh::from_cost(self.revenue, self.kwh)
+        } else {
+            None
+        }
+    }
+}
+
+/// Feed-in earnings of a set of usage records.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct FeedInEarnings {
+    /// Earnings of each day with feed-in records, in chronological order.
+    pub days: Vec<DailyEarnings>,
+    /// Total energy exported.
+    pub kwh: KilowattHours,
+    /// Total revenue earned for the exports (c), including GST.
+    pub revenue: f64,
+}
+
+impl FeedInEarnings {
+    /// Returns the revenue (c) the same exports would have earned at a flat
+    /// feed-in tariff.
+    #[must_use]
+    #[inline]
+    pub fn flat_revenue(&self, rate: CentsPerKwh) -> f64 {
+        self.kwh * rate
+    }
+
+    /// Returns how much more was earned (c) than at a flat feed-in tariff.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Comparing revenue")]
+    pub fn difference(&self, rate: CentsPerKwh) -> f64 {
+        self.revenue - self.flat_revenue(rate)
+    }
+
+    /// Returns the average feed-in rate earned, or `None` if nothing was
+    /// exported.
+    #[must_use]
+    #[inline]
+    pub fn average_rate(&self) -> Option<CentsPerKwh> {
+        if self.kwh > KilowattHours::ZERO {
+            CentsPerKwh::from_cost(self.revenue, self.kwh)
+        } else {
+            None
+        }
+    }
+}
+
+/// Total the energy exported and the revenue earned each day from the
+/// feed-in records of usage.
+///
+/// Records of other channels are ignored. The energy of feed-in records is
+/// negative when exporting, and their cost negative when paid, so both are
+/// negated.
+#[must_use]
+#[inline]
+#[expect(clippy::float_arithmetic, reason = "Accumulating earnings")]
+pub fn feed_in_earnings(usage: &[Usage]) -> FeedInEarnings {
+    let mut days: BTreeMap<Date, DailyEarnings> = BTreeMap::new();
+    for record in usage
+        .iter()
+        .filter(|record| record.base.channel_type == ChannelType::FeedIn)
+    {
+        let day = days.entry(record.base.date).or_insert(DailyEarnings {
+            date: record.base.date,
+            kwh: KilowattHours::ZERO,
+            revenue: 0.0,
+        });
+        day.kwh -= record.energy();
+        day.revenue -= record.cost;
+    }
+
+    let mut earnings = FeedInEarnings::default();
+    for day in days.into_values() {
+        earnings.kwh += day.kwh;
+        earnings.revenue += day.revenue;
+        earnings.days.push(day);
+    }
+    earnings
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a usage record.
+    fn usage(day: &str, channel_type: &str, kwh: f64, cost: f64) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "perKwh": -8.0,
+            "date": day,
+            "channelType": channel_type,
+            "channelIdentifier": "B1",
+            "kwh": kwh,
+            "cost": cost
+        }))
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "Revenues are whole cents")]
+    fn totals_exports_per_day() {
+        let records = vec![
+            usage("2021-05-05", "feedIn", -2.0, -16.0),
+            usage("2021-05-05", "general", 3.0, 60.0),
+            usage("2021-05-04", "feedIn", -4.0, -12.0),
+            usage("2021-05-05", "feedIn", -2.0, -4.0),
+        ];
+        let earnings = feed_in_earnings(&records);
+        assert_eq!(
+            earnings.days,
+            vec![
+                DailyEarnings {
+                    date: date(2021, 5, 4),
+                    kwh: KilowattHours::new(4.0),
+                    revenue: 12.0,
+                },
+                DailyEarnings {
+                    date: date(2021, 5, 5),
+                    kwh: KilowattHours::new(4.0),
+                    revenue: 20.0,
+                },
+            ]
+        );
+        assert_eq!(earnings.kwh, KilowattHours::new(8.0));
+        assert_eq!(earnings.revenue, 32.0_f64);
+        assert_eq!(earnings.average_rate(), Some(CentsPerKwh::new(4.0)));
+        assert_eq!(earnings.flat_revenue(CentsPerKwh::new(5.0)), 40.0_f64);
+        assert_eq!(earnings.difference(CentsPerKwh::new(5.0)), -8.0_f64);
+
+        let first = earnings.days.first().expect("a day");
+        assert_eq!(first.difference(CentsPerKwh::new(2.0)), 4.0_f64);
+        assert_eq!(feed_in_earnings(&[]).average_rate(), None);
+    }
+}
@@ -0,0 +1,390 @@
+//! # Heatmaps
+//!
+//! Pivots a period of data into a matrix of dates by time of day, the
+//! structure expected by calendar heatmap visualisations. Each row is a (NEM)
+//! date and each column a time-of-day slot, so daily patterns such as evening
+//! price peaks or midday solar exports stand out as vertical bands.
+
+use alloc::{vec, vec::Vec};
+use core::borrow::Borrow;
+
+use jiff::{
+    Span, Timestamp,
+    civil::{Date, Time},
+};
+
+use super::{MINUTES_PER_DAY, NEM_OFFSET, slot_index, slot_start};
+use crate::models::{ChannelType, Interval, Resolution, Usage};
+
+/// How the values falling within a cell are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Aggregation {
+    /// Average of the values.
+    #[default]
+    Mean,
+    /// Sum of the values.
+    Sum,
+    /// Smallest value.
+    Min,
+    /// Largest value.
+    Max,
+    /// Number of values.
+    Count,
+}
+
+/// Running totals of the values within a cell.
+#[derive(Debug, Clone, Copy)]
+struct Accumulator {
+    /// Sum of the values.
+    sum: f64,
+    /// Smallest value.
+    min: f64,
+    /// Largest value.
+    max: f64,
+    /// Number of values.
+    count: u32,
+}
+
+impl Accumulator {
+    /// An accumulator with no values.
+    const EMPTY: Self = Self {
+        sum: 0.0,
+        min: f64::INFINITY,
+        max: f64::NEG_INFINITY,
+        count: 0,
+    };
+
+    /// Add a value.
+    #[expect(clippy::float_arithmetic, reason = "Accumulating totals")]
+    fn add(&mut self, value: f64) {
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.count = self.count.saturating_add(1);
+    }
+
+    /// The aggregated value, or `None` if no values were added.
+    #[expect(clippy::float_arithmetic, reason = "Averaging values")]
+    fn finish(&self, aggregation: Aggregation) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(match aggregation {
+            Aggregation::Mean => self.sum / f64::from(self.count),
+            Aggregation::Sum => self.sum,
+            Aggregation::Min => self.min,
+            Aggregation::Max => self.max,
+            Aggregation::Count => f64::from(self.count),
+        })
+    }
+}
+
+/// A matrix of values by date and time of day (in NEM time).
+///
+/// Rows cover every date from the earliest to the latest data point, so that
+/// days without data appear as empty rows rather than being skipped. Columns
+/// cover the whole day in slots of the given [`Resolution`].
+///
+/// # Example
+///
+/// ```
+/// use amber_api::analysis::heatmap::{Aggregation, Heatmap};
+/// use amber_api::models::{ChannelType, Interval, Resolution};
+///
+/// # fn example(intervals: &[Interval]) {
+/// let heatmap = Heatmap::from_prices(
+///     intervals,
+///     &ChannelType::General,
+///     Resolution::ThirtyMinute,
+///     Aggregation::Mean,
+/// );
+///
+/// for (date, row) in heatmap.rows() {
+///     let cells: Vec<String> = row
+///         .iter()
+///         .map(|cell| cell.map_or_else(String::new, |price| format!("{price:.1}")))
+///         .collect();
+///     println!("{date},{}", cells.join(","));
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heatmap {
+    /// Width of each column.
+    resolution: Resolution,
+    /// How values were combined.
+    aggregation: Aggregation,
+    /// Date of the first row.
+    first_date: Option<Date>,
+    /// Start times of the columns.
+    times: Vec<Time>,
+    /// Cells in row-major order.
+    cells: Vec<Option<f64>>,
+}
+
+impl Heatmap {
+    /// Build a heatmap from timestamped values.
+    ///
+    /// Each value is assigned to the cell containing its timestamp, so data
+    /// with a finer resolution than the heatmap is aggregated into the
+    /// containing slot.
+    #[must_use]
+    #[inline]
+    pub fn new<I>(points: I, resolution: Resolution, aggregation: Aggregation) -> Self
+    where
+        I: IntoIterator<Item = (Timestamp, f64)>,
+    {
+        let slot_minutes = u32::from(resolution);
+        let slot_count = MINUTES_PER_DAY.checked_div(slot_minutes).unwrap_or(0);
+        let times: Vec<Time> = (0..slot_count)
+            .map(|index| slot_start(index, slot_minutes))
+            .collect();
+
+        let located: Vec<(Date, usize, f64)> = points
+            .into_iter()
+            .filter_map(|(timestamp, value)| {
+                let datetime = NEM_OFFSET.to_datetime(timestamp);
+                let column = slot_index(datetime.time(), slot_minutes)?;
+                Some((datetime.date(), column, value))
+            })
+            .collect();
+        let first_date = located.iter().map(|&(date, _, _)| date).min();
+        let row_count = located
+            .iter()
+            .filter_map(|&(date, _, _)| row_offset(first_date?, date))
+            .max()
+            .map_or(0, |last| last.saturating_add(1));
+
+        let mut accumulators = vec![Accumulator::EMPTY; row_count.saturating_mul(times.len())];
+        for (date, column, value) in located {
+            let cell = first_date
+                .and_then(|first| row_offset(first, date))
+                .and_then(|row| row.checked_mul(times.len())?.checked_add(column))
+                .and_then(|index| accumulators.get_mut(index));
+            if let Some(accumulator) = cell {
+                accumulator.add(value);
+            }
+        }
+
+        Self {
+            resolution,
+            aggregation,
+            first_date,
+            times,
+            cells: accumulators
+                .iter()
+                .map(|accumulator| accumulator.finish(aggregation))
+                .collect(),
+        }
+    }
+
+    /// Build a heatmap of the retail prices (c/kWh) of a channel.
+    ///
+    /// The intervals can be borrowed from a slice, or decoded from an
+    /// [`IntervalHistory`](crate::history::IntervalHistory) without first
+    /// collecting them.
+    #[must_use]
+    #[inline]
+    pub fn from_prices<I>(
+        intervals: I,
+        channel_type: &ChannelType,
+        resolution: Resolution,
+        aggregation: Aggregation,
+    ) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Interval>,
+    {
+        Self::new(
+            intervals.into_iter().filter_map(|interval| {
+                let base = interval.borrow().as_base_interval()?;
+                (base.channel_type == *channel_type).then_some((base.start_time, base.per_kwh))
+            }),
+            resolution,
+            aggregation,
+        )
+    }
+
+    /// Build a heatmap of the energy (kWh) of a channel's usage.
+    ///
+    /// Feed-in energy is negative, as reported by the API.
+    #[must_use]
+    #[inline]
+    pub fn from_usage(
+        usage: &[Usage],
+        channel_type: &ChannelType,
+        resolution: Resolution,
+        aggregation: Aggregation,
+    ) -> Self {
+        Self::new(
+            usage
+                .iter()
+                .filter(|record| record.base.channel_type == *channel_type)
+                .map(|record| (record.base.start_time, record.kwh)),
+            resolution,
+            aggregation,
+        )
+    }
+
+    /// Returns the resolution of the columns.
+    #[must_use]
+    #[inline]
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Returns how the values of each cell were combined.
+    #[must_use]
+    #[inline]
+    pub fn aggregation(&self) -> Aggregation {
+        self.aggregation
+    }
+
+    /// Returns `true` if the heatmap has no rows.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the start times of the columns, in chronological order.
+    #[must_use]
+    #[inline]
+    pub fn times(&self) -> &[Time] {
+        &self.times
+    }
+
+    /// Returns the dates of the rows, in chronological order.
+    #[inline]
+    pub fn dates(&self) -> impl Iterator<Item = Date> + '_ {
+        self.rows().map(|(date, _)| date)
+    }
+
+    /// Returns each row along with its date, in chronological order.
+    ///
+    /// Cells without data are `None`.
+    #[inline]
+    pub fn rows(&self) -> impl Iterator<Item = (Date, &[Option<f64>])> + '_ {
+        let dates = self
+            .first_date
+            .into_iter()
+            .flat_map(|first| first.series(Span::new().days(1_i32)));
+        dates.zip(self.cells.chunks(self.times.len().max(1)))
+    }
+
+    /// Returns the value of the cell containing the given date and time of
+    /// day, if any data fell within it.
+    #[must_use]
+    #[inline]
+    pub fn get(&self, date: Date, time: Time) -> Option<f64> {
+        let row = row_offset(self.first_date?, date)?;
+        let column = slot_index(time, u32::from(self.resolution))?;
+        let index = row.checked_mul(self.times.len())?.checked_add(column)?;
+        self.cells.get(index).copied().flatten()
+    }
+
+    /// Returns the smallest and largest values of the heatmap, as needed to
+    /// scale its colours.
+    #[must_use]
+    #[inline]
+    pub fn value_range(&self) -> Option<(f64, f64)> {
+        let mut values = self.cells.iter().copied().flatten();
+        let first = values.next()?;
+        Some(values.fold((first, first), |(min, max), value| {
+            (min.min(value), max.max(value))
+        }))
+    }
+}
+
+/// Number of days from `first` to `date`, if `date` is not before `first`.
+fn row_offset(first: Date, date: Date) -> Option<usize> {
+    usize::try_from(first.until(date).ok()?.get_days()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{history::IntervalHistory, simulator::Simulator};
+
+    /// Parse a timestamp.
+    fn at(timestamp: &str) -> Timestamp {
+        timestamp.parse().expect("valid timestamp")
+    }
+
+    #[test]
+    fn pivots_by_date_and_time() {
+        let points = vec![
+            // 10:00 and 10:05 NEM time on 2025-01-01
+            (at("2025-01-01T00:00:00Z"), 10.0_f64),
+            (at("2025-01-01T00:05:00Z"), 20.0_f64),
+            // 10:00 NEM time on 2025-01-03, leaving 2025-01-02 empty
+            (at("2025-01-03T00:00:00Z"), 30.0_f64),
+        ];
+        let heatmap = Heatmap::new(points.clone(), Resolution::ThirtyMinute, Aggregation::Mean);
+
+        assert_eq!(heatmap.times().len(), 48);
+        assert_eq!(
+            heatmap.dates().collect::<Vec<_>>(),
+            vec![
+                Date::constant(2025, 1, 1),
+                Date::constant(2025, 1, 2),
+                Date::constant(2025, 1, 3),
+            ]
+        );
+        let ten = Time::constant(10, 0, 0, 0);
+        assert_eq!(heatmap.get(Date::constant(2025, 1, 1), ten), Some(15.0_f64));
+        assert_eq!(heatmap.get(Date::constant(2025, 1, 2), ten), None);
+        assert_eq!(heatmap.get(Date::constant(2025, 1, 3), ten), Some(30.0_f64));
+        assert_eq!(heatmap.get(Date::constant(2024, 12, 31), ten), None);
+        assert_eq!(heatmap.value_range(), Some((15.0_f64, 30.0_f64)));
+
+        let rows: Vec<_> = heatmap.rows().collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|(_, row)| row.len() == 48));
+
+        let max = Heatmap::new(points.clone(), Resolution::ThirtyMinute, Aggregation::Max);
+        assert_eq!(max.get(Date::constant(2025, 1, 1), ten), Some(20.0_f64));
+        let count = Heatmap::new(points, Resolution::FiveMinute, Aggregation::Count);
+        assert_eq!(count.get(Date::constant(2025, 1, 1), ten), Some(1.0_f64));
+    }
+
+    #[test]
+    fn heatmap_of_history() {
+        let intervals: Vec<Interval> = Simulator::builder()
+            .start(at("2025-01-01T00:00:00Z"))
+            .resolution(Resolution::FiveMinute)
+            .seed(3)
+            .build()
+            .intervals()
+            .take(288 * 2)
+            .collect();
+        let history: IntervalHistory = intervals.iter().collect();
+
+        let from_slice = Heatmap::from_prices(
+            &intervals,
+            &ChannelType::General,
+            Resolution::ThirtyMinute,
+            Aggregation::Max,
+        );
+        let from_history = Heatmap::from_prices(
+            &history,
+            &ChannelType::General,
+            Resolution::ThirtyMinute,
+            Aggregation::Max,
+        );
+        assert_eq!(from_history, from_slice);
+        assert_eq!(from_history.rows().count(), 3);
+    }
+
+    #[test]
+    fn empty_heatmap() {
+        let heatmap = Heatmap::new([], Resolution::FiveMinute, Aggregation::Sum);
+        assert!(heatmap.is_empty());
+        assert_eq!(heatmap.times().len(), 288);
+        assert_eq!(heatmap.rows().count(), 0);
+        assert_eq!(heatmap.value_range(), None);
+    }
+}
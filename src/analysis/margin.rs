@@ -0,0 +1,203 @@
+//! # Spot Versus Retail Price Decomposition
+//!
+//! Each interval reports both the NEM spot price and the retail price you pay
+//! (or are paid). The difference between the two is the margin made up of
+//! network charges, market fees and Amber's own fees. This margin is usually
+//! stable within a time-of-use period and only shifts when a network tariff
+//! changes, so tracking it over time can highlight tariff updates.
+//!
+//! Note that the retail price also accounts for loss factors applied to the
+//! spot price, so the margin may vary slightly with the spot price. Use a
+//! suitable tolerance when looking for changes.
+
+use alloc::vec::Vec;
+
+use jiff::Timestamp;
+
+use super::channel_order;
+use crate::models::{BaseInterval, ChannelType, Interval};
+
+/// Decomposition of a retail price into its spot and margin components.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct PriceDecomposition {
+    /// Retail price (c/kWh), including GST.
+    pub retail: f64,
+    /// NEM spot price component (c/kWh), including GST.
+    pub spot: f64,
+    /// Network, market and retail fees (c/kWh): the retail price less the
+    /// spot price.
+    pub margin: f64,
+}
+
+impl PriceDecomposition {
+    /// Decompose the price of the given interval.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing the price margin")]
+    pub fn of(interval: &BaseInterval) -> Self {
+        Self {
+            retail: interval.per_kwh,
+            spot: interval.spot_per_kwh,
+            margin: interval.per_kwh - interval.spot_per_kwh,
+        }
+    }
+
+    /// Returns the fraction of the retail price made up by the margin.
+    ///
+    /// Returns `None` if the retail price is zero.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing the margin share")]
+    pub fn margin_share(&self) -> Option<f64> {
+        (self.retail != 0.0_f64).then(|| self.margin / self.retail)
+    }
+}
+
+/// The margin of a single interval.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct MarginPoint {
+    /// Start time of the interval.
+    pub start_time: Timestamp,
+    /// Channel the price applies to.
+    pub channel_type: ChannelType,
+    /// Decomposed price of the interval.
+    pub price: PriceDecomposition,
+}
+
+/// A change in margin between consecutive intervals of the same channel.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct MarginChange {
+    /// Start time of the first interval with the new margin.
+    pub start_time: Timestamp,
+    /// Channel whose margin changed.
+    pub channel_type: ChannelType,
+    /// Margin of the preceding interval (c/kWh).
+    pub previous: f64,
+    /// Margin of this interval (c/kWh).
+    pub current: f64,
+}
+
+impl MarginChange {
+    /// Returns the change in margin (c/kWh).
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing the margin change")]
+    pub fn delta(&self) -> f64 {
+        self.current - self.previous
+    }
+}
+
+/// Returns the margin of each interval, ordered by channel and start time.
+#[must_use]
+#[inline]
+pub fn margin_series(intervals: &[Interval]) -> Vec<MarginPoint> {
+    let mut points: Vec<_> = intervals
+        .iter()
+        .filter_map(Interval::as_base_interval)
+        .map(|base| MarginPoint {
+            start_time: base.start_time,
+            channel_type: base.channel_type.clone(),
+            price: PriceDecomposition::of(base),
+        })
+        .collect();
+    points.sort_by(|a, b| {
+        channel_order(&a.channel_type)
+            .cmp(&channel_order(&b.channel_type))
+            .then(a.start_time.cmp(&b.start_time))
+    });
+    points
+}
+
+/// Returns the intervals where the margin differs from the preceding interval
+/// of the same channel by more than `tolerance` (c/kWh).
+#[must_use]
+#[inline]
+#[expect(clippy::float_arithmetic, reason = "Comparing margins")]
+pub fn margin_changes(intervals: &[Interval], tolerance: f64) -> Vec<MarginChange> {
+    margin_series(intervals)
+        .windows(2)
+        .filter_map(|pair| match pair {
+            [previous, current]
+                if previous.channel_type == current.channel_type
+                    && (current.price.margin - previous.price.margin).abs() > tolerance =>
+            {
+                Some(MarginChange {
+                    start_time: current.start_time,
+                    channel_type: current.channel_type.clone(),
+                    previous: previous.price.margin,
+                    current: current.price.margin,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use jiff::civil::Date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::{ActualInterval, PriceDescriptor, SpikeStatus};
+
+    /// Create an actual interval with the given start time and prices.
+    fn interval(start: &str, channel_type: ChannelType, spot: f64, retail: f64) -> Interval {
+        let start_time: Timestamp = start.parse().expect("valid timestamp");
+        Interval::ActualInterval(ActualInterval {
+            base: BaseInterval {
+                duration: 30,
+                spot_per_kwh: spot,
+                per_kwh: retail,
+                date: Date::constant(2025, 1, 1),
+                nem_time: start_time,
+                start_time,
+                end_time: start_time,
+                renewables: 50.0,
+                channel_type,
+                tariff_information: None,
+                spike_status: SpikeStatus::None,
+                descriptor: PriceDescriptor::Neutral,
+            },
+        })
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "Margins of these prices are exact")]
+    fn decomposition() {
+        let Interval::ActualInterval(actual) =
+            interval("2025-01-01T00:00:00Z", ChannelType::General, 10.0, 25.0)
+        else {
+            unreachable!()
+        };
+        let price = PriceDecomposition::of(&actual.base);
+        assert_eq!(price.margin, 15.0_f64);
+        assert_eq!(price.margin_share(), Some(0.6_f64));
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "Margins of these prices are exact")]
+    fn detects_margin_changes_per_channel() {
+        let intervals = vec![
+            interval("2025-01-01T00:30:00Z", ChannelType::General, 12.0, 27.0),
+            interval("2025-01-01T00:00:00Z", ChannelType::General, 10.0, 25.0),
+            interval("2025-01-01T00:00:00Z", ChannelType::FeedIn, -10.0, -8.0),
+            interval("2025-01-01T01:00:00Z", ChannelType::General, 12.0, 32.0),
+            interval("2025-01-01T00:30:00Z", ChannelType::FeedIn, -12.0, -10.0),
+        ];
+
+        assert_eq!(margin_series(&intervals).len(), 5);
+        let changes = margin_changes(&intervals, 0.5);
+        assert_eq!(changes.len(), 1);
+        let change = changes.first().expect("one change");
+        assert_eq!(change.channel_type, ChannelType::General);
+        assert_eq!(change.previous, 15.0_f64);
+        assert_eq!(change.current, 20.0_f64);
+        assert_eq!(change.delta(), 5.0_f64);
+    }
+}
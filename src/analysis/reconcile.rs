@@ -0,0 +1,222 @@
+//! # Cost Reconciliation
+//!
+//! Each usage record reports its own cost, but auditing a bill means checking
+//! that cost against the prices published for the same interval.
+//! [`reconcile`] matches usage records with price intervals of the same
+//! channel and NEM time, recomputes each cost from the energy and the price,
+//! and reports the records whose cost differs as [`Discrepancy`]s.
+//!
+//! ```
+//! use amber_api::analysis::reconcile::reconcile;
+//! use amber_api::models::{Interval, Usage};
+//!
+//! # fn example(usage: &[Usage], prices: &[Interval]) {
+//! let report = reconcile(usage, prices);
+//! println!(
+//!     "Reported {:.2}c, expected {:.2}c over {} intervals",
+//!     report.reported_cost, report.expected_cost, report.matched
+//! );
+//! for discrepancy in &report.discrepancies {
+//!     println!(
+//!         "{} {}: {:+.2}c",
+//!         discrepancy.nem_time,
+//!         discrepancy.channel_identifier,
+//!         discrepancy.difference()
+//!     );
+//! }
+//! # }
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use jiff::Timestamp;
+
+use super::{channel_order, energy_cost};
+use crate::models::{ChannelId, ChannelType, Interval, Usage};
+
+/// Differences in cost (c) up to this size are attributed to rounding.
+pub const DEFAULT_TOLERANCE: f64 = 0.01;
+
+/// A usage record whose reported cost differs from the cost expected from
+/// the published price.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Discrepancy {
+    /// Meter channel of the usage record.
+    pub channel_identifier: ChannelId,
+    /// Type of the channel.
+    pub channel_type: ChannelType,
+    /// NEM time of the interval.
+    pub nem_time: Timestamp,
+    /// Energy of the usage record (kWh).
+    pub kwh: f64,
+    /// Published price of the interval (c/kWh).
+    pub per_kwh: f64,
+    /// Cost reported with the usage (c).
+    pub reported: f64,
+    /// Cost expected from the energy and the published price (c).
+    pub expected: f64,
+}
+
+impl Discrepancy {
+    /// Returns how much more was reported than expected (c).
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Comparing costs")]
+    pub fn difference(&self) -> f64 {
+        self.reported - self.expected
+    }
+}
+
+/// Result of reconciling usage with prices.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Reconciliation {
+    /// Number of usage records matched with a price interval.
+    pub matched: usize,
+    /// Number of usage records without a matching price interval.
+    pub unmatched: usize,
+    /// Total cost reported with the matched usage records (c).
+    pub reported_cost: f64,
+    /// Total cost expected for the matched usage records (c).
+    pub expected_cost: f64,
+    /// Matched records whose cost differs by more than the tolerance.
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl Reconciliation {
+    /// Returns `true` if every usage record was matched and none differed.
+    #[must_use]
+    #[inline]
+    pub fn is_consistent(&self) -> bool {
+        self.unmatched == 0 && self.discrepancies.is_empty()
+    }
+}
+
+/// Reconcile the reported cost of usage records with the published prices,
+/// attributing differences up to [`DEFAULT_TOLERANCE`] to rounding.
+#[must_use]
+#[inline]
+pub fn reconcile(usage: &[Usage], prices: &[Interval]) -> Reconciliation {
+    reconcile_with_tolerance(usage, prices, DEFAULT_TOLERANCE)
+}
+
+/// Reconcile the reported cost of usage records with the published prices,
+/// attributing differences up to `tolerance` (c) to rounding.
+///
+/// The expected cost of a record is its energy multiplied by the retail
+/// price. Feed-in prices are negative when exports are paid, so the expected
+/// cost of an export is its (positive) energy multiplied by the feed-in
+/// price, making credits negative as in the reported cost.
+#[must_use]
+#[inline]
+#[expect(clippy::float_arithmetic, reason = "Computing costs")]
+pub fn reconcile_with_tolerance(
+    usage: &[Usage],
+    prices: &[Interval],
+    tolerance: f64,
+) -> Reconciliation {
+    let published: BTreeMap<((u8, &str), Timestamp), f64> = prices
+        .iter()
+        .filter_map(Interval::as_base_interval)
+        .map(|base| {
+            (
+                (channel_order(&base.channel_type), base.nem_time),
+                base.per_kwh,
+            )
+        })
+        .collect();
+
+    let mut report = Reconciliation::default();
+    for record in usage {
+        let key = (
+            channel_order(&record.base.channel_type),
+            record.base.nem_time,
+        );
+        let Some(per_kwh) = published.get(&key).copied() else {
+            report.unmatched = report.unmatched.saturating_add(1);
+            continue;
+        };
+        let expected = energy_cost(&record.base.channel_type, record.kwh, per_kwh);
+        report.matched = report.matched.saturating_add(1);
+        report.reported_cost += record.cost;
+        report.expected_cost += expected;
+        if (record.cost - expected).abs() > tolerance {
+            report.discrepancies.push(Discrepancy {
+                channel_identifier: record.channel_identifier.clone(),
+                channel_type: record.base.channel_type.clone(),
+                nem_time: record.base.nem_time,
+                kwh: record.kwh,
+                per_kwh,
+                reported: record.cost,
+                expected,
+            });
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a price interval.
+    fn price(time: &str, channel_type: &str, per_kwh: f64) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "perKwh": per_kwh,
+            "nemTime": time,
+            "startTime": time,
+            "endTime": time,
+            "channelType": channel_type
+        }))
+    }
+
+    /// Parse a usage record.
+    fn usage(time: &str, channel_type: &str, kwh: f64, cost: f64) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "perKwh": 0.0,
+            "nemTime": time,
+            "startTime": time,
+            "endTime": time,
+            "channelType": channel_type,
+            "kwh": kwh,
+            "cost": cost
+        }))
+    }
+
+    #[test]
+    fn reports_discrepancies() {
+        let prices = vec![
+            price("2021-05-05T12:00:00+10:00", "general", 20.0),
+            price("2021-05-05T12:30:00+10:00", "general", 30.0),
+            price("2021-05-05T12:00:00+10:00", "feedIn", -5.0),
+        ];
+        let records = vec![
+            usage("2021-05-05T12:00:00+10:00", "general", 1.5, 30.0),
+            usage("2021-05-05T12:30:00+10:00", "general", 1.0, 33.0),
+            usage("2021-05-05T12:00:00+10:00", "feedIn", -2.0, -10.0),
+            usage("2021-05-05T13:00:00+10:00", "general", 1.0, 25.0),
+        ];
+
+        let report = reconcile(&records, &prices);
+        assert_eq!(report.matched, 3);
+        assert_eq!(report.unmatched, 1);
+        assert!(!report.is_consistent());
+        assert!((52.99_f64..53.01_f64).contains(&report.reported_cost));
+        assert!((49.99_f64..50.01_f64).contains(&report.expected_cost));
+
+        let differences: Vec<_> = report
+            .discrepancies
+            .iter()
+            .map(|discrepancy| (discrepancy.expected, discrepancy.difference()))
+            .collect();
+        assert_eq!(differences, vec![(30.0_f64, 3.0_f64)]);
+
+        let lenient = reconcile_with_tolerance(&records, &prices, 5.0);
+        assert!(lenient.discrepancies.is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+//! # Renewables Analysis
+//!
+//! Aggregations over historical renewables data.
+
+use alloc::vec::Vec;
+
+use jiff::civil::Time;
+
+use crate::models::{BaseRenewable, Resolution};
+
+use super::{MINUTES_PER_DAY, nem_time_of_day, slot_index, slot_start};
+
+/// Average renewables percentage by time of day.
+///
+/// The profile divides the day (in NEM time) into slots of the given
+/// [`Resolution`], and averages the renewables percentage of all intervals
+/// starting within each slot. This produces a typical 24-hour curve which can
+/// be used to pre-plan loads when no forecast is available.
+///
+/// The lookback window is determined by the data provided; for example, the
+/// last week of data as returned by
+/// [`Amber::renewables_history`](crate::Amber::renewables_history).
+///
+/// # Example
+///
+/// ```
+/// use amber_api::analysis::renewables::RenewablesProfile;
+/// use amber_api::models::{ActualRenewable, Resolution};
+///
+/// # fn example(history: &[ActualRenewable]) {
+/// let profile = RenewablesProfile::new(
+///     history.iter().map(|renewable| &renewable.base),
+///     Resolution::ThirtyMinute,
+/// );
+///
+/// if let Some(slot) = profile.best_slot() {
+///     println!("Greenest time of day: {}", slot.start);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenewablesProfile {
+    /// Width of each slot.
+    resolution: Resolution,
+    /// One slot per interval of the day, in chronological order.
+    slots: Vec<ProfileSlot>,
+}
+
+/// A single time-of-day slot within a [`RenewablesProfile`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct ProfileSlot {
+    /// Start of the slot (in NEM time).
+    pub start: Time,
+    /// Average renewables percentage, or `None` if no data fell in this slot.
+    pub mean: Option<f64>,
+    /// Number of intervals which contributed to the average.
+    pub samples: u32,
+}
+
+impl RenewablesProfile {
+    /// Build a profile from renewables data.
+    ///
+    /// Each entry is assigned to the slot containing its start time, so data
+    /// with a finer resolution than the profile is averaged into the
+    /// containing slot.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Averaging renewables percentages")]
+    pub fn new<'a, I>(renewables: I, resolution: Resolution) -> Self
+    where
+        I: IntoIterator<Item = &'a BaseRenewable>,
+    {
+        let slot_minutes = u32::from(resolution);
+        let slot_count = MINUTES_PER_DAY.checked_div(slot_minutes).unwrap_or(0);
+        let mut sums: Vec<(f64, u32)> =
+            core::iter::repeat_n((0.0_f64, 0_u32), usize::try_from(slot_count).unwrap_or(0))
+                .collect();
+
+        for renewable in renewables {
+            let Some(index) = slot_index(nem_time_of_day(renewable.start_time), slot_minutes)
+            else {
+                continue;
+            };
+            if let Some((sum, count)) = sums.get_mut(index) {
+                *sum += renewable.renewables;
+                *count = count.saturating_add(1);
+            }
+        }
+
+        let slots = sums
+            .into_iter()
+            .zip(0_u32..)
+            .map(|((sum, samples), index)| ProfileSlot {
+                start: slot_start(index, slot_minutes),
+                mean: (samples > 0).then(|| sum / f64::from(samples)),
+                samples,
+            })
+            .collect();
+
+        Self { resolution, slots }
+    }
+
+    /// Returns the resolution of the profile.
+    #[must_use]
+    #[inline]
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Returns all slots of the profile in chronological order.
+    #[must_use]
+    #[inline]
+    pub fn slots(&self) -> &[ProfileSlot] {
+        &self.slots
+    }
+
+    /// Returns the slot containing the given time of day (in NEM time).
+    #[must_use]
+    #[inline]
+    pub fn slot_at(&self, time: Time) -> Option<&ProfileSlot> {
+        let index = slot_index(time, u32::from(self.resolution))?;
+        self.slots.get(index)
+    }
+
+    /// Returns the average renewables percentage at the given time of day (in
+    /// NEM time), if any data is available for that slot.
+    #[must_use]
+    #[inline]
+    pub fn mean_at(&self, time: Time) -> Option<f64> {
+        let slot = self.slot_at(time)?;
+        slot.mean
+    }
+
+    /// Returns the slot with the highest average renewables percentage.
+    #[must_use]
+    #[inline]
+    pub fn best_slot(&self) -> Option<&ProfileSlot> {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.mean.map(|mean| (slot, mean)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(slot, _)| slot)
+    }
+
+    /// Returns the slot with the lowest average renewables percentage.
+    #[must_use]
+    #[inline]
+    pub fn worst_slot(&self) -> Option<&ProfileSlot> {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.mean.map(|mean| (slot, mean)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(slot, _)| slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use jiff::{Timestamp, civil::Date};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::RenewableDescriptor;
+
+    /// Create renewables data for a 30-minute interval starting at `start`.
+    fn renewable(start: &str, renewables: f64) -> BaseRenewable {
+        let start_time: Timestamp = start.parse().expect("valid timestamp");
+        BaseRenewable {
+            duration: 30,
+            date: Date::constant(2025, 1, 1),
+            nem_time: start_time,
+            start_time,
+            end_time: start_time,
+            renewables,
+            descriptor: RenewableDescriptor::Ok,
+        }
+    }
+
+    #[test]
+    fn profile_averages_by_time_of_day() {
+        let data = vec![
+            // 10:00 NEM time on two different days
+            renewable("2025-01-01T00:00:00Z", 40.0),
+            renewable("2025-01-02T00:00:00Z", 60.0),
+            // 10:05 NEM time falls in the same 30-minute slot
+            renewable("2025-01-03T00:05:00Z", 80.0),
+            // 22:30 NEM time
+            renewable("2025-01-01T12:30:00Z", 10.0),
+        ];
+        let profile = RenewablesProfile::new(&data, Resolution::ThirtyMinute);
+
+        assert_eq!(profile.slots().len(), 48);
+        let slot = profile
+            .slot_at(Time::constant(10, 15, 0, 0))
+            .expect("slot exists");
+        assert_eq!(slot.start, Time::constant(10, 0, 0, 0));
+        assert_eq!(slot.samples, 3);
+        assert_eq!(slot.mean, Some(60.0_f64));
+        assert_eq!(
+            profile.mean_at(Time::constant(22, 30, 0, 0)),
+            Some(10.0_f64)
+        );
+        assert_eq!(profile.mean_at(Time::constant(0, 0, 0, 0)), None);
+
+        assert_eq!(
+            profile.best_slot().map(|best| best.start),
+            Some(Time::constant(10, 0, 0, 0))
+        );
+        assert_eq!(
+            profile.worst_slot().map(|worst| worst.start),
+            Some(Time::constant(22, 30, 0, 0))
+        );
+    }
+
+    #[test]
+    fn empty_profile() {
+        let profile = RenewablesProfile::new([], Resolution::FiveMinute);
+        assert_eq!(profile.slots().len(), 288);
+        assert_eq!(profile.best_slot(), None);
+    }
+}
@@ -0,0 +1,453 @@
+//! # Resampling
+//!
+//! Depending on the site and the requested resolution, the API returns
+//! 5-minute or 30-minute intervals. [`resample_usage`] and
+//! [`resample_intervals`] merge intervals into coarser [`Bucket`]s (30 minutes,
+//! an hour or a day) so analyses behave the same whichever resolution was
+//! fetched.
+//!
+//! Within each bucket the energy and cost of usage are summed, and prices are
+//! averaged with the appropriate weights: usage prices are weighted by the
+//! energy of each record (so the price is what the energy actually cost), and
+//! interval prices and renewables are weighted by the length of each interval.
+//!
+//! ```
+//! use amber_api::analysis::resample::{Bucket, resample_usage};
+//! use amber_api::models::Usage;
+//!
+//! # fn example(usage: &[Usage]) {
+//! for record in resample_usage(usage, Bucket::ThirtyMinutes) {
+//!     println!("{}: {:.2} kWh for {:.2}c", record.base.start_time, record.kwh, record.cost);
+//! }
+//! # }
+//! ```
+//!
+//! Buckets are aligned in NEM time, so daily buckets match the
+//! [`date`](crate::models::BaseInterval::date) of each interval. A bucket spans
+//! its full length even if some of its intervals are missing; use
+//! [`coverage`](super::coverage) to check the data first.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use jiff::{SignedDuration, Timestamp};
+use serde::{Deserialize, Serialize};
+
+use super::{NEM_OFFSET, channel_order};
+use crate::models::{
+    ActualInterval, BaseInterval, CurrentInterval, ForecastInterval, Interval, Usage, UsageQuality,
+};
+
+/// Length of the buckets intervals are merged into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Bucket {
+    /// Half-hour buckets, starting on the hour and half-hour.
+    ThirtyMinutes,
+    /// Hourly buckets.
+    Hour,
+    /// Daily buckets, from midnight NEM time.
+    Day,
+}
+
+impl Bucket {
+    /// Returns the length of the bucket in minutes.
+    #[must_use]
+    #[inline]
+    pub const fn minutes(self) -> u32 {
+        match self {
+            Self::ThirtyMinutes => 30,
+            Self::Hour => 60,
+            Self::Day => 24 * 60,
+        }
+    }
+
+    /// Returns the start of the bucket containing the timestamp.
+    #[must_use]
+    #[inline]
+    pub fn start(self, timestamp: Timestamp) -> Timestamp {
+        let length = i64::from(self.minutes()).saturating_mul(60);
+        let offset = i64::from(NEM_OFFSET.seconds());
+        let local = timestamp.as_second().saturating_add(offset);
+        let start = local
+            .saturating_sub(local.checked_rem_euclid(length).unwrap_or(0))
+            .saturating_sub(offset);
+        Timestamp::from_second(start).unwrap_or(timestamp)
+    }
+
+    /// Returns the end of the bucket starting at the timestamp.
+    fn end(self, start: Timestamp) -> Timestamp {
+        start
+            .checked_add(SignedDuration::from_mins(i64::from(self.minutes())))
+            .unwrap_or(start)
+    }
+}
+
+/// Running totals of the intervals merged into a bucket.
+#[derive(Debug, Clone)]
+struct Accumulator {
+    /// The most expensive interval of the bucket, providing the descriptor,
+    /// spike status and tariff information of the bucket.
+    peak: BaseInterval,
+    /// Total length of the merged intervals (min).
+    minutes: f64,
+    /// Sum of prices weighted by length.
+    per_kwh_minutes: f64,
+    /// Sum of spot prices weighted by length.
+    spot_minutes: f64,
+    /// Sum of renewables weighted by length.
+    renewables_minutes: f64,
+    /// Total magnitude of the energy merged (kWh).
+    energy: f64,
+    /// Sum of prices weighted by energy.
+    per_kwh_energy: f64,
+    /// Sum of spot prices weighted by energy.
+    spot_energy: f64,
+    /// Total energy (kWh).
+    kwh: f64,
+    /// Total cost (c).
+    cost: f64,
+    /// Whether any merged usage was estimated.
+    estimated: bool,
+}
+
+impl Accumulator {
+    /// Start a bucket from its first interval.
+    fn new(base: &BaseInterval) -> Self {
+        Self {
+            peak: base.clone(),
+            minutes: 0.0,
+            per_kwh_minutes: 0.0,
+            spot_minutes: 0.0,
+            renewables_minutes: 0.0,
+            energy: 0.0,
+            per_kwh_energy: 0.0,
+            spot_energy: 0.0,
+            kwh: 0.0,
+            cost: 0.0,
+            estimated: false,
+        }
+    }
+
+    /// Merge an interval, and its usage if any, into the bucket.
+    #[expect(clippy::float_arithmetic, reason = "Computing weighted sums")]
+    fn add(&mut self, base: &BaseInterval, usage: Option<&Usage>) {
+        if base.per_kwh > self.peak.per_kwh {
+            self.peak = base.clone();
+        }
+        let minutes = f64::from(base.duration);
+        self.minutes += minutes;
+        self.per_kwh_minutes += base.per_kwh * minutes;
+        self.spot_minutes += base.spot_per_kwh * minutes;
+        self.renewables_minutes += base.renewables * minutes;
+        if let Some(record) = usage {
+            let energy = record.kwh.abs();
+            self.energy += energy;
+            self.per_kwh_energy += base.per_kwh * energy;
+            self.spot_energy += base.spot_per_kwh * energy;
+            self.kwh += record.kwh;
+            self.cost += record.cost;
+            self.estimated |= record.quality == UsageQuality::Estimated;
+        }
+    }
+
+    /// Returns the merged interval spanning the bucket.
+    ///
+    /// Prices are weighted by energy when `by_energy` is set and the bucket
+    /// has any energy, and by length otherwise.
+    #[expect(clippy::float_arithmetic, reason = "Computing weighted averages")]
+    fn finish(self, bucket: Bucket, start: Timestamp, by_energy: bool) -> BaseInterval {
+        let end = bucket.end(start);
+        let (per_kwh, spot_per_kwh) = if by_energy && self.energy > 0.0_f64 {
+            (
+                self.per_kwh_energy / self.energy,
+                self.spot_energy / self.energy,
+            )
+        } else if self.minutes > 0.0_f64 {
+            (
+                self.per_kwh_minutes / self.minutes,
+                self.spot_minutes / self.minutes,
+            )
+        } else {
+            (self.peak.per_kwh, self.peak.spot_per_kwh)
+        };
+        let renewables = if self.minutes > 0.0_f64 {
+            self.renewables_minutes / self.minutes
+        } else {
+            self.peak.renewables
+        };
+        BaseInterval {
+            duration: bucket.minutes(),
+            spot_per_kwh,
+            per_kwh,
+            date: NEM_OFFSET.to_datetime(start).date(),
+            nem_time: end,
+            start_time: start,
+            end_time: end,
+            renewables,
+            ..self.peak
+        }
+    }
+}
+
+/// Buckets of a channel, keyed by channel order, identifier and start.
+type Buckets<'a> = BTreeMap<((u8, &'a str), Option<&'a str>, Timestamp), Accumulator>;
+
+/// Merge usage records into buckets, per meter channel.
+///
+/// The energy and cost of each bucket are the sums of its records, and its
+/// prices are averaged weighted by the energy of each record (or by length
+/// if no energy was recorded). A bucket is estimated if any of its records
+/// is. The descriptor, spike status and tariff information are those of the
+/// most expensive record.
+///
+/// Records are returned by channel, in the order used by the API, then in
+/// chronological order.
+#[must_use]
+#[inline]
+pub fn resample_usage(usage: &[Usage], bucket: Bucket) -> Vec<Usage> {
+    let mut buckets: Buckets<'_> = BTreeMap::new();
+    let mut channels = BTreeMap::new();
+    for record in usage {
+        let base = &record.base;
+        let identifier = record.channel_identifier.as_str();
+        channels
+            .entry(identifier)
+            .or_insert_with(|| record.channel_identifier.clone());
+        buckets
+            .entry((
+                channel_order(&base.channel_type),
+                Some(identifier),
+                bucket.start(base.start_time),
+            ))
+            .or_insert_with(|| Accumulator::new(base))
+            .add(base, Some(record));
+    }
+
+    buckets
+        .into_iter()
+        .filter_map(|((_, identifier, start), accumulator)| {
+            let channel_identifier = channels.get(identifier?)?.clone();
+            let (kwh, cost, estimated) = (accumulator.kwh, accumulator.cost, accumulator.estimated);
+            Some(Usage {
+                base: accumulator.finish(bucket, start, true),
+                channel_identifier,
+                kwh,
+                quality: if estimated {
+                    UsageQuality::Estimated
+                } else {
+                    UsageQuality::Billable
+                },
+                cost,
+            })
+        })
+        .collect()
+}
+
+/// Merge price intervals into buckets, per channel type.
+///
+/// Prices and renewables are averaged weighted by the length of each
+/// interval. The descriptor, spike status and tariff information are those
+/// of the most expensive interval.
+///
+/// A bucket containing the current interval is a current interval, taking its
+/// estimate flag; otherwise a bucket containing any forecast is a forecast,
+/// and a bucket of only actual intervals is actual. Price ranges and advanced
+/// prices cannot be merged and are dropped. Intervals of an unknown type are
+/// ignored.
+///
+/// Intervals are returned by channel, in the order used by the API, then in
+/// chronological order.
+#[must_use]
+#[inline]
+pub fn resample_intervals(intervals: &[Interval], bucket: Bucket) -> Vec<Interval> {
+    let mut buckets: Buckets<'_> = BTreeMap::new();
+    // Whether each bucket has a forecast, and the estimate flag of the
+    // current interval it contains
+    let mut kinds: BTreeMap<_, (bool, Option<bool>)> = BTreeMap::new();
+    for interval in intervals {
+        let Some(base) = interval.as_base_interval() else {
+            continue;
+        };
+        let key = (
+            channel_order(&base.channel_type),
+            None,
+            bucket.start(base.start_time),
+        );
+        buckets
+            .entry(key)
+            .or_insert_with(|| Accumulator::new(base))
+            .add(base, None);
+        let kind = kinds.entry(key).or_default();
+        match interval {
+            Interval::ForecastInterval(_) => kind.0 = true,
+            Interval::CurrentInterval(current) => kind.1 = Some(current.estimate),
+            Interval::ActualInterval(_) | Interval::Unknown(_) => {}
+        }
+    }
+
+    buckets
+        .into_iter()
+        .map(|(key, accumulator)| {
+            let base = accumulator.finish(bucket, key.2, false);
+            match kinds.get(&key).copied().unwrap_or_default() {
+                (_, Some(estimate)) => Interval::CurrentInterval(CurrentInterval {
+                    base,
+                    range: None,
+                    estimate,
+                    advanced_price: None,
+                }),
+                (true, None) => Interval::ForecastInterval(ForecastInterval {
+                    base,
+                    range: None,
+                    advanced_price: None,
+                }),
+                (false, None) => Interval::ActualInterval(ActualInterval { base }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::ChannelType;
+
+    /// The timestamp the given number of minutes after 02:00 UTC.
+    fn at(minute: u8) -> Timestamp {
+        let base: Timestamp = "2021-05-05T02:00:00Z".parse().expect("valid timestamp");
+        base.checked_add(SignedDuration::from_mins(i64::from(minute)))
+            .expect("timestamp in range")
+    }
+
+    /// Parse a 5-minute interval of the given type starting at the given
+    /// minute past 02:00 UTC.
+    fn interval(kind: &str, minute: u8, per_kwh: f64) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": kind,
+            "duration": 5,
+            "perKwh": per_kwh,
+            "nemTime": at(minute.saturating_add(5)),
+            "startTime": at(minute),
+            "endTime": at(minute.saturating_add(5)),
+            "renewables": 40.0,
+            "descriptor": if per_kwh > 30.0 { "high" } else { "low" },
+            "estimate": true
+        }))
+    }
+
+    /// Parse a 5-minute usage record starting at the given minute past 02:00
+    /// UTC.
+    fn usage(minute: u8, per_kwh: f64, kwh: f64, cost: f64) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "duration": 5,
+            "perKwh": per_kwh,
+            "nemTime": at(minute.saturating_add(5)),
+            "startTime": at(minute),
+            "endTime": at(minute.saturating_add(5)),
+            "renewables": 40.0,
+            "kwh": kwh,
+            "quality": if minute == 25 { "estimated" } else { "billable" },
+            "cost": cost
+        }))
+    }
+
+    #[test]
+    fn bucket_bounds() {
+        assert_eq!(Bucket::ThirtyMinutes.start(at(45)), at(30));
+        assert_eq!(Bucket::Hour.start(at(45)), at(0));
+        // Midnight NEM time is 14:00 UTC on the previous day
+        assert_eq!(
+            Bucket::Day.start(at(45)),
+            "2021-05-04T14:00:00Z"
+                .parse::<Timestamp>()
+                .expect("valid timestamp")
+        );
+    }
+
+    #[test]
+    fn resamples_usage() {
+        let records = vec![
+            usage(0, 20.0, 1.0, 20.0),
+            usage(5, 40.0, 3.0, 120.0),
+            usage(25, 10.0, 0.0, 0.0),
+            usage(30, 30.0, 2.0, 60.0),
+        ];
+        let resampled = resample_usage(&records, Bucket::ThirtyMinutes);
+        let summary: Vec<_> = resampled
+            .iter()
+            .map(|record| {
+                (
+                    record.base.start_time,
+                    record.base.end_time,
+                    record.base.duration,
+                    record.base.per_kwh,
+                    record.kwh,
+                    record.cost,
+                    record.quality.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                // Energy-weighted price: (20 * 1 + 40 * 3) / 4
+                (
+                    at(0),
+                    at(30),
+                    30,
+                    35.0_f64,
+                    4.0_f64,
+                    140.0_f64,
+                    UsageQuality::Estimated
+                ),
+                (
+                    at(30),
+                    at(60),
+                    30,
+                    30.0_f64,
+                    2.0_f64,
+                    60.0_f64,
+                    UsageQuality::Billable
+                ),
+            ]
+        );
+        assert_eq!(
+            resampled.first().map(|record| record.base.renewables),
+            Some(40.0_f64)
+        );
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "The averaged prices are exact")]
+    fn resamples_intervals() {
+        let intervals = vec![
+            interval("ActualInterval", 0, 20.0),
+            interval("ActualInterval", 5, 40.0),
+            interval("CurrentInterval", 10, 30.0),
+            interval("ForecastInterval", 15, 30.0),
+            interval("ForecastInterval", 30, 30.0),
+        ];
+        let resampled = resample_intervals(&intervals, Bucket::ThirtyMinutes);
+        assert_eq!(resampled.len(), 2);
+
+        let first = resampled
+            .first()
+            .and_then(Interval::as_current_interval)
+            .expect("current interval");
+        // Time-weighted price: (20 + 40 + 30 + 30) / 4
+        assert_eq!(first.per_kwh, 30.0_f64);
+        assert_eq!(first.duration, 30);
+        assert_eq!(first.channel_type, ChannelType::General);
+        assert_eq!(first.descriptor, crate::models::PriceDescriptor::High);
+        assert!(first.estimate);
+        assert!(resampled.get(1).is_some_and(Interval::is_forecast_interval));
+
+        let hourly = resample_intervals(&intervals, Bucket::Hour);
+        assert_eq!(hourly.len(), 1);
+    }
+}
@@ -0,0 +1,164 @@
+//! # Daily Summaries
+//!
+//! Condenses a day of usage into the few figures most people care about: how
+//! much energy was bought and sold, and what it cost. A [`DailySummary`]
+//! renders as a one-line report such as
+//! `2021-05-04: $3.52 from 12.34 kWh (5.60 kWh exported)`.
+
+use core::fmt;
+
+use jiff::civil::Date;
+use serde::{Deserialize, Serialize};
+
+use super::CentsTotal;
+use crate::{
+    models::{PriceView, Usage},
+    precision::{DisplayPrecision, PrecisionPolicy},
+    units::KilowattHours,
+};
+
+/// Energy and cost totals of a single (NEM) day.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DailySummary {
+    /// The day summarised.
+    pub date: Date,
+    /// Energy imported from the grid.
+    pub imported_kwh: KilowattHours,
+    /// Energy exported to the grid, as a positive number.
+    pub exported_kwh: KilowattHours,
+    /// Net cost of the day in cents, including GST.
+    ///
+    /// Feed-in credits are included as negative costs.
+    pub cost: f64,
+    /// Number of usage records the summary was computed from.
+    pub records: usize,
+}
+
+impl DailySummary {
+    /// Summarise the usage records belonging to the given date.
+    ///
+    /// Records of other dates are ignored, so a longer usage history can be
+    /// passed directly.
+    #[must_use]
+    #[inline]
+    pub fn from_usage(date: Date, usage: &[Usage]) -> Self {
+        let mut summary = Self {
+            date,
+            imported_kwh: KilowattHours::ZERO,
+            exported_kwh: KilowattHours::ZERO,
+            cost: 0.0,
+            records: 0,
+        };
+        let mut cost = CentsTotal::default();
+        for record in usage.iter().filter(|record| record.base.date == date) {
+            if record.kwh < 0.0_f64 {
+                summary.exported_kwh -= record.energy();
+            } else {
+                summary.imported_kwh += record.energy();
+            }
+            cost.add(record.cost);
+            summary.records = summary.records.saturating_add(1);
+        }
+        summary.cost = cost.cents();
+        summary
+    }
+
+    /// Returns `true` if no usage records were found for the date.
+    ///
+    /// This usually means the data for the day has not been published yet.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records == 0
+    }
+
+    /// Returns the summary with its cost in the given view, such as
+    /// excluding GST.
+    #[must_use]
+    #[inline]
+    pub fn in_view(&self, view: PriceView) -> Self {
+        Self {
+            cost: view.apply(self.cost),
+            ..*self
+        }
+    }
+
+    /// Returns the net cost of the day in dollars.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Converting cents to dollars")]
+    pub fn cost_dollars(&self) -> f64 {
+        self.cost / 100.0
+    }
+}
+
+impl DisplayPrecision for DailySummary {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        let dollars = self.cost_dollars();
+        let sign = if dollars.is_sign_negative() { "-" } else { "" };
+        write!(
+            f,
+            "{}: {sign}${} from {}",
+            self.date,
+            policy.cost(dollars.abs()),
+            self.imported_kwh.display_with(policy)
+        )?;
+        if self.exported_kwh > KilowattHours::ZERO {
+            write!(f, " ({} exported)", self.exported_kwh.display_with(policy))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DailySummary {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec::Vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a usage record.
+    fn usage(date: &str, channel_type: &str, kwh: f64, cost: f64) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "date": date,
+            "channelType": channel_type,
+            "kwh": kwh,
+            "cost": cost
+        }))
+    }
+
+    #[test]
+    fn summarises_day() {
+        let records: Vec<_> = [
+            usage("2021-05-04", "general", 10.5, 300.0),
+            usage("2021-05-04", "general", 1.84, 52.0),
+            usage("2021-05-04", "feedIn", -5.6, -0.25),
+            usage("2021-05-05", "general", 100.0, 1000.0),
+        ]
+        .into();
+        let summary = DailySummary::from_usage(Date::constant(2021, 5, 4), &records);
+        assert_eq!(summary.records, 3);
+        assert!(!summary.is_empty());
+        insta::assert_snapshot!(summary.to_string(), @"2021-05-04: $3.52 from 12.34 kWh (5.60 kWh exported)");
+        insta::assert_snapshot!(
+            summary.in_view(PriceView::ExGst).to_string(),
+            @"2021-05-04: $3.20 from 12.34 kWh (5.60 kWh exported)"
+        );
+        assert_eq!(summary.in_view(PriceView::IncGst), summary);
+
+        let empty = DailySummary::from_usage(Date::constant(2021, 5, 3), &records);
+        assert!(empty.is_empty());
+        insta::assert_snapshot!(empty.to_string(), @"2021-05-03: $0.00 from 0.00 kWh");
+    }
+}
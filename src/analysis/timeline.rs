@@ -0,0 +1,215 @@
+//! # Timelines
+//!
+//! Most reports need the price and the usage of each interval side by side.
+//! The API returns them from separate endpoints, so [`timeline`] joins the two
+//! into a single chronological list of [`TimelineEntry`]s, one for each
+//! interval of each channel.
+//!
+//! With the `std` feature, [`Amber::timeline()`](crate::Amber::timeline)
+//! fetches both and joins them in one call.
+//!
+//! ```
+//! use amber_api::analysis::timeline::timeline;
+//! use amber_api::models::{Interval, Usage};
+//!
+//! # fn example(prices: &[Interval], usage: &[Usage]) {
+//! for entry in timeline(prices, usage) {
+//!     if let (Some(kwh), Some(cost)) = (entry.kwh, entry.cost) {
+//!         println!(
+//!             "{}: {kwh:.2} kWh at {:.2}c/kWh = {cost:.2}c",
+//!             entry.start_time, entry.per_kwh
+//!         );
+//!     }
+//! }
+//! # }
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use super::channel_order;
+use crate::models::{BaseInterval, ChannelType, Interval, Usage};
+
+/// Price and usage of a single interval of a channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct TimelineEntry {
+    /// Start of the interval.
+    pub start_time: Timestamp,
+    /// End of the interval.
+    pub end_time: Timestamp,
+    /// Type of the channel.
+    pub channel_type: ChannelType,
+    /// Retail price (c/kWh), including GST.
+    pub per_kwh: f64,
+    /// Wholesale spot price (c/kWh).
+    pub spot_per_kwh: f64,
+    /// Percentage of renewables in the grid.
+    pub renewables: f64,
+    /// Energy used (kWh), or `None` if no usage was recorded.
+    ///
+    /// This is the total over every meter channel of this type, and is
+    /// negative for exports.
+    pub kwh: Option<f64>,
+    /// Cost of the energy used (c), or `None` if no usage was recorded.
+    ///
+    /// Feed-in credits are negative.
+    pub cost: Option<f64>,
+}
+
+/// Join prices and usage into a single timeline.
+///
+/// Entries are matched by channel type and start time, and returned in
+/// chronological order (grouped by channel within each interval). Intervals
+/// with prices but no usage have no `kwh` or `cost`; usage records without a
+/// matching price use the price reported with the usage. Forecast intervals
+/// are ignored, as they can have no usage.
+#[must_use]
+#[inline]
+#[expect(clippy::float_arithmetic, reason = "Accumulating usage")]
+pub fn timeline(prices: &[Interval], usage: &[Usage]) -> Vec<TimelineEntry> {
+    let mut entries: BTreeMap<(Timestamp, (u8, &str)), TimelineEntry> = BTreeMap::new();
+    for base in prices
+        .iter()
+        .filter(|interval| !interval.is_forecast_interval())
+        .filter_map(Interval::as_base_interval)
+    {
+        entries
+            .entry(key(base))
+            .or_insert_with(|| TimelineEntry::from_base(base));
+    }
+    for record in usage {
+        let entry = entries
+            .entry(key(&record.base))
+            .or_insert_with(|| TimelineEntry::from_base(&record.base));
+        entry.kwh = Some(entry.kwh.unwrap_or(0.0_f64) + record.kwh);
+        entry.cost = Some(entry.cost.unwrap_or(0.0_f64) + record.cost);
+    }
+    entries.into_values().collect()
+}
+
+/// Key ordering entries chronologically, then by channel.
+fn key(base: &BaseInterval) -> (Timestamp, (u8, &str)) {
+    (base.start_time, channel_order(&base.channel_type))
+}
+
+impl TimelineEntry {
+    /// Create an entry without usage from the prices of an interval.
+    fn from_base(base: &BaseInterval) -> Self {
+        Self {
+            start_time: base.start_time,
+            end_time: base.end_time,
+            channel_type: base.channel_type.clone(),
+            per_kwh: base.per_kwh,
+            spot_per_kwh: base.spot_per_kwh,
+            renewables: base.renewables,
+            kwh: None,
+            cost: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String, vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Start and end (UTC) of each half hour from 02:00.
+    const SLOTS: [(&str, &str); 3] = [("02:00", "02:30"), ("02:30", "03:00"), ("03:00", "03:30")];
+
+    /// Returns the start and end (UTC) of the given slot.
+    fn times(slot: usize) -> (String, String) {
+        let (from, to) = SLOTS.get(slot).copied().expect("valid slot");
+        (
+            format!("2021-05-05T{from}:00Z"),
+            format!("2021-05-05T{to}:00Z"),
+        )
+    }
+
+    /// Parse a price interval.
+    fn price(kind: &str, slot: usize, channel_type: &str, per_kwh: f64) -> Interval {
+        let (start, end) = times(slot);
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": kind,
+            "perKwh": per_kwh,
+            "nemTime": end,
+            "startTime": start,
+            "endTime": end,
+            "channelType": channel_type
+        }))
+    }
+
+    /// Parse a usage record.
+    fn usage(slot: usize, channel_type: &str, channel: &str, kwh: f64, cost: f64) -> Usage {
+        let (start, end) = times(slot);
+        crate::test_fixtures::usage(serde_json::json!({
+            "perKwh": 20.0,
+            "nemTime": end,
+            "startTime": start,
+            "endTime": end,
+            "channelType": channel_type,
+            "channelIdentifier": channel,
+            "kwh": kwh,
+            "cost": cost
+        }))
+    }
+
+    #[test]
+    fn joins_prices_and_usage() {
+        let prices = vec![
+            price("ActualInterval", 1, "general", 25.0),
+            price("ActualInterval", 0, "feedIn", -5.0),
+            price("ActualInterval", 0, "general", 24.0),
+            price("ForecastInterval", 2, "general", 30.0),
+        ];
+        let records = vec![
+            usage(0, "general", "E1", 1.0, 24.0),
+            usage(0, "general", "E2", 0.5, 12.0),
+            usage(0, "feedIn", "B1", -2.0, -10.0),
+            usage(2, "controlledLoad", "E3", 1.0, 20.0),
+        ];
+
+        let entries = timeline(&prices, &records);
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.channel_type.clone(),
+                    entry.per_kwh,
+                    entry.kwh,
+                    entry.cost,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    ChannelType::General,
+                    24.0_f64,
+                    Some(1.5_f64),
+                    Some(36.0_f64)
+                ),
+                (
+                    ChannelType::FeedIn,
+                    -5.0_f64,
+                    Some(-2.0_f64),
+                    Some(-10.0_f64)
+                ),
+                (ChannelType::General, 25.0_f64, None, None),
+                (
+                    ChannelType::ControlledLoad,
+                    20.0_f64,
+                    Some(1.0_f64),
+                    Some(20.0_f64)
+                ),
+            ]
+        );
+    }
+}
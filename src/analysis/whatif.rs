@@ -0,0 +1,214 @@
+//! # What-If Pricing
+//!
+//! "What would a different plan have cost me?" can be answered from real
+//! usage by replacing the prices it was billed at. [`PricingOverrides`]
+//! describes the hypothetical prices, such as a higher margin on imports or a
+//! flat feed-in rate, and [`PricingOverrides::apply`] recomputes the cost of
+//! each usage record with them.
+//!
+//! The result is ordinary usage, so it can be passed to any other analysis,
+//! such as [`DailySummary`](super::summary::DailySummary) or
+//! [`aggregate`](super::aggregate::aggregate), and compared with the original.
+//!
+//! ```
+//! use amber_api::analysis::whatif::PricingOverrides;
+//! use amber_api::models::Usage;
+//!
+//! # fn example(usage: &[Usage]) {
+//! // Two cents more per kWh imported, and a flat 5c/kWh for exports
+//! let plan = PricingOverrides::builder()
+//!     .import_adjustment(2.0)
+//!     .feed_in_rate(-5.0)
+//!     .build();
+//!
+//! let comparison = plan.compare(usage);
+//! println!(
+//!     "Actual {:.2}c, simulated {:.2}c ({:+.2}c)",
+//!     comparison.actual_cost,
+//!     comparison.simulated_cost,
+//!     comparison.difference()
+//! );
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+
+use super::energy_cost;
+use crate::models::{
+    ActualInterval, BaseInterval, ChannelType, CurrentInterval, ForecastInterval, Interval, Usage,
+};
+
+/// Hypothetical changes to the prices of each channel.
+///
+/// Prices follow the API's conventions: they are in c/kWh including GST, and
+/// feed-in prices are negative when exports are paid. Rates replace the
+/// original price entirely, while adjustments are added to it; an adjustment
+/// is applied on top of a rate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, bon::Builder)]
+#[non_exhaustive]
+pub struct PricingOverrides {
+    /// Flat price replacing the price of imports (general and controlled
+    /// load channels).
+    pub import_rate: Option<f64>,
+    /// Amount added to the price of imports, such as a different margin.
+    ///
+    /// Defaults to 0.
+    #[builder(default)]
+    pub import_adjustment: f64,
+    /// Flat price replacing the price of exports, negative when paid.
+    pub feed_in_rate: Option<f64>,
+    /// Amount added to the price of exports.
+    ///
+    /// Defaults to 0.
+    #[builder(default)]
+    pub feed_in_adjustment: f64,
+}
+
+/// Costs of usage under the original and the hypothetical prices.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[non_exhaustive]
+pub struct PricingComparison {
+    /// Total cost reported with the usage (c).
+    pub actual_cost: f64,
+    /// Total cost under the hypothetical prices (c).
+    pub simulated_cost: f64,
+}
+
+impl PricingComparison {
+    /// Returns how much more the hypothetical prices would have cost (c).
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Comparing costs")]
+    pub fn difference(&self) -> f64 {
+        self.simulated_cost - self.actual_cost
+    }
+}
+
+impl PricingOverrides {
+    /// Returns the hypothetical price (c/kWh) of an interval.
+    ///
+    /// Channels other than imports and feed-in keep their original price.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Adjusting prices")]
+    pub fn price(&self, base: &BaseInterval) -> f64 {
+        match base.channel_type {
+            ChannelType::General | ChannelType::ControlledLoad => {
+                self.import_rate.unwrap_or(base.per_kwh) + self.import_adjustment
+            }
+            ChannelType::FeedIn => {
+                self.feed_in_rate.unwrap_or(base.per_kwh) + self.feed_in_adjustment
+            }
+            ChannelType::Other(_) => base.per_kwh,
+        }
+    }
+
+    /// Returns the usage records with their price and cost recomputed under
+    /// the hypothetical prices.
+    ///
+    /// The cost of each record is its energy multiplied by the new price.
+    /// Records are otherwise unchanged.
+    #[must_use]
+    #[inline]
+    pub fn apply(&self, usage: &[Usage]) -> Vec<Usage> {
+        usage
+            .iter()
+            .map(|record| {
+                let mut simulated = record.clone();
+                simulated.base.per_kwh = self.price(&record.base);
+                simulated.cost = energy_cost(
+                    &record.base.channel_type,
+                    record.kwh,
+                    simulated.base.per_kwh,
+                );
+                simulated
+            })
+            .collect()
+    }
+
+    /// Returns the price intervals with their retail price replaced by the
+    /// hypothetical price, such as to plan ahead under a different plan.
+    #[must_use]
+    #[inline]
+    pub fn apply_to_prices(&self, intervals: &[Interval]) -> Vec<Interval> {
+        intervals
+            .iter()
+            .map(|interval| {
+                let mut simulated = interval.clone();
+                if let Interval::ActualInterval(ActualInterval { base })
+                | Interval::ForecastInterval(ForecastInterval { base, .. })
+                | Interval::CurrentInterval(CurrentInterval { base, .. }) = &mut simulated
+                {
+                    base.per_kwh = self.price(base);
+                }
+                simulated
+            })
+            .collect()
+    }
+
+    /// Compare the total cost of usage under the original and the
+    /// hypothetical prices.
+    #[must_use]
+    #[inline]
+    pub fn compare(&self, usage: &[Usage]) -> PricingComparison {
+        PricingComparison {
+            actual_cost: usage.iter().map(|record| record.cost).sum(),
+            simulated_cost: self.apply(usage).iter().map(|record| record.cost).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a usage record of the given channel.
+    fn usage(channel_type: &str, per_kwh: f64, kwh: f64, cost: f64) -> Usage {
+        crate::test_fixtures::usage(serde_json::json!({
+            "perKwh": per_kwh,
+            "channelType": channel_type,
+            "kwh": kwh,
+            "cost": cost
+        }))
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "Simulated costs are exact products")]
+    fn recomputes_costs() {
+        let records = vec![
+            usage("general", 20.0, 2.0, 40.0),
+            usage("controlledLoad", 10.0, 1.0, 10.0),
+            usage("feedIn", -8.0, -3.0, -24.0),
+        ];
+        let plan = PricingOverrides::builder()
+            .import_adjustment(2.0)
+            .feed_in_rate(-5.0)
+            .build();
+
+        let simulated: Vec<_> = plan
+            .apply(&records)
+            .iter()
+            .map(|record| (record.base.per_kwh, record.cost))
+            .collect();
+        assert_eq!(
+            simulated,
+            vec![
+                (22.0_f64, 44.0_f64),
+                (12.0_f64, 12.0_f64),
+                (-5.0_f64, -15.0_f64)
+            ]
+        );
+
+        let comparison = plan.compare(&records);
+        assert_eq!(comparison.actual_cost, 26.0_f64);
+        assert_eq!(comparison.simulated_cost, 41.0_f64);
+        assert_eq!(comparison.difference(), 15.0_f64);
+
+        let unchanged = PricingOverrides::default().compare(&records);
+        assert_eq!(unchanged.difference(), 0.0_f64);
+    }
+}
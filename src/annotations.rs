@@ -0,0 +1,297 @@
+//! # Annotations
+//!
+//! Numbers alone rarely explain a spike in usage. An [`AnnotationLog`] keeps
+//! notes and tags (such as "dryer running" or "grid outage") against periods
+//! of time, so that archived prices and usage become an energy diary. Each
+//! [`Annotation`] covers a span of time and applies to every interval
+//! overlapping it.
+//!
+//! The log is serializable and can be stored alongside other archives in any
+//! [`Format`](crate::storage::Format). Annotations can be included in exports,
+//! such as the notes column of a [TSV export](crate::export::tsv).
+//!
+//! ```
+//! use amber_api::annotations::{Annotation, AnnotationLog};
+//! use amber_api::storage::Format;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut log = AnnotationLog::new();
+//! log.add(
+//!     Annotation::builder()
+//!         .start_time("2021-05-05T08:00:00Z".parse()?)
+//!         .end_time("2021-05-05T09:30:00Z".parse()?)
+//!         .note("Washing and drying")
+//!         .tags(vec!["dryer".to_owned()])
+//!         .build(),
+//! );
+//!
+//! assert_eq!(log.with_tag("dryer").count(), 1);
+//!
+//! let bytes = Format::Json.encode(&log)?;
+//! let restored: AnnotationLog = Format::Json.decode(&bytes)?;
+//! assert_eq!(restored, log);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::models::BaseInterval;
+
+/// A note and tags attached to a span of time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, bon::Builder)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Annotation {
+    /// Start of the annotated span.
+    pub start_time: Timestamp,
+    /// End of the annotated span.
+    pub end_time: Timestamp,
+    /// Free-form note.
+    #[builder(into)]
+    pub note: Option<String>,
+    /// Tags, such as `"dryer"` or `"outage"`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    pub tags: Vec<String>,
+}
+
+impl Annotation {
+    /// Returns `true` if the annotation overlaps the span from `start` to
+    /// `end`.
+    ///
+    /// Spans are half-open, so an annotation ending when an interval starts
+    /// does not apply to it. An empty annotation (or span) is treated as an
+    /// instant, overlapping the span (or annotation) containing it.
+    #[must_use]
+    #[inline]
+    pub fn overlaps(&self, start: Timestamp, end: Timestamp) -> bool {
+        if start == end {
+            return self.start_time <= start
+                && (start < self.end_time || self.start_time == self.end_time);
+        }
+        if self.start_time == self.end_time {
+            return start <= self.start_time && self.start_time < end;
+        }
+        self.start_time < end && start < self.end_time
+    }
+
+    /// Returns `true` if the annotation has the given tag.
+    #[must_use]
+    #[inline]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|candidate| candidate == tag)
+    }
+}
+
+impl fmt::Display for Annotation {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(note) = &self.note {
+            f.write_str(note)?;
+        }
+        for (index, tag) in self.tags.iter().enumerate() {
+            let separator = if index == 0 && self.note.is_none() {
+                ""
+            } else {
+                " "
+            };
+            write!(f, "{separator}#{tag}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A collection of annotations, ordered by start time.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AnnotationLog {
+    /// The annotations, ordered by start time.
+    annotations: Vec<Annotation>,
+}
+
+impl AnnotationLog {
+    /// Create an empty log.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of annotations.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.annotations.len()
+    }
+
+    /// Returns `true` if the log has no annotations.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.annotations.is_empty()
+    }
+
+    /// Add an annotation.
+    #[inline]
+    pub fn add(&mut self, annotation: Annotation) {
+        let index = self
+            .annotations
+            .partition_point(|existing| existing.start_time <= annotation.start_time);
+        self.annotations.insert(index, annotation);
+    }
+
+    /// Remove the annotations matching a predicate, returning how many were
+    /// removed.
+    #[inline]
+    pub fn remove_where(&mut self, mut predicate: impl FnMut(&Annotation) -> bool) -> usize {
+        let before = self.annotations.len();
+        self.annotations.retain(|annotation| !predicate(annotation));
+        before.saturating_sub(self.annotations.len())
+    }
+
+    /// Returns all annotations, ordered by start time.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, Annotation> {
+        self.annotations.iter()
+    }
+
+    /// Returns the annotations overlapping the span from `start` to `end`.
+    #[inline]
+    pub fn between(&self, start: Timestamp, end: Timestamp) -> impl Iterator<Item = &Annotation> {
+        self.annotations
+            .iter()
+            .filter(move |annotation| annotation.overlaps(start, end))
+    }
+
+    /// Returns the annotations applying to an interval.
+    #[inline]
+    pub fn for_interval<'a>(
+        &'a self,
+        interval: &BaseInterval,
+    ) -> impl Iterator<Item = &'a Annotation> {
+        self.between(interval.start_time, interval.end_time)
+    }
+
+    /// Returns the annotations with the given tag.
+    #[inline]
+    pub fn with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a Annotation> {
+        self.annotations
+            .iter()
+            .filter(move |annotation| annotation.has_tag(tag))
+    }
+}
+
+impl<'a> IntoIterator for &'a AnnotationLog {
+    type Item = &'a Annotation;
+    type IntoIter = core::slice::Iter<'a, Annotation>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Annotation> for AnnotationLog {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Annotation>>(iter: I) -> Self {
+        let mut log = Self::new();
+        log.extend(iter);
+        log
+    }
+}
+
+impl Extend<Annotation> for AnnotationLog {
+    #[inline]
+    fn extend<I: IntoIterator<Item = Annotation>>(&mut self, iter: I) {
+        for annotation in iter {
+            self.add(annotation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned as _, string::ToString, vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a timestamp on 5 May 2021.
+    fn at(time: &str) -> Timestamp {
+        alloc::format!("2021-05-05T{time}:00Z")
+            .parse()
+            .expect("valid timestamp")
+    }
+
+    /// Sample log with an outage and a dryer run.
+    fn sample() -> AnnotationLog {
+        [
+            Annotation::builder()
+                .start_time(at("08:00"))
+                .end_time(at("09:30"))
+                .note("Washing and drying")
+                .tags(vec!["dryer".to_owned(), "laundry".to_owned()])
+                .build(),
+            Annotation::builder()
+                .start_time(at("02:10"))
+                .end_time(at("02:10"))
+                .tags(vec!["outage".to_owned()])
+                .build(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn queries() {
+        let log = sample();
+        assert_eq!(log.len(), 2);
+        assert_eq!(
+            log.iter()
+                .map(|annotation| annotation.start_time)
+                .collect::<Vec<_>>(),
+            vec![at("02:10"), at("08:00")]
+        );
+
+        assert_eq!(log.between(at("02:00"), at("02:30")).count(), 1);
+        assert_eq!(log.between(at("02:30"), at("08:00")).count(), 0);
+        assert_eq!(log.between(at("09:00"), at("09:30")).count(), 1);
+        assert_eq!(log.with_tag("laundry").count(), 1);
+        assert_eq!(log.with_tag("solar").count(), 0);
+
+        let rendered: Vec<_> = log.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec!["#outage", "Washing and drying #dryer #laundry"]
+        );
+    }
+
+    #[test]
+    fn serde_and_removal() {
+        let mut log = sample();
+        let json = serde_json::to_value(&log).expect("serialises");
+        assert_eq!(
+            json.get(0),
+            Some(&serde_json::json!({
+                "startTime": "2021-05-05T02:10:00Z",
+                "endTime": "2021-05-05T02:10:00Z",
+                "note": null,
+                "tags": ["outage"]
+            }))
+        );
+        let restored: AnnotationLog = serde_json::from_value(json).expect("deserialises");
+        assert_eq!(restored, log);
+
+        assert_eq!(
+            log.remove_where(|annotation| annotation.has_tag("outage")),
+            1
+        );
+        assert_eq!(log.len(), 1);
+    }
+}
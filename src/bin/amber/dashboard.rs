@@ -0,0 +1,224 @@
+//! Live price dashboard in the terminal.
+//!
+//! The prices of a site are polled by a [`Watcher`], and each update redraws
+//! the current price (coloured by its descriptor), the share of renewables and
+//! a sparkline of the forecast. Press `q` or `Esc` to quit.
+
+use core::time::Duration;
+
+use amber_api::{
+    Amber, WatchEvent, Watcher,
+    models::{ChannelType, CurrentInterval, Interval, PriceDescriptor, SiteId, SpikeStatus},
+};
+use anyhow::Result;
+use jiff::Timestamp;
+use ratatui::{
+    Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Style, Stylize as _},
+    text::Line,
+    widgets::{Block, Gauge, Paragraph, Sparkline},
+};
+use tokio::sync::mpsc;
+
+use super::nem_time;
+
+/// State of the dashboard.
+#[derive(Debug)]
+struct Dashboard {
+    /// ID of the site shown.
+    site_id: SiteId,
+    /// Intervals of the last successful poll.
+    intervals: Vec<Interval>,
+    /// When the intervals were fetched.
+    updated: Option<Timestamp>,
+    /// Message of the error of the last poll, if it failed.
+    error: Option<String>,
+}
+
+/// Returns the colour of a price descriptor.
+#[expect(deprecated, reason = "Negative prices are shown as extremely low")]
+#[expect(
+    clippy::match_same_arms,
+    reason = "Descriptors unknown to the API are shown like those unknown to the dashboard"
+)]
+fn colour(descriptor: &PriceDescriptor) -> Color {
+    match descriptor {
+        PriceDescriptor::Negative | PriceDescriptor::ExtremelyLow | PriceDescriptor::VeryLow => {
+            Color::Green
+        }
+        PriceDescriptor::Low => Color::LightGreen,
+        PriceDescriptor::Neutral => Color::Yellow,
+        PriceDescriptor::High => Color::LightRed,
+        PriceDescriptor::Spike => Color::Red,
+        PriceDescriptor::Other(_) => Color::Gray,
+        _ => Color::Gray,
+    }
+}
+
+/// Convert a price to the height of a sparkline bar, in tenths of a cent.
+///
+/// Sparklines cannot show negative values, so negative prices are shown as
+/// empty bars.
+#[expect(
+    clippy::as_conversions,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::float_arithmetic,
+    reason = "Prices are clamped to be non-negative and rounded for display"
+)]
+fn bar(per_kwh: f64) -> u64 {
+    (per_kwh.max(0.0) * 10.0).round() as u64
+}
+
+impl Dashboard {
+    /// Returns the current interval of a channel.
+    fn current(&self, channel_type: &ChannelType) -> Option<&CurrentInterval> {
+        self.intervals
+            .iter()
+            .filter_map(Interval::as_current_interval)
+            .find(|current| current.channel_type == *channel_type)
+    }
+
+    /// Apply an update from the watcher.
+    fn update(&mut self, event: WatchEvent) {
+        match event {
+            WatchEvent::Prices { response, .. } => {
+                self.updated = Some(response.fetched_at);
+                self.intervals = response.data;
+                self.error = None;
+            }
+            WatchEvent::Failed { error, .. } => self.error = Some(error.to_string()),
+            _ => {}
+        }
+    }
+
+    /// Draw the dashboard.
+    fn render(&self, frame: &mut Frame<'_>) {
+        let [price_area, renewables_area, forecast_area, status_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let general = self.current(&ChannelType::General);
+        let mut lines = Vec::new();
+        if let Some(current) = general {
+            let mut line = Line::from(vec![
+                format!("{:.1} c/kWh  ", current.per_kwh).bold(),
+                current
+                    .descriptor
+                    .to_string()
+                    .fg(colour(&current.descriptor)),
+            ]);
+            if current.spike_status == SpikeStatus::Spike {
+                line.push_span("  SPIKE".red().bold());
+            }
+            lines.push(line);
+        } else {
+            lines.push(Line::from("Waiting for prices\u{2026}"));
+        }
+        if let Some(feed_in) = self.current(&ChannelType::FeedIn) {
+            lines.push(Line::from(format!("Feed-in: {:.1} c/kWh", feed_in.per_kwh)));
+        }
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::bordered().title(format!(" Site {} ", self.site_id))),
+            price_area,
+        );
+
+        let renewables = general.map_or(0.0_f64, |current| current.renewables);
+        #[expect(
+            clippy::float_arithmetic,
+            reason = "Converting a percentage to a ratio"
+        )]
+        let ratio = (renewables / 100.0).clamp(0.0, 1.0);
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title(" Renewables "))
+                .gauge_style(Style::default().fg(Color::Green))
+                .ratio(ratio)
+                .label(format!("{renewables:.0}%")),
+            renewables_area,
+        );
+
+        let forecast: Vec<u64> = self
+            .intervals
+            .iter()
+            .filter_map(Interval::as_forecast_interval)
+            .filter(|forecast| forecast.channel_type == ChannelType::General)
+            .map(|forecast| bar(forecast.per_kwh))
+            .collect();
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::bordered().title(" Forecast "))
+                .style(Style::default().fg(Color::Yellow))
+                .data(&forecast),
+            forecast_area,
+        );
+
+        let status = match (&self.error, self.updated) {
+            (Some(error), _) => Line::from(format!("Update failed: {error}")).red(),
+            (None, Some(updated)) => {
+                Line::from(format!("Updated {} · q to quit", nem_time(updated)))
+            }
+            (None, None) => Line::from("q to quit"),
+        };
+        frame.render_widget(status, status_area);
+    }
+}
+
+/// Show the dashboard of a site until the user quits.
+#[expect(
+    clippy::integer_division_remainder_used,
+    reason = "Used by `tokio::select!` to pick a branch"
+)]
+pub(crate) async fn run(client: Amber, site_id: SiteId, every: Duration, next: u32) -> Result<()> {
+    let mut handle = Watcher::builder()
+        .client(client)
+        .sites(vec![site_id.clone()])
+        .every(every)
+        .next(next)
+        .build()
+        .start();
+
+    // Terminal events are read on a separate thread, as reading blocks
+    let (sender, mut keys) = mpsc::unbounded_channel();
+    std::thread::spawn(move || {
+        while let Ok(event) = event::read() {
+            if sender.send(event).is_err() {
+                return;
+            }
+        }
+    });
+
+    let mut dashboard = Dashboard {
+        site_id,
+        intervals: Vec::new(),
+        updated: None,
+        error: None,
+    };
+    let mut terminal = ratatui::init();
+    let result = loop {
+        if let Err(err) = terminal.draw(|frame| dashboard.render(frame)) {
+            break Err(err.into());
+        }
+        tokio::select! {
+            Some(event) = handle.next() => dashboard.update(event),
+            Some(event) = keys.recv() => {
+                if let Event::Key(key) = event
+                    && key.kind == KeyEventKind::Press
+                    && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+                {
+                    break Ok(());
+                }
+            }
+            else => break Ok(()),
+        }
+    };
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,626 @@
+//! Command line interface to the Amber Electric API.
+//!
+//! Prints the sites, prices, usage and renewables of an account as a table,
+//! as JSON (`--json`) or as CSV (`--csv`).
+//!
+//! # Usage
+//!
+//! ```console
+//! $ export AMBER_API_KEY="your-api-key-here"
+//! $ amber sites
+//! $ amber current --next 12
+//! $ amber prices --start-date 2021-05-01 --end-date 2021-05-05 --csv
+//! $ amber usage --start-date 2021-05-01 --json
+//! $ amber renewables --state vic
+//! $ amber dashboard
+//! ```
+//!
+//! The `dashboard` command, showing the prices of a site live, requires the
+//! `dashboard` feature.
+//!
+//! Settings are read from the configuration file and environment variables
+//! (see [`Config::load()`]), with `--api-key` taking precedence. Commands which
+//! need a site use the site given by `--site`, then the `site_id` of the
+//! configuration, and otherwise the first active site of the account.
+
+#![allow(
+    clippy::print_stdout,
+    reason = "The CLI writes its output to the console"
+)]
+
+#[cfg(feature = "dashboard")]
+mod dashboard;
+
+use core::fmt;
+
+use amber_api::{
+    Amber, Config,
+    export::csv::{self, CsvOptions, TimestampFormat},
+    models::{Interval, Renewable, Resolution, Site, SiteId, State, Usage, nem_time_zone},
+};
+use anyhow::{Context as _, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use jiff::{Timestamp, civil::Date};
+
+/// Query the Amber Electric API.
+#[derive(Debug, Parser)]
+#[command(name = "amber", version)]
+struct Cli {
+    /// API key, instead of the configured one.
+    #[arg(long, global = true)]
+    api_key: Option<String>,
+    /// Print the output as JSON.
+    #[arg(long, global = true, conflicts_with = "csv")]
+    json: bool,
+    /// Print the output as CSV.
+    #[arg(long, global = true)]
+    csv: bool,
+    /// Command to run.
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Commands of the CLI.
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List the sites of the account.
+    Sites,
+    /// Show the current prices of a site.
+    Current {
+        /// ID of the site, instead of the first active site.
+        #[arg(long)]
+        site: Option<SiteId>,
+        /// Number of previous intervals to include.
+        #[arg(long)]
+        previous: Option<u32>,
+        /// Number of forecast intervals to include.
+        #[arg(long)]
+        next: Option<u32>,
+        /// Length of the intervals, in minutes.
+        #[arg(long, value_enum)]
+        resolution: Option<ResolutionArg>,
+    },
+    /// Show the prices of a site between two dates.
+    Prices {
+        /// ID of the site, instead of the first active site.
+        #[arg(long)]
+        site: Option<SiteId>,
+        /// First day (defaults to today).
+        #[arg(long)]
+        start_date: Option<Date>,
+        /// Last day (defaults to the first day).
+        #[arg(long)]
+        end_date: Option<Date>,
+        /// Length of the intervals, in minutes.
+        #[arg(long, value_enum)]
+        resolution: Option<ResolutionArg>,
+    },
+    /// Show the usage of a site between two dates.
+    Usage {
+        /// ID of the site, instead of the first active site.
+        #[arg(long)]
+        site: Option<SiteId>,
+        /// First day.
+        #[arg(long)]
+        start_date: Date,
+        /// Last day (defaults to the first day).
+        #[arg(long)]
+        end_date: Option<Date>,
+    },
+    /// Show the current renewables of a state.
+    Renewables {
+        /// State of the grid.
+        #[arg(long, value_enum)]
+        state: StateArg,
+        /// Number of previous intervals to include.
+        #[arg(long)]
+        previous: Option<u32>,
+        /// Number of forecast intervals to include.
+        #[arg(long)]
+        next: Option<u32>,
+        /// Length of the intervals, in minutes.
+        #[arg(long, value_enum)]
+        resolution: Option<ResolutionArg>,
+    },
+    /// Show the prices of a site live in the terminal.
+    #[cfg(feature = "dashboard")]
+    Dashboard {
+        /// ID of the site, instead of the first active site.
+        #[arg(long)]
+        site: Option<SiteId>,
+        /// Seconds between updates.
+        #[arg(long, default_value_t = 60)]
+        every: u64,
+        /// Number of forecast intervals to show.
+        #[arg(long, default_value_t = 24)]
+        next: u32,
+    },
+}
+
+/// State selected on the command line.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum StateArg {
+    /// New South Wales.
+    Nsw,
+    /// Victoria.
+    Vic,
+    /// Queensland.
+    Qld,
+    /// South Australia.
+    Sa,
+}
+
+impl From<StateArg> for State {
+    #[inline]
+    fn from(value: StateArg) -> Self {
+        match value {
+            StateArg::Nsw => Self::Nsw,
+            StateArg::Vic => Self::Vic,
+            StateArg::Qld => Self::Qld,
+            StateArg::Sa => Self::Sa,
+        }
+    }
+}
+
+/// Resolution selected on the command line.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ResolutionArg {
+    /// 5-minute intervals.
+    #[value(name = "5")]
+    Five,
+    /// 30-minute intervals.
+    #[value(name = "30")]
+    Thirty,
+}
+
+impl From<ResolutionArg> for Resolution {
+    #[inline]
+    fn from(value: ResolutionArg) -> Self {
+        match value {
+            ResolutionArg::Five => Self::FiveMinute,
+            ResolutionArg::Thirty => Self::ThirtyMinute,
+        }
+    }
+}
+
+/// How the output is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Output {
+    /// An aligned table.
+    Table,
+    /// Pretty-printed JSON.
+    Json,
+    /// CSV with a header row.
+    Csv,
+}
+
+/// A table of text cells, printed with aligned columns.
+#[derive(Debug)]
+struct Table {
+    /// Names of the columns.
+    header: Vec<&'static str>,
+    /// Cells of each row.
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Returns the width of each column.
+    fn widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .header
+            .iter()
+            .map(|name| name.chars().count())
+            .collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        widths
+    }
+
+    /// Render the table as CSV.
+    fn to_csv(&self) -> String {
+        let quote = |cell: &str| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_owned()
+            }
+        };
+        let mut output = String::new();
+        let header = self.header.iter().map(|name| quote(name));
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|cell| quote(cell)));
+        for line in core::iter::once(header.collect::<Vec<_>>()).chain(rows.map(Iterator::collect))
+        {
+            output.push_str(&line.join(","));
+            output.push_str("\r\n");
+        }
+        output
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.widths();
+        let header = self.header.iter().map(|name| String::from(*name)).collect();
+        for row in core::iter::once(&header).chain(&self.rows) {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            writeln!(f, "{}", cells.join("  ").trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Format a timestamp in NEM time, to the minute.
+fn nem_time(timestamp: Timestamp) -> String {
+    timestamp
+        .to_zoned(nem_time_zone())
+        .strftime("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// Returns the table of sites.
+fn sites_table(sites: &[Site]) -> Table {
+    Table {
+        header: vec!["id", "nmi", "network", "status", "active_from", "channels"],
+        rows: sites
+            .iter()
+            .map(|site| {
+                let channels: Vec<String> = site
+                    .channels
+                    .iter()
+                    .map(|channel| format!("{} ({})", channel.identifier, channel.channel_type))
+                    .collect();
+                vec![
+                    site.id.to_string(),
+                    site.nmi.to_string(),
+                    site.network.to_string(),
+                    site.status.to_string(),
+                    site.active_from
+                        .map(|date| date.to_string())
+                        .unwrap_or_default(),
+                    channels.join(", "),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Returns the table of price intervals.
+fn intervals_table(intervals: &[Interval]) -> Table {
+    Table {
+        header: vec![
+            "start",
+            "type",
+            "channel",
+            "per_kwh",
+            "spot",
+            "renewables",
+            "descriptor",
+        ],
+        rows: intervals
+            .iter()
+            .filter_map(|interval| {
+                let kind = if interval.is_actual_interval() {
+                    "actual"
+                } else if interval.is_current_interval() {
+                    "current"
+                } else {
+                    "forecast"
+                };
+                let base = interval.as_base_interval()?;
+                Some(vec![
+                    nem_time(base.start_time),
+                    kind.to_owned(),
+                    base.channel_type.to_string(),
+                    format!("{:.2}", base.per_kwh),
+                    format!("{:.2}", base.spot_per_kwh),
+                    format!("{:.0}%", base.renewables),
+                    base.descriptor.to_string(),
+                ])
+            })
+            .collect(),
+    }
+}
+
+/// Returns the table of usage.
+fn usage_table(usage: &[Usage]) -> Table {
+    Table {
+        header: vec!["start", "channel", "kwh", "cost", "per_kwh", "quality"],
+        rows: usage
+            .iter()
+            .map(|record| {
+                vec![
+                    nem_time(record.base.start_time),
+                    record.channel_identifier.to_string(),
+                    format!("{:.3}", record.kwh),
+                    format!("{:.2}", record.cost),
+                    format!("{:.2}", record.base.per_kwh),
+                    record.quality.to_string(),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Returns the table of renewables.
+fn renewables_table(renewables: &[Renewable]) -> Table {
+    Table {
+        header: vec!["start", "renewables", "descriptor"],
+        rows: renewables
+            .iter()
+            .filter_map(Renewable::as_base_renewable)
+            .map(|base| {
+                vec![
+                    nem_time(base.start_time),
+                    format!("{:.1}%", base.renewables),
+                    base.descriptor.to_string(),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Print data in the selected format.
+///
+/// CSV is rendered by `csv` if given, and from the table otherwise.
+fn print<T: serde::Serialize>(
+    output: Output,
+    data: &[T],
+    table: impl FnOnce(&[T]) -> Table,
+    csv: Option<fn(&[T], &CsvOptions) -> String>,
+) -> Result<()> {
+    match output {
+        Output::Json => println!("{}", serde_json::to_string_pretty(data)?),
+        Output::Csv => {
+            let options = CsvOptions::builder()
+                .timestamps(TimestampFormat::Nem)
+                .build();
+            match csv {
+                Some(render) => print!("{}", render(data, &options)),
+                None => print!("{}", table(data).to_csv()),
+            }
+        }
+        Output::Table => print!("{}", table(data)),
+    }
+    Ok(())
+}
+
+/// Returns the selected site, the configured site, or the first active site of
+/// the account.
+async fn site_id(client: &Amber, config: &Config, site: Option<SiteId>) -> Result<SiteId> {
+    if let Some(selected) = site.or_else(|| config.site_id.clone()) {
+        return Ok(selected);
+    }
+    client
+        .active_sites()
+        .await?
+        .into_iter()
+        .next()
+        .map(|first| first.id)
+        .context("No active sites found; select one with --site")
+}
+
+#[tokio::main]
+#[expect(clippy::too_many_lines, reason = "Dispatches every subcommand")]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let output = if cli.json {
+        Output::Json
+    } else if cli.csv {
+        Output::Csv
+    } else {
+        Output::Table
+    };
+    let mut config = Config::load()?;
+    if let Some(api_key) = cli.api_key {
+        config.api_key = Some(api_key);
+        config.api_key_file = None;
+    }
+    let client = config.builder()?.build()?;
+
+    match cli.command {
+        Command::Sites => {
+            let sites = client.sites().await?;
+            print(output, &sites, sites_table, None)?;
+        }
+        Command::Current {
+            site,
+            previous,
+            next,
+            resolution,
+        } => {
+            let site_id = site_id(&client, &config, site).await?;
+            let intervals = client
+                .current_prices()
+                .site_id(&site_id)
+                .maybe_previous(previous)
+                .maybe_next(next)
+                .maybe_resolution(resolution.map(Resolution::from))
+                .call()
+                .await?;
+            print(
+                output,
+                &intervals,
+                intervals_table,
+                Some(|data, options| csv::intervals(data, options).to_string()),
+            )?;
+        }
+        Command::Prices {
+            site,
+            start_date,
+            end_date,
+            resolution,
+        } => {
+            let site_id = site_id(&client, &config, site).await?;
+            let intervals = client
+                .prices()
+                .site_id(&site_id)
+                .maybe_start_date(start_date)
+                .maybe_end_date(end_date.or(start_date))
+                .maybe_resolution(resolution.map(Resolution::from))
+                .call()
+                .await?;
+            print(
+                output,
+                &intervals,
+                intervals_table,
+                Some(|data, options| csv::intervals(data, options).to_string()),
+            )?;
+        }
+        Command::Usage {
+            site,
+            start_date,
+            end_date,
+        } => {
+            let site_id = site_id(&client, &config, site).await?;
+            let usage = client
+                .usage()
+                .site_id(&site_id)
+                .start_date(start_date)
+                .end_date(end_date.unwrap_or(start_date))
+                .call()
+                .await?;
+            print(
+                output,
+                &usage,
+                usage_table,
+                Some(|data, options| csv::usage(data, options).to_string()),
+            )?;
+        }
+        Command::Renewables {
+            state,
+            previous,
+            next,
+            resolution,
+        } => {
+            let renewables = client
+                .current_renewables()
+                .state(state.into())
+                .maybe_previous(previous)
+                .maybe_next(next)
+                .maybe_resolution(resolution.map(Resolution::from))
+                .call()
+                .await?;
+            print(
+                output,
+                &renewables,
+                renewables_table,
+                Some(|data, options| csv::renewables(data, options).to_string()),
+            )?;
+        }
+        #[cfg(feature = "dashboard")]
+        Command::Dashboard { site, every, next } => {
+            let site_id = site_id(&client, &config, site).await?;
+            dashboard::run(
+                client,
+                site_id,
+                core::time::Duration::from_secs(every),
+                next,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory as _;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn parses_arguments() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::try_parse_from([
+            "amber",
+            "current",
+            "--next",
+            "12",
+            "--resolution",
+            "5",
+            "--json",
+        ])
+        .expect("valid arguments");
+        assert!(cli.json);
+        assert!(matches!(
+            cli.command,
+            Command::Current {
+                site: None,
+                previous: None,
+                next: Some(12),
+                resolution: Some(ResolutionArg::Five),
+            }
+        ));
+
+        let renewables = Cli::try_parse_from(["amber", "renewables", "--state", "vic"])
+            .expect("valid arguments");
+        assert!(matches!(
+            renewables.command,
+            Command::Renewables {
+                state: StateArg::Vic,
+                ..
+            }
+        ));
+
+        Cli::try_parse_from(["amber", "--json", "--csv", "sites"])
+            .expect_err("output formats conflict");
+        Cli::try_parse_from(["amber", "usage"]).expect_err("dates are required");
+        Cli::try_parse_from(["amber", "current", "--resolution", "15"])
+            .expect_err("unsupported resolution");
+    }
+
+    #[test]
+    fn formats_nem_time() {
+        let timestamp: Timestamp = "2021-05-05T14:00:00Z".parse().expect("valid timestamp");
+        assert_eq!(nem_time(timestamp), "2021-05-06 00:00");
+    }
+
+    #[tokio::test]
+    async fn renders_renewables() {
+        let renewables = Amber::offline()
+            .current_renewables()
+            .state(State::Vic)
+            .call()
+            .await
+            .expect("renewables");
+        let table = renewables_table(&renewables);
+        assert_eq!(
+            table.to_string(),
+            "start             renewables  descriptor\n\
+             2021-05-05 11:30  44.5%       ok\n\
+             2021-05-05 12:00  45.5%       ok\n\
+             2021-05-05 12:30  51.0%       great\n"
+        );
+        assert_eq!(
+            table.to_csv(),
+            "start,renewables,descriptor\r\n\
+             2021-05-05 11:30,44.5%,ok\r\n\
+             2021-05-05 12:00,45.5%,ok\r\n\
+             2021-05-05 12:30,51.0%,great\r\n"
+        );
+    }
+
+    #[test]
+    fn quotes_csv_cells() {
+        let table = Table {
+            header: vec!["channels"],
+            rows: vec![vec!["E1 (general), B1 (\"feedIn\")".to_owned()]],
+        };
+        assert_eq!(
+            table.to_csv(),
+            "channels\r\n\"E1 (general), B1 (\"\"feedIn\"\")\"\r\n"
+        );
+    }
+}
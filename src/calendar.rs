@@ -0,0 +1,198 @@
+//! # Holiday Calendars
+//!
+//! Time-of-use tariffs often price weekends and public holidays differently
+//! from weekdays, as reflected by the [`TariffSeason::Weekend`] and
+//! [`TariffSeason::Holiday`] seasons. Which days are public holidays depends on
+//! the state (and sometimes the region), so tariff logic takes a
+//! [`HolidayCalendar`] rather than assuming a fixed set.
+//!
+//! Calendars can be provided as:
+//!
+//! - [`NoHolidays`], treating every day as a regular day;
+//! - a [`HolidaySet`] of explicit dates, such as loaded from a configuration
+//!   file;
+//! - any closure `Fn(Date) -> bool`; or
+//! - with the `au-holidays` feature, the built-in `AustralianHolidays`.
+//!
+//! ```
+//! use amber_api::calendar::{DayType, HolidaySet};
+//! use jiff::civil::date;
+//!
+//! let holidays: HolidaySet = [date(2025, 12, 25), date(2025, 12, 26)].into_iter().collect();
+//! assert_eq!(DayType::of(date(2025, 12, 25), &holidays), DayType::Holiday);
+//! assert_eq!(DayType::of(date(2025, 12, 27), &holidays), DayType::Weekend);
+//! assert_eq!(DayType::of(date(2025, 12, 29), &holidays), DayType::Weekday);
+//! ```
+
+use alloc::collections::BTreeSet;
+
+use jiff::civil::{Date, Weekday};
+
+use crate::models::TariffSeason;
+
+#[cfg(feature = "au-holidays")]
+mod australia;
+
+#[cfg(feature = "au-holidays")]
+pub use australia::AustralianHolidays;
+
+/// A source of public holidays.
+pub trait HolidayCalendar {
+    /// Returns `true` if the given date is a public holiday.
+    fn is_holiday(&self, date: Date) -> bool;
+}
+
+impl<F: Fn(Date) -> bool> HolidayCalendar for F {
+    #[inline]
+    fn is_holiday(&self, date: Date) -> bool {
+        self(date)
+    }
+}
+
+/// A calendar without any public holidays.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct NoHolidays;
+
+impl HolidayCalendar for NoHolidays {
+    #[inline]
+    fn is_holiday(&self, _date: Date) -> bool {
+        false
+    }
+}
+
+/// A calendar of explicitly listed public holidays.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct HolidaySet {
+    /// The holidays.
+    dates: BTreeSet<Date>,
+}
+
+impl HolidaySet {
+    /// Create an empty set.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a holiday, returning `true` if it was not already present.
+    #[inline]
+    pub fn insert(&mut self, date: Date) -> bool {
+        self.dates.insert(date)
+    }
+
+    /// Returns the holidays in chronological order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Date> + '_ {
+        self.dates.iter().copied()
+    }
+}
+
+impl HolidayCalendar for HolidaySet {
+    #[inline]
+    fn is_holiday(&self, date: Date) -> bool {
+        self.dates.contains(&date)
+    }
+}
+
+impl FromIterator<Date> for HolidaySet {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Date>>(iter: I) -> Self {
+        Self {
+            dates: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Date> for HolidaySet {
+    #[inline]
+    fn extend<I: IntoIterator<Item = Date>>(&mut self, iter: I) {
+        self.dates.extend(iter);
+    }
+}
+
+/// Kind of day, as distinguished by time-of-use tariffs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DayType {
+    /// A working day.
+    Weekday,
+    /// A Saturday or Sunday which is not a public holiday.
+    Weekend,
+    /// A public holiday, whether or not it falls on a weekend.
+    Holiday,
+}
+
+impl DayType {
+    /// Classify a date according to the given calendar.
+    #[must_use]
+    #[inline]
+    pub fn of<C: HolidayCalendar + ?Sized>(date: Date, calendar: &C) -> Self {
+        if calendar.is_holiday(date) {
+            Self::Holiday
+        } else if matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday) {
+            Self::Weekend
+        } else {
+            Self::Weekday
+        }
+    }
+
+    /// Returns whether a tariff season applies to this kind of day.
+    ///
+    /// Returns `None` for seasons which do not depend on the kind of day
+    /// (such as [`TariffSeason::Summer`]).
+    #[must_use]
+    #[inline]
+    pub fn matches(self, season: &TariffSeason) -> Option<bool> {
+        match season {
+            TariffSeason::Weekday => Some(self == Self::Weekday),
+            TariffSeason::Weekend => Some(self == Self::Weekend),
+            TariffSeason::Holiday => Some(self == Self::Holiday),
+            TariffSeason::WeekendHoliday => Some(self != Self::Weekday),
+            TariffSeason::Default
+            | TariffSeason::Summer
+            | TariffSeason::Autumn
+            | TariffSeason::Winter
+            | TariffSeason::Spring
+            | TariffSeason::NonSummer
+            | TariffSeason::Other(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn classifies_days() {
+        let closure = |day: Date| day == date(2025, 1, 27);
+        assert_eq!(DayType::of(date(2025, 1, 27), &closure), DayType::Holiday);
+        assert_eq!(
+            DayType::of(date(2025, 1, 27), &NoHolidays),
+            DayType::Weekday
+        );
+        assert_eq!(
+            DayType::of(date(2025, 1, 25), &NoHolidays),
+            DayType::Weekend
+        );
+
+        assert_eq!(
+            DayType::Holiday.matches(&TariffSeason::WeekendHoliday),
+            Some(true)
+        );
+        assert_eq!(
+            DayType::Weekday.matches(&TariffSeason::WeekendHoliday),
+            Some(false)
+        );
+        assert_eq!(
+            DayType::Weekend.matches(&TariffSeason::Holiday),
+            Some(false)
+        );
+        assert_eq!(DayType::Weekend.matches(&TariffSeason::Summer), None);
+    }
+}
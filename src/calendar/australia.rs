@@ -0,0 +1,243 @@
+//! Built-in Australian public holidays.
+
+use alloc::vec::Vec;
+
+use jiff::civil::{Date, Weekday};
+
+use super::HolidayCalendar;
+use crate::models::State;
+
+/// Australian national public holidays, optionally with those of a state.
+///
+/// Holidays are computed from the current rules for each year, including
+/// Easter and the Monday holidays of each state. Substitute days are added
+/// when New Year's Day, Australia Day, Christmas Day or Boxing Day fall on a
+/// weekend.
+///
+/// Some holidays cannot be derived from rules and are not included, such as
+/// Victoria's Friday before the AFL Grand Final. Regional holidays (such as
+/// show days) and part-day holidays are also excluded. Combine this calendar
+/// with a [`HolidaySet`](super::HolidaySet) to add them:
+///
+/// ```
+/// use amber_api::calendar::{AustralianHolidays, HolidayCalendar, HolidaySet};
+/// use amber_api::models::State;
+/// use jiff::civil::{Date, date};
+///
+/// let state = AustralianHolidays::state(State::Vic);
+/// let extra: HolidaySet = [date(2025, 9, 26)].into_iter().collect();
+/// let calendar = |day: Date| state.is_holiday(day) || extra.is_holiday(day);
+///
+/// assert!(calendar.is_holiday(date(2025, 11, 4))); // Melbourne Cup
+/// assert!(calendar.is_holiday(date(2025, 9, 26))); // AFL Grand Final eve
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct AustralianHolidays {
+    /// State whose holidays are included, if any.
+    pub state: Option<State>,
+}
+
+impl AustralianHolidays {
+    /// National public holidays only.
+    #[must_use]
+    #[inline]
+    pub fn national() -> Self {
+        Self { state: None }
+    }
+
+    /// National public holidays and those of the given state.
+    #[must_use]
+    #[inline]
+    pub fn state(state: State) -> Self {
+        Self { state: Some(state) }
+    }
+
+    /// Returns the public holidays of a year, in chronological order.
+    #[must_use]
+    #[inline]
+    pub fn holidays(&self, year: i16) -> Vec<Date> {
+        let mut days = Vec::new();
+        let mut add = |day: Option<Date>| days.extend(day);
+
+        add(fixed(year, 1, 1));
+        add(fixed(year, 1, 26));
+        add(fixed(year, 4, 25));
+        add(fixed(year, 12, 25));
+        add(fixed(year, 12, 26));
+        if let Some(sunday) = easter_sunday(year) {
+            add(sunday.yesterday().ok().and_then(|day| day.yesterday().ok()));
+            add(sunday.tomorrow().ok());
+            if self.state.is_some() {
+                // Easter Saturday and Sunday are state holidays
+                add(sunday.yesterday().ok());
+                add(Some(sunday));
+            }
+        }
+
+        match self.state {
+            None => {}
+            Some(State::Nsw) => {
+                add(nth_monday(year, 6, 2)); // King's Birthday
+                add(nth_monday(year, 10, 1)); // Labour Day
+            }
+            Some(State::Vic) => {
+                add(nth_monday(year, 3, 2)); // Labour Day
+                add(nth_monday(year, 6, 2)); // King's Birthday
+                add(fixed(year, 11, 1)
+                    .and_then(|first| first.nth_weekday_of_month(1, Weekday::Tuesday).ok())); // Melbourne Cup
+            }
+            Some(State::Qld) => {
+                add(nth_monday(year, 5, 1)); // Labour Day
+                add(nth_monday(year, 10, 1)); // King's Birthday
+            }
+            Some(State::Sa) => {
+                add(nth_monday(year, 3, 2)); // Adelaide Cup
+                add(nth_monday(year, 6, 2)); // King's Birthday
+                add(nth_monday(year, 10, 1)); // Labour Day
+            }
+        }
+
+        days.sort_unstable();
+        days.dedup();
+        add_substitutes(&mut days);
+        days
+    }
+}
+
+impl HolidayCalendar for AustralianHolidays {
+    #[inline]
+    fn is_holiday(&self, date: Date) -> bool {
+        self.holidays(date.year()).contains(&date)
+    }
+}
+
+/// The given date, if valid.
+fn fixed(year: i16, month: i8, day: i8) -> Option<Date> {
+    Date::new(year, month, day).ok()
+}
+
+/// The `nth` Monday of a month.
+fn nth_monday(year: i16, month: i8, nth: i8) -> Option<Date> {
+    fixed(year, month, 1)?
+        .nth_weekday_of_month(nth, Weekday::Monday)
+        .ok()
+}
+
+/// Add substitute days for the weekend holidays which are observed on the
+/// following weekday.
+///
+/// A substitute is the first weekday after the holiday which is not itself a
+/// holiday, so that Christmas Day and Boxing Day on a weekend are observed on
+/// the following Monday and Tuesday.
+fn add_substitutes(days: &mut Vec<Date>) {
+    let substituted: Vec<Date> = days
+        .iter()
+        .copied()
+        .filter(|day| {
+            matches!((day.month(), day.day()), (1, 1 | 26) | (12, 25 | 26)) && is_weekend(*day)
+        })
+        .collect();
+    for holiday in substituted {
+        let mut candidate = holiday;
+        while let Ok(next) = candidate.tomorrow() {
+            candidate = next;
+            if !is_weekend(candidate) && !days.contains(&candidate) {
+                days.push(candidate);
+                break;
+            }
+        }
+    }
+    days.sort_unstable();
+}
+
+/// Returns `true` for Saturdays and Sundays.
+fn is_weekend(day: Date) -> bool {
+    matches!(day.weekday(), Weekday::Saturday | Weekday::Sunday)
+}
+
+/// Date of Easter Sunday, using the anonymous Gregorian algorithm.
+#[expect(
+    clippy::many_single_char_names,
+    reason = "Named after the variables of the published algorithm"
+)]
+fn easter_sunday(year: i16) -> Option<Date> {
+    let y = i32::from(year);
+    let a = y.rem_euclid(19);
+    let b = y.div_euclid(100);
+    let c = y.rem_euclid(100);
+    let d = b.div_euclid(4);
+    let e = b.rem_euclid(4);
+    let f = b.saturating_add(8).div_euclid(25);
+    let g = b.saturating_sub(f).saturating_add(1).div_euclid(3);
+    let h = a
+        .saturating_mul(19)
+        .saturating_add(b)
+        .saturating_sub(d)
+        .saturating_sub(g)
+        .saturating_add(15)
+        .rem_euclid(30);
+    let i = c.div_euclid(4);
+    let k = c.rem_euclid(4);
+    let l = e
+        .saturating_add(i)
+        .saturating_mul(2)
+        .saturating_add(32)
+        .saturating_sub(h)
+        .saturating_sub(k)
+        .rem_euclid(7);
+    let m = h
+        .saturating_mul(11)
+        .saturating_add(a)
+        .saturating_add(l.saturating_mul(22))
+        .div_euclid(451);
+    let n = h
+        .saturating_add(l)
+        .saturating_sub(m.saturating_mul(7))
+        .saturating_add(114);
+    let month = i8::try_from(n.div_euclid(31)).ok()?;
+    let day = i8::try_from(n.rem_euclid(31).saturating_add(1)).ok()?;
+    fixed(year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn computes_easter() {
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+    }
+
+    #[test]
+    fn state_holidays() {
+        let nsw = AustralianHolidays::state(State::Nsw);
+        // Christmas on Saturday and Boxing Day on Sunday, observed Monday and
+        // Tuesday
+        for day in [
+            date(2021, 12, 25),
+            date(2021, 12, 26),
+            date(2021, 12, 27),
+            date(2021, 12, 28),
+        ] {
+            assert!(nsw.is_holiday(day), "{day}");
+        }
+        assert!(nsw.is_holiday(date(2025, 6, 9)));
+        assert!(nsw.is_holiday(date(2025, 10, 6)));
+        assert!(!nsw.is_holiday(date(2025, 10, 7)));
+
+        let qld = AustralianHolidays::state(State::Qld);
+        assert!(qld.is_holiday(date(2025, 5, 5)));
+        assert!(!qld.is_holiday(date(2025, 6, 9)));
+
+        let national = AustralianHolidays::national();
+        assert!(national.is_holiday(date(2025, 4, 18))); // Good Friday
+        assert!(!national.is_holiday(date(2025, 4, 19))); // Easter Saturday
+        assert!(national.is_holiday(date(2025, 4, 21))); // Easter Monday
+    }
+}
@@ -3,16 +3,77 @@
 //! This module provides a client for interacting with the [Amber Electric
 //! Public API](https://api.amber.com.au/v1).
 
+pub(crate) mod all_sites;
+pub(crate) mod all_states;
+pub(crate) mod api;
+pub(crate) mod backend;
+pub(crate) mod backfill;
+pub(crate) mod cache;
+pub(crate) mod cassette;
+#[cfg(feature = "config")]
+pub(crate) mod config;
+pub(crate) mod feed;
+pub(crate) mod fixtures;
+mod guard;
+#[cfg(feature = "keyring")]
+mod keyring;
+pub(crate) mod nightly;
+pub(crate) mod pages;
+pub(crate) mod pool;
+pub(crate) mod proxy;
+pub(crate) mod recorder;
+pub(crate) mod response;
+pub(crate) mod retry;
+pub(crate) mod secret;
+mod site_lookup;
+pub(crate) mod startup;
+pub(crate) mod watcher;
+
 use alloc::{
     borrow::ToOwned as _,
+    boxed::Box,
     format,
     string::{String, ToString as _},
+    sync::Arc,
     vec::Vec,
 };
+use core::{fmt, time::Duration};
+use std::{
+    sync::Mutex,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 
-use crate::{error::Result, models};
+use crate::{
+    analysis::{
+        billing::{BillingPeriod, BillingSummary},
+        channel_order,
+        timeline::{TimelineEntry, timeline},
+    },
+    error::{AmberError, Result},
+    limits::{self, IntervalCount, MAX_INTERVALS},
+    models,
+    rate_limit::RateLimitInfo,
+    requests::{
+        ApiRequest, CurrentPricesRequest, CurrentRenewablesRequest, PricesRequest, UsageRequest,
+    },
+    storage::Format,
+};
+use backend::{HttpBackend, HttpRequest, ReqwestBackend};
+use cache::{CachePolicy, CacheStats, Lookup, ResponseCache};
+use guard::RequestGuard;
+use jiff::{Timestamp, fmt::rfc2822::DateTimeParser};
+use pages::{PricesPages, UsagePages};
+use proxy::ProxyConfig;
+use response::{Fetched, RequestInfo, Response};
+use retry::RetryPolicy;
+use secret::ApiKey;
 use serde::de::DeserializeOwned;
-use tracing::{debug, instrument};
+use site_lookup::{DEFAULT_SITES_TTL, SiteCache};
+use startup::StartupReport;
+use tracing::{debug, instrument, warn};
+
+/// Default overall timeout of each request.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// The base URL for the Amber Electric API.
 const API_BASE_URL: &str = "https://api.amber.com.au/v1/";
@@ -22,28 +83,46 @@ const API_BASE_URL: &str = "https://api.amber.com.au/v1/";
 /// This client provides a high-level interface to all Amber Electric API
 /// endpoints with automatic retry logic for rate limit errors.
 ///
-/// Internally, it uses `reqwest` as the HTTP client, which is stored behind
-/// an [`Arc`][std::sync::Arc] and can be cloned cheaply.
+/// Requests are performed through an [`HttpBackend`], which uses `reqwest` by
+/// default. The backend is stored behind an [`Arc`] so the client can be cloned
+/// cheaply.
 ///
 /// # Rate Limit Handling
 ///
 /// By default, the client automatically retries requests that hit rate limits
-/// (HTTP 429). The client reads the `RateLimit-Reset` header to determine how
-/// long to wait before retrying. By default, up to 3 retry attempts will be
-/// made. You can configure this behavior:
+/// (HTTP 429). The client reads the `RateLimit-Reset` (or `Retry-After`)
+/// header to determine how long to wait before retrying. By default, up to 3
+/// retry attempts will be made. You can configure this behavior with a
+/// [`RetryPolicy`]:
 ///
 /// ```
-/// use amber_api::Amber;
+/// use core::time::Duration;
+///
+/// use amber_api::{Amber, RetryPolicy};
 ///
+/// # fn main() -> amber_api::Result<()> {
 /// // Disable automatic retries
 /// let client = Amber::builder()
 ///     .retry_on_rate_limit(false)
-///     .build();
+///     .build()?;
 ///
 /// // Customize max retry attempts (default: 3)
 /// let client = Amber::builder()
 ///     .max_retries(5)
-///     .build();
+///     .build()?;
+///
+/// // Full control over the retry behaviour
+/// let client = Amber::builder()
+///     .retry_policy(
+///         RetryPolicy::builder()
+///             .max_retries(5)
+///             .jitter(Duration::from_secs(5))
+///             .max_sleep(Duration::from_secs(120))
+///             .build(),
+///     )
+///     .build()?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// When rate limit retries are disabled or exhausted, the client returns an
@@ -57,35 +136,76 @@ const API_BASE_URL: &str = "https://api.amber.com.au/v1/";
 /// // Create a client with default retry behavior (3 retries, enabled)
 /// let client = Amber::default();
 /// ```
-#[derive(Debug, Clone, bon::Builder)]
+#[derive(Clone)]
 pub struct Amber {
-    /// HTTP client for making requests.
-    client: reqwest::Client,
+    /// HTTP backend for making requests.
+    backend: Arc<dyn HttpBackend>,
     /// Optional API key for authenticated requests.
-    api_key: Option<String>,
+    ///
+    /// The key is redacted from the client's `Debug` output.
+    api_key: Option<ApiKey>,
     /// Base URL for the Amber API.
     base_url: String,
-    /// Maximum number of retry attempts for rate limit errors.
+    /// Policy controlling retries of rate limited requests.
     ///
     /// When the API returns HTTP 429 (rate limit exceeded), the client will
-    /// automatically retry up to this many times. Set to 0 to disable retries,
-    /// or use `retry_on_rate_limit(false)` for clearer intent.
-    ///
-    /// Defaults to 3.
-    #[builder(default = 3)]
-    max_retries: u32,
+    /// automatically wait and retry according to this policy.
+    retry_policy: RetryPolicy,
     /// Whether to automatically retry on rate limit errors.
     ///
     /// When enabled (default), the client automatically waits and retries when
-    /// hitting rate limits. The wait time is read from the `RateLimit-Reset`
-    /// header, or defaults to 60 seconds if not present.
+    /// hitting rate limits, as configured by the retry policy.
     ///
     /// When disabled, rate limit errors are returned immediately as
     /// [`AmberError::RateLimitExceeded`].
     ///
     /// Default to `true`.
-    #[builder(default = true)]
     retry_on_rate_limit: bool,
+    /// Preferred encoding of response bodies.
+    ///
+    /// Sent in the `Accept` header, with JSON as a fallback.
+    wire_format: Format,
+    /// Additional headers sent with every request, including any custom user
+    /// agent.
+    headers: Vec<(String, String)>,
+    /// Memoized responses to historical queries, if enabled.
+    guard: Option<Arc<RequestGuard>>,
+    /// Recent responses, if caching is enabled.
+    ///
+    /// Shared between clones of the client.
+    cache: Option<Arc<ResponseCache>>,
+    /// Rate limit metadata from the most recent response.
+    ///
+    /// Shared between clones of the client.
+    rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    /// Sites listed for the site lookups.
+    ///
+    /// Shared between clones of the client.
+    site_cache: Arc<SiteCache>,
+}
+
+impl fmt::Debug for Amber {
+    /// Formats the client's configuration.
+    ///
+    /// Credentials are never included: the API key is redacted, and only the
+    /// names of the additional headers are shown as their values may hold
+    /// tokens.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header_names: Vec<&str> = self.headers.iter().map(|(name, _)| name.as_str()).collect();
+        f.debug_struct("Amber")
+            .field("backend", &self.backend)
+            .field("api_key", &self.api_key)
+            .field("base_url", &self.base_url)
+            .field("retry_policy", &self.retry_policy)
+            .field("retry_on_rate_limit", &self.retry_on_rate_limit)
+            .field("wire_format", &self.wire_format)
+            .field("headers", &header_names)
+            .field("guard", &self.guard.is_some())
+            .field("cache", &self.cache.as_ref().map(|cache| cache.policy()))
+            .field("sites_ttl", &self.site_cache.ttl())
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for Amber {
@@ -103,139 +223,716 @@ impl Default for Amber {
     )]
     fn default() -> Self {
         debug!("Creating default Amber API client");
-        let client = reqwest::Client::builder()
-            .user_agent(format!("amber-api/{}", env!("CARGO_PKG_VERSION")))
-            .timeout(core::time::Duration::from_secs(30))
+        Self::builder()
             .build()
-            .expect("Failed to build HTTP client");
+            .expect("Failed to build HTTP client")
+    }
+}
 
-        Self {
-            client,
-            #[cfg(feature = "std")]
-            api_key: std::env::var("AMBER_API_KEY")
-                .ok()
-                .filter(|s| !s.is_empty()),
-            #[cfg(not(feature = "std"))]
-            api_key: None,
-            base_url: API_BASE_URL.to_owned(),
-            max_retries: 3,
-            retry_on_rate_limit: true,
-        }
+impl<S: amber_builder::IsComplete> AmberBuilder<S> {
+    /// Build the client and check that it is ready for use.
+    ///
+    /// This is intended for long-running services, so that configuration
+    /// problems are reported at startup with an actionable message rather
+    /// than on the first scheduled request. See
+    /// [`Amber::startup_report()`] for the checks performed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client cannot be built (as for `build()`), or
+    /// under the same conditions as [`Amber::startup_report()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (client, report) = Amber::builder().build_and_validate().await?;
+    /// for warning in &report.warnings {
+    ///     eprintln!("Warning: {warning}");
+    /// }
+    /// let site = report.active_sites().next().ok_or("no active site")?;
+    /// let prices = client.current_prices().site_id(&site.id).call().await?;
+    /// println!("{} current prices", prices.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn build_and_validate(self) -> Result<(Amber, StartupReport)> {
+        let client = self.build()?;
+        let report = client.startup_report().await?;
+        Ok((client, report))
     }
 }
 
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "The endpoints are defined with the client"
+)]
+#[expect(
+    clippy::same_name_method,
+    reason = "The `AmberApi` trait mirrors the endpoints"
+)]
 #[bon::bon]
 impl Amber {
+    /// Create a new Amber API client.
+    ///
+    /// All parameters are optional:
+    ///
+    /// - `api_key`: API key used to authenticate requests. If not set, the
+    ///   `AMBER_API_KEY` environment variable is used if present.
+    /// - `base_url`: Base URL of the API (defaults to the public Amber API).
+    /// - `retry_policy`: Policy controlling retries on rate limit errors (see
+    ///   [`RetryPolicy::default()`]).
+    /// - `max_retries`: Maximum number of retries on rate limit errors,
+    ///   overriding the value in the retry policy.
+    /// - `retry_on_rate_limit`: Whether to retry on rate limit errors (defaults
+    ///   to `true`).
+    /// - `client`: A pre-configured `reqwest` client. If not set, a client with
+    ///   an `amber-api` user agent and the timeouts below is created.
+    /// - `timeout`: Overall timeout of each request, from connecting until the
+    ///   body is read (defaults to 30 seconds).
+    /// - `connect_timeout`: Timeout for establishing a connection (unlimited
+    ///   by default, other than by `timeout`).
+    /// - `read_timeout`: Timeout for each read of the response (unlimited by
+    ///   default, other than by `timeout`).
+    /// - `proxy`: A proxy through which to send requests (see [`ProxyConfig`]).
+    /// - `system_proxy`: Whether to honour the proxy environment variables such
+    ///   as `HTTPS_PROXY` when no `proxy` is given (defaults to `true`).
+    /// - `backend`: A custom [`HttpBackend`] used to perform requests. This
+    ///   takes precedence over `client`.
+    /// - `user_agent`: Value of the `User-Agent` header, such as the name and
+    ///   version of a home automation integration (defaults to
+    ///   `amber-api/<version>`).
+    /// - `default_headers`: Additional headers sent with every request, such
+    ///   as tracing headers. These are sent with `client` and `backend` too.
+    /// - `dedupe_historical`: Whether to memoize the responses of historical
+    ///   queries (defaults to `false`). When enabled, repeating the exact same
+    ///   [`prices()`](Self::prices) or [`usage()`](Self::usage) query for a
+    ///   date range entirely in the past returns the previous response without
+    ///   contacting the API. Clones of the client share the memoized
+    ///   responses, of which the 256 most recent are kept.
+    /// - `cache`: Cache recent responses in memory for the time-to-live of
+    ///   each endpoint given by the [`CachePolicy`] (disabled by default).
+    ///   Repeating a request within its time-to-live returns the cached
+    ///   response, including its original `fetched_at` time. Clones of the
+    ///   client share the cache, and requests with a per-request API key
+    ///   bypass it. Expired responses with an `ETag` or `Last-Modified`
+    ///   header are revalidated with a conditional request, and expired
+    ///   responses can be served while the API is unavailable (see
+    ///   [`CachePolicy::stale_if_error`]). Endpoints returning only the data
+    ///   then return stale data silently (logging a warning); use the
+    ///   `*_response` variants to detect it.
+    /// - `sites_ttl`: How long the list of sites used by the site lookups,
+    ///   such as [`active_sites()`](Self::active_sites), is reused (defaults
+    ///   to an hour).
+    /// - `wire_format`: Preferred encoding of response bodies (defaults to
+    ///   [`Format::Json`]). Other formats are requested through the `Accept`
+    ///   header with JSON as a fallback, and each response is decoded
+    ///   according to its `Content-Type`. This is useful when requests go
+    ///   through a proxy or cache which serves a binary encoding.
+    ///
+    /// The timeouts and proxy settings only configure the client created when
+    /// neither `client` nor `backend` is given. Requests exceeding a timeout
+    /// fail with [`AmberError::Timeout`]. The overall timeout can also be
+    /// overridden for individual requests through the `timeout` parameter of
+    /// each endpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::Http`] if the underlying HTTP client cannot be
+    /// created, or [`AmberError::InvalidRequest`] if the user agent or a
+    /// default header is not a valid HTTP header.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use amber_api::Amber;
+    ///
+    /// # fn main() -> amber_api::Result<()> {
+    /// let client = Amber::builder()
+    ///     .api_key("your-api-key-here")
+    ///     .max_retries(5)
+    ///     .user_agent("my-integration/1.0")
+    ///     .default_headers(vec![("X-Request-Source".into(), "dashboard".into())])
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`AmberError::Http`]: crate::AmberError::Http
+    /// [`AmberError::InvalidRequest`]: crate::AmberError::InvalidRequest
+    /// [`AmberError::Timeout`]: crate::AmberError::Timeout
+    #[inline]
+    #[builder]
+    #[expect(
+        clippy::needless_pass_by_value,
+        reason = "The builder passes its arguments by value"
+    )]
+    pub fn new(
+        #[builder(into)] api_key: Option<String>,
+        #[builder(into, default = API_BASE_URL.to_owned())] base_url: String,
+        #[builder(default)] retry_policy: RetryPolicy,
+        max_retries: Option<u32>,
+        #[builder(default = true)] retry_on_rate_limit: bool,
+        client: Option<reqwest::Client>,
+        backend: Option<Box<dyn HttpBackend>>,
+        #[builder(into)] user_agent: Option<String>,
+        #[builder(default)] default_headers: Vec<(String, String)>,
+        #[builder(default = DEFAULT_TIMEOUT)] timeout: Duration,
+        connect_timeout: Option<Duration>,
+        read_timeout: Option<Duration>,
+        proxy: Option<ProxyConfig>,
+        #[builder(default = true)] system_proxy: bool,
+        #[builder(default)] dedupe_historical: bool,
+        cache: Option<CachePolicy>,
+        #[builder(default)] wire_format: Format,
+        #[builder(default = DEFAULT_SITES_TTL)] sites_ttl: Duration,
+    ) -> Result<Self> {
+        let mut headers = default_headers;
+        if let Some(agent) = user_agent {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("User-Agent"));
+            headers.push(("User-Agent".to_owned(), agent));
+        }
+        for (name, value) in &headers {
+            validate_header(name, value)?;
+        }
+
+        let http_backend: Arc<dyn HttpBackend> = match (backend, client) {
+            (Some(custom), _) => Arc::from(custom),
+            (None, Some(http_client)) => Arc::new(ReqwestBackend::new(http_client)),
+            (None, None) => {
+                let mut http_client = reqwest::Client::builder()
+                    .user_agent(format!("amber-api/{}", env!("CARGO_PKG_VERSION")))
+                    .timeout(timeout);
+                if let Some(connect) = connect_timeout {
+                    http_client = http_client.connect_timeout(connect);
+                }
+                if let Some(read) = read_timeout {
+                    http_client = http_client.read_timeout(read);
+                }
+                if let Some(config) = &proxy {
+                    http_client = http_client.proxy(config.to_reqwest()?);
+                } else if !system_proxy {
+                    http_client = http_client.no_proxy();
+                }
+                Arc::new(ReqwestBackend::new(http_client.build()?))
+            }
+        };
+        let key = api_key.or_else(|| {
+            std::env::var("AMBER_API_KEY")
+                .ok()
+                .filter(|s| !s.is_empty())
+        });
+
+        Ok(Self {
+            backend: http_backend,
+            api_key: key.map(ApiKey::from),
+            base_url,
+            retry_policy: RetryPolicy {
+                max_retries: max_retries.unwrap_or(retry_policy.max_retries),
+                ..retry_policy
+            },
+            retry_on_rate_limit,
+            wire_format,
+            headers,
+            guard: dedupe_historical.then(Arc::default),
+            cache: cache.map(|policy| Arc::new(ResponseCache::new(policy))),
+            rate_limit: Arc::default(),
+            site_cache: Arc::new(SiteCache::new(sites_ttl)),
+        })
+    }
+
     /// Perform a GET request to the Amber API with automatic retry on rate
     /// limits.
     ///
     /// This method automatically retries requests that hit rate limits (HTTP
-    /// 429), reading the `RateLimit-Reset` header from the 429 response to
-    /// determine the exact number of seconds to wait before retrying. If the
-    /// header is missing or invalid, it falls back to 60 seconds.
-    ///
-    /// The number of retries is controlled by the `max_retries` and
-    /// `retry_on_rate_limit` configuration options.
-    #[instrument(skip(self, query), level = "debug")]
-    async fn get<T: DeserializeOwned, I, K, V>(&self, path: &str, query: I) -> Result<T>
+    /// 429), reading the `RateLimit-Reset` (or `Retry-After`) header from the
+    /// 429 response to determine the exact number of seconds to wait before
+    /// retrying. If neither header is present, it falls back to the retry
+    /// policy's default delay.
+    ///
+    /// Retries are controlled by the `retry_policy` and `retry_on_rate_limit`
+    /// configuration options. The `overrides` replace the client's timeout and
+    /// API key for this request.
+    #[instrument(skip(self, query, overrides), level = "debug")]
+    async fn get<T: DeserializeOwned, I, K, V>(
+        &self,
+        path: &str,
+        query: I,
+        overrides: Overrides,
+    ) -> Result<Response<T>>
     where
         I: IntoIterator<Item = (K, V)>,
         K: AsRef<str>,
         V: AsRef<str>,
     {
-        let endpoint = format!("{}{}", self.base_url, path);
-        let query_params: Vec<(String, String)> = query
-            .into_iter()
-            .map(|(k, v)| (k.as_ref().to_owned(), v.as_ref().to_owned()))
-            .collect();
+        let request = self.request_info(path, query);
+        let fetched = self.fetch_cached(path, &request, &overrides).await?;
+        parse_response(request, &fetched)
+    }
+
+    /// Describe a request to the given path, relative to the base URL.
+    fn request_info<I, K, V>(&self, path: &str, query: I) -> RequestInfo
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        RequestInfo {
+            url: format!("{}{}", self.base_url, path),
+            query: collect_query(query),
+        }
+    }
+
+    /// Send a request, validating its parameters first.
+    async fn send_with<R: ApiRequest>(
+        &self,
+        request: &R,
+        overrides: Overrides,
+    ) -> Result<Response<R::Output>> {
+        request.validate()?;
+        self.get_historical(
+            &request.path(),
+            request.query(),
+            request.end_date(),
+            overrides,
+        )
+        .await
+    }
+
+    /// Perform a GET request for historical data.
+    ///
+    /// This behaves like [`get`](Self::get), except that if duplicate request
+    /// detection is enabled and the query ends before today, the response is
+    /// memoized and identical queries are answered from memory. Requests with
+    /// a per-request API key bypass the memoized responses, as these are
+    /// specific to the client's account.
+    #[instrument(skip(self, query, overrides), level = "debug")]
+    async fn get_historical<T: DeserializeOwned, I, K, V>(
+        &self,
+        path: &str,
+        query: I,
+        end_date: Option<jiff::civil::Date>,
+        overrides: Overrides,
+    ) -> Result<Response<T>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let request = self.request_info(path, query);
+        let Some(request_guard) = self
+            .guard
+            .as_ref()
+            .filter(|_| overrides.api_key.is_none() && end_date.is_some_and(guard::is_historical))
+        else {
+            let fetched = self.fetch_cached(path, &request, &overrides).await?;
+            return parse_response(request, &fetched);
+        };
+
+        let key = guard::request_key(&request.url, &request.query);
+        let fetched = if let Some(memoized) = request_guard.get(&key) {
+            debug!("Returning memoized response for {key}");
+            memoized
+        } else {
+            let fresh = Arc::new(self.fetch(&request, &overrides, &[]).await?);
+            request_guard.insert(key, Arc::clone(&fresh));
+            fresh
+        };
+        parse_response(request, &fetched)
+    }
+
+    /// Perform a GET request through the response cache, if enabled.
+    ///
+    /// Requests with a per-request API key bypass the cache, as the cache is
+    /// specific to the client's account.
+    async fn fetch_cached(
+        &self,
+        path: &str,
+        request: &RequestInfo,
+        overrides: &Overrides,
+    ) -> Result<Arc<Fetched>> {
+        let Some(cache) = self.cache.as_ref().filter(|_| overrides.api_key.is_none()) else {
+            return Ok(Arc::new(self.fetch(request, overrides, &[]).await?));
+        };
+
+        let key = guard::request_key(&request.url, &request.query);
+        let lookup = cache.get(&key, Instant::now());
+        if let Lookup::Fresh(cached) = &lookup {
+            debug!("Returning cached response for {key}");
+            cache.record(&lookup, false);
+            return Ok(Arc::clone(cached));
+        }
+
+        let fetched = match self
+            .fetch(request, overrides, &lookup.conditional_headers())
+            .await
+        {
+            Ok(fetched) => fetched,
+            Err(err) if err.is_transient() || err.retry_after().is_some() => {
+                let Some((cached, age)) = cache.stale(&key, Instant::now()) else {
+                    return Err(err);
+                };
+                warn!("Serving cached response for {key} from {age:?} ago: {err}");
+                return Ok(Arc::new(cached.served_stale(age)));
+            }
+            Err(err) => return Err(err),
+        };
+        let not_modified = fetched.status() == 304;
+        cache.record(&lookup, not_modified);
+        let fresh = match &lookup {
+            Lookup::Expired(cached) if not_modified => {
+                debug!("Cached response for {key} not modified");
+                Arc::new(cached.revalidated(fetched))
+            }
+            Lookup::Fresh(_) | Lookup::Expired(_) | Lookup::Missing => Arc::new(fetched),
+        };
+        cache.insert(key, Arc::clone(&fresh), cache.ttl(path), Instant::now());
+        Ok(fresh)
+    }
+
+    /// Perform a GET request, returning the raw successful response.
+    ///
+    /// Rate limited requests are retried as described in [`get`](Self::get).
+    /// The `conditional` headers make the request conditional on a cached
+    /// response having changed, in which case a `304 Not Modified` response
+    /// (with an empty body) is also considered successful.
+    async fn fetch(
+        &self,
+        request_info: &RequestInfo,
+        overrides: &Overrides,
+        conditional: &[(String, String)],
+    ) -> Result<Fetched> {
+        let endpoint = &request_info.url;
+        let query_params = &request_info.query;
+        let api_key = overrides.api_key.as_ref().or(self.api_key.as_ref());
         let mut attempt: u32 = 0;
 
         loop {
             let current_attempt = attempt.saturating_add(1);
-            let max_attempts = self.max_retries.saturating_add(1);
+            let max_attempts = self.retry_policy.max_retries.saturating_add(1);
             debug!("GET {endpoint} (attempt {current_attempt}/{max_attempts})");
 
             // Build request
-            let mut request = self.client.get(&endpoint);
-
-            if let Some(api_key) = &self.api_key {
-                request = request.bearer_auth(api_key);
-            }
-
-            if !query_params.is_empty() {
-                for (key, value) in &query_params {
-                    debug!("Query parameter: {}={}", key, value);
-                }
-                request = request.query(&query_params);
+            for (key, value) in query_params {
+                debug!("Query parameter: {}={}", key, value);
             }
+            let request = HttpRequest {
+                url: endpoint.clone(),
+                query: query_params.clone(),
+                bearer_token: api_key.cloned(),
+                accept: Some(accept_header(self.wire_format)),
+                timeout: overrides.timeout,
+                headers: self.headers.iter().chain(conditional).cloned().collect(),
+            };
 
             // Make request
-            match request.send().await {
-                Ok(response) => {
-                    let status = response.status();
-                    debug!("Status code: {}", status);
-
-                    // Log rate limit info if available
-                    if let Some(remaining) = response
-                        .headers()
-                        .get("RateLimit-Remaining")
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|s| s.parse::<u64>().ok())
-                    {
-                        debug!("Rate limit remaining: {}", remaining);
-                    }
+            let response = self.backend.get(request).await?;
+            let status = response.status;
+            debug!("Status code: {}", status);
 
-                    // Handle rate limiting
-                    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                        let retry_after = response
-                            .headers()
-                            .get("RateLimit-Reset")
-                            .and_then(|v| v.to_str().ok())
-                            .and_then(|s| s.parse::<u64>().ok())
-                            .unwrap_or(60);
-
-                        if !self.retry_on_rate_limit {
-                            return Err(crate::error::AmberError::RateLimitExceeded(retry_after));
-                        }
-
-                        if attempt >= self.max_retries {
-                            return Err(crate::error::AmberError::RateLimitExhausted {
-                                attempts: attempt,
-                                retry_after,
-                            });
-                        }
-
-                        // Wait and retry
-                        debug!(
-                            "Rate limit hit. Waiting {} seconds before retry",
-                            retry_after
-                        );
-                        tokio::time::sleep(tokio::time::Duration::from_secs(retry_after)).await;
-                        attempt = attempt.saturating_add(1);
-                        continue;
-                    }
+            // Record rate limit info if available
+            let rate_limit = RateLimitInfo::from_headers(
+                response.header("RateLimit-Limit"),
+                response.header("RateLimit-Remaining"),
+                response.header("RateLimit-Reset"),
+                response.header("RateLimit-Policy"),
+            );
+            if !rate_limit.is_empty() {
+                debug!("Rate limit: {}", rate_limit);
+                if let Ok(mut last) = self.rate_limit.lock() {
+                    *last = Some(rate_limit);
+                }
+            }
 
-                    // Check for success
-                    if status.is_success() {
-                        return response.json::<T>().await.map_err(Into::into);
-                    }
+            // Handle rate limiting
+            if status == 429 {
+                let retry_after = response
+                    .header("RateLimit-Reset")
+                    .or_else(|| response.header("Retry-After"))
+                    .and_then(|s| s.trim().parse::<u64>().ok());
+                let reported_delay =
+                    retry_after.unwrap_or(self.retry_policy.default_delay.as_secs());
 
-                    // Other error statuses
-                    let body = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| String::from("<body not available>"));
-                    return Err(crate::error::AmberError::UnexpectedStatus {
-                        status: status.as_u16(),
-                        body,
+                if !self.retry_on_rate_limit {
+                    return Err(crate::error::AmberError::RateLimitExceeded {
+                        retry_after: reported_delay,
+                        rate_limit,
                     });
                 }
-                Err(e) => {
-                    // Network or other transport errors
-                    return Err(e.into());
+
+                if attempt >= self.retry_policy.max_retries {
+                    return Err(crate::error::AmberError::RateLimitExhausted {
+                        attempts: attempt,
+                        retry_after: reported_delay,
+                        rate_limit,
+                    });
                 }
+
+                let Some(delay) = self.retry_policy.base_delay(retry_after) else {
+                    debug!(
+                        "Rate limit reset in {} seconds exceeds the maximum sleep",
+                        reported_delay
+                    );
+                    return Err(crate::error::AmberError::RateLimitExceeded {
+                        retry_after: reported_delay,
+                        rate_limit,
+                    });
+                };
+                let wait = delay.saturating_add(self.retry_policy.random_jitter());
+
+                // Wait and retry
+                debug!("Rate limit hit. Waiting {:?} before retry", wait);
+                tokio::time::sleep(wait).await;
+                attempt = attempt.saturating_add(1);
+                continue;
+            }
+
+            // Check for success
+            if response.is_success() || (status == 304 && !conditional.is_empty()) {
+                let format = response
+                    .header("Content-Type")
+                    .and_then(Format::from_media_type)
+                    .unwrap_or_default();
+                let server_time = response.header("Date").and_then(parse_http_date);
+                let etag = response.header("ETag").map(str::to_owned);
+                let last_modified = response.header("Last-Modified").map(str::to_owned);
+                return Ok(Fetched::new(status, response.body, format, timestamp_now())
+                    .with_rate_limit((!rate_limit.is_empty()).then_some(rate_limit))
+                    .with_server_time(server_time)
+                    .with_validators(etag, last_modified));
+            }
+
+            // Authentication failures
+            if status == 401 || status == 403 {
+                return Err(if api_key.is_none() {
+                    crate::error::AmberError::MissingApiKey
+                } else {
+                    crate::error::AmberError::Unauthorized {
+                        status,
+                        body: response.text(),
+                    }
+                });
             }
+
+            // Other error statuses
+            return Err(crate::error::AmberError::UnexpectedStatus {
+                status,
+                body: response.text(),
+            });
+        }
+    }
+
+    /// Returns the rate limit metadata reported by the most recent response.
+    ///
+    /// Returns `None` if no request has been made yet, or the API did not
+    /// report any rate limit information. Clones of the client share this
+    /// status.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let sites = client.sites().await?;
+    ///
+    /// if let Some(status) = client.rate_limit_status() {
+    ///     println!("{status}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn rate_limit_status(&self) -> Option<RateLimitInfo> {
+        let last = self.rate_limit.lock().ok()?;
+        *last
+    }
+
+    /// Returns the counts of how requests were answered by the response
+    /// cache, or `None` if the client was built without a `cache`.
+    ///
+    /// Clones of the client share these counts.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::{Amber, CachePolicy};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::builder().cache(CachePolicy::default()).build()?;
+    /// client.sites().await?;
+    /// client.sites().await?;
+    ///
+    /// if let Some(stats) = client.cache_stats() {
+    ///     println!("{} hits out of {} requests", stats.hits, stats.requests());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// Discard all cached responses, so that the next request of each
+    /// endpoint contacts the API.
+    ///
+    /// This has no effect unless the client was built with a `cache`, and
+    /// does not discard the responses memoized by `dedupe_historical`.
+    #[inline]
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Watch the current prices of a site, polling every `every`.
+    ///
+    /// The returned [`PriceWatch`] yields the current interval of each
+    /// channel when it changes, that is when a new interval starts or its
+    /// price is revised. Periods shorter than a second are rounded up to one
+    /// second.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use core::time::Duration;
+    ///
+    /// use amber_api::Amber;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let site_id = "01F5A5CRKMZ5BCX9P1S4V990AM".parse()?;
+    ///
+    /// let mut watch = client.watch_current_prices(site_id, Duration::from_secs(30));
+    /// loop {
+    ///     match watch.next().await {
+    ///         Ok(interval) => println!("{interval}"),
+    ///         Err(err) => eprintln!("Failed to poll prices: {err}"),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`PriceWatch`]: crate::PriceWatch
+    #[must_use]
+    #[inline]
+    pub fn watch_current_prices(
+        &self,
+        site_id: models::SiteId,
+        every: Duration,
+    ) -> watcher::PriceWatch {
+        watcher::PriceWatch::new(self.clone(), site_id, every)
+    }
+
+    /// Returns a copy of the client authenticating with a different API key.
+    ///
+    /// The copy shares the HTTP backend with this client, but tracks the rate
+    /// limit, caches and memoizes the responses of the new key separately.
+    /// See
+    /// [`AmberPool`] to manage the clients of several accounts.
+    ///
+    /// [`AmberPool`]: crate::AmberPool
+    #[must_use]
+    #[inline]
+    pub fn with_api_key(&self, api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: Some(ApiKey::from(api_key.into())),
+            rate_limit: Arc::default(),
+            site_cache: Arc::new(SiteCache::new(self.site_cache.ttl())),
+            cache: self
+                .cache
+                .as_ref()
+                .map(|cache| Arc::new(ResponseCache::new(cache.policy()))),
+            guard: self.guard.as_ref().map(|_| Arc::default()),
+            ..self.clone()
+        }
+    }
+
+    /// Check that the client can authenticate with the API.
+    ///
+    /// This performs a single inexpensive authenticated request (listing
+    /// sites), so that applications can fail fast at startup rather than on
+    /// their first real request.
+    ///
+    /// # Errors
+    ///
+    /// The error distinguishes the possible failures:
+    ///
+    /// - [`AmberError::MissingApiKey`] if no API key is configured, in which
+    ///   case no request is made;
+    /// - [`AmberError::Unauthorized`] if the API key was rejected;
+    /// - any other error (see [`AmberError::is_transient`]) if the API could
+    ///   not be reached or failed to respond.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::{Amber, AmberError};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// match client.verify_auth().await {
+    ///     Ok(()) => println!("Authenticated"),
+    ///     Err(AmberError::MissingApiKey) => eprintln!("Set AMBER_API_KEY"),
+    ///     Err(AmberError::Unauthorized { .. }) => eprintln!("API key rejected"),
+    ///     Err(err) => eprintln!("Could not reach the API: {err}"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`AmberError::MissingApiKey`]: crate::AmberError::MissingApiKey
+    /// [`AmberError::Unauthorized`]: crate::AmberError::Unauthorized
+    /// [`AmberError::is_transient`]: crate::AmberError::is_transient
+    #[inline]
+    pub async fn verify_auth(&self) -> Result<()> {
+        if self.api_key.is_none() {
+            return Err(crate::error::AmberError::MissingApiKey);
+        }
+        self.sites_response().await.map(|_| ())
+    }
+
+    /// Check that the client is ready for use, returning a report of the
+    /// account's sites and any problems found.
+    ///
+    /// This authenticates with the API as [`verify_auth()`] does, and
+    /// additionally checks that:
+    ///
+    /// - the account has at least one active site;
+    /// - the local clock agrees with the server's (within
+    ///   [`CLOCK_SKEW_TOLERANCE`]); and
+    /// - the rate limit quota is not exhausted.
+    ///
+    /// Failures of these additional checks are reported as
+    /// [`StartupWarning`]s, as the client remains usable. Only a single
+    /// request is made, whose response primes the cache of the client (if
+    /// enabled) so that the sites can be listed again without delay.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`verify_auth()`].
+    ///
+    /// [`verify_auth()`]: Self::verify_auth
+    /// [`CLOCK_SKEW_TOLERANCE`]: startup::CLOCK_SKEW_TOLERANCE
+    /// [`StartupWarning`]: crate::StartupWarning
+    #[inline]
+    pub async fn startup_report(&self) -> Result<StartupReport> {
+        if self.api_key.is_none() {
+            return Err(crate::error::AmberError::MissingApiKey);
         }
+        self.sites_response().await.map(StartupReport::new)
     }
 
     /// Returns the current percentage of renewables in the grid for a specific
@@ -251,6 +948,8 @@ impl Amber {
     /// - `next`: Optional number of forecast intervals to return
     /// - `previous`: Optional number of historical intervals to return
     /// - `resolution`: Optional interval duration (5 or 30 minutes, default 30)
+    /// - `timeout`: Optional timeout overriding the client's overall timeout
+    /// - `api_key`: Optional API key overriding the client's key
     ///
     /// # Authentication
     ///
@@ -266,22 +965,25 @@ impl Amber {
     ///
     /// This method will return an error if:
     ///
+    /// - `next` and `previous` together exceed
+    ///   [`MAX_INTERVALS`](crate::limits::MAX_INTERVALS)
     /// - There's a network error communicating with the API
     /// - The API returns an internal server error (HTTP 500)
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use amber_api::Amber;
     /// use amber_api::models::{State, Resolution};
     ///
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Amber::default();
     ///
     /// // Get current renewables data for Victoria
     /// let renewables = client.current_renewables()
     ///     .state(State::Vic)
-    ///     .call()?;
+    ///     .call().await?;
     ///
     /// for renewable in renewables {
     ///     println!("{}", renewable);
@@ -292,7 +994,7 @@ impl Amber {
     ///     .state(State::Nsw)
     ///     .next(8)
     ///     .resolution(Resolution::FiveMinute)
-    ///     .call()?;
+    ///     .call().await?;
     /// # Ok(())
     /// # }
     /// ```
@@ -305,67 +1007,405 @@ impl Amber {
         state: models::State,
         next: Option<u32>,
         previous: Option<u32>,
-        resolution: Option<models::Resolution>,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
     ) -> Result<Vec<models::Renewable>> {
-        self.get(
-            &format!("state/{state}/renewables/current"),
-            [
-                ("next", next.map(|n| n.to_string())),
-                ("previous", previous.map(|p| p.to_string())),
-                ("resolution", resolution.map(|r| r.to_string())),
-            ]
-            .into_iter()
-            .filter_map(|(k, v)| v.map(|val| (k, val))),
+        self.current_renewables_response()
+            .state(state)
+            .maybe_next(next)
+            .maybe_previous(previous)
+            .maybe_resolution(resolution)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key)
+            .call()
+            .await
+            .map(Response::into_inner)
+    }
+
+    /// Returns the current percentage of renewables in the grid, along with
+    /// the response metadata.
+    ///
+    /// This takes the same parameters as [`current_renewables()`].
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`current_renewables()`].
+    ///
+    /// [`current_renewables()`]: Self::current_renewables
+    #[inline]
+    #[builder]
+    pub async fn current_renewables_response(
+        &self,
+        state: models::State,
+        next: Option<u32>,
+        previous: Option<u32>,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<Response<Vec<models::Renewable>>> {
+        let request = CurrentRenewablesRequest::builder()
+            .state(state)
+            .maybe_next(next)
+            .maybe_previous(previous)
+            .maybe_resolution(requested_resolution(resolution)?)
+            .build();
+        self.send_with(
+            &request,
+            Overrides {
+                timeout,
+                api_key: api_key.map(ApiKey::from),
+            },
         )
         .await
     }
 
-    /// Return all sites linked to your account.
+    /// Returns historical renewables data covering the given time span.
     ///
-    /// This method returns information about all electricity sites associated
-    /// with your Amber account. Each site represents a location where you have
-    /// electricity service.
+    /// This is a convenience wrapper around [`current_renewables()`] which
+    /// converts the time span into the number of `previous` intervals to
+    /// request at the given resolution, and returns only the
+    /// [`ActualRenewable`] entries in chronological order.
     ///
-    /// # Authentication
+    /// # Parameters
     ///
-    /// This method requires authentication via API key. The API key can be
-    /// provided either through the `AMBER_API_KEY` environment variable (when
-    /// using [`Amber::default()`]) or by explicitly setting it when building
-    /// the client.
+    /// - `state`: Australian state for which to retrieve renewables data
+    /// - `span`: How far back from now to retrieve data
+    /// - `resolution`: Optional interval duration (5 or 30 minutes, default 30)
+    /// - `timeout`: Optional timeout overriding the client's overall timeout
+    /// - `api_key`: Optional API key overriding the client's key
     ///
-    /// # Returns
+    /// # Limitations
     ///
-    /// Returns a [`Result`] containing a [`Vec`] of [`Site`] objects on
-    /// success.
+    /// The API only supports retrieving renewables history relative to the
+    /// current interval, and returns at most 2048 previous intervals. Spans
+    /// longer than this (about 7 days at 5-minute resolution, or 42 days at
+    /// 30-minute resolution) cannot be fetched, and are rejected rather than
+    /// silently truncated.
     ///
     /// # Errors
     ///
-    /// This method will return an error if:
-    ///
-    /// - The API key is missing or invalid (HTTP 401)
-    /// - There's a network error communicating with the API
-    /// - The API returns an internal server error (HTTP 500)
+    /// Returns [`AmberError::InvalidRequest`] if the span covers more than
+    /// [`MAX_INTERVALS`] intervals at the requested resolution, and otherwise
+    /// errors under the same conditions as [`current_renewables()`].
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
+    /// use core::time::Duration;
+    ///
     /// use amber_api::Amber;
+    /// use amber_api::models::State;
     ///
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Amber::default();
-    /// let sites = client.sites()?;
     ///
-    /// for site in sites {
-    ///     println!("Site {}: {} ({})", site.id, site.network, site.status);
-    /// }
-    /// # Ok(())
+    /// // Get the last 24 hours of renewables data for Victoria
+    /// let history = client.renewables_history()
+    ///     .state(State::Vic)
+    ///     .span(Duration::from_secs(24 * 60 * 60))
+    ///     .call().await?;
+    ///
+    /// for renewable in history {
+    ///     println!("{}", renewable);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`current_renewables()`]: Self::current_renewables
+    /// [`ActualRenewable`]: crate::models::ActualRenewable
+    /// [`AmberError::InvalidRequest`]: crate::AmberError::InvalidRequest
+    /// [`MAX_INTERVALS`]: crate::limits::MAX_INTERVALS
+    #[inline]
+    #[builder]
+    pub async fn renewables_history(
+        &self,
+        state: models::State,
+        span: core::time::Duration,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<Vec<models::ActualRenewable>> {
+        let interval_resolution =
+            requested_resolution(resolution)?.unwrap_or(models::Resolution::ThirtyMinute);
+        let previous = intervals_in_span(span, interval_resolution);
+        if previous > MAX_INTERVALS {
+            return Err(AmberError::InvalidRequest(format!(
+                "span of {} seconds covers {previous} intervals at {interval_resolution}-minute \
+                 resolution, exceeding the maximum of {MAX_INTERVALS}",
+                span.as_secs()
+            )));
+        }
+        debug!(%state, previous, resolution = %interval_resolution, "Fetching renewables history");
+
+        let renewables = self
+            .current_renewables()
+            .state(state)
+            .previous(previous)
+            .next(0)
+            .resolution(interval_resolution)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key)
+            .call()
+            .await?;
+
+        let mut history: Vec<_> = renewables
+            .into_iter()
+            .filter_map(|renewable| match renewable {
+                models::Renewable::ActualRenewable(actual) => Some(actual),
+                models::Renewable::ForecastRenewable(_)
+                | models::Renewable::CurrentRenewable(_)
+                | models::Renewable::Unknown(_) => None,
+            })
+            .collect();
+        history.sort_by_key(|actual| actual.base.start_time);
+        Ok(history)
+    }
+
+    /// Return all sites linked to your account.
+    ///
+    /// This method returns information about all electricity sites associated
+    /// with your Amber account. Each site represents a location where you have
+    /// electricity service.
+    ///
+    /// # Authentication
+    ///
+    /// This method requires authentication via API key. The API key can be
+    /// provided either through the `AMBER_API_KEY` environment variable (when
+    /// using [`Amber::default()`]) or by explicitly setting it when building
+    /// the client.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`Result`] containing a [`Vec`] of [`Site`] objects on
+    /// success.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if:
+    ///
+    /// - The API key is missing or invalid
+    ///   ([`MissingApiKey`](crate::AmberError::MissingApiKey) or
+    ///   [`Unauthorized`](crate::AmberError::Unauthorized))
+    /// - There's a network error communicating with the API
+    /// - The API returns an internal server error (HTTP 500)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let sites = client.sites().await?;
+    ///
+    /// for site in sites {
+    ///     println!("Site {}: {} ({})", site.id, site.network, site.status);
+    /// }
+    /// # Ok(())
     /// # }
     /// ```
     ///
     /// [`Site`]: crate::models::Site
     #[inline]
     pub async fn sites(&self) -> Result<Vec<crate::models::Site>> {
-        self.get("sites", core::iter::empty::<(&str, &str)>()).await
+        self.sites_response().await.map(Response::into_inner)
+    }
+
+    /// Return all sites linked to your account, along with the response
+    /// metadata.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`sites()`].
+    ///
+    /// [`sites()`]: Self::sites
+    #[inline]
+    pub async fn sites_response(&self) -> Result<Response<Vec<crate::models::Site>>> {
+        self.get(
+            "sites",
+            core::iter::empty::<(&str, &str)>(),
+            Overrides::default(),
+        )
+        .await
+    }
+
+    /// Returns only the active sites linked to your account.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`active_sites()`](Self::active_sites).
+    #[deprecated(note = "Use `active_sites()` instead, which caches the list of sites.")]
+    #[inline]
+    pub async fn sites_active(&self) -> Result<Vec<crate::models::Site>> {
+        self.active_sites().await
+    }
+
+    /// Send a typed request, such as a [`PricesRequest`] or a [`UsageRequest`].
+    ///
+    /// This is equivalent to calling the corresponding endpoint method with
+    /// the same parameters, and is convenient when requests are stored or
+    /// generated ahead of time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::InvalidRequest`] if the request fails
+    /// [validation](ApiRequest::validate), and otherwise the same errors as
+    /// the corresponding endpoint method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    /// use amber_api::requests::CurrentPricesRequest;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let sites = client.sites().await?;
+    /// let request = CurrentPricesRequest::builder()
+    ///     .site_id(sites[0].id.clone())
+    ///     .next(12)
+    ///     .build();
+    /// let response = client.send(&request).await?;
+    /// println!("{} intervals", response.data.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`PricesRequest`]: crate::requests::PricesRequest
+    /// [`UsageRequest`]: crate::requests::UsageRequest
+    /// [`AmberError::InvalidRequest`]: crate::AmberError::InvalidRequest
+    #[inline]
+    pub async fn send<R: ApiRequest>(&self, request: &R) -> Result<Response<R::Output>> {
+        self.send_with(request, Overrides::default()).await
+    }
+
+    /// Returns the URL and query parameters [`send()`] would request, without
+    /// sending anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::InvalidRequest`] if the API would reject the
+    /// request, as [`send()`] would.
+    ///
+    /// [`send()`]: Self::send
+    /// [`AmberError::InvalidRequest`]: crate::AmberError::InvalidRequest
+    #[inline]
+    pub fn plan<R: ApiRequest>(&self, request: &R) -> Result<RequestInfo> {
+        request.validate()?;
+        Ok(self.request_info(&request.path(), request.query()))
+    }
+
+    /// Returns the requests a call for the prices of a site would perform,
+    /// without sending anything.
+    ///
+    /// If both dates are given, this describes the chunks requested by
+    /// [`prices_range()`]; otherwise it describes the single request of
+    /// [`prices()`]. This is useful to debug or audit what a call will do,
+    /// such as how many requests a long range needs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::InvalidRequest`] if the dates are in the wrong
+    /// order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use amber_api::Amber;
+    /// use amber_api::models::SiteId;
+    /// use jiff::civil::date;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::builder().api_key("psk_example").build()?;
+    /// let site_id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse()?;
+    ///
+    /// let plan = client
+    ///     .prices_plan()
+    ///     .site_id(&site_id)
+    ///     .start_date(date(2021, 5, 1))
+    ///     .end_date(date(2021, 5, 31))
+    ///     .call()?;
+    /// assert_eq!(plan.len(), 5);
+    /// for request in &plan {
+    ///     println!("GET {} {:?}", request.url, request.query);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`prices()`]: Self::prices
+    /// [`prices_range()`]: Self::prices_range
+    /// [`AmberError::InvalidRequest`]: crate::AmberError::InvalidRequest
+    #[inline]
+    #[builder]
+    pub fn prices_plan(
+        &self,
+        site_id: &models::SiteId,
+        start_date: Option<jiff::civil::Date>,
+        end_date: Option<jiff::civil::Date>,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+    ) -> Result<Vec<RequestInfo>> {
+        let requested = requested_resolution(resolution)?;
+        let chunks = match (start_date, end_date) {
+            (Some(start), Some(end)) => date_chunks(start, end)?
+                .into_iter()
+                .map(|(chunk_start, chunk_end)| (Some(chunk_start), Some(chunk_end)))
+                .collect(),
+            _ => Vec::from([(start_date, end_date)]),
+        };
+        chunks
+            .into_iter()
+            .map(|(chunk_start, chunk_end)| {
+                self.plan(
+                    &PricesRequest::builder()
+                        .site_id(site_id.clone())
+                        .maybe_start_date(chunk_start)
+                        .maybe_end_date(chunk_end)
+                        .maybe_resolution(requested)
+                        .build(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the requests [`usage_range()`] would perform, without sending
+    /// anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::InvalidRequest`] if the dates are in the wrong
+    /// order.
+    ///
+    /// [`usage_range()`]: Self::usage_range
+    /// [`AmberError::InvalidRequest`]: crate::AmberError::InvalidRequest
+    #[inline]
+    #[builder]
+    pub fn usage_plan(
+        &self,
+        site_id: &models::SiteId,
+        start_date: jiff::civil::Date,
+        end_date: jiff::civil::Date,
+    ) -> Result<Vec<RequestInfo>> {
+        date_chunks(start_date, end_date)?
+            .into_iter()
+            .map(|(chunk_start, chunk_end)| {
+                self.plan(
+                    &UsageRequest::builder()
+                        .site_id(site_id.clone())
+                        .start_date(chunk_start)
+                        .end_date(chunk_end)
+                        .build(),
+                )
+            })
+            .collect()
     }
 
     /// Returns all the prices between the start and end dates for a specific
@@ -382,7 +1422,10 @@ impl Amber {
     ///   today)
     /// - `end_date`: Optional end date for the price range (defaults to today)
     /// - `resolution`: Optional interval duration (5 or 30 minutes, defaults to
-    ///   your billing interval)
+    ///   your billing interval), given as a [`Resolution`](models::Resolution),
+    ///   in minutes, or as the [`interval_duration()`] of the site
+    /// - `timeout`: Optional timeout overriding the client's overall timeout
+    /// - `api_key`: Optional API key overriding the client's key
     ///
     /// # Authentication
     ///
@@ -401,31 +1444,37 @@ impl Amber {
     ///
     /// This method will return an error if:
     ///
-    /// - The API key is missing or invalid (HTTP 401)
+    /// - The API key is missing or invalid
+    ///   ([`MissingApiKey`](crate::AmberError::MissingApiKey) or
+    ///   [`Unauthorized`](crate::AmberError::Unauthorized))
     /// - The site ID is invalid (HTTP 400)
     /// - The site is not found (HTTP 404)
-    /// - The date range exceeds 7 days (HTTP 422)
+    /// - The date range is reversed or exceeds 7 days
+    ///   ([`AmberError::InvalidRequest`], checked before sending)
+    /// - The resolution is not 5 or 30 minutes ([`AmberError::InvalidRequest`],
+    ///   checked before sending)
     /// - There's a network error communicating with the API
     /// - The API returns an internal server error (HTTP 500)
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::str::FromStr;
     ///
     /// use amber_api::Amber;
     /// use amber_api::models::Resolution;
     /// use jiff::civil::Date;
     ///
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Amber::default();
-    /// let sites = client.sites()?;
+    /// let sites = client.sites().await?;
     /// let site_id = &sites[0].id;
     ///
     /// // Get prices for today
     /// let prices = client.prices()
     ///     .site_id(site_id)
-    ///     .call()?;
+    ///     .call().await?;
     ///
     /// // Get prices for a specific date range
     /// let start_date = Date::from_str("2021-05-01").expect("Invalid start date");
@@ -435,7 +1484,7 @@ impl Amber {
     ///     .start_date(start_date)
     ///     .end_date(end_date)
     ///     .resolution(Resolution::FiveMinute)
-    ///     .call()?;
+    ///     .call().await?;
     ///
     /// for interval in prices {
     ///     match interval {
@@ -450,29 +1499,172 @@ impl Amber {
     /// ```
     ///
     /// [`sites()`]: Self::sites
+    /// [`interval_duration()`]: models::Site::interval_duration
     /// [`Interval`]: crate::models::Interval
+    /// [`AmberError::InvalidRequest`]: crate::AmberError::InvalidRequest
     #[inline]
     #[builder]
     pub async fn prices(
         &self,
-        site_id: &str,
+        site_id: &models::SiteId,
         start_date: Option<jiff::civil::Date>,
         end_date: Option<jiff::civil::Date>,
-        resolution: Option<models::Resolution>,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
     ) -> Result<Vec<models::Interval>> {
-        self.get(
-            &format!("sites/{site_id}/prices"),
-            [
-                ("startDate", start_date.map(|d| d.to_string())),
-                ("endDate", end_date.map(|d| d.to_string())),
-                ("resolution", resolution.map(|r| r.to_string())),
-            ]
-            .into_iter()
-            .filter_map(|(k, v)| v.map(|val| (k, val))),
+        self.prices_response()
+            .site_id(site_id)
+            .maybe_start_date(start_date)
+            .maybe_end_date(end_date)
+            .maybe_resolution(resolution)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key)
+            .call()
+            .await
+            .map(Response::into_inner)
+    }
+
+    /// Returns all the prices between the start and end dates for a specific
+    /// site, along with the response metadata.
+    ///
+    /// This takes the same parameters as [`prices()`].
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`prices()`].
+    ///
+    /// [`prices()`]: Self::prices
+    #[inline]
+    #[builder]
+    pub async fn prices_response(
+        &self,
+        site_id: &models::SiteId,
+        start_date: Option<jiff::civil::Date>,
+        end_date: Option<jiff::civil::Date>,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<Response<Vec<models::Interval>>> {
+        let request = PricesRequest::builder()
+            .site_id(site_id.clone())
+            .maybe_start_date(start_date)
+            .maybe_end_date(end_date)
+            .maybe_resolution(requested_resolution(resolution)?)
+            .build();
+        self.send_with(
+            &request,
+            Overrides {
+                timeout,
+                api_key: api_key.map(ApiKey::from),
+            },
         )
         .await
     }
 
+    /// Returns all the prices between the start and end dates for a specific
+    /// site, splitting long ranges into multiple requests.
+    ///
+    /// The API rejects [`prices()`] requests spanning more than
+    /// [`MAX_DATE_RANGE_DAYS`](limits::MAX_DATE_RANGE_DAYS) days. This method
+    /// instead splits the range into consecutive chunks of at most seven days
+    /// and fetches them sequentially (each subject to the client's rate limit
+    /// handling).
+    ///
+    /// The intervals are returned grouped by channel in the same order as the
+    /// API (General > Controlled Load > Feed In), and in chronological order
+    /// within each channel. Intervals of an unrecognised type are placed last.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`prices()`], except that long date ranges are accepted. No partial
+    /// results are returned if any chunk fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    /// use amber_api::models::Resolution;
+    /// use jiff::civil::date;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let sites = client.sites().await?;
+    ///
+    /// // A month of 30-minute prices, fetched in five requests
+    /// let prices = client.prices_range()
+    ///     .site_id(&sites[0].id)
+    ///     .start_date(date(2021, 5, 1))
+    ///     .end_date(date(2021, 5, 31))
+    ///     .resolution(Resolution::ThirtyMinute)
+    ///     .call().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`prices()`]: Self::prices
+    #[inline]
+    #[builder]
+    pub async fn prices_range(
+        &self,
+        site_id: &models::SiteId,
+        start_date: jiff::civil::Date,
+        end_date: jiff::civil::Date,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<Vec<models::Interval>> {
+        self.prices_pages()
+            .site_id(site_id)
+            .start_date(start_date)
+            .end_date(end_date)
+            .maybe_resolution(resolution)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key)
+            .call()?
+            .collect_all()
+            .await
+    }
+
+    /// Returns the prices between the start and end dates for a specific
+    /// site, as pages fetched one request at a time.
+    ///
+    /// The range is split into the same chunks as [`prices_range()`], but no
+    /// request is made until a page is fetched from the returned
+    /// [`PricesPages`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the end date is before the start date.
+    ///
+    /// [`prices_range()`]: Self::prices_range
+    #[inline]
+    #[builder]
+    pub fn prices_pages<'a>(
+        &'a self,
+        site_id: &'a models::SiteId,
+        start_date: jiff::civil::Date,
+        end_date: jiff::civil::Date,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<PricesPages<'a>> {
+        let requested = requested_resolution(resolution)?;
+        let chunks = date_chunks(start_date, end_date)?;
+        debug!(%site_id, chunks = chunks.len(), "Fetching prices in chunks");
+        Ok(PricesPages::new(
+            self,
+            site_id,
+            chunks.into_iter(),
+            requested,
+            timeout,
+            api_key,
+        ))
+    }
+
     /// Returns the current price for a specific site.
     ///
     /// This method retrieves the current pricing data for the specified site,
@@ -487,7 +1679,10 @@ impl Amber {
     /// - `previous`: Optional number of historical intervals to return (max
     ///   2048 total)
     /// - `resolution`: Optional interval duration (5 or 30 minutes, defaults to
-    ///   your billing interval)
+    ///   your billing interval), given as a [`Resolution`](models::Resolution),
+    ///   in minutes, or as the [`interval_duration()`] of the site
+    /// - `timeout`: Optional timeout overriding the client's overall timeout
+    /// - `api_key`: Optional API key overriding the client's key
     ///
     /// # Authentication
     ///
@@ -506,42 +1701,72 @@ impl Amber {
     ///
     /// This method will return an error if:
     ///
-    /// - The API key is missing or invalid (HTTP 401)
+    /// - The API key is missing or invalid
+    ///   ([`MissingApiKey`](crate::AmberError::MissingApiKey) or
+    ///   [`Unauthorized`](crate::AmberError::Unauthorized))
     /// - The site ID is invalid (HTTP 400)
     /// - The site is not found (HTTP 404)
-    /// - The total number of intervals exceeds 2048 (HTTP 422)
+    /// - `next + previous` exceeds 2048 ([`AmberError::InvalidRequest`],
+    ///   checked before sending)
+    /// - The resolution is not 5 or 30 minutes ([`AmberError::InvalidRequest`],
+    ///   checked before sending)
     /// - There's a network error communicating with the API
     /// - The API returns an internal server error (HTTP 500)
     ///
+    /// # Per-Channel Counts
+    ///
+    /// The `next` and `previous` counts apply to each channel of the site, so
+    /// `next(3)` on a site with general and feed-in channels returns six
+    /// forecast intervals. Plain integers are interpreted per channel; pass an
+    /// [`IntervalCount::total`] to instead specify the number of intervals
+    /// across all channels.
+    ///
+    /// # Interval Cap
+    ///
+    /// The API returns at most 2048 intervals across all channels, and
+    /// silently truncates each channel's results when a request would exceed
+    /// this. A warning is logged when the response contains fewer intervals
+    /// than requested for any channel. Use [`limits::check_request`] with the
+    /// number of channels of the site to check a request in advance.
+    ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use amber_api::Amber;
+    /// use amber_api::limits::IntervalCount;
     /// use amber_api::models::Resolution;
     ///
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Amber::default();
-    /// let sites = client.sites()?;
+    /// let sites = client.sites().await?;
     /// let site_id = &sites[0].id;
     ///
     /// // Get current prices only
     /// let current_prices = client.current_prices()
     ///     .site_id(site_id)
-    ///     .call()?;
+    ///     .call().await?;
     ///
     /// // Get current prices with forecast
     /// let prices_with_forecast = client.current_prices()
     ///     .site_id(site_id)
     ///     .next(8)
     ///     .resolution(Resolution::ThirtyMinute)
-    ///     .call()?;
+    ///     .call().await?;
     ///
     /// // Get current prices with history and forecast
     /// let full_prices = client.current_prices()
     ///     .site_id(site_id)
     ///     .previous(8)
     ///     .next(8)
-    ///     .call()?;
+    ///     .call().await?;
+    ///
+    /// // Get 12 forecast intervals in total, split across the site's channels
+    /// let channels = u32::try_from(sites[0].channels.len())?;
+    /// let split_prices = client.current_prices()
+    ///     .site_id(site_id)
+    ///     .next(IntervalCount::total(12, channels))
+    ///     .call().await?;
     ///
     /// for interval in current_prices {
     ///     match interval {
@@ -557,27 +1782,88 @@ impl Amber {
     /// ```
     ///
     /// [`sites()`]: Self::sites
+    /// [`interval_duration()`]: models::Site::interval_duration
     /// [`Interval`]: crate::models::Interval
+    /// [`limits::check_request`]: crate::limits::check_request
+    /// [`IntervalCount::total`]: crate::limits::IntervalCount::total
+    /// [`AmberError::InvalidRequest`]: crate::AmberError::InvalidRequest
     #[inline]
     #[builder]
     pub async fn current_prices(
         &self,
-        site_id: &str,
-        next: Option<u32>,
-        previous: Option<u32>,
-        resolution: Option<models::Resolution>,
+        site_id: &models::SiteId,
+        #[builder(into)] next: Option<IntervalCount>,
+        #[builder(into)] previous: Option<IntervalCount>,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
     ) -> Result<Vec<models::Interval>> {
-        self.get(
-            &format!("sites/{site_id}/prices/current"),
-            [
-                ("next", next.map(|n| n.to_string())),
-                ("previous", previous.map(|p| p.to_string())),
-                ("resolution", resolution.map(|r| r.to_string())),
-            ]
-            .into_iter()
-            .filter_map(|(k, v)| v.map(|val| (k, val))),
-        )
-        .await
+        self.current_prices_response()
+            .site_id(site_id)
+            .maybe_next(next)
+            .maybe_previous(previous)
+            .maybe_resolution(resolution)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key)
+            .call()
+            .await
+            .map(Response::into_inner)
+    }
+
+    /// Returns the current price for a specific site, along with the response
+    /// metadata.
+    ///
+    /// This takes the same parameters as [`current_prices()`].
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`current_prices()`].
+    ///
+    /// [`current_prices()`]: Self::current_prices
+    #[inline]
+    #[builder]
+    pub async fn current_prices_response(
+        &self,
+        site_id: &models::SiteId,
+        #[builder(into)] next: Option<IntervalCount>,
+        #[builder(into)] previous: Option<IntervalCount>,
+        #[builder(into)] resolution: Option<models::IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<Response<Vec<models::Interval>>> {
+        let request = CurrentPricesRequest::builder()
+            .site_id(site_id.clone())
+            .maybe_next(next.map(IntervalCount::per_channel_count))
+            .maybe_previous(previous.map(IntervalCount::per_channel_count))
+            .maybe_resolution(requested_resolution(resolution)?)
+            .build();
+        let requested_next = request.next.unwrap_or(0);
+        let requested_previous = request.previous.unwrap_or(0);
+        let response = self
+            .send_with(
+                &request,
+                Overrides {
+                    timeout,
+                    api_key: api_key.map(ApiKey::from),
+                },
+            )
+            .await?;
+
+        for coverage in limits::ChannelCoverage::analyze(&response.data) {
+            if coverage.is_truncated(requested_next, requested_previous) {
+                warn!(
+                    channel = %coverage.channel_type,
+                    requested_next,
+                    requested_previous,
+                    returned_next = coverage.next,
+                    returned_previous = coverage.previous,
+                    "Current prices truncated, likely due to the {MAX_INTERVALS} interval cap"
+                );
+            }
+        }
+
+        Ok(response)
     }
 
     /// Returns all usage data between the start and end dates for a specific
@@ -594,6 +1880,8 @@ impl Amber {
     /// - `start_date`: Start date for the usage data (required)
     /// - `end_date`: End date for the usage data (required)
     /// - `resolution`: Optional interval duration (deprecated, will be ignored)
+    /// - `timeout`: Optional timeout overriding the client's overall timeout
+    /// - `api_key`: Optional API key overriding the client's key
     ///
     /// # Authentication
     ///
@@ -612,24 +1900,28 @@ impl Amber {
     ///
     /// This method will return an error if:
     ///
-    /// - The API key is missing or invalid (HTTP 401)
+    /// - The API key is missing or invalid
+    ///   ([`MissingApiKey`](crate::AmberError::MissingApiKey) or
+    ///   [`Unauthorized`](crate::AmberError::Unauthorized))
     /// - The site ID is invalid (HTTP 400)
     /// - The site is not found (HTTP 404)
-    /// - The date range exceeds 7 days (HTTP 422)
+    /// - The date range is reversed or exceeds 7 days
+    ///   ([`AmberError::InvalidRequest`], checked before sending)
     /// - There's a network error communicating with the API
     /// - The API returns an internal server error (HTTP 500)
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::str::FromStr;
     ///
     /// use amber_api::Amber;
     /// use jiff::civil::Date;
     ///
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Amber::default();
-    /// let sites = client.sites()?;
+    /// let sites = client.sites().await?;
     /// let site_id = &sites[0].id;
     ///
     /// // Get usage data for a specific date range
@@ -639,7 +1931,7 @@ impl Amber {
     ///     .site_id(site_id)
     ///     .start_date(start_date)
     ///     .end_date(end_date)
-    ///     .call()?;
+    ///     .call().await?;
     ///
     /// for usage in usage_data {
     ///     println!("Channel {}: {:.2} kWh, Cost: ${:.2}",
@@ -651,22 +1943,1158 @@ impl Amber {
     ///
     /// [`sites()`]: Self::sites
     /// [`Usage`]: crate::models::Usage
+    /// [`AmberError::InvalidRequest`]: crate::AmberError::InvalidRequest
     #[inline]
     #[builder]
     pub async fn usage(
         &self,
-        site_id: &str,
+        site_id: &models::SiteId,
         start_date: jiff::civil::Date,
         end_date: jiff::civil::Date,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
     ) -> Result<Vec<models::Usage>> {
-        let start_date_str = start_date.to_string();
-        let end_date_str = end_date.to_string();
-        let query_params = [
-            ("startDate", start_date_str.as_str()),
-            ("endDate", end_date_str.as_str()),
+        self.usage_response()
+            .site_id(site_id)
+            .start_date(start_date)
+            .end_date(end_date)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key)
+            .call()
+            .await
+            .map(Response::into_inner)
+    }
+
+    /// Returns all usage data between the start and end dates for a specific
+    /// site, along with the response metadata.
+    ///
+    /// This takes the same parameters as [`usage()`].
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`usage()`].
+    ///
+    /// [`usage()`]: Self::usage
+    #[inline]
+    #[builder]
+    pub async fn usage_response(
+        &self,
+        site_id: &models::SiteId,
+        start_date: jiff::civil::Date,
+        end_date: jiff::civil::Date,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<Response<Vec<models::Usage>>> {
+        let request = UsageRequest::builder()
+            .site_id(site_id.clone())
+            .start_date(start_date)
+            .end_date(end_date)
+            .build();
+        self.send_with(
+            &request,
+            Overrides {
+                timeout,
+                api_key: api_key.map(ApiKey::from),
+            },
+        )
+        .await
+    }
+
+    /// Returns all usage data between the start and end dates for a specific
+    /// site, splitting long ranges into multiple requests.
+    ///
+    /// The API rejects [`usage()`] requests spanning more than
+    /// [`MAX_DATE_RANGE_DAYS`](limits::MAX_DATE_RANGE_DAYS) days. This method
+    /// instead splits the range into consecutive chunks of at most seven days,
+    /// fetches them sequentially (each subject to the client's rate limit
+    /// handling), and concatenates the results in date order.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`usage()`], except that long date ranges are accepted. No partial
+    /// results are returned if any chunk fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    /// use jiff::civil::date;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let sites = client.sites().await?;
+    ///
+    /// // Four weeks of usage, fetched in four requests
+    /// let usage = client.usage_range()
+    ///     .site_id(&sites[0].id)
+    ///     .start_date(date(2021, 5, 1))
+    ///     .end_date(date(2021, 5, 28))
+    ///     .call().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`usage()`]: Self::usage
+    #[inline]
+    #[builder]
+    pub async fn usage_range(
+        &self,
+        site_id: &models::SiteId,
+        start_date: jiff::civil::Date,
+        end_date: jiff::civil::Date,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<Vec<models::Usage>> {
+        self.usage_pages()
+            .site_id(site_id)
+            .start_date(start_date)
+            .end_date(end_date)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key)
+            .call()?
+            .collect_all()
+            .await
+    }
+
+    /// Returns the usage between the start and end dates for a specific site,
+    /// as pages fetched one request at a time.
+    ///
+    /// The range is split into the same chunks as [`usage_range()`], but no
+    /// request is made until a page is fetched from the returned
+    /// [`UsagePages`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the end date is before the start date.
+    ///
+    /// [`usage_range()`]: Self::usage_range
+    #[inline]
+    #[builder]
+    pub fn usage_pages<'a>(
+        &'a self,
+        site_id: &'a models::SiteId,
+        start_date: jiff::civil::Date,
+        end_date: jiff::civil::Date,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<UsagePages<'a>> {
+        let chunks = date_chunks(start_date, end_date)?;
+        debug!(%site_id, chunks = chunks.len(), "Fetching usage in chunks");
+        Ok(UsagePages::new(
+            self,
+            site_id,
+            chunks.into_iter(),
+            timeout,
+            api_key,
+        ))
+    }
+
+    /// Returns the prices and usage of a site between the start and end dates,
+    /// joined into a single timeline.
+    ///
+    /// This fetches the range with [`prices_range()`] and [`usage_range()`],
+    /// and joins them with [`analysis::timeline::timeline`] into one entry for
+    /// each interval of each channel. Prices are fetched at the site's billing
+    /// resolution, matching its usage.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`prices_range()`] and [`usage_range()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    /// use jiff::civil::date;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let sites = client.sites().await?;
+    ///
+    /// let timeline = client.timeline()
+    ///     .site_id(&sites[0].id)
+    ///     .start_date(date(2021, 5, 1))
+    ///     .end_date(date(2021, 5, 7))
+    ///     .call().await?;
+    /// for entry in timeline {
+    ///     println!("{}: {:?} kWh, {:?}c", entry.start_time, entry.kwh, entry.cost);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`prices_range()`]: Self::prices_range
+    /// [`usage_range()`]: Self::usage_range
+    /// [`analysis::timeline::timeline`]: crate::analysis::timeline::timeline
+    #[inline]
+    #[builder]
+    pub async fn timeline(
+        &self,
+        site_id: &models::SiteId,
+        start_date: jiff::civil::Date,
+        end_date: jiff::civil::Date,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<Vec<TimelineEntry>> {
+        let prices = self
+            .prices_range()
+            .site_id(site_id)
+            .start_date(start_date)
+            .end_date(end_date)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key.clone())
+            .call()
+            .await?;
+        let usage = self
+            .usage_range()
+            .site_id(site_id)
+            .start_date(start_date)
+            .end_date(end_date)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key)
+            .call()
+            .await?;
+        Ok(timeline(&prices, &usage))
+    }
+
+    /// Returns the summary of a site's usage over a billing period.
+    ///
+    /// This fetches the usage of the period with [`usage_range()`], which
+    /// splits it into chunks the API accepts, and totals it with
+    /// [`BillingSummary::from_usage`]. The usage of a period that has not
+    /// ended yet is only fetched up to today, summarising the bill so far.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`usage_range()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    /// use amber_api::analysis::billing::{BillingPeriod, CycleLength};
+    /// use jiff::civil::date;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let sites = client.sites().await?;
+    ///
+    /// let period = BillingPeriod::new(date(2021, 4, 14), CycleLength::Months(1));
+    /// let summary = client.summarise_billing_period()
+    ///     .site_id(&sites[0].id)
+    ///     .period(period)
+    ///     .call().await?;
+    /// println!("{summary}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`usage_range()`]: Self::usage_range
+    #[inline]
+    #[builder]
+    pub async fn summarise_billing_period(
+        &self,
+        site_id: &models::SiteId,
+        period: BillingPeriod,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<BillingSummary> {
+        let end_date = period.end().min(guard::nem_today());
+        if end_date < period.start() {
+            return Ok(BillingSummary::from_usage(period, &[]));
+        }
+        let usage = self
+            .usage_range()
+            .site_id(site_id)
+            .start_date(period.start())
+            .end_date(end_date)
+            .maybe_timeout(timeout)
+            .maybe_api_key(api_key)
+            .call()
+            .await?;
+        Ok(BillingSummary::from_usage(period, &usage))
+    }
+}
+
+/// Deserialize a raw response, attaching the request metadata.
+///
+/// The body is decoded according to the format reported by the server.
+fn parse_response<T: DeserializeOwned>(
+    request: RequestInfo,
+    fetched: &Fetched,
+) -> Result<Response<T>> {
+    let data = if fetched.format() == Format::Json {
+        serde_json::from_slice(fetched.body())?
+    } else {
+        fetched.format().decode(fetched.body())?
+    };
+    Ok(fetched.to_response(request, data))
+}
+
+/// Parse the value of an HTTP `Date` header, such as
+/// `Tue, 15 Nov 1994 08:12:31 GMT`.
+fn parse_http_date(value: &str) -> Option<Timestamp> {
+    DateTimeParser::new().parse_timestamp(value).ok()
+}
+
+/// Per-request settings overriding those of the client.
+#[derive(Debug, Clone, Default)]
+struct Overrides {
+    /// Overall timeout of the request.
+    timeout: Option<Duration>,
+    /// API key used to authenticate the request.
+    api_key: Option<ApiKey>,
+}
+
+/// Check that a header can be sent, so that invalid headers are reported when
+/// building the client rather than on every request.
+fn validate_header(name: &str, value: &str) -> Result<()> {
+    if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+        return Err(crate::error::AmberError::InvalidRequest(format!(
+            "invalid header name {name:?}"
+        )));
+    }
+    if reqwest::header::HeaderValue::from_str(value).is_err() {
+        return Err(crate::error::AmberError::InvalidRequest(format!(
+            "invalid value for header {name}"
+        )));
+    }
+    Ok(())
+}
+
+/// Value of the `Accept` header for the preferred format.
+///
+/// Binary formats are preferred, but JSON remains acceptable so that the API
+/// can always respond.
+fn accept_header(preferred: Format) -> String {
+    let json = Format::Json.media_type();
+    if preferred == Format::Json {
+        json.to_owned()
+    } else {
+        format!("{}, {json};q=0.9", preferred.media_type())
+    }
+}
+
+/// Returns the current time.
+pub(crate) fn timestamp_now() -> Timestamp {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|elapsed| {
+            Timestamp::new(
+                i64::try_from(elapsed.as_secs()).ok()?,
+                i32::try_from(elapsed.subsec_nanos()).ok()?,
+            )
+            .ok()
+        })
+        .unwrap_or(Timestamp::UNIX_EPOCH)
+}
+
+/// Collect query parameters into owned pairs.
+fn collect_query<I, K, V>(query: I) -> Vec<(String, String)>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    query
+        .into_iter()
+        .map(|(k, v)| (k.as_ref().to_owned(), v.as_ref().to_owned()))
+        .collect()
+}
+
+/// Channel and start time of an interval, and whether its type is unknown.
+type IntervalOrder<'a> = (bool, Option<((u8, &'a str), Timestamp)>);
+
+/// Sort key grouping intervals by channel, in chronological order within each
+/// channel.
+///
+/// Intervals of an unknown type sort last.
+fn interval_order(interval: &models::Interval) -> IntervalOrder<'_> {
+    let key = interval
+        .as_base_interval()
+        .map(|base| (channel_order(&base.channel_type), base.start_time));
+    (key.is_none(), key)
+}
+
+/// Split an inclusive date range into consecutive chunks of at most seven
+/// days, each accepted by the API.
+fn date_chunks(
+    start: jiff::civil::Date,
+    end: jiff::civil::Date,
+) -> Result<Vec<(jiff::civil::Date, jiff::civil::Date)>> {
+    if end < start {
+        // Reuse the validation error for reversed ranges.
+        limits::validate_date_range(start, end)?;
+    }
+    let chunk_days = jiff::Span::new().days(limits::MAX_DATE_RANGE_DAYS.saturating_sub(1));
+
+    let mut chunks = Vec::new();
+    let mut chunk_start = start;
+    loop {
+        let chunk_end = chunk_start
+            .checked_add(chunk_days)
+            .map_or(end, |date| date.min(end));
+        chunks.push((chunk_start, chunk_end));
+        match chunk_end.tomorrow() {
+            Ok(next) if chunk_end < end => chunk_start = next,
+            _ => return Ok(chunks),
+        }
+    }
+}
+
+/// Returns the resolution of a requested interval duration.
+///
+/// Durations are accepted by the endpoint builders so that a site's interval
+/// length can be passed through as is, but only the durations of a
+/// [`Resolution`](models::Resolution) are supported by the API.
+fn requested_resolution(
+    duration: Option<models::IntervalDuration>,
+) -> Result<Option<models::Resolution>> {
+    duration
+        .map(|length| {
+            models::Resolution::try_from(length)
+                .map_err(|err| AmberError::InvalidRequest(err.to_string()))
+        })
+        .transpose()
+}
+
+/// Number of intervals of the given resolution needed to cover the span.
+///
+/// Partial intervals are rounded up, and the result saturates at
+/// [`u32::MAX`].
+#[cfg_attr(
+    feature = "no-panic",
+    inline(never),
+    no_panic::no_panic,
+    expect(clippy::mem_forget, reason = "Expanded by no_panic")
+)]
+fn intervals_in_span(span: core::time::Duration, resolution: models::Resolution) -> u32 {
+    let interval_secs = u64::from(u32::from(resolution)).saturating_mul(60);
+    let count = span.as_secs().div_ceil(interval_secs);
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned as _, string::ToString as _, vec};
+    use core::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::Duration,
+    };
+
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn intervals_in_span_rounds_up() {
+        let day = Duration::from_hours(24);
+        assert_eq!(intervals_in_span(day, models::Resolution::ThirtyMinute), 48);
+        assert_eq!(intervals_in_span(day, models::Resolution::FiveMinute), 288);
+        assert_eq!(
+            intervals_in_span(Duration::from_secs(61), models::Resolution::FiveMinute),
+            1
+        );
+        assert_eq!(
+            intervals_in_span(Duration::ZERO, models::Resolution::FiveMinute),
+            0
+        );
+        assert_eq!(
+            intervals_in_span(day.saturating_mul(365), models::Resolution::FiveMinute),
+            105_120
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_renewables_history_beyond_the_interval_cap() {
+        let client = Amber::offline();
+        let week = Duration::from_hours(168);
+
+        client
+            .renewables_history()
+            .state(models::State::Vic)
+            .span(week)
+            .resolution(models::Resolution::ThirtyMinute)
+            .call()
+            .await
+            .expect("a week of renewables");
+        assert!(matches!(
+            client
+                .renewables_history()
+                .state(models::State::Vic)
+                .span(week.saturating_mul(2))
+                .resolution(models::Resolution::FiveMinute)
+                .call()
+                .await,
+            Err(crate::AmberError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn plans_requests_without_sending() {
+        let client = Amber::builder()
+            .api_key("psk_plan")
+            .base_url("https://example.com/v1/")
+            .build()
+            .expect("valid client");
+        let site_id: models::SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+
+        let single = client
+            .prices_plan()
+            .site_id(&site_id)
+            .resolution(models::Resolution::FiveMinute)
+            .call()
+            .expect("valid plan");
+        assert_eq!(
+            single,
+            vec![RequestInfo {
+                url: "https://example.com/v1/sites/01F5A5CRKMZ5BCX9P1S4V990AM/prices".to_owned(),
+                query: vec![("resolution".to_owned(), "5".to_owned())],
+            }]
+        );
+
+        let chunked = client
+            .usage_plan()
+            .site_id(&site_id)
+            .start_date(date(2021, 5, 1))
+            .end_date(date(2021, 5, 10))
+            .call()
+            .expect("valid plan");
+        let queries: Vec<_> = chunked
+            .iter()
+            .map(|request| request.query.clone())
+            .collect();
+        assert_eq!(
+            queries,
+            vec![
+                vec![
+                    ("startDate".to_owned(), "2021-05-01".to_owned()),
+                    ("endDate".to_owned(), "2021-05-07".to_owned()),
+                ],
+                vec![
+                    ("startDate".to_owned(), "2021-05-08".to_owned()),
+                    ("endDate".to_owned(), "2021-05-10".to_owned()),
+                ],
+            ]
+        );
+
+        assert_eq!(
+            client
+                .prices_plan()
+                .site_id(&site_id)
+                .resolution(30)
+                .call()
+                .expect("valid plan"),
+            vec![RequestInfo {
+                url: "https://example.com/v1/sites/01F5A5CRKMZ5BCX9P1S4V990AM/prices".to_owned(),
+                query: vec![("resolution".to_owned(), "30".to_owned())],
+            }]
+        );
+
+        client
+            .prices_plan()
+            .site_id(&site_id)
+            .resolution(15)
+            .call()
+            .expect_err("unsupported resolution");
+
+        client
+            .prices_plan()
+            .site_id(&site_id)
+            .start_date(date(2021, 5, 2))
+            .end_date(date(2021, 5, 1))
+            .call()
+            .expect_err("reversed range");
+    }
+
+    #[test]
+    fn date_chunks_cover_range() {
+        assert_eq!(
+            date_chunks(date(2021, 5, 1), date(2021, 5, 1)).expect("valid range"),
+            vec![(date(2021, 5, 1), date(2021, 5, 1))]
+        );
+        assert_eq!(
+            date_chunks(date(2021, 5, 1), date(2021, 5, 16)).expect("valid range"),
+            vec![
+                (date(2021, 5, 1), date(2021, 5, 7)),
+                (date(2021, 5, 8), date(2021, 5, 14)),
+                (date(2021, 5, 15), date(2021, 5, 16)),
+            ]
+        );
+        date_chunks(date(2021, 5, 2), date(2021, 5, 1)).expect_err("reversed range");
+
+        // Every chunk is accepted by the API, and a chunk one day longer is not
+        for (start, end) in date_chunks(date(2021, 5, 1), date(2021, 5, 16)).expect("valid range") {
+            limits::validate_date_range(start, end).expect("chunk within the limit");
+        }
+        assert!(limits::validate_date_range(date(2021, 5, 1), date(2021, 5, 8)).is_err());
+    }
+
+    #[test]
+    fn interval_order_groups_channels() {
+        let interval = |channel_type: models::ChannelType, second: i64| {
+            models::Interval::ActualInterval(models::ActualInterval {
+                base: models::BaseInterval {
+                    duration: 30,
+                    spot_per_kwh: 10.0,
+                    per_kwh: 20.0,
+                    date: date(2021, 5, 1),
+                    nem_time: Timestamp::UNIX_EPOCH,
+                    start_time: Timestamp::from_second(second).expect("valid timestamp"),
+                    end_time: Timestamp::UNIX_EPOCH,
+                    renewables: 50.0,
+                    channel_type,
+                    tariff_information: None,
+                    spike_status: models::SpikeStatus::None,
+                    descriptor: models::PriceDescriptor::Neutral,
+                },
+            })
+        };
+        let mut intervals = [
+            models::Interval::Unknown(serde_json::json!({"type": "FutureInterval"})),
+            interval(models::ChannelType::FeedIn, 0),
+            interval(models::ChannelType::General, 1800),
+            interval(models::ChannelType::General, 0),
         ];
+        intervals.sort_by(|a, b| interval_order(a).cmp(&interval_order(b)));
+
+        let order: Vec<_> = intervals
+            .iter()
+            .map(|sorted| {
+                sorted
+                    .as_base_interval()
+                    .map(|base| (base.channel_type.to_string(), base.start_time.as_second()))
+            })
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                Some(("general".to_owned(), 0)),
+                Some(("general".to_owned(), 1800)),
+                Some(("feed-in".to_owned(), 0)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_http_date_rejects_malformed() {
+        assert_eq!(
+            parse_http_date("Tue, 15 Nov 1994 08:12:31 GMT"),
+            Some("1994-11-15T08:12:31Z".parse().expect("valid timestamp"))
+        );
+        for value in [
+            "",
+            "Tue",
+            "Tue, 15 Nov 1994",
+            "Tue, 32 Nov 1994 08:12:31 GMT",
+            "Tue, 15 Nov 99999 08:12:31 GMT",
+            "Tue, 15 Nov 1994 25:12:31 GMT",
+            "Tu\u{e9}, 15 Nov 1994 08:12:31 GMT",
+        ] {
+            assert_eq!(parse_http_date(value), None, "{value:?}");
+        }
+    }
 
-        self.get(&format!("sites/{site_id}/usage"), query_params)
+    #[test]
+    fn parse_response_uses_content_type() {
+        let request = RequestInfo {
+            url: "https://example.com/sites".to_owned(),
+            query: Vec::new(),
+        };
+        let json =
+            |body: &[u8]| Fetched::new(200, body.to_vec(), Format::Json, Timestamp::UNIX_EPOCH);
+        let fetched = json(b"[1, 2]");
+        let parsed: Response<Vec<u8>> =
+            parse_response(request.clone(), &fetched).expect("valid JSON");
+        assert_eq!(parsed.data, vec![1, 2]);
+        assert!(matches!(
+            parse_response::<Vec<u8>>(request.clone(), &json(b"[")),
+            Err(crate::AmberError::Decode(_))
+        ));
+
+        for body in [
+            &b""[..],
+            b"null",
+            b"[{}]",
+            b"[{\"type\": 1}]",
+            b"[{\"type\": \"ActualInterval\", \"duration\": -5}]",
+            b"\xff\xfe",
+            &[b'['; 1024],
+        ] {
+            assert!(
+                parse_response::<Vec<models::Interval>>(request.clone(), &json(body)).is_err(),
+                "{body:?}"
+            );
+        }
+
+        #[cfg(feature = "cbor")]
+        {
+            let cbor = Fetched::new(
+                200,
+                Format::Cbor.encode(&[3_u8, 4_u8]).expect("encodes"),
+                Format::Cbor,
+                Timestamp::UNIX_EPOCH,
+            );
+            let decoded: Response<Vec<u8>> = parse_response(request, &cbor).expect("valid CBOR");
+            assert_eq!(decoded.data, vec![3, 4]);
+        }
+    }
+
+    #[test]
+    fn validates_headers() {
+        validate_header("X-Trace-Id", "abc123").expect("valid header");
+        assert!(matches!(
+            validate_header("Bad Header", "value"),
+            Err(crate::AmberError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            validate_header("X-Trace-Id", "line\nbreak"),
+            Err(crate::AmberError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn accept_header_falls_back_to_json() {
+        assert_eq!(accept_header(Format::Json), "application/json");
+        #[cfg(feature = "msgpack")]
+        assert_eq!(
+            accept_header(Format::MessagePack),
+            "application/msgpack, application/json;q=0.9"
+        );
+    }
+
+    /// Backend responding to every request with the given status.
+    #[derive(Debug)]
+    struct StatusBackend(u16);
+
+    impl HttpBackend for StatusBackend {
+        fn get(
+            &self,
+            _request: HttpRequest,
+        ) -> backend::BoxFuture<'_, Result<backend::HttpResponse>> {
+            let status = self.0;
+            Box::pin(async move { Ok(backend::HttpResponse::new(status, "denied")) })
+        }
+    }
+
+    /// Backend recording the bearer token of each request.
+    #[derive(Debug, Default)]
+    struct TokenBackend(Mutex<Vec<Option<String>>>);
+
+    impl HttpBackend for TokenBackend {
+        fn get(
+            &self,
+            request: HttpRequest,
+        ) -> backend::BoxFuture<'_, Result<backend::HttpResponse>> {
+            if let Ok(mut tokens) = self.0.lock() {
+                tokens.push(request.bearer_token.map(|key| key.expose().to_owned()));
+            }
+            Box::pin(async { Ok(backend::HttpResponse::new(200, "[]")) })
+        }
+    }
+
+    #[test]
+    fn debug_omits_credentials() {
+        let client = Amber::builder()
+            .api_key("psk_secret")
+            .default_headers(vec![("X-Token".to_owned(), "header_secret".to_owned())])
+            .build()
+            .expect("valid client");
+        let debug = format!("{client:?}");
+        assert!(!debug.contains("psk_secret"), "{debug}");
+        assert!(!debug.contains("header_secret"), "{debug}");
+        assert!(debug.contains("X-Token"), "{debug}");
+    }
+
+    #[tokio::test]
+    async fn api_key_overrides() {
+        let backend = Arc::new(TokenBackend::default());
+        let client = Amber::builder()
+            .api_key("psk_client")
+            .backend(Box::new(ArcBackend(Arc::clone(&backend))))
+            .build()
+            .expect("valid client");
+        let site_id: models::SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+
+        client.sites().await.expect("succeeds");
+        client
+            .current_prices()
+            .site_id(&site_id)
+            .api_key("psk_request")
+            .call()
+            .await
+            .expect("succeeds");
+        client
+            .with_api_key("psk_account")
+            .sites()
             .await
+            .expect("succeeds");
+
+        let tokens = backend.0.lock().expect("not poisoned").clone();
+        assert_eq!(
+            tokens,
+            vec![
+                Some("psk_client".to_owned()),
+                Some("psk_request".to_owned()),
+                Some("psk_account".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn pages_are_fetched_lazily() {
+        let backend = Arc::new(TokenBackend::default());
+        let client = Amber::builder()
+            .api_key("psk_client")
+            .backend(Box::new(ArcBackend(Arc::clone(&backend))))
+            .build()
+            .expect("valid client");
+        let site_id: models::SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+        let requests = || backend.0.lock().expect("not poisoned").len();
+
+        let mut usage = client
+            .usage_pages()
+            .site_id(&site_id)
+            .start_date(date(2021, 5, 1))
+            .end_date(date(2021, 5, 16))
+            .call()
+            .expect("valid range");
+        assert_eq!(usage.remaining(), 3);
+        assert_eq!(usage.size_hint(), (3, Some(3)));
+        assert_eq!(requests(), 0);
+
+        let page = usage.next_page().await.expect("a page");
+        assert!(page.expect("succeeds").is_empty());
+        assert_eq!(requests(), 1);
+        assert_eq!(
+            usage.dates(),
+            &[
+                (date(2021, 5, 8), date(2021, 5, 14)),
+                (date(2021, 5, 15), date(2021, 5, 16)),
+            ]
+        );
+
+        assert!(usage.collect_all().await.expect("succeeds").is_empty());
+        assert_eq!(requests(), 3);
+
+        let pages = client
+            .prices_pages()
+            .site_id(&site_id)
+            .start_date(date(2021, 5, 1))
+            .end_date(date(2021, 5, 7))
+            .call()
+            .expect("valid range");
+        assert_eq!(pages.remaining(), 1);
+
+        client
+            .prices_pages()
+            .site_id(&site_id)
+            .start_date(date(2021, 5, 2))
+            .end_date(date(2021, 5, 1))
+            .call()
+            .expect_err("reversed range");
+    }
+
+    /// Backend delegating to a shared backend, so tests can inspect it.
+    #[derive(Debug)]
+    struct ArcBackend(Arc<TokenBackend>);
+
+    impl HttpBackend for ArcBackend {
+        fn get(
+            &self,
+            request: HttpRequest,
+        ) -> backend::BoxFuture<'_, Result<backend::HttpResponse>> {
+            self.0.get(request)
+        }
+    }
+
+    /// Backend serving the client's account and rejecting any other key.
+    #[derive(Debug)]
+    struct AccountBackend;
+
+    impl HttpBackend for AccountBackend {
+        fn get(
+            &self,
+            request: HttpRequest,
+        ) -> backend::BoxFuture<'_, Result<backend::HttpResponse>> {
+            let status = if request.bearer_token.as_ref().map(ApiKey::expose) == Some("psk_client")
+            {
+                200
+            } else {
+                403
+            };
+            Box::pin(async move { Ok(backend::HttpResponse::new(status, "[]")) })
+        }
+    }
+
+    #[tokio::test]
+    async fn memoized_responses_are_per_account() {
+        let client = Amber::builder()
+            .api_key("psk_client")
+            .dedupe_historical(true)
+            .backend(Box::new(AccountBackend))
+            .build()
+            .expect("valid client");
+        let site_id: models::SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+        let memoized = client
+            .usage()
+            .site_id(&site_id)
+            .start_date(date(2021, 5, 1))
+            .end_date(date(2021, 5, 1))
+            .call()
+            .await
+            .expect("succeeds");
+        assert!(memoized.is_empty());
+
+        let overridden = client
+            .usage()
+            .site_id(&site_id)
+            .start_date(date(2021, 5, 1))
+            .end_date(date(2021, 5, 1))
+            .api_key("psk_other")
+            .call()
+            .await;
+        assert!(matches!(
+            overridden,
+            Err(crate::AmberError::Unauthorized { status: 403, .. })
+        ));
+
+        let other_account = client
+            .with_api_key("psk_other")
+            .usage()
+            .site_id(&site_id)
+            .start_date(date(2021, 5, 1))
+            .end_date(date(2021, 5, 1))
+            .call()
+            .await;
+        assert!(matches!(
+            other_account,
+            Err(crate::AmberError::Unauthorized { status: 403, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejected_key_is_unauthorized() {
+        for status in [401, 403] {
+            let client = Amber::builder()
+                .api_key("psk_invalid")
+                .backend(Box::new(StatusBackend(status)))
+                .build()
+                .expect("valid client");
+            assert!(matches!(
+                client.verify_auth().await,
+                Err(crate::AmberError::Unauthorized { status: actual, .. }) if actual == status
+            ));
+        }
+    }
+
+    /// Backend listing a single closed site, as reported by a server whose
+    /// clock is far behind.
+    #[derive(Debug)]
+    struct ClosedSiteBackend;
+
+    impl HttpBackend for ClosedSiteBackend {
+        fn get(
+            &self,
+            _request: HttpRequest,
+        ) -> backend::BoxFuture<'_, Result<backend::HttpResponse>> {
+            let body = serde_json::json!([{
+                "id": "01F5A5CRKMZ5BCX9P1S4V990AM",
+                "nmi": "3052282872",
+                "channels": [{"identifier": "E1", "type": "general", "tariff": "A100"}],
+                "network": "Jemena",
+                "status": "closed",
+                "activeFrom": "2022-01-01",
+                "closedOn": "2022-05-01",
+                "intervalLength": 30_i32
+            }])
+            .to_string();
+            Box::pin(async move {
+                Ok(backend::HttpResponse::new(200, body)
+                    .with_header("Date", "Tue, 15 Nov 1994 08:12:31 GMT")
+                    .with_header("RateLimit-Remaining", "0"))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn build_and_validate_reports_warnings() {
+        let (_client, report) = Amber::builder()
+            .api_key("psk_valid")
+            .backend(Box::new(ClosedSiteBackend))
+            .build_and_validate()
+            .await
+            .expect("valid client");
+        assert_eq!(report.sites.len(), 1);
+        assert_eq!(report.active_sites().count(), 0);
+        assert!(!report.is_healthy());
+
+        let skew = report.clock_skew.expect("server time reported");
+        assert!(skew > jiff::SignedDuration::from_hours(24 * 365));
+        assert_eq!(
+            report.warnings,
+            vec![
+                crate::StartupWarning::NoActiveSites,
+                crate::StartupWarning::ClockSkew(skew),
+                crate::StartupWarning::RateLimited,
+            ]
+        );
+        let message = crate::StartupWarning::ClockSkew(skew).to_string();
+        assert!(message.contains("ahead of the server's"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn caches_responses() {
+        let backend = Arc::new(TokenBackend::default());
+        let client = Amber::builder()
+            .api_key("psk_client")
+            .backend(Box::new(ArcBackend(Arc::clone(&backend))))
+            .cache(CachePolicy::default())
+            .build()
+            .expect("valid client");
+        let requests = || backend.0.lock().expect("not poisoned").len();
+
+        client.sites().await.expect("succeeds");
+        client.clone().sites().await.expect("succeeds");
+        assert_eq!(requests(), 1);
+
+        // Per-request keys and other accounts bypass the cache
+        client
+            .with_api_key("psk_account")
+            .sites()
+            .await
+            .expect("succeeds");
+        assert_eq!(requests(), 2);
+
+        client.clear_cache();
+        client.sites().await.expect("succeeds");
+        assert_eq!(requests(), 3);
+    }
+
+    /// Backend serving a versioned response, answering conditional requests
+    /// for the current version with `304 Not Modified`.
+    #[derive(Debug)]
+    struct EtagBackend;
+
+    impl HttpBackend for EtagBackend {
+        fn get(
+            &self,
+            request: HttpRequest,
+        ) -> backend::BoxFuture<'_, Result<backend::HttpResponse>> {
+            let matches = request
+                .headers
+                .iter()
+                .any(|(name, value)| name == "If-None-Match" && value == "\"v1\"");
+            Box::pin(async move {
+                Ok(if matches {
+                    backend::HttpResponse::new(304, "")
+                } else {
+                    backend::HttpResponse::new(200, "[]").with_header("ETag", "\"v1\"")
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn revalidates_expired_responses() {
+        let client = Amber::builder()
+            .api_key("psk_client")
+            .backend(Box::new(EtagBackend))
+            .cache(
+                CachePolicy::builder()
+                    .sites(Duration::from_millis(1))
+                    .build(),
+            )
+            .build()
+            .expect("valid client");
+
+        let first = client.sites_response().await.expect("succeeds");
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let second = client.sites_response().await.expect("succeeds");
+        assert_eq!(second.data, first.data);
+        assert_eq!(second.status, 200);
+        assert_eq!(
+            client.cache_stats(),
+            Some(CacheStats {
+                hits: 0,
+                misses: 1,
+                revalidated: 1,
+                stale: 0,
+            })
+        );
+    }
+
+    /// Backend answering the first request, then failing as if the API were
+    /// down.
+    #[derive(Debug, Default)]
+    struct OutageBackend(AtomicBool);
+
+    impl HttpBackend for OutageBackend {
+        fn get(
+            &self,
+            _request: HttpRequest,
+        ) -> backend::BoxFuture<'_, Result<backend::HttpResponse>> {
+            let down = self.0.swap(true, Ordering::Relaxed);
+            Box::pin(async move {
+                Ok(if down {
+                    backend::HttpResponse::new(503, "unavailable")
+                } else {
+                    backend::HttpResponse::new(200, "[]")
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_stale_responses_during_outage() {
+        let policy = CachePolicy::builder()
+            .sites(Duration::from_millis(1))
+            .stale_if_error(Duration::from_mins(1))
+            .build();
+        let client = Amber::builder()
+            .api_key("psk_client")
+            .backend(Box::new(OutageBackend::default()))
+            .cache(policy)
+            .build()
+            .expect("valid client");
+
+        let live = client.sites_response().await.expect("succeeds");
+        assert!(!live.is_stale());
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let stale = client
+            .sites_response()
+            .await
+            .expect("served from cache")
+            .into_live()
+            .expect_err("stale");
+        assert!(stale.data.is_empty());
+        assert!(stale.age >= Duration::from_millis(1));
+        assert_eq!(client.cache_stats().map(|stats| stats.stale), Some(1));
+
+        // Without a stale policy, the error is returned
+        let strict = Amber::builder()
+            .api_key("psk_client")
+            .backend(Box::new(OutageBackend::default()))
+            .cache(
+                CachePolicy::builder()
+                    .sites(Duration::from_millis(1))
+                    .build(),
+            )
+            .build()
+            .expect("valid client");
+        strict.sites().await.expect("succeeds");
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        strict.sites().await.expect_err("request timed out");
     }
 }
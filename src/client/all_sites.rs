@@ -0,0 +1,204 @@
+//! # Prices of Every Site
+//!
+//! Accounts with several properties need the prices of each of their sites.
+//! Rather than requesting them one after the other,
+//! [`Amber::current_prices_all_sites()`] requests them concurrently, with a
+//! bound on the number of requests in flight so that the rate limit of the
+//! account is not exhausted in a single burst.
+
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::time::Duration;
+use std::collections::HashMap;
+
+use tokio::{sync::Semaphore, task::JoinSet};
+use tracing::debug;
+
+use super::Amber;
+use crate::{
+    error::{AmberError, Result},
+    limits::IntervalCount,
+    models::{Interval, IntervalDuration, SiteId},
+};
+
+/// Default number of sites whose prices are requested at once.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+impl Amber {
+    /// Wait for the rate limit window to reset if the last response reported
+    /// that no requests remain.
+    ///
+    /// Waits longer than the [`max_sleep`](super::RetryPolicy::max_sleep) of
+    /// the retry policy are skipped, leaving the request to fail as usual.
+    pub(super) async fn wait_for_rate_limit(&self) {
+        let Some(rate_limit) = self.rate_limit_status() else {
+            return;
+        };
+        if !rate_limit.is_exhausted() {
+            return;
+        }
+        if let Some(delay) = self.retry_policy.base_delay(rate_limit.reset) {
+            debug!("Rate limit exhausted. Waiting {:?} before request", delay);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "The fan-out over sites is kept with its helpers"
+)]
+#[bon::bon]
+impl Amber {
+    /// Returns the current prices of every active site, keyed by site ID.
+    ///
+    /// The prices of the sites are requested concurrently, with at most
+    /// `concurrency` requests (4 by default) in flight at once. All requests
+    /// are made by this client, so they share its rate limit tracking and its
+    /// retries on rate limit errors. Additionally, while the last response
+    /// reported that no requests remain in the rate limit window, new requests
+    /// wait for the window to reset rather than being rejected.
+    ///
+    /// The `next`, `previous` and `resolution` parameters apply to every site,
+    /// as with [`current_prices()`](Self::current_prices). If an `api_key` is
+    /// given, the sites of its account are listed and their prices requested
+    /// with it, as with a client from [`with_api_key()`](Self::with_api_key).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered while listing the sites or fetching
+    /// the prices of any of them, after cancelling the remaining requests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> amber_api::Result<()> {
+    /// let client = Amber::default();
+    /// let prices = client.current_prices_all_sites().next(12).call().await?;
+    /// for (site_id, intervals) in &prices {
+    ///     println!("{site_id}: {} intervals", intervals.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[builder]
+    pub async fn current_prices_all_sites(
+        &self,
+        #[builder(into)] next: Option<IntervalCount>,
+        #[builder(into)] previous: Option<IntervalCount>,
+        #[builder(into)] resolution: Option<IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+        #[builder(default = DEFAULT_CONCURRENCY)] concurrency: usize,
+    ) -> Result<HashMap<SiteId, Vec<Interval>>> {
+        let account = api_key.map_or_else(|| self.clone(), |key| self.with_api_key(key));
+        let sites = account.active_sites().await?;
+        let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+        for site in sites {
+            let client = account.clone();
+            let semaphore = Arc::clone(&permits);
+            tasks.spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .map_err(|err| AmberError::Backend(err.into()))?;
+                client.wait_for_rate_limit().await;
+                let intervals = client
+                    .current_prices()
+                    .site_id(&site.id)
+                    .maybe_next(next)
+                    .maybe_previous(previous)
+                    .maybe_resolution(resolution)
+                    .maybe_timeout(timeout)
+                    .call()
+                    .await?;
+                Ok::<_, AmberError>((site.id, intervals))
+            });
+        }
+
+        let mut prices = HashMap::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (site_id, intervals) = joined.map_err(|err| AmberError::Backend(err.into()))??;
+            prices.insert(site_id, intervals);
+        }
+        Ok(prices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use std::sync::Mutex;
+
+    use pretty_assertions::assert_eq;
+
+    use super::super::backend::{BoxFuture, HttpBackend, HttpRequest, HttpResponse};
+    use super::*;
+    use crate::{ApiKey, FixtureBackend};
+
+    /// Fixture backend recording the bearer token of each request.
+    #[derive(Debug)]
+    struct TokenBackend(Arc<Mutex<Vec<Option<ApiKey>>>>, FixtureBackend);
+
+    impl HttpBackend for TokenBackend {
+        fn get(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            if let Ok(mut tokens) = self.0.lock() {
+                tokens.push(request.bearer_token.clone());
+            }
+            self.1.get(request)
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_every_active_site() {
+        let client = Amber::offline();
+        let prices = client
+            .current_prices_all_sites()
+            .concurrency(2)
+            .call()
+            .await
+            .expect("prices");
+        assert_eq!(prices.len(), 1);
+        let site_id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+        assert!(
+            prices
+                .get(&site_id)
+                .is_some_and(|intervals| !intervals.is_empty())
+        );
+    }
+
+    #[tokio::test]
+    async fn lists_the_sites_of_the_overriding_key() {
+        let tokens = Arc::default();
+        let client = Amber::builder()
+            .api_key("psk_client")
+            .backend(Box::new(TokenBackend(
+                Arc::clone(&tokens),
+                FixtureBackend::builtin(),
+            )))
+            .build()
+            .expect("valid client");
+        let prices = client
+            .current_prices_all_sites()
+            .api_key("psk_other")
+            .call()
+            .await
+            .expect("prices");
+        assert_eq!(prices.len(), 1);
+
+        let seen = tokens.lock().expect("not poisoned").clone();
+        assert_eq!(seen.len(), 2);
+        assert!(
+            seen.iter()
+                .all(|token| token.as_ref().map(ApiKey::expose) == Some("psk_other"))
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! # Renewables of Every State
+//!
+//! National dashboards, and virtual power plants spanning several regions,
+//! need the renewables of every state of the NEM.
+//! [`Amber::current_renewables_all_states()`] requests them all concurrently.
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::time::Duration;
+
+use tokio::task::JoinSet;
+
+use super::Amber;
+use crate::{
+    error::{AmberError, Result},
+    models::{IntervalDuration, Renewable, State},
+};
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "The fan-out over states is kept in its own module"
+)]
+#[bon::bon]
+impl Amber {
+    /// Returns the current renewables of every state, keyed by state.
+    ///
+    /// The renewables of each state in [`State::ALL`] are requested
+    /// concurrently. As with
+    /// [`current_prices_all_sites()`](Self::current_prices_all_sites), the
+    /// requests share the rate limit tracking and retries of this client, and
+    /// wait for the rate limit window to reset if no requests remain.
+    ///
+    /// The `next`, `previous` and `resolution` parameters apply to every state,
+    /// as with [`current_renewables()`](Self::current_renewables).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered while fetching the renewables of
+    /// any state, after cancelling the remaining requests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> amber_api::Result<()> {
+    /// let client = Amber::default();
+    /// let renewables = client.current_renewables_all_states().call().await?;
+    /// for (state, renewables) in &renewables {
+    ///     if let Some(current) = renewables.iter().find_map(|r| r.as_current_renewable()) {
+    ///         println!("{state}: {:.0}% renewable", current.renewables);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[builder]
+    pub async fn current_renewables_all_states(
+        &self,
+        next: Option<u32>,
+        previous: Option<u32>,
+        #[builder(into)] resolution: Option<IntervalDuration>,
+        timeout: Option<Duration>,
+        #[builder(into)] api_key: Option<String>,
+    ) -> Result<BTreeMap<State, Vec<Renewable>>> {
+        let mut tasks = JoinSet::new();
+        for state in State::ALL {
+            let client = self.clone();
+            let key = api_key.clone();
+            tasks.spawn(async move {
+                client.wait_for_rate_limit().await;
+                let renewables = client
+                    .current_renewables()
+                    .state(state)
+                    .maybe_next(next)
+                    .maybe_previous(previous)
+                    .maybe_resolution(resolution)
+                    .maybe_timeout(timeout)
+                    .maybe_api_key(key)
+                    .call()
+                    .await?;
+                Ok::<_, AmberError>((state, renewables))
+            });
+        }
+
+        let mut renewables = BTreeMap::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (state, data) = joined.map_err(|err| AmberError::Backend(err.into()))??;
+            renewables.insert(state, data);
+        }
+        Ok(renewables)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fetches_every_state() {
+        let client = Amber::offline();
+        let renewables = client
+            .current_renewables_all_states()
+            .call()
+            .await
+            .expect("renewables");
+        assert_eq!(renewables.keys().copied().collect::<Vec<_>>(), State::ALL);
+        assert!(renewables.values().all(|data| !data.is_empty()));
+    }
+}
@@ -0,0 +1,246 @@
+//! # API Abstraction
+//!
+//! The [`AmberApi`] trait describes the endpoints of the API, so that
+//! application logic can accept any implementation rather than the concrete
+//! [`Amber`] client, and be tested against a mock without any HTTP:
+//!
+//! ```
+//! use amber_api::{
+//!     AmberApi, BoxFuture, Result,
+//!     models::{Interval, Renewable, Site, Usage},
+//!     requests::{CurrentPricesRequest, CurrentRenewablesRequest, PricesRequest, UsageRequest},
+//! };
+//!
+//! /// Count the active sites of the account.
+//! async fn active_sites(api: &impl AmberApi) -> Result<usize> {
+//!     Ok(api.sites().await?.iter().filter(|site| site.is_active()).count())
+//! }
+//!
+//! /// A mock without any sites.
+//! struct NoSites;
+//!
+//! impl AmberApi for NoSites {
+//!     fn sites(&self) -> BoxFuture<'_, Result<Vec<Site>>> {
+//!         Box::pin(async { Ok(Vec::new()) })
+//!     }
+//!
+//!     fn prices<'a>(&'a self, _: &'a PricesRequest) -> BoxFuture<'a, Result<Vec<Interval>>> {
+//!         Box::pin(async { Ok(Vec::new()) })
+//!     }
+//!
+//!     fn current_prices<'a>(
+//!         &'a self,
+//!         _: &'a CurrentPricesRequest,
+//!     ) -> BoxFuture<'a, Result<Vec<Interval>>> {
+//!         Box::pin(async { Ok(Vec::new()) })
+//!     }
+//!
+//!     fn usage<'a>(&'a self, _: &'a UsageRequest) -> BoxFuture<'a, Result<Vec<Usage>>> {
+//!         Box::pin(async { Ok(Vec::new()) })
+//!     }
+//!
+//!     fn current_renewables<'a>(
+//!         &'a self,
+//!         _: &'a CurrentRenewablesRequest,
+//!     ) -> BoxFuture<'a, Result<Vec<Renewable>>> {
+//!         Box::pin(async { Ok(Vec::new()) })
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! assert_eq!(active_sites(&NoSites).await?, 0);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The trait methods take the [typed requests](crate::requests) rather than
+//! builders. As the inherent builder methods of [`Amber`] share their names,
+//! call the trait methods on an `impl AmberApi` (or with
+//! `AmberApi::prices(&client, &request)`) rather than directly on a client.
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+use super::{Amber, backend::BoxFuture, response::Response};
+use crate::{
+    error::Result,
+    models::{Interval, Renewable, Site, Usage},
+    requests::{CurrentPricesRequest, CurrentRenewablesRequest, PricesRequest, UsageRequest},
+};
+
+/// The endpoints of the Amber Electric API.
+///
+/// Implemented by the [`Amber`] client, and by mocks in tests. See the
+/// [module documentation](self) for an example.
+pub trait AmberApi: Send + Sync {
+    /// Returns all sites linked to the account.
+    fn sites(&self) -> BoxFuture<'_, Result<Vec<Site>>>;
+
+    /// Returns the prices of a site between two dates.
+    fn prices<'a>(&'a self, request: &'a PricesRequest) -> BoxFuture<'a, Result<Vec<Interval>>>;
+
+    /// Returns the current prices of a site, along with neighbouring intervals.
+    fn current_prices<'a>(
+        &'a self,
+        request: &'a CurrentPricesRequest,
+    ) -> BoxFuture<'a, Result<Vec<Interval>>>;
+
+    /// Returns the usage of a site between two dates.
+    fn usage<'a>(&'a self, request: &'a UsageRequest) -> BoxFuture<'a, Result<Vec<Usage>>>;
+
+    /// Returns the current percentage of renewables in the grid of a state,
+    /// along with neighbouring intervals.
+    fn current_renewables<'a>(
+        &'a self,
+        request: &'a CurrentRenewablesRequest,
+    ) -> BoxFuture<'a, Result<Vec<Renewable>>>;
+}
+
+impl AmberApi for Amber {
+    #[inline]
+    fn sites(&self) -> BoxFuture<'_, Result<Vec<Site>>> {
+        Box::pin(Self::sites(self))
+    }
+
+    #[inline]
+    fn prices<'a>(&'a self, request: &'a PricesRequest) -> BoxFuture<'a, Result<Vec<Interval>>> {
+        Box::pin(async move { self.send(request).await.map(Response::into_inner) })
+    }
+
+    #[inline]
+    fn current_prices<'a>(
+        &'a self,
+        request: &'a CurrentPricesRequest,
+    ) -> BoxFuture<'a, Result<Vec<Interval>>> {
+        Box::pin(async move { self.send(request).await.map(Response::into_inner) })
+    }
+
+    #[inline]
+    fn usage<'a>(&'a self, request: &'a UsageRequest) -> BoxFuture<'a, Result<Vec<Usage>>> {
+        Box::pin(async move { self.send(request).await.map(Response::into_inner) })
+    }
+
+    #[inline]
+    fn current_renewables<'a>(
+        &'a self,
+        request: &'a CurrentRenewablesRequest,
+    ) -> BoxFuture<'a, Result<Vec<Renewable>>> {
+        Box::pin(async move { self.send(request).await.map(Response::into_inner) })
+    }
+}
+
+impl<T: AmberApi + ?Sized> AmberApi for &T {
+    #[inline]
+    fn sites(&self) -> BoxFuture<'_, Result<Vec<Site>>> {
+        (**self).sites()
+    }
+
+    #[inline]
+    fn prices<'a>(&'a self, request: &'a PricesRequest) -> BoxFuture<'a, Result<Vec<Interval>>> {
+        (**self).prices(request)
+    }
+
+    #[inline]
+    fn current_prices<'a>(
+        &'a self,
+        request: &'a CurrentPricesRequest,
+    ) -> BoxFuture<'a, Result<Vec<Interval>>> {
+        (**self).current_prices(request)
+    }
+
+    #[inline]
+    fn usage<'a>(&'a self, request: &'a UsageRequest) -> BoxFuture<'a, Result<Vec<Usage>>> {
+        (**self).usage(request)
+    }
+
+    #[inline]
+    fn current_renewables<'a>(
+        &'a self,
+        request: &'a CurrentRenewablesRequest,
+    ) -> BoxFuture<'a, Result<Vec<Renewable>>> {
+        (**self).current_renewables(request)
+    }
+}
+
+impl<T: AmberApi + ?Sized> AmberApi for Arc<T> {
+    #[inline]
+    fn sites(&self) -> BoxFuture<'_, Result<Vec<Site>>> {
+        (**self).sites()
+    }
+
+    #[inline]
+    fn prices<'a>(&'a self, request: &'a PricesRequest) -> BoxFuture<'a, Result<Vec<Interval>>> {
+        (**self).prices(request)
+    }
+
+    #[inline]
+    fn current_prices<'a>(
+        &'a self,
+        request: &'a CurrentPricesRequest,
+    ) -> BoxFuture<'a, Result<Vec<Interval>>> {
+        (**self).current_prices(request)
+    }
+
+    #[inline]
+    fn usage<'a>(&'a self, request: &'a UsageRequest) -> BoxFuture<'a, Result<Vec<Usage>>> {
+        (**self).usage(request)
+    }
+
+    #[inline]
+    fn current_renewables<'a>(
+        &'a self,
+        request: &'a CurrentRenewablesRequest,
+    ) -> BoxFuture<'a, Result<Vec<Renewable>>> {
+        (**self).current_renewables(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString as _};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::State;
+
+    /// Returns the IDs of the sites of an account.
+    async fn site_ids(api: &impl AmberApi) -> Result<Vec<String>> {
+        Ok(api
+            .sites()
+            .await?
+            .into_iter()
+            .map(|site| site.id.to_string())
+            .collect())
+    }
+
+    #[tokio::test]
+    async fn client_implements_api() {
+        let client = Amber::offline();
+        assert_eq!(
+            site_ids(&client).await.expect("sites"),
+            ["01F5A5CRKMZ5BCX9P1S4V990AM"]
+        );
+
+        let api: Arc<dyn AmberApi> = Arc::new(client);
+        let site_id = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+        let request = CurrentPricesRequest::builder().site_id(site_id).build();
+        assert!(
+            !api.current_prices(&request)
+                .await
+                .expect("current prices")
+                .is_empty()
+        );
+        let renewables = CurrentRenewablesRequest::builder()
+            .state(State::Vic)
+            .next(2)
+            .previous(2)
+            .build();
+        assert!(
+            !api.current_renewables(&renewables)
+                .await
+                .expect("renewables")
+                .is_empty()
+        );
+    }
+}
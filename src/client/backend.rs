@@ -0,0 +1,278 @@
+//! # HTTP Backends
+//!
+//! The [`Amber`](crate::Amber) client performs all requests through an
+//! [`HttpBackend`], allowing the transport to be replaced. By default, requests
+//! are made with [`reqwest`] through the [`ReqwestBackend`].
+//!
+//! Custom backends can be used to integrate with a different HTTP stack (such
+//! as `hyper` directly), or to provide canned responses in tests.
+
+use alloc::{borrow::ToOwned as _, boxed::Box, string::String, vec::Vec};
+use core::{fmt, future::Future, pin::Pin, time::Duration};
+
+use super::secret::ApiKey;
+use crate::error::{AmberError, Result};
+
+/// A boxed, sendable future as returned by [`HttpBackend`] methods.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A GET request to be performed by an [`HttpBackend`].
+///
+/// The bearer token is redacted from the `Debug` output, so that requests can
+/// be logged safely.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HttpRequest {
+    /// Full URL of the endpoint, without query parameters.
+    pub url: String,
+    /// Query parameters to append to the URL.
+    pub query: Vec<(String, String)>,
+    /// API key to send as a bearer token in the `Authorization` header, if
+    /// any.
+    pub bearer_token: Option<ApiKey>,
+    /// Media types to send in the `Accept` header, if any.
+    pub accept: Option<String>,
+    /// Overall timeout of the request, overriding the backend's default.
+    ///
+    /// Backends should report requests exceeding it as
+    /// [`AmberError::Timeout`].
+    pub timeout: Option<Duration>,
+    /// Additional headers to send, such as a custom `User-Agent`.
+    pub headers: Vec<(String, String)>,
+}
+
+impl HttpRequest {
+    /// Create a new request for the given URL.
+    #[must_use]
+    #[inline]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            query: Vec::new(),
+            bearer_token: None,
+            accept: None,
+            timeout: None,
+            headers: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for HttpRequest {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpRequest")
+            .field("url", &self.url)
+            .field("query", &self.query)
+            .field(
+                "bearer_token",
+                &self.bearer_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("accept", &self.accept)
+            .field("timeout", &self.timeout)
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+/// A response returned by an [`HttpBackend`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HttpResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers.
+    pub headers: Vec<(String, String)>,
+    /// Raw response body.
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Create a new response with the given status code and body, and no
+    /// headers.
+    #[must_use]
+    #[inline]
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Returns the response with an additional header.
+    #[must_use]
+    #[inline]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Returns the value of the first header with the given name.
+    ///
+    /// Header names are compared case-insensitively.
+    #[must_use]
+    #[inline]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns `true` if the status code is in the 2xx range.
+    #[must_use]
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the body as text, replacing invalid UTF-8 sequences.
+    #[must_use]
+    #[inline]
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Transport used by the [`Amber`](crate::Amber) client to perform requests.
+///
+/// Implementations only need to perform the request and return the raw
+/// response; rate limit handling, retries and deserialization are performed by
+/// the client. Non-2xx responses should be returned as an [`HttpResponse`]
+/// rather than an error, with errors reserved for transport failures.
+///
+/// Transport errors from custom backends can be reported with
+/// [`AmberError::Backend`](crate::AmberError::Backend).
+///
+/// # Example
+///
+/// ```
+/// use amber_api::{Amber, BoxFuture, HttpBackend, HttpRequest, HttpResponse};
+///
+/// #[derive(Debug)]
+/// struct NoSites;
+///
+/// impl HttpBackend for NoSites {
+///     fn get(&self, _request: HttpRequest) -> BoxFuture<'_, amber_api::Result<HttpResponse>> {
+///         Box::pin(async { Ok(HttpResponse::new(200, "[]")) })
+///     }
+/// }
+///
+/// # fn main() -> amber_api::Result<()> {
+/// let client = Amber::builder().backend(Box::new(NoSites)).build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait HttpBackend: fmt::Debug + Send + Sync {
+    /// Perform a GET request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request could not be performed.
+    fn get(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>>;
+}
+
+/// The default [`HttpBackend`], using [`reqwest`].
+#[derive(Debug, Clone)]
+pub struct ReqwestBackend {
+    /// The underlying `reqwest` client.
+    client: reqwest::Client,
+}
+
+impl ReqwestBackend {
+    /// Create a new backend from an existing `reqwest` client.
+    #[must_use]
+    #[inline]
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl From<reqwest::Client> for ReqwestBackend {
+    #[inline]
+    fn from(client: reqwest::Client) -> Self {
+        Self::new(client)
+    }
+}
+
+impl HttpBackend for ReqwestBackend {
+    #[inline]
+    fn get(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        Box::pin(async move {
+            let mut builder = self.client.get(&request.url);
+            if let Some(token) = &request.bearer_token {
+                // Marks the header as sensitive, so it is never logged
+                builder = builder.bearer_auth(token.expose());
+            }
+            if let Some(accept) = &request.accept {
+                builder = builder.header(reqwest::header::ACCEPT, accept);
+            }
+            if !request.query.is_empty() {
+                builder = builder.query(&request.query);
+            }
+            for (name, value) in &request.headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+
+            let response = builder.send().await.map_err(from_reqwest)?;
+            let status = response.status().as_u16();
+            let headers = response
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    value
+                        .to_str()
+                        .ok()
+                        .map(|text| (name.as_str().to_owned(), text.to_owned()))
+                })
+                .collect();
+            let body = response.bytes().await.map_err(from_reqwest)?.to_vec();
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/// Convert a `reqwest` error, distinguishing timeouts.
+fn from_reqwest(err: reqwest::Error) -> AmberError {
+    if err.is_timeout() {
+        AmberError::Timeout
+    } else {
+        AmberError::Http(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn response_helpers() {
+        let response = HttpResponse::new(429, "slow down").with_header("RateLimit-Reset", "5");
+        assert_eq!(response.header("ratelimit-reset"), Some("5"));
+        assert_eq!(response.header("RateLimit-Remaining"), None);
+        assert!(!response.is_success());
+        assert_eq!(response.text(), "slow down");
+        assert!(HttpResponse::new(204, Vec::new()).is_success());
+    }
+
+    #[test]
+    fn request_debug_redacts_token() {
+        let mut request = HttpRequest::new("https://api.amber.com.au/v1/sites");
+        request.bearer_token = Some(ApiKey::from("psk_secret"));
+        let debug = format!("{request:?}");
+        assert!(!debug.contains("psk_secret"), "{debug}");
+        assert!(debug.contains("<redacted>"), "{debug}");
+    }
+}
@@ -0,0 +1,181 @@
+//! # Usage Backfill
+//!
+//! The API only serves the last [`MAX_HISTORY_DAYS`] days of usage, at most
+//! seven days at a time. Seeding a local database therefore takes over a dozen
+//! requests, any of which may hit a rate limit or a transient failure.
+//! [`Amber::backfill_usage`] takes care of splitting the range, retrying and
+//! reporting progress.
+
+use alloc::vec::Vec;
+
+use jiff::{Span, civil::Date};
+use tracing::{debug, warn};
+
+use super::{Amber, date_chunks, guard};
+use crate::{
+    error::Result,
+    limits::MAX_HISTORY_DAYS,
+    models::{SiteId, Usage},
+};
+
+/// Progress of a usage backfill, reported after each chunk is fetched.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct BackfillProgress<'a> {
+    /// Number of chunks fetched so far, including this one.
+    pub completed: usize,
+    /// Total number of chunks.
+    pub total: usize,
+    /// First date of this chunk.
+    pub start_date: Date,
+    /// Last date of this chunk.
+    pub end_date: Date,
+    /// Usage records of this chunk.
+    pub usage: &'a [Usage],
+}
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "The backfill is kept in its own module"
+)]
+#[bon::bon]
+impl Amber {
+    /// Fetch the usage history of a site, reporting progress after each
+    /// request.
+    ///
+    /// The range is split into seven-day chunks which are fetched
+    /// sequentially, as with [`usage_range()`]. Rate limited requests are
+    /// retried as usual, and chunks failing with a transient error (see
+    /// [`AmberError::is_transient`]) are retried up to the retry policy's
+    /// `max_retries`, waiting a little longer after each attempt.
+    ///
+    /// # Parameters
+    ///
+    /// - `site_id`: ID of the site to fetch usage for.
+    /// - `start_date`: First date to fetch. Defaults to the start of the
+    ///   [`MAX_HISTORY_DAYS`] days ending on `end_date`.
+    /// - `end_date`: Last date to fetch. Defaults to yesterday (in NEM time).
+    /// - `on_progress`: Called after each chunk is fetched, with the chunk's
+    ///   records. This can be used to write records to a database as they
+    ///   arrive, or to drive a progress bar.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first non-transient error, or the last transient error once
+    /// retries are exhausted. Chunks fetched before the error have already
+    /// been passed to `on_progress`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let sites = client.sites().await?;
+    ///
+    /// let usage = client
+    ///     .backfill_usage()
+    ///     .site_id(&sites[0].id)
+    ///     .on_progress(|progress| {
+    ///         println!(
+    ///             "{}/{}: {} to {} ({} records)",
+    ///             progress.completed,
+    ///             progress.total,
+    ///             progress.start_date,
+    ///             progress.end_date,
+    ///             progress.usage.len()
+    ///         );
+    ///     })
+    ///     .call()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`usage_range()`]: Self::usage_range
+    /// [`AmberError::is_transient`]: crate::AmberError::is_transient
+    #[inline]
+    #[builder]
+    pub async fn backfill_usage<F: FnMut(&BackfillProgress<'_>)>(
+        &self,
+        site_id: &SiteId,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        on_progress: F,
+    ) -> Result<Vec<Usage>> {
+        let mut report = on_progress;
+        let last = end_date.unwrap_or_else(|| {
+            let today = guard::nem_today();
+            today.yesterday().unwrap_or(today)
+        });
+        let first = start_date.unwrap_or_else(|| {
+            last.checked_sub(Span::new().days(MAX_HISTORY_DAYS.saturating_sub(1)))
+                .unwrap_or(last)
+        });
+        let chunks = date_chunks(first, last)?;
+        let total = chunks.len();
+        debug!(%site_id, %first, %last, chunks = total, "Backfilling usage");
+
+        let mut usage = Vec::new();
+        for (index, (chunk_start, chunk_end)) in chunks.into_iter().enumerate() {
+            let chunk = self
+                .usage_with_retries(site_id, chunk_start, chunk_end)
+                .await?;
+            report(&BackfillProgress {
+                completed: index.saturating_add(1),
+                total,
+                start_date: chunk_start,
+                end_date: chunk_end,
+                usage: &chunk,
+            });
+            usage.extend(chunk);
+        }
+        Ok(usage)
+    }
+}
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "The retries are kept alongside the backfill"
+)]
+impl Amber {
+    /// Fetch usage for a date range, retrying transient failures.
+    async fn usage_with_retries(
+        &self,
+        site_id: &SiteId,
+        start_date: Date,
+        end_date: Date,
+    ) -> Result<Vec<Usage>> {
+        let mut attempt: u32 = 0;
+        loop {
+            match self
+                .usage()
+                .site_id(site_id)
+                .start_date(start_date)
+                .end_date(end_date)
+                .call()
+                .await
+            {
+                Ok(chunk) => return Ok(chunk),
+                Err(err) if err.is_transient() && attempt < self.retry_policy.max_retries => {
+                    attempt = attempt.saturating_add(1);
+                    let wait = self
+                        .retry_policy
+                        .default_delay
+                        .saturating_mul(attempt)
+                        .saturating_add(self.retry_policy.random_jitter());
+                    warn!(
+                        %start_date,
+                        %end_date,
+                        attempt,
+                        "Transient error fetching usage, retrying in {wait:?}: {err}"
+                    );
+                    tokio::time::sleep(wait).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! # Response Cache
+//!
+//! An opt-in, in-memory cache of recent responses, so that naive polling loops
+//! (such as a dashboard refreshing every few seconds) do not send a request
+//! each time and run into the rate limit.
+//!
+//! Each response is kept for a time-to-live depending on the endpoint, as set
+//! in the [`CachePolicy`]. This is independent of the duplicate request guard,
+//! which memoizes historical queries for the lifetime of the client.
+//!
+//! If the API identifies a response with an `ETag` or `Last-Modified` header,
+//! the response is kept after it expires, and the next request for it is made
+//! conditional (with `If-None-Match` or `If-Modified-Since`). When the API
+//! answers `304 Not Modified`, the cached body is reused and its lifetime
+//! renewed. The [`CacheStats`] of the client show how often each case occurs.
+//!
+//! With [`stale_if_error`](CachePolicy::stale_if_error), expired responses are
+//! also kept so that they can be served when the API is unavailable, allowing
+//! dashboards and controllers to keep functioning through brief outages. Such
+//! responses are marked as stale (see [`Response::into_live()`]).
+//!
+//! [`Response::into_live()`]: crate::Response::into_live
+
+use alloc::{collections::BTreeMap, string::String, sync::Arc, vec::Vec};
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{sync::Mutex, time::Instant};
+
+use super::response::Fetched;
+
+/// Time-to-live of cached responses for each endpoint.
+///
+/// A zero duration disables caching of that endpoint.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use amber_api::{Amber, CachePolicy};
+///
+/// # fn main() -> amber_api::Result<()> {
+/// let client = Amber::builder()
+///     .cache(
+///         CachePolicy::builder()
+///             .current_prices(Duration::from_secs(60))
+///             .build(),
+///     )
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bon::Builder)]
+#[non_exhaustive]
+pub struct CachePolicy {
+    /// Time-to-live of the list of sites.
+    ///
+    /// Defaults to 1 hour.
+    #[builder(default = Duration::from_hours(1))]
+    pub sites: Duration,
+    /// Time-to-live of current (and forecast) prices.
+    ///
+    /// Defaults to 30 seconds.
+    #[builder(default = Duration::from_secs(30))]
+    pub current_prices: Duration,
+    /// Time-to-live of the current renewables of a state.
+    ///
+    /// Defaults to 30 seconds.
+    #[builder(default = Duration::from_secs(30))]
+    pub current_renewables: Duration,
+    /// Time-to-live of price and usage queries over a date range.
+    ///
+    /// Ranges including today change as data arrives, so these are only
+    /// cached briefly. Ranges entirely in the past can be memoized
+    /// indefinitely with the `dedupe_historical` option of the client.
+    ///
+    /// Defaults to 5 minutes.
+    #[builder(default = Duration::from_mins(5))]
+    pub ranges: Duration,
+    /// Maximum age of an expired response served in place of a live one when
+    /// the API is unavailable.
+    ///
+    /// This applies when a request fails with a transient error (see
+    /// [`AmberError::is_transient`]) or is rate limited. Other errors, such
+    /// as a rejected API key, are always returned.
+    ///
+    /// Defaults to `None`, returning all errors.
+    ///
+    /// [`AmberError::is_transient`]: crate::AmberError::is_transient
+    pub stale_if_error: Option<Duration>,
+}
+
+impl Default for CachePolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl CachePolicy {
+    /// Returns the time-to-live of responses for the given endpoint path,
+    /// relative to the base URL.
+    #[must_use]
+    #[inline]
+    pub(crate) fn ttl(&self, path: &str) -> Duration {
+        if path == "sites" {
+            self.sites
+        } else if path.ends_with("/prices/current") {
+            self.current_prices
+        } else if path.ends_with("/renewables/current") {
+            self.current_renewables
+        } else if path.ends_with("/prices") || path.ends_with("/usage") {
+            self.ranges
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+/// Counts of how requests were answered by the response cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CacheStats {
+    /// Requests answered from the cache without contacting the API.
+    pub hits: u64,
+    /// Requests for which the API returned a new response.
+    pub misses: u64,
+    /// Conditional requests for which the API confirmed that the cached
+    /// response is unchanged (`304 Not Modified`).
+    pub revalidated: u64,
+    /// Failed requests answered by an expired response, as allowed by
+    /// [`CachePolicy::stale_if_error`].
+    pub stale: u64,
+}
+
+impl CacheStats {
+    /// Returns the total number of requests through the cache.
+    #[must_use]
+    #[inline]
+    pub fn requests(&self) -> u64 {
+        self.hits
+            .saturating_add(self.misses)
+            .saturating_add(self.revalidated)
+            .saturating_add(self.stale)
+    }
+}
+
+/// A cached response.
+#[derive(Debug, Clone)]
+struct Entry {
+    /// When the response was cached.
+    stored: Instant,
+    /// When the response expires.
+    expiry: Instant,
+    /// The raw response.
+    response: Arc<Fetched>,
+}
+
+/// Result of looking up a request in the cache.
+#[derive(Debug, Clone)]
+pub(crate) enum Lookup {
+    /// A response which has not expired yet.
+    Fresh(Arc<Fetched>),
+    /// An expired response, which can be revalidated.
+    Expired(Arc<Fetched>),
+    /// No response is cached.
+    Missing,
+}
+
+/// Recent responses, keyed by the full request.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    /// Time-to-live of each endpoint.
+    policy: CachePolicy,
+    /// Raw responses by request key.
+    entries: Mutex<BTreeMap<String, Entry>>,
+    /// Number of requests answered from the cache.
+    hits: AtomicU64,
+    /// Number of requests answered by a new response.
+    misses: AtomicU64,
+    /// Number of requests answered by `304 Not Modified`.
+    revalidated: AtomicU64,
+    /// Number of failed requests answered by an expired response.
+    stale: AtomicU64,
+}
+
+impl ResponseCache {
+    /// Create an empty cache.
+    pub(crate) fn new(policy: CachePolicy) -> Self {
+        Self {
+            policy,
+            entries: Mutex::new(BTreeMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            revalidated: AtomicU64::new(0),
+            stale: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the cache policy.
+    pub(crate) fn policy(&self) -> CachePolicy {
+        self.policy
+    }
+
+    /// Returns the time-to-live of responses for the given endpoint path.
+    pub(crate) fn ttl(&self, path: &str) -> Duration {
+        self.policy.ttl(path)
+    }
+
+    /// Look up the cached response for the given key.
+    pub(crate) fn get(&self, key: &str, now: Instant) -> Lookup {
+        let Ok(entries) = self.entries.lock() else {
+            return Lookup::Missing;
+        };
+        match entries.get(key) {
+            Some(entry) if now < entry.expiry => Lookup::Fresh(Arc::clone(&entry.response)),
+            Some(entry) => Lookup::Expired(Arc::clone(&entry.response)),
+            None => Lookup::Missing,
+        }
+    }
+
+    /// Returns an expired response which can be served while the API is
+    /// unavailable, along with its age.
+    ///
+    /// The response is counted in the statistics as stale.
+    pub(crate) fn stale(&self, key: &str, now: Instant) -> Option<(Arc<Fetched>, Duration)> {
+        let max_age = self.policy.stale_if_error?;
+        let entries = self.entries.lock().ok()?;
+        let entry = entries.get(key)?;
+        let age = now.saturating_duration_since(entry.stored);
+        (age <= max_age).then(|| {
+            self.stale.fetch_add(1, Ordering::Relaxed);
+            (Arc::clone(&entry.response), age)
+        })
+    }
+
+    /// Cache a response for the given time-to-live.
+    ///
+    /// Expired responses are evicted, unless they can be revalidated or may
+    /// still be served when the API is unavailable.
+    pub(crate) fn insert(&self, key: String, response: Arc<Fetched>, ttl: Duration, now: Instant) {
+        if ttl.is_zero() {
+            return;
+        }
+        let Some(expiry) = now.checked_add(ttl) else {
+            return;
+        };
+        let stale_until = |entry: &Entry| {
+            let max_age = self.policy.stale_if_error?;
+            entry.stored.checked_add(max_age)
+        };
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|_, entry| {
+                now < entry.expiry
+                    || entry.response.has_validators()
+                    || stale_until(entry).is_some_and(|until| now <= until)
+            });
+            entries.insert(
+                key,
+                Entry {
+                    stored: now,
+                    expiry,
+                    response,
+                },
+            );
+        }
+    }
+
+    /// Record how a request was answered.
+    pub(crate) fn record(&self, lookup: &Lookup, not_modified: bool) {
+        let counter = match lookup {
+            Lookup::Fresh(_) => &self.hits,
+            Lookup::Expired(_) if not_modified => &self.revalidated,
+            Lookup::Expired(_) | Lookup::Missing => &self.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the counts of how requests were answered.
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            revalidated: self.revalidated.load(Ordering::Relaxed),
+            stale: self.stale.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Remove all cached responses.
+    pub(crate) fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+impl Lookup {
+    /// Returns the headers making a request conditional on the cached
+    /// response having changed.
+    pub(crate) fn conditional_headers(&self) -> Vec<(String, String)> {
+        match self {
+            Self::Expired(response) => response.conditional_headers(),
+            Self::Fresh(_) | Self::Missing => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned as _, vec};
+
+    use jiff::Timestamp;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::storage::Format;
+
+    #[test]
+    fn ttl_by_endpoint() {
+        let policy = CachePolicy::default();
+        assert_eq!(policy.ttl("sites"), Duration::from_hours(1));
+        assert_eq!(
+            policy.ttl("sites/01F5A5CRKMZ5BCX9P1S4V990AM/prices/current"),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            policy.ttl("state/nsw/renewables/current"),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            policy.ttl("sites/01F5A5CRKMZ5BCX9P1S4V990AM/usage"),
+            Duration::from_mins(5)
+        );
+        assert_eq!(policy.ttl("unknown"), Duration::ZERO);
+    }
+
+    #[test]
+    fn expires_responses() {
+        let cache = ResponseCache::new(CachePolicy::default());
+        let response = Arc::new(Fetched::new(
+            200,
+            vec![b'[', b']'],
+            Format::Json,
+            Timestamp::UNIX_EPOCH,
+        ));
+        let now = Instant::now();
+        let later = now
+            .checked_add(Duration::from_secs(31))
+            .expect("representable");
+
+        cache.insert(
+            "sites".to_owned(),
+            Arc::clone(&response),
+            Duration::ZERO,
+            now,
+        );
+        assert!(matches!(cache.get("sites", now), Lookup::Missing));
+
+        cache.insert(
+            "sites".to_owned(),
+            Arc::clone(&response),
+            Duration::from_secs(30),
+            now,
+        );
+        assert!(matches!(cache.get("sites", now), Lookup::Fresh(_)));
+        let expired = cache.get("sites", later);
+        assert!(matches!(expired, Lookup::Expired(_)));
+        assert_eq!(expired.conditional_headers(), vec![]);
+
+        // Expired responses without validators are evicted
+        cache.insert(
+            "other".to_owned(),
+            Arc::new(
+                (*response)
+                    .clone()
+                    .with_validators(Some("\"v1\"".to_owned()), None),
+            ),
+            Duration::from_secs(30),
+            later,
+        );
+        assert!(matches!(cache.get("sites", later), Lookup::Missing));
+        let later_still = later
+            .checked_add(Duration::from_secs(31))
+            .expect("representable");
+        assert_eq!(
+            cache.get("other", later_still).conditional_headers(),
+            vec![("If-None-Match".to_owned(), "\"v1\"".to_owned())]
+        );
+
+        cache.record(&Lookup::Missing, false);
+        cache.record(&Lookup::Fresh(Arc::clone(&response)), false);
+        cache.record(&Lookup::Expired(response), true);
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 1,
+                revalidated: 1,
+                stale: 0,
+            }
+        );
+
+        cache.clear();
+        assert!(matches!(cache.get("other", now), Lookup::Missing));
+    }
+}
@@ -0,0 +1,493 @@
+//! # Record and Replay
+//!
+//! A [`RecordingBackend`] wraps another [`HttpBackend`] and captures each
+//! request and its response into a [`Cassette`], which can be saved to disk
+//! and served again later by a [`ReplayBackend`]. Tests can then run against
+//! real-world payloads without network access or an API key.
+//!
+//! The API key is never written to a cassette: request headers (including the
+//! `Authorization` header) are not recorded, and any occurrence of the key in
+//! the recorded URLs, query parameters, response headers or bodies is replaced
+//! with `<redacted>`.
+//!
+//! Bodies are recorded byte for byte: bodies which are not valid UTF-8, such
+//! as CBOR or MessagePack responses, are stored as base64.
+//!
+//! ```no_run
+//! use amber_api::{Amber, Cassette, RecordingBackend, ReplayBackend};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> amber_api::Result<()> {
+//! // Record real responses once
+//! let recorder = RecordingBackend::default();
+//! let client = Amber::builder().backend(Box::new(recorder.clone())).build()?;
+//! client.sites().await?;
+//! recorder.cassette().save("tests/cassettes/sites.json")?;
+//!
+//! // Replay them in tests
+//! let cassette = Cassette::load("tests/cassettes/sites.json")?;
+//! let client = Amber::builder()
+//!     .backend(Box::new(ReplayBackend::new(cassette)))
+//!     .build()?;
+//! let sites = client.sites().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{
+    borrow::ToOwned as _, boxed::Box, collections::BTreeMap, format, string::String, sync::Arc,
+    vec::Vec,
+};
+use std::{path::Path, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    backend::{BoxFuture, HttpBackend, HttpRequest, HttpResponse, ReqwestBackend},
+    secret::ApiKey,
+};
+use crate::error::{AmberError, Result};
+
+/// Replacement for the API key in recorded data.
+const REDACTED: &str = "<redacted>";
+
+/// Recorded requests and their responses.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Cassette {
+    /// The interactions, in the order they were recorded.
+    pub interactions: Vec<Interaction>,
+}
+
+/// A recorded request and its response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Interaction {
+    /// Full URL of the endpoint, without query parameters.
+    pub url: String,
+    /// Query parameters of the request.
+    pub query: Vec<(String, String)>,
+    /// HTTP status code of the response.
+    pub status: u16,
+    /// Headers of the response.
+    pub headers: Vec<(String, String)>,
+    /// Body of the response.
+    pub body: Body,
+}
+
+/// Body of a recorded response.
+///
+/// JSON bodies are stored as JSON, so that cassettes are readable and diff
+/// cleanly. Bodies which are not valid UTF-8 are stored as base64.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Body {
+    /// A JSON body.
+    Json(serde_json::Value),
+    /// Any other text body.
+    Text(String),
+    /// A body which is not valid UTF-8.
+    Binary(#[serde(with = "base64")] Vec<u8>),
+}
+
+impl Body {
+    /// Returns the raw bytes of the body.
+    #[must_use]
+    #[inline]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Json(value) => serde_json::to_vec(value).unwrap_or_default(),
+            Self::Text(text) => text.as_bytes().to_vec(),
+            Self::Binary(bytes) => bytes.clone(),
+        }
+    }
+}
+
+impl Cassette {
+    /// Read a cassette from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid cassette.
+    #[inline]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Write the cassette to a JSON file, replacing any existing file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    #[inline]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut json = serde_json::to_vec_pretty(self)?;
+        json.push(b'\n');
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Replace any occurrence of a secret in a string.
+fn redact(text: &str, secret: Option<&str>) -> String {
+    match secret {
+        Some(key) if !key.is_empty() => text.replace(key, REDACTED),
+        _ => text.to_owned(),
+    }
+}
+
+/// Replace any occurrence of a secret in binary data.
+fn redact_bytes(bytes: &[u8], secret: Option<&str>) -> Vec<u8> {
+    let Some(key) = secret.filter(|key| !key.is_empty()) else {
+        return bytes.to_vec();
+    };
+    let mut redacted = Vec::with_capacity(bytes.len());
+    let mut rest = bytes;
+    while let Some((&first, tail)) = rest.split_first() {
+        if let Some(after) = rest.strip_prefix(key.as_bytes()) {
+            redacted.extend_from_slice(REDACTED.as_bytes());
+            rest = after;
+        } else {
+            redacted.push(first);
+            rest = tail;
+        }
+    }
+    redacted
+}
+
+/// Record an interaction, without the API key.
+fn record(request: &HttpRequest, response: &HttpResponse) -> Interaction {
+    let secret = request.bearer_token.as_ref().map(ApiKey::expose);
+    let body = match core::str::from_utf8(&response.body) {
+        Ok(text) => {
+            let redacted = redact(text, secret);
+            serde_json::from_str(&redacted).map_or(Body::Text(redacted), Body::Json)
+        }
+        Err(_) => Body::Binary(redact_bytes(&response.body, secret)),
+    };
+    Interaction {
+        url: redact(&request.url, secret),
+        query: request
+            .query
+            .iter()
+            .map(|(name, value)| (name.clone(), redact(value, secret)))
+            .collect(),
+        status: response.status,
+        headers: response
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), redact(value, secret)))
+            .collect(),
+        body,
+    }
+}
+
+/// An [`HttpBackend`] recording the interactions with another backend.
+///
+/// Clones share the same cassette, so a clone can be kept to save the
+/// cassette after the original has been given to the client.
+#[derive(Debug, Clone)]
+pub struct RecordingBackend {
+    /// Backend performing the requests.
+    inner: Arc<dyn HttpBackend>,
+    /// The interactions recorded so far.
+    cassette: Arc<Mutex<Cassette>>,
+}
+
+impl RecordingBackend {
+    /// Create a backend recording the interactions with another backend.
+    #[must_use]
+    #[inline]
+    pub fn new(inner: impl HttpBackend + 'static) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            cassette: Arc::default(),
+        }
+    }
+
+    /// Returns the interactions recorded so far.
+    #[must_use]
+    #[inline]
+    pub fn cassette(&self) -> Cassette {
+        self.cassette
+            .lock()
+            .map(|cassette| cassette.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for RecordingBackend {
+    /// Create a backend recording the interactions with the API, using a
+    /// default `reqwest` client.
+    #[inline]
+    fn default() -> Self {
+        Self::new(ReqwestBackend::new(reqwest::Client::new()))
+    }
+}
+
+impl HttpBackend for RecordingBackend {
+    #[inline]
+    fn get(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        Box::pin(async move {
+            let response = self.inner.get(request.clone()).await?;
+            if let Ok(mut cassette) = self.cassette.lock() {
+                cassette.interactions.push(record(&request, &response));
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Identifies the interactions matching a request.
+type RequestKey = (String, Vec<(String, String)>);
+
+/// An [`HttpBackend`] serving the interactions of a [`Cassette`].
+///
+/// Requests are matched to interactions by URL and query parameters. When a
+/// request was recorded several times, the recorded responses are served in
+/// order, and the last one is repeated once they are exhausted. Requests which
+/// were not recorded fail with [`AmberError::Backend`].
+#[derive(Debug)]
+pub struct ReplayBackend {
+    /// The recorded interactions.
+    cassette: Cassette,
+    /// Number of times each request has been served.
+    served: Mutex<BTreeMap<RequestKey, usize>>,
+}
+
+impl ReplayBackend {
+    /// Create a backend serving the interactions of a cassette.
+    #[must_use]
+    #[inline]
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            cassette,
+            served: Mutex::default(),
+        }
+    }
+
+    /// Returns the recorded response to a request.
+    fn respond(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let secret = request.bearer_token.as_ref().map(ApiKey::expose);
+        let key: RequestKey = (
+            redact(&request.url, secret),
+            request
+                .query
+                .iter()
+                .map(|(name, value)| (name.clone(), redact(value, secret)))
+                .collect(),
+        );
+        let matching: Vec<&Interaction> = self
+            .cassette
+            .interactions
+            .iter()
+            .filter(|interaction| interaction.url == key.0 && interaction.query == key.1)
+            .collect();
+        let count = self
+            .served
+            .lock()
+            .map(|mut served| {
+                let count = served.entry(key.clone()).or_default();
+                let current = *count;
+                *count = count.saturating_add(1);
+                current
+            })
+            .unwrap_or_default();
+        let interaction = matching
+            .get(count)
+            .or_else(|| matching.last())
+            .ok_or_else(|| {
+                AmberError::Backend(
+                    format!("No recorded interaction for {} {:?}", key.0, key.1).into(),
+                )
+            })?;
+        Ok(HttpResponse {
+            status: interaction.status,
+            headers: interaction.headers.clone(),
+            body: interaction.body.to_bytes(),
+        })
+    }
+}
+
+impl HttpBackend for ReplayBackend {
+    #[inline]
+    fn get(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        let response = self.respond(&request);
+        Box::pin(async move { response })
+    }
+}
+
+/// Base64 encoding of binary bodies, with the standard alphabet and padding.
+mod base64 {
+    use alloc::{string::String, vec::Vec};
+
+    use serde::{Deserialize as _, Deserializer, Serializer, de::Error as _};
+
+    /// Symbols of the standard alphabet, by value.
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    /// Serialise bytes as a base64 string.
+    pub(super) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode(bytes))
+    }
+
+    /// Deserialise bytes from a base64 string.
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        decode(&encoded).ok_or_else(|| D::Error::custom("invalid base64"))
+    }
+
+    /// Encode bytes as base64.
+    pub(super) fn encode(bytes: &[u8]) -> String {
+        let mut encoded = String::new();
+        for chunk in bytes.chunks(3) {
+            let mut group = [0_u8; 3];
+            for (slot, &byte) in group.iter_mut().zip(chunk) {
+                *slot = byte;
+            }
+            let [a, b, c] = group;
+            let sextets = [
+                a.wrapping_shr(2),
+                (a & 0x03).wrapping_shl(4) | b.wrapping_shr(4),
+                (b & 0x0F).wrapping_shl(2) | c.wrapping_shr(6),
+                c & 0x3F,
+            ];
+            for (index, sextet) in sextets.into_iter().enumerate() {
+                let symbol = ALPHABET
+                    .get(usize::from(sextet))
+                    .filter(|_| index <= chunk.len())
+                    .copied()
+                    .unwrap_or(b'=');
+                encoded.push(char::from(symbol));
+            }
+        }
+        encoded
+    }
+
+    /// Decode base64, returning `None` if it is malformed.
+    pub(super) fn decode(encoded: &str) -> Option<Vec<u8>> {
+        let mut decoded = Vec::new();
+        for chunk in encoded.trim_end_matches('=').as_bytes().chunks(4) {
+            let mut sextets = [0_u8; 4];
+            for (slot, symbol) in sextets.iter_mut().zip(chunk) {
+                let value = ALPHABET.iter().position(|candidate| candidate == symbol)?;
+                *slot = u8::try_from(value).ok()?;
+            }
+            let [a, b, c, d] = sextets;
+            let bytes = [
+                a.wrapping_shl(2) | b.wrapping_shr(4),
+                b.wrapping_shl(4) | c.wrapping_shr(2),
+                c.wrapping_shl(6) | d,
+            ];
+            let count = chunk.len().checked_sub(1).filter(|&count| count > 0)?;
+            decoded.extend(bytes.iter().take(count));
+        }
+        Some(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::Amber;
+
+    /// Backend echoing the bearer token in its responses.
+    #[derive(Debug)]
+    struct EchoBackend;
+
+    impl HttpBackend for EchoBackend {
+        fn get(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            let token = request
+                .bearer_token
+                .map(|key| key.expose().to_owned())
+                .unwrap_or_default();
+            let body = if request.url.ends_with("/sites") {
+                "[]".to_owned()
+            } else {
+                format!("unknown token {token}")
+            };
+            Box::pin(async move { Ok(HttpResponse::new(200, body).with_header("X-Token", token)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn records_and_replays() {
+        let recorder = RecordingBackend::new(EchoBackend);
+        let client = Amber::builder()
+            .api_key("psk_secret")
+            .backend(Box::new(recorder.clone()))
+            .build()
+            .expect("valid client");
+        assert!(client.sites().await.expect("sites").is_empty());
+
+        let cassette = recorder.cassette();
+        let json = serde_json::to_string(&cassette).expect("serializable");
+        assert!(!json.contains("psk_secret"), "{json}");
+        assert_eq!(
+            cassette
+                .interactions
+                .first()
+                .map(|interaction| &interaction.body),
+            Some(&Body::Json(serde_json::json!([])))
+        );
+
+        let replay = Amber::builder()
+            .backend(Box::new(ReplayBackend::new(
+                serde_json::from_str(&json).expect("valid cassette"),
+            )))
+            .build()
+            .expect("valid client");
+        assert!(replay.sites().await.expect("sites").is_empty());
+        assert!(replay.sites().await.expect("repeated sites").is_empty());
+        assert!(matches!(
+            replay
+                .current_prices()
+                .site_id(&"01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID"))
+                .call()
+                .await,
+            Err(AmberError::Backend(_))
+        ));
+    }
+
+    #[test]
+    fn base64_round_trips() {
+        for (bytes, encoded) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (&[0xFF, 0x00, 0xFE, 0x80][..], "/wD+gA=="),
+        ] {
+            assert_eq!(base64::encode(bytes), encoded);
+            assert_eq!(base64::decode(encoded).as_deref(), Some(bytes));
+        }
+        assert_eq!(base64::decode("Z"), None);
+        assert_eq!(base64::decode("Zm9*"), None);
+    }
+
+    #[test]
+    fn records_binary_bodies_exactly() {
+        let mut request = HttpRequest::new("https://api.amber.com.au/v1/sites");
+        request.bearer_token = Some(ApiKey::from("psk_secret"));
+        let mut body = vec![0xA1, 0xFF, 0x00];
+        body.extend_from_slice(b"psk_secret");
+        let interaction = record(&request, &HttpResponse::new(200, body));
+
+        let mut expected = vec![0xA1, 0xFF, 0x00];
+        expected.extend_from_slice(REDACTED.as_bytes());
+        assert_eq!(interaction.body, Body::Binary(expected.clone()));
+
+        let json = serde_json::to_value(&interaction.body).expect("serializable");
+        assert_eq!(json, serde_json::json!({"binary": "of8APHJlZGFjdGVkPg=="}));
+        let parsed: Body = serde_json::from_value(json).expect("valid body");
+        assert_eq!(parsed.to_bytes(), expected);
+    }
+}
@@ -0,0 +1,423 @@
+//! # Configuration Files
+//!
+//! Command line tools and daemons typically read their settings from a file
+//! rather than hardcoding them. A [`Config`] holds the settings of the client
+//! (API key, base URL, timeouts and retry policy) along with the default site,
+//! and can be read from a TOML or YAML file:
+//!
+//! ```toml
+//! api_key_file = "amber.key"
+//! site_id = "01F5A5CRKMZ5BCX9P1S4V990AM"
+//! timeout = 10
+//!
+//! [retry]
+//! max_retries = 5
+//! max_sleep = 120
+//! ```
+//!
+//! Durations are in seconds. A relative `api_key_file` is resolved from the
+//! directory of the configuration file, and the file's content (without
+//! surrounding whitespace) is used as the API key.
+//!
+//! [`Config::load()`] (used by [`Amber::from_env_and_config()`]) combines the
+//! configuration file with environment variables, in increasing order of
+//! precedence:
+//!
+//! 1. The file named by `AMBER_CONFIG`, or `amber/config.toml` in the user's
+//!    configuration directory (`$XDG_CONFIG_HOME`, or `~/.config`) if it
+//!    exists.
+//! 2. The `AMBER_API_KEY`, `AMBER_API_KEY_FILE`, `AMBER_BASE_URL` and
+//!    `AMBER_SITE_ID` environment variables.
+
+use alloc::{
+    borrow::ToOwned as _,
+    string::{String, ToString as _},
+};
+use core::{fmt, time::Duration};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Deserializer, de::Error as _};
+
+use super::{Amber, AmberBuilder, amber_builder, retry::RetryPolicy};
+use crate::{
+    error::{AmberError, Result},
+    models::SiteId,
+};
+
+/// State of an [`AmberBuilder`] configured by [`Config::builder()`].
+type Configured = amber_builder::SetRetryPolicy<
+    amber_builder::SetReadTimeout<
+        amber_builder::SetConnectTimeout<
+            amber_builder::SetTimeout<amber_builder::SetBaseUrl<amber_builder::SetApiKey>>,
+        >,
+    >,
+>;
+
+/// Deserialize an optional duration given in seconds.
+fn seconds<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> core::result::Result<Option<Duration>, D::Error> {
+    Option::<f64>::deserialize(deserializer)?
+        .map(|secs| Duration::try_from_secs_f64(secs).map_err(D::Error::custom))
+        .transpose()
+}
+
+/// Settings of the client, as read from a configuration file.
+///
+/// All settings are optional; unset settings keep the defaults of
+/// [`Amber::builder()`].
+#[derive(Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Config {
+    /// API key.
+    pub api_key: Option<String>,
+    /// Path of a file containing the API key, used if `api_key` is not set.
+    pub api_key_file: Option<PathBuf>,
+    /// Base URL of the API.
+    pub base_url: Option<String>,
+    /// Timeout of each request.
+    #[serde(default, deserialize_with = "seconds")]
+    pub timeout: Option<Duration>,
+    /// Timeout for establishing connections.
+    #[serde(default, deserialize_with = "seconds")]
+    pub connect_timeout: Option<Duration>,
+    /// Timeout between reads of the response.
+    #[serde(default, deserialize_with = "seconds")]
+    pub read_timeout: Option<Duration>,
+    /// Site used when none is given.
+    pub site_id: Option<SiteId>,
+    /// Retry policy.
+    pub retry: Option<RetryConfig>,
+}
+
+/// Settings of the retry policy, as read from a configuration file.
+///
+/// Unset settings keep the defaults of [`RetryPolicy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct RetryConfig {
+    /// Maximum number of retries after the initial attempt.
+    pub max_retries: Option<u32>,
+    /// Delay used when the response does not indicate how long to wait.
+    #[serde(default, deserialize_with = "seconds")]
+    pub default_delay: Option<Duration>,
+    /// Maximum random delay added to each wait.
+    #[serde(default, deserialize_with = "seconds")]
+    pub jitter: Option<Duration>,
+    /// Longest the client will wait before a single retry.
+    #[serde(default, deserialize_with = "seconds")]
+    pub max_sleep: Option<Duration>,
+}
+
+impl RetryConfig {
+    /// Returns the retry policy with these settings.
+    #[must_use]
+    #[inline]
+    pub fn policy(&self) -> RetryPolicy {
+        RetryPolicy::builder()
+            .maybe_max_retries(self.max_retries)
+            .maybe_default_delay(self.default_delay)
+            .maybe_jitter(self.jitter)
+            .maybe_max_sleep(self.max_sleep)
+            .build()
+    }
+}
+
+impl fmt::Debug for Config {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("api_key_file", &self.api_key_file)
+            .field("base_url", &self.base_url)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("site_id", &self.site_id)
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+/// Returns the value of an environment variable, if set and not empty.
+fn var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+impl Config {
+    /// Parse a configuration in the format named by a file extension
+    /// (`toml`, `yaml` or `yml`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::UnsupportedFormat`] for other extensions, and
+    /// [`AmberError::Config`] if the configuration is invalid.
+    #[inline]
+    pub fn parse(text: &str, extension: &str) -> Result<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "toml" => toml::from_str(text).map_err(|err| AmberError::Config(err.to_string())),
+            "yaml" | "yml" => {
+                serde_yaml::from_str(text).map_err(|err| AmberError::Config(err.to_string()))
+            }
+            other => Err(AmberError::UnsupportedFormat(other.to_owned())),
+        }
+    }
+
+    /// Read a configuration file, in the format given by its extension.
+    ///
+    /// A relative `api_key_file` is resolved from the directory of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or under the same
+    /// conditions as [`parse()`](Self::parse).
+    #[inline]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let file = path.as_ref();
+        let extension = file
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        let mut config = Self::parse(&std::fs::read_to_string(file)?, extension)?;
+        if let Some(key_file) = &config.api_key_file
+            && key_file.is_relative()
+            && let Some(dir) = file.parent()
+        {
+            config.api_key_file = Some(dir.join(key_file));
+        }
+        Ok(config)
+    }
+
+    /// Returns the settings given by environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::Config`] if `AMBER_SITE_ID` is not a valid site
+    /// ID.
+    #[inline]
+    pub fn from_env() -> Result<Self> {
+        let site_id = var("AMBER_SITE_ID")
+            .map(|id| id.parse::<SiteId>())
+            .transpose()
+            .map_err(|err| AmberError::Config(err.to_string()))?;
+        Ok(Self {
+            api_key: var("AMBER_API_KEY"),
+            api_key_file: var("AMBER_API_KEY_FILE").map(PathBuf::from),
+            base_url: var("AMBER_BASE_URL"),
+            site_id,
+            ..Self::default()
+        })
+    }
+
+    /// Returns the default path of the configuration file:
+    /// `amber/config.toml` in the user's configuration directory.
+    #[must_use]
+    #[inline]
+    pub fn default_path() -> Option<PathBuf> {
+        var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| Path::new(&home).join(".config")))
+            .map(|dir| dir.join("amber").join("config.toml"))
+    }
+
+    /// Load the configuration from the configuration file and environment
+    /// variables.
+    ///
+    /// See the [module documentation](self) for the order of precedence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration file cannot be read or is
+    /// invalid, or under the same conditions as [`from_env()`](Self::from_env).
+    #[inline]
+    pub fn load() -> Result<Self> {
+        let file = match var("AMBER_CONFIG") {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default_path()
+                .filter(|path| path.is_file())
+                .map(Self::from_file)
+                .transpose()?
+                .unwrap_or_default(),
+        };
+        Ok(file.merge(Self::from_env()?))
+    }
+
+    /// Combine with settings taking precedence over these.
+    ///
+    /// The API key and key file are treated as a single setting: if `other`
+    /// sets either, it replaces both.
+    #[must_use]
+    #[inline]
+    pub fn merge(self, other: Self) -> Self {
+        let (api_key, api_key_file) = if other.api_key.is_some() || other.api_key_file.is_some() {
+            (other.api_key, other.api_key_file)
+        } else {
+            (self.api_key, self.api_key_file)
+        };
+        Self {
+            api_key,
+            api_key_file,
+            base_url: other.base_url.or(self.base_url),
+            timeout: other.timeout.or(self.timeout),
+            connect_timeout: other.connect_timeout.or(self.connect_timeout),
+            read_timeout: other.read_timeout.or(self.read_timeout),
+            site_id: other.site_id.or(self.site_id),
+            retry: other.retry.or(self.retry),
+        }
+    }
+
+    /// Returns the API key, reading it from the key file if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key file cannot be read.
+    #[inline]
+    pub fn resolve_api_key(&self) -> Result<Option<String>> {
+        if let Some(key) = &self.api_key {
+            return Ok(Some(key.clone()));
+        }
+        self.api_key_file
+            .as_ref()
+            .map(|path| Ok(std::fs::read_to_string(path)?.trim().to_owned()))
+            .transpose()
+    }
+
+    /// Returns a client builder with these settings.
+    ///
+    /// The remaining options of the builder (such as the proxy or cache) can
+    /// still be set before building the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key file cannot be read.
+    #[inline]
+    pub fn builder(&self) -> Result<AmberBuilder<Configured>> {
+        Ok(Amber::builder()
+            .maybe_api_key(self.resolve_api_key()?)
+            .maybe_base_url(self.base_url.clone())
+            .maybe_timeout(self.timeout)
+            .maybe_connect_timeout(self.connect_timeout)
+            .maybe_read_timeout(self.read_timeout)
+            .retry_policy(self.retry.unwrap_or_default().policy()))
+    }
+}
+
+impl AmberBuilder {
+    /// Create a builder configured by a configuration file.
+    ///
+    /// See [`Config`] for the settings and formats supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Config::from_file()`]
+    /// and [`Config::builder()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::AmberBuilder;
+    ///
+    /// # fn main() -> amber_api::Result<()> {
+    /// let client = AmberBuilder::from_config_file("amber.toml")?.build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn from_config_file(path: impl AsRef<Path>) -> Result<AmberBuilder<Configured>> {
+        Config::from_file(path)?.builder()
+    }
+}
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "Configuration files are only supported with the `config` feature"
+)]
+impl Amber {
+    /// Create a client configured by the configuration file and environment
+    /// variables.
+    ///
+    /// See [`Config::load()`] for the order of precedence. Use
+    /// [`Config::load()`] directly to also obtain the default site.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Config::load()`] and
+    /// [`Amber::builder()`].
+    #[inline]
+    pub fn from_env_and_config() -> Result<Self> {
+        Config::load()?.builder()?.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn parses_toml_and_yaml() {
+        let toml = Config::parse(
+            r#"
+            api_key_file = "amber.key"
+            site_id = "01F5A5CRKMZ5BCX9P1S4V990AM"
+            timeout = 2.5
+
+            [retry]
+            max_retries = 5
+            "#,
+            "toml",
+        )
+        .expect("valid TOML");
+        let yaml = Config::parse(
+            "api_key_file: amber.key\n\
+             site_id: 01F5A5CRKMZ5BCX9P1S4V990AM\n\
+             timeout: 2.5\n\
+             retry:\n  max_retries: 5\n",
+            "yml",
+        )
+        .expect("valid YAML");
+        assert_eq!(toml, yaml);
+        assert_eq!(toml.timeout, Some(Duration::from_millis(2500)));
+        assert_eq!(toml.retry.map(|retry| retry.policy().max_retries), Some(5));
+
+        assert!(matches!(
+            Config::parse("", "ini"),
+            Err(AmberError::UnsupportedFormat(_))
+        ));
+        assert!(matches!(
+            Config::parse("timeout = -1", "toml"),
+            Err(AmberError::Config(_))
+        ));
+        assert!(matches!(
+            Config::parse("api_kee = \"typo\"", "toml"),
+            Err(AmberError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn merge_prefers_overrides() {
+        let file = Config {
+            api_key_file: Some(PathBuf::from("amber.key")),
+            base_url: Some("https://example.com".into()),
+            timeout: Some(Duration::from_secs(5)),
+            ..Config::default()
+        };
+        let env = Config {
+            api_key: Some("psk_env".into()),
+            timeout: Some(Duration::from_secs(1)),
+            ..Config::default()
+        };
+        let merged = file.clone().merge(env);
+        assert_eq!(merged.api_key.as_deref(), Some("psk_env"));
+        assert_eq!(merged.api_key_file, None);
+        assert_eq!(merged.base_url, file.base_url);
+        assert_eq!(merged.timeout, Some(Duration::from_secs(1)));
+        assert_eq!(file.clone().merge(Config::default()), file);
+        assert!(!format!("{merged:?}").contains("psk_env"));
+    }
+}
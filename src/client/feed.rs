@@ -0,0 +1,349 @@
+//! # Price Feed
+//!
+//! Control loops, such as those driving an inverter or a battery, consult the
+//! current price far more often than it changes and cannot afford to wait on
+//! a request (or on a lock held by one). A [`PriceFeed`] refreshes the current
+//! prices and forecasts of each site in a background task and publishes them
+//! as an immutable [`PriceSnapshot`]. Reading the latest snapshot from the
+//! [`PriceFeedHandle`] is lock-free, so it can be done from any hot path.
+//!
+//! A failed refresh keeps the previous snapshot in place. Its
+//! [`fetched_at`](PriceSnapshot::fetched_at) time can be checked against a
+//! [`FreshnessPolicy`](crate::control::FreshnessPolicy) before acting on it.
+
+use alloc::{
+    collections::BTreeMap,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::time::Duration;
+
+use arc_swap::ArcSwapOption;
+use jiff::Timestamp;
+use tokio::{task::JoinHandle, time};
+use tracing::warn;
+
+use super::Amber;
+use crate::models::{CurrentInterval, ForecastInterval, Interval, SiteId};
+
+/// Keeps the latest prices of several sites up to date in the background.
+///
+/// # Example
+///
+/// ```no_run
+/// use core::time::Duration;
+///
+/// use amber_api::{Amber, PriceFeed};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Amber::default();
+/// let sites = client.active_sites().await?;
+/// let site_id = sites.first().expect("an active site").id.clone();
+///
+/// let feed = PriceFeed::builder()
+///     .client(client)
+///     .sites(vec![site_id.clone()])
+///     .every(Duration::from_secs(30))
+///     .build()
+///     .start();
+///
+/// loop {
+///     if let Some(snapshot) = feed.latest(&site_id) {
+///         for current in snapshot.current() {
+///             println!("{current}");
+///         }
+///     }
+///     tokio::time::sleep(Duration::from_secs(1)).await;
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, bon::Builder)]
+#[non_exhaustive]
+pub struct PriceFeed {
+    /// Client used to fetch prices.
+    client: Amber,
+    /// IDs of the sites to keep prices for.
+    sites: Vec<SiteId>,
+    /// Time between refreshes of each site.
+    ///
+    /// Defaults to 1 minute.
+    #[builder(default = Duration::from_mins(1))]
+    every: Duration,
+    /// Number of forecast intervals to fetch for each channel.
+    ///
+    /// Defaults to 12.
+    #[builder(default = 12)]
+    next: u32,
+}
+
+/// Prices of a site as of a refresh of a [`PriceFeed`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PriceSnapshot {
+    /// ID of the site.
+    pub site_id: SiteId,
+    /// When the prices were fetched from the API.
+    pub fetched_at: Timestamp,
+    /// The current and forecast intervals of every channel.
+    pub intervals: Vec<Interval>,
+}
+
+impl PriceSnapshot {
+    /// Returns the current interval of each channel.
+    #[inline]
+    pub fn current(&self) -> impl Iterator<Item = &CurrentInterval> {
+        self.intervals
+            .iter()
+            .filter_map(Interval::as_current_interval)
+    }
+
+    /// Returns the forecast intervals of every channel.
+    #[inline]
+    pub fn forecasts(&self) -> impl Iterator<Item = &ForecastInterval> {
+        self.intervals
+            .iter()
+            .filter_map(Interval::as_forecast_interval)
+    }
+
+    /// Returns the age of the snapshot as of `now`.
+    #[must_use]
+    #[inline]
+    pub fn age(&self, now: Timestamp) -> Duration {
+        Duration::try_from(now.duration_since(self.fetched_at)).unwrap_or_default()
+    }
+}
+
+/// Latest snapshot of each site, shared with the refreshers.
+///
+/// The set of sites is fixed when the feed is started, so that only the
+/// snapshots themselves are swapped.
+type Snapshots = Arc<BTreeMap<SiteId, ArcSwapOption<PriceSnapshot>>>;
+
+impl PriceFeed {
+    /// Returns the IDs of the sites in the feed.
+    #[must_use]
+    #[inline]
+    pub fn sites(&self) -> &[SiteId] {
+        &self.sites
+    }
+
+    /// Start refreshing each site in its own task.
+    ///
+    /// Each site is refreshed immediately, and then on its own schedule. The
+    /// refreshers stop when the returned handle is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    #[must_use]
+    #[inline]
+    pub fn start(&self) -> PriceFeedHandle {
+        let snapshots: Snapshots = Arc::new(
+            self.sites
+                .iter()
+                .map(|site_id| (site_id.clone(), ArcSwapOption::empty()))
+                .collect(),
+        );
+        let tasks = self
+            .sites
+            .iter()
+            .map(|site_id| {
+                tokio::spawn(refresh_site(
+                    self.clone(),
+                    site_id.clone(),
+                    Arc::downgrade(&snapshots),
+                ))
+            })
+            .collect();
+        PriceFeedHandle { snapshots, tasks }
+    }
+}
+
+/// Refresh the prices of a single site until the handle is dropped.
+///
+/// Only the handle holds the snapshots strongly, so the refresher returns as
+/// soon as it finds them gone, even if it was not aborted.
+async fn refresh_site(
+    feed: PriceFeed,
+    site_id: SiteId,
+    snapshots: Weak<BTreeMap<SiteId, ArcSwapOption<PriceSnapshot>>>,
+) {
+    let mut ticker = time::interval(feed.every.max(Duration::from_secs(1)));
+    ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        let result = feed
+            .client
+            .current_prices_response()
+            .site_id(&site_id)
+            .next(feed.next)
+            .call()
+            .await;
+        let Some(shared) = snapshots.upgrade() else {
+            return;
+        };
+        let Some(latest) = shared.get(&site_id) else {
+            return;
+        };
+        match result {
+            Ok(response) => latest.store(Some(Arc::new(PriceSnapshot {
+                site_id: site_id.clone(),
+                fetched_at: response.fetched_at,
+                intervals: response.data,
+            }))),
+            Err(err) => warn!("Failed to refresh prices of site {site_id}: {err}"),
+        }
+    }
+}
+
+/// Handle to the refreshers started by [`PriceFeed::start`].
+///
+/// Dropping the handle stops the refreshers.
+#[derive(Debug)]
+pub struct PriceFeedHandle {
+    /// Latest snapshot of each site.
+    snapshots: Snapshots,
+    /// The refreshers.
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl PriceFeedHandle {
+    /// Returns the latest prices of a site.
+    ///
+    /// This never blocks. It returns `None` if the site is not part of the
+    /// feed, or if its prices have not been fetched successfully yet.
+    #[must_use]
+    #[inline]
+    pub fn latest(&self, site_id: &SiteId) -> Option<Arc<PriceSnapshot>> {
+        self.snapshots.get(site_id)?.load_full()
+    }
+
+    /// Returns the latest prices of every site fetched so far.
+    #[must_use]
+    #[inline]
+    pub fn latest_all(&self) -> Vec<Arc<PriceSnapshot>> {
+        self.snapshots
+            .values()
+            .filter_map(ArcSwapOption::load_full)
+            .collect()
+    }
+
+    /// Stop all refreshers.
+    ///
+    /// The latest snapshots remain available.
+    #[inline]
+    pub fn stop(&self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl Drop for PriceFeedHandle {
+    #[inline]
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, string::ToString as _, vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        HttpBackend,
+        client::backend::{BoxFuture, HttpRequest, HttpResponse},
+    };
+
+    /// ID of the site in the feed.
+    const SITE: &str = "01F5A5CRKMZ5BCX9P1S4V990AM";
+
+    /// Backend returning a current and a forecast interval.
+    #[derive(Debug)]
+    struct PricesBackend;
+
+    impl HttpBackend for PricesBackend {
+        fn get(&self, _request: HttpRequest) -> BoxFuture<'_, crate::Result<HttpResponse>> {
+            let interval = |kind: &str, start: &str| {
+                serde_json::json!({
+                    "type": kind,
+                    "duration": 30_u32,
+                    "spotPerKwh": 6.0_f64,
+                    "perKwh": 24.0_f64,
+                    "date": "2021-05-05",
+                    "nemTime": start,
+                    "startTime": start,
+                    "endTime": start,
+                    "renewables": 45.0_f64,
+                    "channelType": "general",
+                    "spikeStatus": "none",
+                    "descriptor": "low",
+                    "estimate": true
+                })
+            };
+            let body = serde_json::json!([
+                interval("CurrentInterval", "2021-05-05T02:00:00Z"),
+                interval("ForecastInterval", "2021-05-05T02:30:00Z"),
+            ])
+            .to_string();
+            Box::pin(async move { Ok(HttpResponse::new(200, body)) })
+        }
+    }
+
+    /// Build a client using [`PricesBackend`].
+    fn client() -> Amber {
+        Amber::builder()
+            .api_key("psk_client")
+            .backend(Box::new(PricesBackend))
+            .build()
+            .expect("valid client")
+    }
+
+    #[tokio::test]
+    async fn publishes_latest_prices() {
+        let site_id: SiteId = SITE.parse().expect("valid ID");
+        let feed = PriceFeed::builder()
+            .client(client())
+            .sites(vec![site_id.clone()])
+            .build()
+            .start();
+
+        let snapshot = loop {
+            if let Some(snapshot) = feed.latest(&site_id) {
+                break snapshot;
+            }
+            time::sleep(Duration::from_millis(1)).await;
+        };
+        assert_eq!(snapshot.site_id, site_id);
+        assert_eq!(snapshot.current().count(), 1);
+        assert_eq!(snapshot.forecasts().count(), 1);
+        assert_eq!(feed.latest_all().len(), 1);
+
+        let other: SiteId = "01G7A5CRKMZ5BCX9P1S4V990BN".parse().expect("valid ID");
+        assert_eq!(feed.latest(&other), None);
+
+        feed.stop();
+        assert!(feed.latest(&site_id).is_some());
+    }
+
+    #[tokio::test]
+    async fn refresher_exits_without_handle() {
+        let site_id: SiteId = SITE.parse().expect("valid ID");
+        let feed = PriceFeed::builder()
+            .client(client())
+            .sites(vec![site_id.clone()])
+            .build();
+        let snapshots: Snapshots =
+            Arc::new(BTreeMap::from([(site_id.clone(), ArcSwapOption::empty())]));
+        let weak = Arc::downgrade(&snapshots);
+        drop(snapshots);
+
+        time::timeout(Duration::from_secs(5), refresh_site(feed, site_id, weak))
+            .await
+            .expect("refresher returned");
+    }
+}
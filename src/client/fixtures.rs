@@ -0,0 +1,293 @@
+//! # Offline Fixtures
+//!
+//! The [`FixtureBackend`] answers every request with canned JSON rather than
+//! contacting the API, so that applications can be tested deterministically,
+//! and demonstrated without network access or an API key.
+//!
+//! Each endpoint is served from a fixture of the following name:
+//!
+//! | Endpoint                              | Fixture              |
+//! | ------------------------------------- | -------------------- |
+//! | `sites`                               | `sites`              |
+//! | `sites/{id}/prices`                   | `prices`             |
+//! | `sites/{id}/prices/current`           | `current_prices`     |
+//! | `sites/{id}/usage`                    | `usage`              |
+//! | `state/{state}/renewables/current`    | `current_renewables` |
+//!
+//! The [built-in fixtures](FixtureBackend::builtin) describe a single site
+//! (`01F5A5CRKMZ5BCX9P1S4V990AM`) on 5 May 2021. Fixtures can instead be read
+//! from a directory of `<fixture>.json` files, such as responses saved from
+//! the API, with [`FixtureBackend::from_dir`]. Requests for a missing fixture
+//! receive a 404 response. The query parameters are ignored, so the same
+//! fixture is served whatever the dates or number of intervals requested.
+//!
+//! ```
+//! use amber_api::Amber;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> amber_api::Result<()> {
+//! let client = Amber::offline();
+//! for site in client.sites().await? {
+//!     let prices = client.current_prices().site_id(&site.id).call().await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{borrow::ToOwned as _, boxed::Box, format, sync::Arc, vec::Vec};
+use std::path::{Path, PathBuf};
+
+use super::{
+    Amber, AmberBuilder, amber_builder,
+    backend::{BoxFuture, HttpBackend, HttpRequest, HttpResponse},
+};
+use crate::error::Result;
+
+/// Built-in fixtures, by name.
+const BUILTIN: [(&str, &str); 5] = [
+    ("sites", include_str!("fixtures/sites.json")),
+    ("prices", include_str!("fixtures/prices.json")),
+    (
+        "current_prices",
+        include_str!("fixtures/current_prices.json"),
+    ),
+    ("usage", include_str!("fixtures/usage.json")),
+    (
+        "current_renewables",
+        include_str!("fixtures/current_renewables.json"),
+    ),
+];
+
+/// Where the fixtures are read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    /// The built-in fixtures.
+    Builtin,
+    /// JSON files in a directory.
+    Dir(Arc<Path>),
+}
+
+/// An [`HttpBackend`] serving canned JSON fixtures.
+///
+/// See the [module documentation](self) for the fixtures served.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureBackend {
+    /// Where the fixtures are read from.
+    source: Source,
+}
+
+/// Returns the name of the fixture serving a URL, if any.
+fn fixture_name(url: &str) -> Option<&'static str> {
+    let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let mut segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .skip(1);
+    let mut segment = segments.next()?;
+    // Skip any prefix of the base URL, such as the API version
+    while segment != "sites" && segment != "state" {
+        segment = segments.next()?;
+    }
+    let rest: Vec<&str> = segments.collect();
+    match (segment, rest.as_slice()) {
+        ("sites", []) => Some("sites"),
+        ("sites", [_, "prices"]) => Some("prices"),
+        ("sites", [_, "prices", "current"]) => Some("current_prices"),
+        ("sites", [_, "usage"]) => Some("usage"),
+        ("state", [_, "renewables", "current"]) => Some("current_renewables"),
+        _ => None,
+    }
+}
+
+/// Returns the response for a missing fixture.
+fn not_found(url: &str) -> HttpResponse {
+    HttpResponse::new(404, format!("No fixture for {url}"))
+}
+
+impl FixtureBackend {
+    /// Create a backend serving the built-in fixtures.
+    #[must_use]
+    #[inline]
+    pub fn builtin() -> Self {
+        Self {
+            source: Source::Builtin,
+        }
+    }
+
+    /// Create a backend serving the `<fixture>.json` files of a directory.
+    ///
+    /// Files are read on each request, so fixtures can be changed between
+    /// requests.
+    #[must_use]
+    #[inline]
+    pub fn from_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            source: Source::Dir(Arc::from(dir.into())),
+        }
+    }
+
+    /// Returns the response to a request for a URL.
+    fn respond(&self, url: &str) -> Result<HttpResponse> {
+        let Some(name) = fixture_name(url) else {
+            return Ok(not_found(url));
+        };
+        match &self.source {
+            Source::Builtin => Ok(BUILTIN
+                .iter()
+                .find(|(builtin, _)| *builtin == name)
+                .map_or_else(|| not_found(url), |(_, body)| HttpResponse::new(200, *body))),
+            Source::Dir(dir) => {
+                let path = dir.join(format!("{name}.json"));
+                match std::fs::read(&path) {
+                    Ok(body) => Ok(HttpResponse::new(200, body)),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(not_found(url)),
+                    Err(err) => Err(err.into()),
+                }
+            }
+        }
+    }
+}
+
+impl Default for FixtureBackend {
+    #[inline]
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl HttpBackend for FixtureBackend {
+    #[inline]
+    fn get(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        let response = self.respond(&request.url);
+        Box::pin(async move { response })
+    }
+}
+
+impl<S: amber_builder::State> AmberBuilder<S> {
+    /// Serve the `<fixture>.json` files of a directory instead of contacting
+    /// the API.
+    ///
+    /// See [`FixtureBackend`] for the fixtures served.
+    #[inline]
+    pub fn offline_with_fixtures(
+        self,
+        dir: impl AsRef<Path>,
+    ) -> AmberBuilder<amber_builder::SetBackend<S>>
+    where
+        S::Backend: amber_builder::IsUnset,
+    {
+        self.backend(Box::new(FixtureBackend::from_dir(dir.as_ref().to_owned())))
+    }
+}
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "The fixtures are only available with the `test-util` feature"
+)]
+impl Amber {
+    /// Create a client serving the built-in fixtures instead of contacting
+    /// the API.
+    ///
+    /// No API key is needed. See [`FixtureBackend`] for the data served.
+    ///
+    /// # Panics
+    ///
+    /// Does not panic in practice: building a client with a custom backend
+    /// cannot fail.
+    #[must_use]
+    #[inline]
+    #[expect(
+        clippy::expect_used,
+        reason = "Building a client with a custom backend cannot fail"
+    )]
+    pub fn offline() -> Self {
+        Self::builder()
+            .backend(Box::new(FixtureBackend::builtin()))
+            .build()
+            .expect("Failed to build offline client")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::State;
+
+    #[test]
+    fn routes_endpoints() {
+        let base = "https://api.amber.com.au/v1";
+        assert_eq!(fixture_name(&format!("{base}/sites")), Some("sites"));
+        assert_eq!(
+            fixture_name(&format!(
+                "{base}/sites/01F5A5CRKMZ5BCX9P1S4V990AM/prices/current"
+            )),
+            Some("current_prices")
+        );
+        assert_eq!(
+            fixture_name(&format!("{base}/state/vic/renewables/current")),
+            Some("current_renewables")
+        );
+        assert_eq!(
+            fixture_name("http://localhost:8080/sites/x/usage"),
+            Some("usage")
+        );
+        assert_eq!(fixture_name(&format!("{base}/sites/x/unknown")), None);
+    }
+
+    #[tokio::test]
+    async fn serves_builtin_fixtures() {
+        let client = Amber::offline();
+        let sites = client.sites().await.expect("sites");
+        let site = sites.first().expect("a site");
+        let current = client
+            .current_prices()
+            .site_id(&site.id)
+            .call()
+            .await
+            .expect("current prices");
+        assert!(
+            current
+                .iter()
+                .any(crate::models::Interval::is_current_interval)
+        );
+        let usage = client
+            .usage()
+            .site_id(&site.id)
+            .start_date(jiff::civil::date(2021, 5, 5))
+            .end_date(jiff::civil::date(2021, 5, 5))
+            .call()
+            .await
+            .expect("usage");
+        assert_eq!(usage.len(), 4);
+        let renewables = client
+            .current_renewables()
+            .state(State::Vic)
+            .call()
+            .await
+            .expect("renewables");
+        assert_eq!(renewables.len(), 3);
+        assert!(
+            !client
+                .prices()
+                .site_id(&site.id)
+                .call()
+                .await
+                .expect("prices")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_fixtures_are_not_found() {
+        let client = Amber::builder()
+            .offline_with_fixtures("/nonexistent/fixtures")
+            .build()
+            .expect("valid client");
+        assert!(matches!(
+            client.sites().await,
+            Err(crate::AmberError::UnexpectedStatus { status: 404, .. })
+        ));
+    }
+}
@@ -0,0 +1,177 @@
+//! # Duplicate Request Guard
+//!
+//! Memoizes the responses of historical queries for the lifetime of the
+//! client, so that repeating the exact same query (for example, from a retry
+//! loop in user code) does not hit the API again.
+//!
+//! Only queries whose date range lies entirely in the past are memoized, as
+//! their results cannot change. This is independent of any time-based caching.
+//! At most [`CAPACITY`] responses are kept, the oldest being evicted first, so
+//! that a long-running client querying many ranges does not grow unbounded.
+
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    string::String,
+    sync::Arc,
+};
+use std::sync::Mutex;
+
+use jiff::civil::Date;
+
+use super::{response::Fetched, timestamp_now};
+use crate::analysis::NEM_OFFSET;
+
+/// Maximum number of responses memoized by default.
+const CAPACITY: usize = 256;
+
+/// Memoized responses to historical queries, keyed by the full request.
+#[derive(Debug)]
+pub(crate) struct RequestGuard {
+    /// Maximum number of responses kept.
+    capacity: usize,
+    /// Raw responses by request key.
+    responses: Mutex<Memo>,
+}
+
+/// Memoized responses, along with the order in which they were inserted.
+#[derive(Debug, Default)]
+struct Memo {
+    /// Raw responses by request key.
+    responses: BTreeMap<String, Arc<Fetched>>,
+    /// Keys of the responses, oldest first.
+    order: VecDeque<String>,
+}
+
+impl Default for RequestGuard {
+    #[inline]
+    fn default() -> Self {
+        Self::with_capacity(CAPACITY)
+    }
+}
+
+impl RequestGuard {
+    /// Create a guard keeping at most `capacity` responses.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            responses: Mutex::default(),
+        }
+    }
+
+    /// Returns the memoized response for the given key, if any.
+    pub(crate) fn get(&self, key: &str) -> Option<Arc<Fetched>> {
+        let memo = self.responses.lock().ok()?;
+        memo.responses.get(key).cloned()
+    }
+
+    /// Memoize the response for the given key, evicting the oldest responses
+    /// if the guard is full.
+    pub(crate) fn insert(&self, key: String, response: Arc<Fetched>) {
+        let Ok(mut memo) = self.responses.lock() else {
+            return;
+        };
+        if memo.responses.insert(key.clone(), response).is_none() {
+            memo.order.push_back(key);
+        }
+        while memo.responses.len() > self.capacity {
+            let Some(oldest) = memo.order.pop_front() else {
+                break;
+            };
+            memo.responses.remove(&oldest);
+        }
+    }
+
+    /// Returns the number of memoized responses.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.responses.lock().map_or(0, |memo| memo.responses.len())
+    }
+}
+
+/// Build the key identifying a request.
+pub(crate) fn request_key(endpoint: &str, query: &[(String, String)]) -> String {
+    let mut key = String::from(endpoint);
+    for (index, (name, value)) in query.iter().enumerate() {
+        key.push(if index == 0 { '?' } else { '&' });
+        key.push_str(name);
+        key.push('=');
+        key.push_str(value);
+    }
+    key
+}
+
+/// Returns `true` if a query ending on the given date only covers the past.
+///
+/// Dates are compared in NEM time, as used by the API.
+pub(crate) fn is_historical(end_date: Date) -> bool {
+    end_date < nem_today()
+}
+
+/// Returns the current date in NEM time.
+pub(crate) fn nem_today() -> Date {
+    NEM_OFFSET.to_datetime(timestamp_now()).date()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned as _, vec};
+
+    use jiff::Timestamp;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::storage::Format;
+
+    #[test]
+    fn memoizes_by_key() {
+        let guard = RequestGuard::default();
+        let key = request_key(
+            "https://example.com/sites/1/usage",
+            &[
+                ("startDate".to_owned(), "2021-05-01".to_owned()),
+                ("endDate".to_owned(), "2021-05-03".to_owned()),
+            ],
+        );
+        assert_eq!(
+            key,
+            "https://example.com/sites/1/usage?startDate=2021-05-01&endDate=2021-05-03"
+        );
+        assert!(guard.get(&key).is_none());
+
+        let response = Fetched::new(200, vec![b'[', b']'], Format::Json, Timestamp::UNIX_EPOCH);
+        guard.insert(key.clone(), Arc::new(response));
+        assert_eq!(
+            guard.get(&key).map(|memoized| memoized.body().to_vec()),
+            Some(vec![b'[', b']'])
+        );
+        assert_eq!(guard.len(), 1);
+    }
+
+    #[test]
+    fn evicts_oldest_when_full() {
+        let guard = RequestGuard::with_capacity(2);
+        let response = Arc::new(Fetched::new(
+            200,
+            vec![],
+            Format::Json,
+            Timestamp::UNIX_EPOCH,
+        ));
+        guard.insert("a".to_owned(), Arc::clone(&response));
+        guard.insert("b".to_owned(), Arc::clone(&response));
+        // Replacing a response does not change its age
+        guard.insert("a".to_owned(), Arc::clone(&response));
+        assert_eq!(guard.len(), 2);
+
+        guard.insert("c".to_owned(), response);
+        assert_eq!(guard.len(), 2);
+        assert!(guard.get("a").is_none());
+        assert!(guard.get("b").is_some());
+        assert!(guard.get("c").is_some());
+    }
+
+    #[test]
+    fn historical_dates() {
+        assert!(is_historical(Date::constant(2021, 5, 1)));
+        assert!(!is_historical(Date::constant(9999, 12, 31)));
+    }
+}
@@ -0,0 +1,116 @@
+//! # Keyring Storage
+//!
+//! On desktop machines, the API key can be kept in the platform's credential
+//! store (the macOS Keychain, the Windows Credential Manager, or the Secret
+//! Service on Linux) rather than in a plaintext environment variable or file.
+//!
+//! Store the key once with [`Amber::store_api_key_in_keyring()`], then build
+//! clients with [`AmberBuilder::api_key_from_keyring()`]:
+//!
+//! ```no_run
+//! use amber_api::Amber;
+//!
+//! # fn main() -> amber_api::Result<()> {
+//! Amber::store_api_key_in_keyring("amber-api", "me", "psk_0123456789abcdef")?;
+//! let client = Amber::builder()
+//!     .api_key_from_keyring("amber-api", "me")?
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use keyring::Entry;
+
+use super::{Amber, AmberBuilder, amber_builder};
+use crate::error::{AmberError, Result};
+
+/// Convert an error of the credential store, treating a missing entry as a
+/// missing API key.
+fn error(err: keyring::Error) -> AmberError {
+    if matches!(err, keyring::Error::NoEntry) {
+        AmberError::MissingApiKey
+    } else {
+        AmberError::Keyring(err)
+    }
+}
+
+/// Read the API key stored for a service and user.
+fn get(service: &str, user: &str) -> Result<String> {
+    Entry::new(service, user)
+        .and_then(|entry| entry.get_password())
+        .map_err(error)
+}
+
+impl<S: amber_builder::State> AmberBuilder<S> {
+    /// Use the API key stored in the platform's credential store for the
+    /// given service and user.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::MissingApiKey`] if no key is stored, and
+    /// [`AmberError::Keyring`] if the credential store cannot be accessed.
+    #[inline]
+    pub fn api_key_from_keyring(
+        self,
+        service: &str,
+        user: &str,
+    ) -> Result<AmberBuilder<amber_builder::SetApiKey<S>>>
+    where
+        S::ApiKey: amber_builder::IsUnset,
+    {
+        Ok(self.api_key(get(service, user)?))
+    }
+}
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "The credential store is only supported with the `keyring` feature"
+)]
+impl Amber {
+    /// Store an API key in the platform's credential store, for use with
+    /// [`AmberBuilder::api_key_from_keyring()`].
+    ///
+    /// Any key already stored for the service and user is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::Keyring`] if the credential store cannot be
+    /// accessed.
+    #[inline]
+    pub fn store_api_key_in_keyring(service: &str, user: &str, api_key: &str) -> Result<()> {
+        Entry::new(service, user)
+            .and_then(|entry| entry.set_password(api_key))
+            .map_err(error)
+    }
+
+    /// Remove the API key stored in the platform's credential store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::MissingApiKey`] if no key is stored, and
+    /// [`AmberError::Keyring`] if the credential store cannot be accessed.
+    #[inline]
+    pub fn delete_api_key_from_keyring(service: &str, user: &str) -> Result<()> {
+        Entry::new(service, user)
+            .and_then(|entry| entry.delete_credential())
+            .map_err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_entry_is_missing_key() {
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+        assert!(matches!(
+            Amber::builder().api_key_from_keyring("amber-api-test", "nobody"),
+            Err(AmberError::MissingApiKey)
+        ));
+        assert!(matches!(
+            error(keyring::Error::NoStorageAccess("locked".into())),
+            AmberError::Keyring(_)
+        ));
+    }
+}
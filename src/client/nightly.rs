@@ -0,0 +1,252 @@
+//! # Nightly Summaries
+//!
+//! Usage for a day is published by the API some time after the NEM day
+//! closes. The [`NightlySummary`] job waits for this, computes the
+//! [`DailySummary`] of the previous day and delivers it to a set of
+//! [`SummarySink`]s, so that a report such as "yesterday cost $3.52 from
+//! 12.34 kWh" arrives automatically.
+//!
+//! Sinks are pluggable: closures can be used directly, [`FileSink`] appends
+//! summaries to a JSON lines file, and other destinations (such as webhooks or
+//! MQTT) can be added by implementing [`SummarySink`].
+
+use alloc::{boxed::Box, vec::Vec};
+use core::time::Duration;
+use std::{fs::OpenOptions, io::Write as _, path::PathBuf};
+
+use jiff::{
+    Span, Timestamp,
+    civil::{Date, Time},
+};
+use tracing::{debug, warn};
+
+use super::{Amber, backend::BoxFuture, timestamp_now};
+use crate::{
+    Result,
+    analysis::{NEM_OFFSET, summary::DailySummary},
+    models::SiteId,
+    storage::Format,
+};
+
+/// Destination for daily summaries.
+pub trait SummarySink: Send {
+    /// Deliver a summary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the summary could not be delivered. Errors are
+    /// logged by the job, and do not prevent delivery to other sinks.
+    fn deliver<'a>(&'a mut self, summary: &'a DailySummary) -> BoxFuture<'a, Result<()>>;
+}
+
+impl<F: FnMut(&DailySummary) + Send> SummarySink for F {
+    #[inline]
+    fn deliver<'a>(&'a mut self, summary: &'a DailySummary) -> BoxFuture<'a, Result<()>> {
+        self(summary);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Appends each summary to a file as a line of JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FileSink {
+    /// Path of the file to append to.
+    pub path: PathBuf,
+}
+
+impl FileSink {
+    /// Create a sink appending to the given file, which is created if needed.
+    #[must_use]
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl SummarySink for FileSink {
+    #[inline]
+    fn deliver<'a>(&'a mut self, summary: &'a DailySummary) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut line = Format::Json.encode(summary)?;
+            line.push(b'\n');
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            file.write_all(&line)?;
+            Ok(())
+        })
+    }
+}
+
+/// Computes and delivers the summary of each day once its usage is
+/// published.
+///
+/// # Example
+///
+/// ```no_run
+/// use amber_api::analysis::summary::DailySummary;
+/// use amber_api::{Amber, FileSink, NightlySummary};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let job = NightlySummary::builder()
+///     .client(Amber::default())
+///     .site_id("01F5A5CRKMZ5BCX9P1S4V990AM".parse()?)
+///     .sinks(vec![
+///         Box::new(|summary: &DailySummary| println!("{summary}")),
+///         Box::new(FileSink::new("summaries.jsonl")),
+///     ])
+///     .build();
+/// job.run().await;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(bon::Builder)]
+#[non_exhaustive]
+pub struct NightlySummary {
+    /// Client used to fetch usage.
+    client: Amber,
+    /// ID of the site to summarise.
+    site_id: SiteId,
+    /// Destinations of the summaries.
+    #[builder(default)]
+    sinks: Vec<Box<dyn SummarySink>>,
+    /// Time to wait after the NEM day closes before fetching its usage.
+    ///
+    /// Defaults to 2 hours, giving the API time to publish the day's usage.
+    #[builder(default = Duration::from_hours(2))]
+    delay: Duration,
+}
+
+impl core::fmt::Debug for NightlySummary {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NightlySummary")
+            .field("client", &self.client)
+            .field("site_id", &self.site_id)
+            .field("sinks", &self.sinks.len())
+            .field("delay", &self.delay)
+            .finish()
+    }
+}
+
+impl NightlySummary {
+    /// Compute the summary of the given date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Amber::usage`].
+    #[inline]
+    pub async fn summarize(&self, date: Date) -> Result<DailySummary> {
+        let usage = self
+            .client
+            .usage()
+            .site_id(&self.site_id)
+            .start_date(date)
+            .end_date(date)
+            .call()
+            .await?;
+        Ok(DailySummary::from_usage(date, &usage))
+    }
+
+    /// Deliver a summary to every sink, logging failures.
+    #[inline]
+    pub async fn deliver(&mut self, summary: &DailySummary) {
+        for sink in &mut self.sinks {
+            if let Err(err) = sink.deliver(summary).await {
+                warn!(date = %summary.date, "Failed to deliver daily summary: {err}");
+            }
+        }
+    }
+
+    /// Deliver the summary of each day, shortly after it closes.
+    ///
+    /// This runs until dropped, or returns immediately if no sinks are
+    /// configured. Each night, the job waits until the configured delay after
+    /// NEM midnight, then summarises the day just closed. If no usage has been
+    /// published yet, or fetching fails, it tries again an hour later (up to
+    /// the following night).
+    #[inline]
+    pub async fn run(mut self) {
+        loop {
+            if self.sinks.is_empty() {
+                warn!("No sinks configured for the nightly summary");
+                return;
+            }
+            let (date, due) = next_run(timestamp_now(), self.delay);
+            let wait =
+                Duration::try_from(due.duration_since(timestamp_now())).unwrap_or(Duration::ZERO);
+            debug!(%date, "Waiting {wait:?} to summarise");
+            tokio::time::sleep(wait).await;
+
+            for _ in 0..23_u8 {
+                match self.summarize(date).await {
+                    Ok(summary) if !summary.is_empty() => {
+                        self.deliver(&summary).await;
+                        break;
+                    }
+                    Ok(_) => debug!(%date, "No usage published yet"),
+                    Err(err) => warn!(%date, "Failed to summarise usage: {err}"),
+                }
+                tokio::time::sleep(Duration::from_hours(1)).await;
+            }
+        }
+    }
+}
+
+/// Returns the next day to summarise, and when to do so.
+///
+/// The summary of a day is due `delay` after the end of that day in NEM time.
+fn next_run(now: Timestamp, delay: Duration) -> (Date, Timestamp) {
+    let delay_span = Span::try_from(delay).unwrap_or_default();
+    let today = NEM_OFFSET.to_datetime(now).date();
+    let due_for = |date: Date| {
+        date.tomorrow()
+            .ok()
+            .and_then(|end| {
+                NEM_OFFSET
+                    .to_timestamp(end.to_datetime(Time::midnight()))
+                    .ok()
+            })
+            .and_then(|end| end.checked_add(delay_span).ok())
+            .unwrap_or(now)
+    };
+    let yesterday = today.yesterday().unwrap_or(today);
+    if now < due_for(yesterday) {
+        (yesterday, due_for(yesterday))
+    } else {
+        (today, due_for(today))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn next_run_waits_for_delay() {
+        let delay = Duration::from_hours(2);
+        // 00:30 NEM time, before yesterday's summary is due
+        let early: Timestamp = "2021-05-04T14:30:00Z".parse().expect("valid timestamp");
+        assert_eq!(
+            next_run(early, delay),
+            (
+                Date::constant(2021, 5, 4),
+                "2021-05-04T16:00:00Z".parse().expect("valid timestamp")
+            )
+        );
+        // 09:00 NEM time, after yesterday's summary was due
+        let late: Timestamp = "2021-05-04T23:00:00Z".parse().expect("valid timestamp");
+        assert_eq!(
+            next_run(late, delay),
+            (
+                Date::constant(2021, 5, 5),
+                "2021-05-05T16:00:00Z".parse().expect("valid timestamp")
+            )
+        );
+    }
+}
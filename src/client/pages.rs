@@ -0,0 +1,275 @@
+//! # Paged Requests
+//!
+//! The API limits each price and usage request to a short date range, so long
+//! ranges are fetched one chunk at a time. [`PricesPages`] and [`UsagePages`]
+//! are pagers: they fetch the chunks of a range lazily, one request per call
+//! to `next_page()`, so that callers can process (or store) each page as it
+//! arrives and stop early. Both report how many pages remain and can collect
+//! the remaining pages into a single result.
+//!
+//! The pagers implement neither [`Iterator`] nor `Stream`: fetching a page is
+//! asynchronous, and the crate does not depend on an async stream trait.
+//! Drive them with a `while let` loop over `next_page()` instead.
+//!
+//! Unlike `impl Trait` returns, both types can be named, such as to store a
+//! partially consumed range in a struct.
+
+use alloc::{
+    string::String,
+    vec::{self, Vec},
+};
+use core::time::Duration;
+
+use jiff::civil::Date;
+
+use super::{Amber, interval_order};
+use crate::{
+    error::Result,
+    models::{Interval, Resolution, SiteId, Usage},
+};
+
+/// Pager over the prices of a date range, fetched one chunk at a time.
+///
+/// Created by [`Amber::prices_pages()`].
+///
+/// # Example
+///
+/// ```no_run
+/// use amber_api::Amber;
+/// use jiff::civil::date;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Amber::default();
+/// let sites = client.sites().await?;
+///
+/// let mut pages = client.prices_pages()
+///     .site_id(&sites[0].id)
+///     .start_date(date(2021, 5, 1))
+///     .end_date(date(2021, 5, 31))
+///     .call()?;
+/// println!("Fetching {} pages", pages.remaining());
+/// while let Some(page) = pages.next_page().await {
+///     println!("{} intervals", page?.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PricesPages<'a> {
+    /// Client used to fetch the pages.
+    client: &'a Amber,
+    /// ID of the site.
+    site_id: &'a SiteId,
+    /// Date ranges of the pages not yet fetched.
+    chunks: vec::IntoIter<(Date, Date)>,
+    /// Resolution of the prices.
+    resolution: Option<Resolution>,
+    /// Timeout of each request.
+    timeout: Option<Duration>,
+    /// API key overriding the client's key.
+    api_key: Option<String>,
+}
+
+impl<'a> PricesPages<'a> {
+    /// Create the pages of the given date ranges.
+    pub(crate) fn new(
+        client: &'a Amber,
+        site_id: &'a SiteId,
+        chunks: vec::IntoIter<(Date, Date)>,
+        resolution: Option<Resolution>,
+        timeout: Option<Duration>,
+        api_key: Option<String>,
+    ) -> Self {
+        Self {
+            client,
+            site_id,
+            chunks,
+            resolution,
+            timeout,
+            api_key,
+        }
+    }
+
+    /// Fetch the next page, or return `None` once every page was fetched.
+    ///
+    /// # Errors
+    ///
+    /// The page's result is an error under the same conditions as
+    /// [`Amber::prices()`]. A failed page is not retried; the following call
+    /// fetches the next page.
+    #[inline]
+    pub async fn next_page(&mut self) -> Option<Result<Vec<Interval>>> {
+        let (start_date, end_date) = self.chunks.next()?;
+        Some(
+            self.client
+                .prices()
+                .site_id(self.site_id)
+                .start_date(start_date)
+                .end_date(end_date)
+                .maybe_resolution(self.resolution)
+                .maybe_timeout(self.timeout)
+                .maybe_api_key(self.api_key.clone())
+                .call()
+                .await,
+        )
+    }
+
+    /// Returns the number of pages not yet fetched.
+    #[must_use]
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the bounds on the number of pages not yet fetched, as with
+    /// [`Iterator::size_hint`].
+    #[must_use]
+    #[inline]
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    /// Returns the date ranges of the pages not yet fetched.
+    #[must_use]
+    #[inline]
+    pub fn dates(&self) -> &[(Date, Date)] {
+        self.chunks.as_slice()
+    }
+
+    /// Fetch every remaining page, returning the intervals grouped by channel
+    /// and in chronological order within each channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first page failing, without partial results.
+    #[inline]
+    pub async fn collect_all(mut self) -> Result<Vec<Interval>> {
+        let mut intervals = Vec::new();
+        while let Some(page) = self.next_page().await {
+            intervals.extend(page?);
+        }
+        intervals.sort_by(|a, b| interval_order(a).cmp(&interval_order(b)));
+        Ok(intervals)
+    }
+}
+
+/// Pager over the usage of a date range, fetched one chunk at a time.
+///
+/// Created by [`Amber::usage_pages()`].
+///
+/// # Example
+///
+/// ```no_run
+/// use amber_api::Amber;
+/// use jiff::civil::date;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Amber::default();
+/// let sites = client.sites().await?;
+///
+/// let mut pages = client.usage_pages()
+///     .site_id(&sites[0].id)
+///     .start_date(date(2021, 5, 1))
+///     .end_date(date(2021, 5, 28))
+///     .call()?;
+/// while let Some(page) = pages.next_page().await {
+///     for usage in page? {
+///         println!("{usage}");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct UsagePages<'a> {
+    /// Client used to fetch the pages.
+    client: &'a Amber,
+    /// ID of the site.
+    site_id: &'a SiteId,
+    /// Date ranges of the pages not yet fetched.
+    chunks: vec::IntoIter<(Date, Date)>,
+    /// Timeout of each request.
+    timeout: Option<Duration>,
+    /// API key overriding the client's key.
+    api_key: Option<String>,
+}
+
+impl<'a> UsagePages<'a> {
+    /// Create the pages of the given date ranges.
+    pub(crate) fn new(
+        client: &'a Amber,
+        site_id: &'a SiteId,
+        chunks: vec::IntoIter<(Date, Date)>,
+        timeout: Option<Duration>,
+        api_key: Option<String>,
+    ) -> Self {
+        Self {
+            client,
+            site_id,
+            chunks,
+            timeout,
+            api_key,
+        }
+    }
+
+    /// Fetch the next page, or return `None` once every page was fetched.
+    ///
+    /// # Errors
+    ///
+    /// The page's result is an error under the same conditions as
+    /// [`Amber::usage()`]. A failed page is not retried; the following call
+    /// fetches the next page.
+    #[inline]
+    pub async fn next_page(&mut self) -> Option<Result<Vec<Usage>>> {
+        let (start_date, end_date) = self.chunks.next()?;
+        Some(
+            self.client
+                .usage()
+                .site_id(self.site_id)
+                .start_date(start_date)
+                .end_date(end_date)
+                .maybe_timeout(self.timeout)
+                .maybe_api_key(self.api_key.clone())
+                .call()
+                .await,
+        )
+    }
+
+    /// Returns the number of pages not yet fetched.
+    #[must_use]
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the bounds on the number of pages not yet fetched, as with
+    /// [`Iterator::size_hint`].
+    #[must_use]
+    #[inline]
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    /// Returns the date ranges of the pages not yet fetched.
+    #[must_use]
+    #[inline]
+    pub fn dates(&self) -> &[(Date, Date)] {
+        self.chunks.as_slice()
+    }
+
+    /// Fetch every remaining page, returning the usage in date order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first page failing, without partial results.
+    #[inline]
+    pub async fn collect_all(mut self) -> Result<Vec<Usage>> {
+        let mut usage = Vec::new();
+        while let Some(page) = self.next_page().await {
+            usage.extend(page?);
+        }
+        Ok(usage)
+    }
+}
@@ -0,0 +1,122 @@
+//! # Multiple Accounts
+//!
+//! Each Amber account has its own API key, so a process monitoring several
+//! accounts (such as a household and a holiday home) needs one client per key.
+//! An [`AmberPool`] keeps these clients under a label for each account, all
+//! sharing the configuration and HTTP backend of a single base client.
+//!
+//! For a one-off request with a different key, the `api_key` parameter of
+//! each endpoint can be used instead.
+
+use alloc::{collections::BTreeMap, string::String};
+
+use super::Amber;
+
+/// Clients for several Amber accounts, keyed by label.
+///
+/// Every account's client behaves exactly as an [`Amber`] client, so the same
+/// endpoint API is available for each account through [`account()`].
+///
+/// # Example
+///
+/// ```no_run
+/// use amber_api::{Amber, AmberPool};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let pool = AmberPool::new(Amber::builder().max_retries(5).build()?)
+///     .with_account("home", "psk_home")
+///     .with_account("beach", "psk_beach");
+///
+/// for (label, client) in pool.accounts() {
+///     let sites = client.sites().await?;
+///     println!("{label}: {} sites", sites.len());
+/// }
+///
+/// let beach = pool.account("beach").ok_or("unknown account")?;
+/// let prices = beach
+///     .current_prices()
+///     .site_id(&beach.sites().await?[0].id)
+///     .call()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`account()`]: Self::account
+#[derive(Debug, Clone)]
+pub struct AmberPool {
+    /// Client from which the account clients are derived.
+    base: Amber,
+    /// Client of each account.
+    accounts: BTreeMap<String, Amber>,
+}
+
+impl AmberPool {
+    /// Create an empty pool whose accounts share the configuration of the
+    /// given client.
+    #[must_use]
+    #[inline]
+    pub fn new(base: Amber) -> Self {
+        Self {
+            base,
+            accounts: BTreeMap::new(),
+        }
+    }
+
+    /// Add an account, returning the pool.
+    ///
+    /// An existing account with the same label is replaced.
+    #[must_use]
+    #[inline]
+    pub fn with_account(mut self, label: impl Into<String>, api_key: impl Into<String>) -> Self {
+        self.insert(label, api_key);
+        self
+    }
+
+    /// Add an account, returning the client it replaces, if any.
+    #[inline]
+    pub fn insert(
+        &mut self,
+        label: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Option<Amber> {
+        self.accounts
+            .insert(label.into(), self.base.with_api_key(api_key))
+    }
+
+    /// Remove an account, returning its client.
+    #[inline]
+    pub fn remove(&mut self, label: &str) -> Option<Amber> {
+        self.accounts.remove(label)
+    }
+
+    /// Returns the client of an account.
+    #[must_use]
+    #[inline]
+    pub fn account(&self, label: &str) -> Option<&Amber> {
+        self.accounts.get(label)
+    }
+
+    /// Returns the labels and clients of all accounts, ordered by label.
+    #[inline]
+    pub fn accounts(&self) -> impl Iterator<Item = (&str, &Amber)> {
+        self.accounts
+            .iter()
+            .map(|(label, client)| (label.as_str(), client))
+    }
+
+    /// Returns the number of accounts.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    /// Returns `true` if the pool has no accounts.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
@@ -0,0 +1,144 @@
+//! # Proxy Configuration
+//!
+//! Requests can be routed through an HTTP, HTTPS or SOCKS proxy, such as when
+//! running behind a corporate network.
+//!
+//! ## Precedence
+//!
+//! By default, the client created by [`Amber::builder()`] honours the
+//! `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` environment
+//! variables (as well as their lowercase forms). When an explicit
+//! [`ProxyConfig`] is given, it takes precedence and the environment variables
+//! are ignored. Environment proxies can also be disabled entirely with
+//! `system_proxy(false)`.
+//!
+//! Neither setting applies when a pre-configured `client` or custom `backend`
+//! is given, as these are responsible for their own proxy configuration.
+//!
+//! ## SOCKS Proxies
+//!
+//! Proxies with a `socks5://` or `socks5h://` URL require the `socks` feature.
+//! With `socks5h://`, host names are resolved by the proxy.
+//!
+//! [`Amber::builder()`]: crate::Amber::builder
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::error::Result;
+
+/// Which requests are sent through a proxy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ProxyScope {
+    /// All requests.
+    #[default]
+    All,
+    /// Only requests to `http://` URLs.
+    Http,
+    /// Only requests to `https://` URLs.
+    Https,
+}
+
+/// A proxy through which requests are sent.
+///
+/// # Example
+///
+/// ```
+/// use amber_api::{Amber, ProxyConfig};
+///
+/// # fn main() -> amber_api::Result<()> {
+/// let proxy = ProxyConfig::builder()
+///     .url("http://proxy.example.com:8080")
+///     .username("alice")
+///     .password("hunter2")
+///     .build();
+/// let client = Amber::builder().proxy(proxy).build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, bon::Builder)]
+#[non_exhaustive]
+pub struct ProxyConfig {
+    /// URL of the proxy, such as `http://proxy.example.com:8080` or
+    /// `socks5h://127.0.0.1:1080`.
+    #[builder(into)]
+    pub url: String,
+    /// Username used to authenticate with the proxy, if required.
+    #[builder(into)]
+    pub username: Option<String>,
+    /// Password used to authenticate with the proxy.
+    ///
+    /// Only used if a username is set.
+    #[builder(into)]
+    pub password: Option<String>,
+    /// Which requests are sent through the proxy.
+    ///
+    /// Defaults to [`ProxyScope::All`].
+    #[builder(default)]
+    pub scope: ProxyScope,
+}
+
+impl ProxyConfig {
+    /// Create a proxy for all requests, without authentication.
+    #[must_use]
+    #[inline]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::builder().url(url).build()
+    }
+
+    /// Convert to a `reqwest` proxy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::Http`](crate::AmberError::Http) if the URL is
+    /// invalid or its scheme is not supported.
+    pub(crate) fn to_reqwest(&self) -> Result<reqwest::Proxy> {
+        let proxy = match self.scope {
+            ProxyScope::All => reqwest::Proxy::all(&self.url)?,
+            ProxyScope::Http => reqwest::Proxy::http(&self.url)?,
+            ProxyScope::Https => reqwest::Proxy::https(&self.url)?,
+        };
+        Ok(match &self.username {
+            Some(username) => {
+                proxy.basic_auth(username, self.password.as_deref().unwrap_or_default())
+            }
+            None => proxy,
+        })
+    }
+}
+
+impl fmt::Debug for ProxyConfig {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyConfig")
+            .field("url", &self.url)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn debug_redacts_password() {
+        let proxy = ProxyConfig::builder()
+            .url("http://proxy.example.com:8080")
+            .username("alice")
+            .password("hunter2")
+            .build();
+        let debug = format!("{proxy:?}");
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains("alice"));
+        assert_eq!(proxy.scope, ProxyScope::All);
+        proxy.to_reqwest().expect("valid proxy");
+    }
+}
@@ -0,0 +1,145 @@
+//! # Forecast Recorder
+//!
+//! The API only ever returns the latest forecast for each interval. The
+//! [`Recorder`] periodically archives the full forecast horizon of a site as
+//! [`ForecastSnapshot`]s, building up the dataset needed to evaluate forecast
+//! accuracy or to look back at what was known at a given time.
+
+use core::{ops::ControlFlow, time::Duration};
+
+use tokio::time::{self, MissedTickBehavior};
+
+use super::Amber;
+use crate::{
+    Result,
+    history::ForecastSnapshot,
+    limits::IntervalCount,
+    models::{Resolution, SiteId},
+};
+
+/// Default number of forecast intervals recorded per channel.
+///
+/// This covers 48 hours at 5-minute resolution.
+const DEFAULT_HORIZON: u32 = 576;
+
+/// Periodically records the forecast horizon of a site.
+///
+/// A recorder can be used standalone by calling [`snapshot`](Self::snapshot)
+/// whenever a snapshot is wanted, or left to run on a fixed schedule with
+/// [`run`](Self::run).
+///
+/// # Example
+///
+/// ```no_run
+/// use core::{ops::ControlFlow, time::Duration};
+///
+/// use amber_api::{Amber, Recorder};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let recorder = Recorder::builder()
+///     .client(Amber::default())
+///     .site_id("01F5A5CRKMZ5BCX9P1S4V990AM".parse()?)
+///     .every(Duration::from_secs(15 * 60))
+///     .build();
+///
+/// let mut archive = Vec::new();
+/// recorder
+///     .run(|snapshot| {
+///         match snapshot {
+///             Ok(snapshot) => archive.push(snapshot),
+///             Err(err) => eprintln!("Failed to record forecast: {err}"),
+///         }
+///         ControlFlow::Continue(())
+///     })
+///     .await;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, bon::Builder)]
+#[non_exhaustive]
+pub struct Recorder {
+    /// Client used to fetch prices.
+    client: Amber,
+    /// ID of the site to record.
+    site_id: SiteId,
+    /// Time between snapshots.
+    ///
+    /// Defaults to 5 minutes. Periods shorter than a second are rounded up to
+    /// one second.
+    #[builder(default = Duration::from_mins(5))]
+    every: Duration,
+    /// Number of forecast intervals to record.
+    ///
+    /// Defaults to 576 per channel (48 hours at 5-minute resolution).
+    #[builder(into, default = IntervalCount::PerChannel(DEFAULT_HORIZON))]
+    next: IntervalCount,
+    /// Resolution of the recorded intervals.
+    ///
+    /// Defaults to the billing interval of the site.
+    resolution: Option<Resolution>,
+}
+
+impl Recorder {
+    /// Returns the ID of the recorded site.
+    #[must_use]
+    #[inline]
+    pub fn site_id(&self) -> &SiteId {
+        &self.site_id
+    }
+
+    /// Returns the time between snapshots.
+    #[must_use]
+    #[inline]
+    pub fn every(&self) -> Duration {
+        self.every
+    }
+
+    /// Take a single snapshot of the current prices and forecasts.
+    ///
+    /// The snapshot is timestamped with the time the response was received.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as
+    /// [`Amber::current_prices`].
+    #[inline]
+    pub async fn snapshot(&self) -> Result<ForecastSnapshot> {
+        let response = self
+            .client
+            .current_prices_response()
+            .site_id(&self.site_id)
+            .next(self.next)
+            .maybe_resolution(self.resolution)
+            .call()
+            .await?;
+        Ok(ForecastSnapshot::new(
+            self.site_id.clone(),
+            response.fetched_at,
+            &response.data,
+        ))
+    }
+
+    /// Record snapshots on a fixed schedule, passing each to `sink`.
+    ///
+    /// The first snapshot is taken immediately. Failed snapshots are passed to
+    /// the sink as errors, so it can decide whether to carry on. Recording
+    /// stops once the sink returns [`ControlFlow::Break`].
+    ///
+    /// If taking a snapshot takes longer than the configured period, the next
+    /// snapshot is delayed rather than taken in a burst.
+    #[inline]
+    pub async fn run<F>(&self, mut sink: F)
+    where
+        F: FnMut(Result<ForecastSnapshot>) -> ControlFlow<()>,
+    {
+        let mut ticker = time::interval(self.every.max(Duration::from_secs(1)));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if sink(self.snapshot().await).is_break() {
+                return;
+            }
+        }
+    }
+}
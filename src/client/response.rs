@@ -0,0 +1,304 @@
+//! # Response Metadata
+//!
+//! Endpoints are available in two forms: one returning only the parsed data,
+//! and a `*_response` variant returning a [`Response`] which also carries the
+//! provenance of the data. This allows caches, stores and debug output to
+//! record where data came from without relying on side channels.
+
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+use jiff::Timestamp;
+
+use crate::{rate_limit::RateLimitInfo, storage::Format};
+
+/// Parsed data returned by the API, along with metadata about the request.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Response<T> {
+    /// The parsed response data.
+    pub data: T,
+    /// The request which produced this response.
+    pub request: RequestInfo,
+    /// When the response was received.
+    ///
+    /// For responses memoized by the duplicate request guard, this is when the
+    /// original response was received.
+    pub fetched_at: Timestamp,
+    /// Time reported by the server in the `Date` header, if any.
+    ///
+    /// Comparing this with `fetched_at` reveals any skew of the local clock.
+    pub server_time: Option<Timestamp>,
+    /// HTTP status code of the response.
+    pub status: u16,
+    /// Rate limit metadata reported with the response, if any.
+    pub rate_limit: Option<RateLimitInfo>,
+    /// Age of the response, if it was served from the cache because the API
+    /// was unavailable.
+    ///
+    /// This is `None` for live responses. See
+    /// [`CachePolicy::stale_if_error`](crate::CachePolicy::stale_if_error).
+    pub stale: Option<Duration>,
+}
+
+/// Data served from the cache because the API was unavailable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stale<T> {
+    /// The cached data.
+    pub data: T,
+    /// How long ago the data was received from the API.
+    pub age: Duration,
+}
+
+impl<T> Response<T> {
+    /// Returns the data if the response is live, or the data marked as stale
+    /// if it was served from the cache because the API was unavailable.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`Stale`] data if the response is not live.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use core::time::Duration;
+    ///
+    /// use amber_api::{Amber, CachePolicy, Stale};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::builder()
+    ///     .cache(
+    ///         CachePolicy::builder()
+    ///             .stale_if_error(Duration::from_secs(15 * 60))
+    ///             .build(),
+    ///     )
+    ///     .build()?;
+    ///
+    /// match client.sites_response().await?.into_live() {
+    ///     Ok(sites) => println!("{} sites", sites.len()),
+    ///     Err(Stale { data, age, .. }) => {
+    ///         println!("{} sites (as of {}s ago)", data.len(), age.as_secs());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn into_live(self) -> Result<T, Stale<T>> {
+        match self.stale {
+            None => Ok(self.data),
+            Some(age) => Err(Stale {
+                data: self.data,
+                age,
+            }),
+        }
+    }
+
+    /// Returns `true` if the response was served from the cache because the
+    /// API was unavailable.
+    #[must_use]
+    #[inline]
+    pub fn is_stale(&self) -> bool {
+        self.stale.is_some()
+    }
+
+    /// Consume the response, returning only the data.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+
+    /// Transform the data, keeping the metadata.
+    #[must_use]
+    #[inline]
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Response<U> {
+        Response {
+            data: f(self.data),
+            request: self.request,
+            fetched_at: self.fetched_at,
+            server_time: self.server_time,
+            status: self.status,
+            rate_limit: self.rate_limit,
+            stale: self.stale,
+        }
+    }
+}
+
+/// Description of a request made to the API.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct RequestInfo {
+    /// Full URL of the endpoint, without query parameters.
+    pub url: String,
+    /// Query parameters sent with the request.
+    pub query: Vec<(String, String)>,
+}
+
+/// Raw successful response, before deserialization.
+#[derive(Debug, Clone)]
+pub(crate) struct Fetched {
+    /// Raw response body.
+    body: Vec<u8>,
+    /// Encoding of the body, as reported by the `Content-Type` header.
+    format: Format,
+    /// HTTP status code.
+    status: u16,
+    /// Rate limit metadata reported with the response.
+    rate_limit: Option<RateLimitInfo>,
+    /// When the response was received.
+    received_at: Timestamp,
+    /// Time reported by the server in the `Date` header.
+    server_time: Option<Timestamp>,
+    /// Value of the `ETag` header, identifying this version of the response.
+    etag: Option<String>,
+    /// Value of the `Last-Modified` header.
+    last_modified: Option<String>,
+    /// Age of the response, if served from the cache because the API was
+    /// unavailable.
+    stale: Option<Duration>,
+}
+
+impl Fetched {
+    /// Create a successful response received at `received_at`.
+    pub(crate) fn new(status: u16, body: Vec<u8>, format: Format, received_at: Timestamp) -> Self {
+        Self {
+            body,
+            format,
+            status,
+            rate_limit: None,
+            received_at,
+            server_time: None,
+            etag: None,
+            last_modified: None,
+            stale: None,
+        }
+    }
+
+    /// Returns the response with the rate limit metadata set.
+    pub(crate) fn with_rate_limit(mut self, rate_limit: Option<RateLimitInfo>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Returns the response with the time reported by the server set.
+    pub(crate) fn with_server_time(mut self, server_time: Option<Timestamp>) -> Self {
+        self.server_time = server_time;
+        self
+    }
+
+    /// Returns the response with the values of its `ETag` and
+    /// `Last-Modified` headers set.
+    pub(crate) fn with_validators(
+        mut self,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    ) -> Self {
+        self.etag = etag;
+        self.last_modified = last_modified;
+        self
+    }
+
+    /// Returns a copy of the response, marked as served from the cache at
+    /// the given age.
+    pub(crate) fn served_stale(&self, age: Duration) -> Self {
+        Self {
+            stale: Some(age),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the response, updated with the metadata of a
+    /// `304 Not Modified` response confirming it is still current.
+    pub(crate) fn revalidated(&self, not_modified: Self) -> Self {
+        Self {
+            received_at: not_modified.received_at,
+            server_time: not_modified.server_time,
+            rate_limit: not_modified.rate_limit.or(self.rate_limit),
+            etag: not_modified.etag.or_else(|| self.etag.clone()),
+            last_modified: not_modified
+                .last_modified
+                .or_else(|| self.last_modified.clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the raw response body.
+    pub(crate) fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Returns the encoding of the body.
+    pub(crate) const fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the HTTP status code.
+    pub(crate) const fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns `true` if the response can be revalidated with a conditional
+    /// request.
+    pub(crate) fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+
+    /// Returns the headers making a request conditional on this response
+    /// having changed.
+    pub(crate) fn conditional_headers(&self) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(("If-None-Match".into(), etag.clone()));
+        }
+        if let Some(modified) = &self.last_modified {
+            headers.push(("If-Modified-Since".into(), modified.clone()));
+        }
+        headers
+    }
+
+    /// Attach the metadata of this response to its parsed data.
+    pub(crate) fn to_response<T>(&self, request: RequestInfo, data: T) -> Response<T> {
+        Response {
+            data,
+            request,
+            fetched_at: self.received_at,
+            server_time: self.server_time,
+            status: self.status,
+            rate_limit: self.rate_limit,
+            stale: self.stale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned as _, vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn map_keeps_metadata() {
+        let response = Response {
+            data: vec![1_u32, 2, 3],
+            request: RequestInfo {
+                url: "https://example.com/sites".to_owned(),
+                query: vec![],
+            },
+            fetched_at: Timestamp::UNIX_EPOCH,
+            server_time: None,
+            status: 200,
+            rate_limit: None,
+            stale: None,
+        };
+        let mapped = response.map(|data| data.len());
+        assert_eq!(mapped.data, 3);
+        assert_eq!(mapped.status, 200);
+        assert_eq!(mapped.request.url, "https://example.com/sites");
+        assert_eq!(mapped.into_inner(), 3);
+    }
+}
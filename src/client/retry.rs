@@ -0,0 +1,307 @@
+//! # Retry Policy
+//!
+//! Configuration of how the [`Amber`](crate::Amber) client retries requests
+//! that hit the API rate limit (HTTP 429).
+
+use core::{fmt, hash, time::Duration};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default delay used when a rate-limited response does not say how long to
+/// wait.
+const DEFAULT_DELAY: Duration = Duration::from_mins(1);
+
+/// Policy controlling automatic retries of rate-limited requests.
+///
+/// When a request is rate limited, the client waits for the duration indicated
+/// by the `RateLimit-Reset` (or `Retry-After`) header, falling back to
+/// [`default_delay`](Self::default_delay) if neither header is present. A
+/// random jitter of up to [`jitter`](Self::jitter) is added to the delay to
+/// avoid multiple clients retrying in lockstep. The randomness is drawn from
+/// the [`jitter_source`](Self::jitter_source).
+///
+/// If the delay would exceed [`max_sleep`](Self::max_sleep), the client does
+/// not wait and instead returns
+/// [`AmberError::RateLimitExceeded`](crate::AmberError::RateLimitExceeded).
+/// Once [`max_retries`](Self::max_retries) retries have been made, the client
+/// returns
+/// [`AmberError::RateLimitExhausted`](crate::AmberError::RateLimitExhausted).
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use amber_api::{Amber, RetryPolicy};
+///
+/// # fn main() -> amber_api::Result<()> {
+/// let policy = RetryPolicy::builder()
+///     .max_retries(5)
+///     .jitter(Duration::from_secs(2))
+///     .max_sleep(Duration::from_secs(120))
+///     .build();
+/// let client = Amber::builder().retry_policy(policy).build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bon::Builder)]
+#[non_exhaustive]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the initial attempt.
+    ///
+    /// Defaults to 3.
+    #[builder(default = 3)]
+    pub max_retries: u32,
+    /// Delay used when the response does not indicate how long to wait.
+    ///
+    /// Defaults to 60 seconds.
+    #[builder(default = DEFAULT_DELAY)]
+    pub default_delay: Duration,
+    /// Maximum random delay added to each wait.
+    ///
+    /// Defaults to 1 second.
+    #[builder(default = Duration::from_secs(1))]
+    pub jitter: Duration,
+    /// Longest the client will wait before a single retry.
+    ///
+    /// Defaults to 5 minutes.
+    #[builder(default = Duration::from_mins(5))]
+    pub max_sleep: Duration,
+    /// Source of the randomness of the jitter.
+    ///
+    /// Defaults to [`JitterSource::clock()`].
+    #[builder(default)]
+    pub jitter_source: JitterSource,
+}
+
+/// Source of random fractions used to jitter retry delays.
+///
+/// By default, the fraction is derived from the system clock, which requires
+/// neither an RNG nor any dependency. Targets with a hardware RNG can supply
+/// their own source, and tests can use [`JitterSource::none()`] (or a fixed
+/// fraction) to make delays deterministic.
+///
+/// Function pointers cannot be compared reliably, so all sources compare
+/// equal (and hash alike), and the source of a [`RetryPolicy`] does not take
+/// part in its comparisons.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use amber_api::{JitterSource, RetryPolicy};
+///
+/// let policy = RetryPolicy::builder()
+///     .jitter(Duration::from_secs(2))
+///     .jitter_source(JitterSource::new(|| 0.5))
+///     .build();
+/// assert_eq!(policy.random_jitter(), Duration::from_secs(1));
+/// ```
+#[derive(Clone, Copy)]
+pub struct JitterSource(fn() -> f64);
+
+impl JitterSource {
+    /// Create a source from a function returning fractions in the range
+    /// `[0, 1)`.
+    ///
+    /// Values outside of the range are clamped to it, and non-finite values
+    /// are treated as zero.
+    #[must_use]
+    #[inline]
+    pub const fn new(fraction: fn() -> f64) -> Self {
+        Self(fraction)
+    }
+
+    /// A source deriving fractions from the system clock.
+    ///
+    /// This is not cryptographically secure; it is only meant to spread out
+    /// retries from multiple clients.
+    #[must_use]
+    #[inline]
+    pub const fn clock() -> Self {
+        Self(clock_fraction)
+    }
+
+    /// A source always returning zero, disabling the jitter.
+    #[must_use]
+    #[inline]
+    pub const fn none() -> Self {
+        Self(|| 0.0_f64)
+    }
+
+    /// Returns a fraction in the range `[0, 1)`.
+    #[must_use]
+    #[inline]
+    pub fn fraction(&self) -> f64 {
+        unit_fraction((self.0)())
+    }
+}
+
+impl Default for JitterSource {
+    #[inline]
+    fn default() -> Self {
+        Self::clock()
+    }
+}
+
+impl fmt::Debug for JitterSource {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("JitterSource").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for JitterSource {
+    #[inline]
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for JitterSource {}
+
+impl hash::Hash for JitterSource {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, _state: &mut H) {}
+}
+
+impl Default for RetryPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    /// A policy which never retries.
+    #[must_use]
+    #[inline]
+    pub fn none() -> Self {
+        Self::builder().max_retries(0).build()
+    }
+
+    /// Returns the base delay before retrying, given the number of seconds
+    /// indicated by the server (if any).
+    ///
+    /// Returns `None` if the delay exceeds [`max_sleep`](Self::max_sleep).
+    #[must_use]
+    #[inline]
+    pub fn base_delay(&self, retry_after: Option<u64>) -> Option<Duration> {
+        let delay = retry_after.map_or(self.default_delay, Duration::from_secs);
+        (delay <= self.max_sleep).then_some(delay)
+    }
+
+    /// Returns the jitter to add to a delay, given a random fraction in the
+    /// range `[0, 1)`.
+    ///
+    /// Fractions outside of the range are clamped to it, and non-finite
+    /// fractions are treated as zero.
+    #[must_use]
+    #[inline]
+    pub fn jitter_for(&self, fraction: f64) -> Duration {
+        self.jitter.mul_f64(unit_fraction(fraction))
+    }
+
+    /// Returns a random jitter to add to a delay, drawn from the
+    /// [`jitter_source`](Self::jitter_source).
+    #[must_use]
+    #[inline]
+    pub fn random_jitter(&self) -> Duration {
+        self.jitter_for(self.jitter_source.fraction())
+    }
+}
+
+/// Largest fraction below one.
+const MAX_FRACTION: f64 = f64::from_bits(0x3FEF_FFFF_FFFF_FFFF);
+
+/// Clamp a fraction to the range `[0, 1)`, treating non-finite values as zero.
+fn unit_fraction(fraction: f64) -> f64 {
+    if fraction.is_finite() {
+        fraction.clamp(0.0, MAX_FRACTION)
+    } else {
+        0.0
+    }
+}
+
+/// Returns a pseudo-random fraction in the range `[0, 1)`, derived from the
+/// system clock.
+#[expect(clippy::float_arithmetic, reason = "Scaling to the unit interval")]
+fn clock_fraction() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.subsec_nanos());
+    // Scramble the low-entropy timestamp with a simple integer hash.
+    let mut hash = u64::from(nanos).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    hash ^= hash.wrapping_shr(32);
+    f64::from(u32::try_from(hash & u64::from(u32::MAX)).unwrap_or(0)) / 4_294_967_296.0_f64
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn base_delay() {
+        let policy = RetryPolicy::builder()
+            .max_sleep(Duration::from_secs(90))
+            .build();
+        assert_eq!(policy.base_delay(Some(5)), Some(Duration::from_secs(5)));
+        assert_eq!(policy.base_delay(None), Some(Duration::from_mins(1)));
+        assert_eq!(policy.base_delay(Some(120)), None);
+    }
+
+    #[test]
+    fn jitter() {
+        let policy = RetryPolicy::builder()
+            .jitter(Duration::from_secs(2))
+            .build();
+        assert_eq!(policy.jitter_for(0.0), Duration::ZERO);
+        assert_eq!(policy.jitter_for(0.5), Duration::from_secs(1));
+        assert!(policy.jitter_for(5.0) <= Duration::from_secs(2));
+        assert!(policy.jitter_for(5.0) > Duration::from_millis(1999));
+        assert_eq!(policy.jitter_for(f64::NAN), Duration::ZERO);
+        assert_eq!(policy.jitter_for(f64::INFINITY), Duration::ZERO);
+        assert_eq!(policy.jitter_for(-1.0), Duration::ZERO);
+
+        let fraction = JitterSource::clock().fraction();
+        assert!((0.0_f64..1.0_f64).contains(&fraction));
+    }
+
+    #[test]
+    fn jitter_source() {
+        let policy = RetryPolicy::builder()
+            .jitter(Duration::from_secs(2))
+            .jitter_source(JitterSource::new(|| 0.25_f64))
+            .build();
+        assert_eq!(policy.random_jitter(), Duration::from_millis(500));
+        assert_eq!(
+            RetryPolicy::builder()
+                .jitter_source(JitterSource::none())
+                .build()
+                .random_jitter(),
+            Duration::ZERO
+        );
+        assert!((0.999_f64..1.0_f64).contains(&JitterSource::new(|| 7.0_f64).fraction()));
+        assert_eq!(
+            RetryPolicy::builder()
+                .jitter_source(JitterSource::new(|| f64::NAN))
+                .build()
+                .random_jitter(),
+            Duration::ZERO
+        );
+        assert_eq!(
+            RetryPolicy::builder()
+                .jitter_source(JitterSource::none())
+                .build(),
+            RetryPolicy::default()
+        );
+    }
+
+    #[test]
+    fn none() {
+        assert_eq!(RetryPolicy::none().max_retries, 0);
+        assert_eq!(RetryPolicy::default().max_retries, 3);
+    }
+}
@@ -0,0 +1,109 @@
+//! # API Key Storage
+//!
+//! The API key grants access to the account's usage data, so it is kept out of
+//! `Debug` output and logs. It is handed to the
+//! [`HttpBackend`](crate::HttpBackend) as an [`ApiKey`] rather than a `String`,
+//! so that it is only exposed where the `Authorization` header is built.
+//!
+//! With the `zeroize` feature, the key is wiped from memory once the last
+//! client holding it is dropped, including keys replaced with
+//! [`Amber::with_api_key()`](crate::Amber::with_api_key) and keys passed to a
+//! single request. The `secrecy` feature implies `zeroize`, and additionally
+//! holds the key in a [`secrecy::SecretString`].
+
+use alloc::string::String;
+#[cfg(feature = "zeroize")]
+use alloc::sync::Arc;
+use core::fmt;
+
+/// Storage of the key, wiped when dropped.
+#[cfg(feature = "secrecy")]
+type Secret = secrecy::SecretString;
+/// Storage of the key, wiped when dropped.
+#[cfg(all(feature = "zeroize", not(feature = "secrecy")))]
+type Secret = zeroize::Zeroizing<String>;
+
+/// An API key, redacted from `Debug` output.
+///
+/// With the `zeroize` feature, clones share the key rather than copying it.
+#[derive(Clone)]
+pub struct ApiKey {
+    /// The key, shared between clones so that it is only wiped once.
+    #[cfg(feature = "zeroize")]
+    secret: Arc<Secret>,
+    /// The key.
+    #[cfg(not(feature = "zeroize"))]
+    secret: String,
+}
+
+impl ApiKey {
+    /// Returns the key, for sending in the `Authorization` header.
+    #[must_use]
+    #[inline]
+    pub fn expose(&self) -> &str {
+        #[cfg(feature = "secrecy")]
+        {
+            secrecy::ExposeSecret::expose_secret(&*self.secret)
+        }
+        #[cfg(all(feature = "zeroize", not(feature = "secrecy")))]
+        {
+            self.secret.as_str()
+        }
+        #[cfg(not(feature = "zeroize"))]
+        {
+            &self.secret
+        }
+    }
+}
+
+impl From<String> for ApiKey {
+    #[inline]
+    fn from(key: String) -> Self {
+        Self {
+            #[cfg(feature = "zeroize")]
+            secret: Arc::new(Secret::from(key)),
+            #[cfg(not(feature = "zeroize"))]
+            secret: key,
+        }
+    }
+}
+
+impl From<&str> for ApiKey {
+    #[inline]
+    fn from(key: &str) -> Self {
+        Self::from(String::from(key))
+    }
+}
+
+impl PartialEq for ApiKey {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.expose() == other.expose()
+    }
+}
+
+impl Eq for ApiKey {}
+
+impl fmt::Debug for ApiKey {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ApiKey(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned as _, format};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn redacts_key() {
+        let key = ApiKey::from("psk_secret".to_owned());
+        assert_eq!(key.expose(), "psk_secret");
+        assert_eq!(format!("{key:?}"), "ApiKey(<redacted>)");
+        assert_eq!(format!("{:?}", Some(key)), "Some(ApiKey(<redacted>))");
+    }
+}
@@ -0,0 +1,265 @@
+//! # Site Lookups
+//!
+//! Most applications start by finding their site: the single active site of
+//! the account, or the site of a known NMI or ID. The lookups of this module
+//! share a list of sites cached by the client, so that they can be called
+//! freely without listing the sites on every call.
+
+use alloc::{sync::Arc, vec::Vec};
+use core::time::Duration;
+use std::{sync::Mutex, time::Instant};
+
+use super::Amber;
+use crate::{
+    error::Result,
+    models::{Nmi, Site, SiteId},
+};
+
+/// Default time for which the list of sites is cached.
+pub(crate) const DEFAULT_SITES_TTL: Duration = Duration::from_hours(1);
+
+/// The list of sites, cached for the site lookups.
+#[derive(Debug)]
+pub(crate) struct SiteCache {
+    /// Time for which the list is reused.
+    ttl: Duration,
+    /// The sites, and when they were listed.
+    sites: Mutex<Option<(Instant, Arc<[Site]>)>>,
+}
+
+impl SiteCache {
+    /// Create an empty cache.
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            sites: Mutex::default(),
+        }
+    }
+
+    /// Returns the time for which the list is reused.
+    pub(crate) fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the cached sites, unless they have expired.
+    fn get(&self, now: Instant) -> Option<Arc<[Site]>> {
+        let cached = self.sites.lock().ok()?;
+        let (listed, sites) = cached.as_ref()?;
+        (now.saturating_duration_since(*listed) < self.ttl).then(|| Arc::clone(sites))
+    }
+
+    /// Cache a list of sites.
+    fn insert(&self, sites: Arc<[Site]>, now: Instant) {
+        if let Ok(mut cached) = self.sites.lock() {
+            *cached = Some((now, sites));
+        }
+    }
+
+    /// Discard the cached sites.
+    fn clear(&self) {
+        if let Ok(mut cached) = self.sites.lock() {
+            *cached = None;
+        }
+    }
+}
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "The site lookups are kept alongside their cache"
+)]
+impl Amber {
+    /// Returns the sites of the account, from the cache if they were listed
+    /// recently.
+    async fn cached_sites(&self) -> Result<Arc<[Site]>> {
+        if let Some(cached) = self.site_cache.get(Instant::now()) {
+            return Ok(cached);
+        }
+        let sites: Arc<[Site]> = self.sites().await?.into();
+        self.site_cache.insert(Arc::clone(&sites), Instant::now());
+        Ok(sites)
+    }
+
+    /// Returns only the active sites linked to your account.
+    ///
+    /// This should be preferred over taking the first site returned by
+    /// [`sites()`](Self::sites), as pending and closed sites may be listed
+    /// first.
+    ///
+    /// The list of sites is cached by the client for the `sites_ttl` given to
+    /// the [builder](Self::builder) (an hour by default), and shared between
+    /// [`active_sites()`](Self::active_sites), [`site()`](Self::site) and
+    /// [`site_by_nmi()`](Self::site_by_nmi). Use
+    /// [`refresh_sites()`](Self::refresh_sites) to list the sites again.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`sites()`](Self::sites).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// if let Some(site) = client.active_sites().await?.first() {
+    ///     println!("Using site {}", site.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn active_sites(&self) -> Result<Vec<Site>> {
+        Ok(self
+            .cached_sites()
+            .await?
+            .iter()
+            .filter(|site| site.is_active())
+            .cloned()
+            .collect())
+    }
+
+    /// Returns the site with the given ID, or `None` if the account has no
+    /// such site.
+    ///
+    /// The list of sites is cached as for
+    /// [`active_sites()`](Self::active_sites).
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`sites()`](Self::sites).
+    #[inline]
+    pub async fn site(&self, site_id: &SiteId) -> Result<Option<Site>> {
+        Ok(self
+            .cached_sites()
+            .await?
+            .iter()
+            .find(|site| site.id == *site_id)
+            .cloned())
+    }
+
+    /// Returns the site with the given National Metering Identifier, or
+    /// `None` if the account has no such site.
+    ///
+    /// NMIs may be given with or without their checksum digit. If the NMI
+    /// has had several sites (such as after moving out and back in), the
+    /// active site is preferred.
+    ///
+    /// The list of sites is cached as for
+    /// [`active_sites()`](Self::active_sites).
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`sites()`](Self::sites).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amber_api::Amber;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Amber::default();
+    /// let site = client
+    ///     .site_by_nmi(&"3052282872".parse()?)
+    ///     .await?
+    ///     .ok_or("unknown NMI")?;
+    /// println!("Site {} is {}", site.id, site.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn site_by_nmi(&self, nmi: &Nmi) -> Result<Option<Site>> {
+        let sites = self.cached_sites().await?;
+        let mut matching = sites
+            .iter()
+            .filter(|site| same_nmi(&site.nmi, nmi))
+            .peekable();
+        let first = matching.peek().copied();
+        Ok(matching.find(|site| site.is_active()).or(first).cloned())
+    }
+
+    /// List the sites of the account again, replacing the list cached for
+    /// the site lookups.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error under the same conditions as
+    /// [`sites()`](Self::sites). The cached list is discarded even on error.
+    #[inline]
+    pub async fn refresh_sites(&self) -> Result<Vec<Site>> {
+        self.site_cache.clear();
+        Ok(self.cached_sites().await?.to_vec())
+    }
+}
+
+/// Returns `true` if two NMIs are the same, ignoring any checksum digit.
+fn same_nmi(a: &Nmi, b: &Nmi) -> bool {
+    a.get(..10) == b.get(..10)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, format};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::FixtureBackend;
+
+    #[tokio::test]
+    async fn looks_up_sites() {
+        let client = Amber::offline();
+        let site_id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+
+        let active = client.active_sites().await.expect("active sites");
+        assert_eq!(active.len(), 1);
+        let site = client
+            .site(&site_id)
+            .await
+            .expect("site")
+            .expect("known site");
+        assert_eq!(Some(&site), active.first());
+
+        let nmi = site.nmi.clone();
+        let with_checksum: Nmi = format!("{}0", nmi.get(..10).expect("10 characters"))
+            .parse()
+            .expect("valid NMI");
+        assert_eq!(
+            client.site_by_nmi(&with_checksum).await.expect("site"),
+            Some(site)
+        );
+        assert_eq!(
+            client
+                .site_by_nmi(&"9999999999".parse().expect("valid NMI"))
+                .await
+                .expect("site"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn caches_the_sites() {
+        let dir = std::env::temp_dir().join(format!("amber-site-cache-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temporary directory");
+        std::fs::write(dir.join("sites.json"), include_str!("fixtures/sites.json"))
+            .expect("fixture written");
+        let client = Amber::builder()
+            .backend(Box::new(FixtureBackend::from_dir(&dir)))
+            .build()
+            .expect("valid client");
+        assert_eq!(client.active_sites().await.expect("sites").len(), 1);
+
+        // Served from the cache once the API no longer lists the site
+        std::fs::write(dir.join("sites.json"), "[]").expect("fixture written");
+        assert_eq!(client.active_sites().await.expect("cached sites").len(), 1);
+        assert!(client.refresh_sites().await.expect("sites").is_empty());
+        assert!(client.active_sites().await.expect("sites").is_empty());
+
+        std::fs::remove_dir_all(&dir).expect("temporary directory removed");
+    }
+}
@@ -0,0 +1,133 @@
+//! # Startup Validation
+//!
+//! Long-running services such as home automation daemons should find out about
+//! a missing or revoked API key, an account without active sites or a wrong
+//! system clock when they start, rather than on their first scheduled poll.
+//! [`AmberBuilder::build_and_validate()`](crate::AmberBuilder) builds the
+//! client and performs these checks eagerly, describing the outcome in a
+//! [`StartupReport`].
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use jiff::SignedDuration;
+
+use super::response::Response;
+use crate::{models::Site, rate_limit::RateLimitInfo};
+
+/// Clock skew beyond which a [`StartupWarning::ClockSkew`] is reported.
+///
+/// Interval boundaries are five minutes apart, so a larger skew would make the
+/// client request or label the wrong interval.
+pub const CLOCK_SKEW_TOLERANCE: SignedDuration = SignedDuration::from_secs(60);
+
+/// Outcome of the checks performed when starting up a client.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct StartupReport {
+    /// All sites linked to the account.
+    pub sites: Vec<Site>,
+    /// Difference between the local clock and the server's clock, positive if
+    /// the local clock is ahead.
+    ///
+    /// This is `None` if the server did not report its time. As the `Date`
+    /// header has a resolution of one second and includes the latency of the
+    /// request, small differences are expected.
+    pub clock_skew: Option<SignedDuration>,
+    /// Rate limit status after the checks, if reported.
+    pub rate_limit: Option<RateLimitInfo>,
+    /// Problems found which do not prevent the client from being used.
+    pub warnings: Vec<StartupWarning>,
+}
+
+/// A problem found when starting up a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StartupWarning {
+    /// The account has no sites.
+    NoSites,
+    /// The account has sites, but none of them is active.
+    NoActiveSites,
+    /// The local clock differs from the server's by more than
+    /// [`CLOCK_SKEW_TOLERANCE`].
+    ClockSkew(SignedDuration),
+    /// The rate limit quota is exhausted, so the next request will be delayed.
+    RateLimited,
+}
+
+impl StartupReport {
+    /// Check the response of the sites endpoint.
+    pub(crate) fn new(response: Response<Vec<Site>>) -> Self {
+        let clock_skew = response
+            .server_time
+            .map(|server| response.fetched_at.duration_since(server));
+
+        let mut warnings = Vec::new();
+        if response.data.is_empty() {
+            warnings.push(StartupWarning::NoSites);
+        } else if !response.data.iter().any(Site::is_active) {
+            warnings.push(StartupWarning::NoActiveSites);
+        }
+        if let Some(skew) = clock_skew
+            && skew.unsigned_abs() > CLOCK_SKEW_TOLERANCE.unsigned_abs()
+        {
+            warnings.push(StartupWarning::ClockSkew(skew));
+        }
+        if response
+            .rate_limit
+            .is_some_and(|rate_limit| rate_limit.remaining == Some(0))
+        {
+            warnings.push(StartupWarning::RateLimited);
+        }
+
+        Self {
+            sites: response.data,
+            clock_skew,
+            rate_limit: response.rate_limit,
+            warnings,
+        }
+    }
+
+    /// Returns the active sites.
+    #[inline]
+    pub fn active_sites(&self) -> impl Iterator<Item = &Site> {
+        self.sites.iter().filter(|site| site.is_active())
+    }
+
+    /// Returns `true` if no problems were found.
+    #[must_use]
+    #[inline]
+    pub fn is_healthy(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+impl fmt::Display for StartupWarning {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSites => write!(
+                f,
+                "The account has no sites; check that the API key belongs to the right account"
+            ),
+            Self::NoActiveSites => write!(
+                f,
+                "None of the account's sites is active yet; prices and usage may be unavailable"
+            ),
+            Self::ClockSkew(skew) => write!(
+                f,
+                "The local clock is {:#} {} the server's; synchronise it (e.g. with NTP)",
+                skew.abs(),
+                if skew.is_negative() {
+                    "behind"
+                } else {
+                    "ahead of"
+                }
+            ),
+            Self::RateLimited => write!(
+                f,
+                "The rate limit is exhausted; requests will be delayed or rejected until it resets"
+            ),
+        }
+    }
+}
@@ -0,0 +1,708 @@
+//! # Site Watcher
+//!
+//! Home automation services typically poll the current prices of each of
+//! their sites on a fixed schedule. The [`Watcher`] runs one poller per site
+//! as an independent task, so that a site whose requests keep failing (for
+//! example, because it was closed) backs off on its own without delaying the
+//! others. The health of each site is available at any time from the
+//! [`WatcherHandle`].
+//!
+//! Most automations only need to react when the price changes. A
+//! [`PriceWatch`], created with
+//! [`Amber::watch_current_prices()`](crate::Amber::watch_current_prices),
+//! polls a single site and only yields a [`CurrentInterval`] when a new
+//! interval starts or the price of the current one is revised. The same
+//! changes can be replayed from archived snapshots with a [`PriceReplay`].
+
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    string::{String, ToString as _},
+    sync::Arc,
+    vec::Vec,
+};
+use core::time::Duration;
+use std::sync::Mutex;
+
+use jiff::Timestamp;
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    task::JoinHandle,
+    time,
+};
+
+use super::{Amber, response::Response, timestamp_now};
+use crate::{
+    error::AmberError,
+    error::Result,
+    history::ForecastSnapshot,
+    models::{CurrentInterval, Interval, SiteId},
+};
+
+/// Polls the current prices of several sites, each in its own task.
+///
+/// # Example
+///
+/// ```no_run
+/// use core::time::Duration;
+///
+/// use amber_api::{Amber, WatchEvent, Watcher};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Amber::default();
+/// let sites = client.active_sites().await?;
+///
+/// let mut handle = Watcher::builder()
+///     .client(client)
+///     .sites(sites.into_iter().map(|site| site.id).collect())
+///     .every(Duration::from_secs(60))
+///     .build()
+///     .start();
+///
+/// while let Some(event) = handle.next().await {
+///     match event {
+///         WatchEvent::Prices { site_id, response } => {
+///             println!("{site_id}: {} intervals", response.data.len());
+///         }
+///         WatchEvent::Failed { site_id, error, retry_in } => {
+///             eprintln!("{site_id}: {error} (retrying in {retry_in:?})");
+///         }
+///         _ => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, bon::Builder)]
+#[non_exhaustive]
+pub struct Watcher {
+    /// Client used to fetch prices.
+    client: Amber,
+    /// IDs of the sites to watch.
+    sites: Vec<SiteId>,
+    /// Time between polls of each site.
+    ///
+    /// Defaults to 1 minute.
+    #[builder(default = Duration::from_mins(1))]
+    every: Duration,
+    /// Longest time between polls of a failing site.
+    ///
+    /// After each consecutive failure, the time until the site is polled
+    /// again doubles (starting from `every`), up to this maximum.
+    ///
+    /// Defaults to 15 minutes.
+    #[builder(default = Duration::from_mins(15))]
+    max_backoff: Duration,
+    /// Number of forecast intervals to fetch along with the current prices.
+    ///
+    /// Defaults to none.
+    next: Option<u32>,
+}
+
+/// An update from a [`Watcher`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WatchEvent {
+    /// The current prices of a site were fetched.
+    Prices {
+        /// ID of the site.
+        site_id: SiteId,
+        /// The current prices.
+        response: Response<Vec<Interval>>,
+    },
+    /// Fetching the prices of a site failed.
+    Failed {
+        /// ID of the site.
+        site_id: SiteId,
+        /// The error.
+        error: AmberError,
+        /// Time until the site is polled again.
+        retry_in: Duration,
+    },
+}
+
+impl WatchEvent {
+    /// Returns the ID of the site the event is about.
+    #[must_use]
+    #[inline]
+    pub fn site_id(&self) -> &SiteId {
+        match self {
+            Self::Prices { site_id, .. } | Self::Failed { site_id, .. } => site_id,
+        }
+    }
+}
+
+/// Health of the poller of a site.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SiteHealth {
+    /// When prices were last fetched successfully.
+    pub last_success: Option<Timestamp>,
+    /// When fetching prices last failed.
+    pub last_failure: Option<Timestamp>,
+    /// Message of the most recent error, if the last poll failed.
+    pub last_error: Option<String>,
+    /// Number of polls which have failed since the last success.
+    pub consecutive_failures: u32,
+    /// Time until the site is next polled, as of the last poll.
+    pub next_poll_in: Duration,
+}
+
+impl SiteHealth {
+    /// Returns `true` if the last poll of the site succeeded.
+    #[must_use]
+    #[inline]
+    pub fn is_healthy(&self) -> bool {
+        self.last_success.is_some() && self.consecutive_failures == 0
+    }
+}
+
+/// Health of all watched sites, shared with the pollers.
+type HealthMap = Arc<Mutex<BTreeMap<SiteId, SiteHealth>>>;
+
+impl Watcher {
+    /// Returns the IDs of the watched sites.
+    #[must_use]
+    #[inline]
+    pub fn sites(&self) -> &[SiteId] {
+        &self.sites
+    }
+
+    /// Start polling each site in its own task.
+    ///
+    /// Each site is polled immediately, and then on its own schedule. The
+    /// pollers stop when the returned handle is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    #[must_use]
+    #[inline]
+    pub fn start(&self) -> WatcherHandle {
+        let (sender, events) = mpsc::unbounded_channel();
+        let health: HealthMap = Arc::new(Mutex::new(
+            self.sites
+                .iter()
+                .map(|site_id| (site_id.clone(), SiteHealth::default()))
+                .collect(),
+        ));
+        let tasks = self
+            .sites
+            .iter()
+            .map(|site_id| {
+                tokio::spawn(poll_site(
+                    self.clone(),
+                    site_id.clone(),
+                    Arc::clone(&health),
+                    sender.clone(),
+                ))
+            })
+            .collect();
+        WatcherHandle {
+            events,
+            health,
+            tasks,
+        }
+    }
+
+    /// Returns the delay before polling a site again after the given number
+    /// of consecutive failures.
+    fn backoff(&self, failures: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(failures.saturating_sub(1).min(16));
+        self.every
+            .saturating_mul(factor)
+            .min(self.max_backoff.max(self.every))
+    }
+}
+
+/// Poll a single site until the handle is dropped.
+async fn poll_site(
+    watcher: Watcher,
+    site_id: SiteId,
+    health: HealthMap,
+    events: UnboundedSender<WatchEvent>,
+) {
+    let mut failures: u32 = 0;
+    loop {
+        let result = watcher
+            .client
+            .current_prices_response()
+            .site_id(&site_id)
+            .maybe_next(watcher.next)
+            .call()
+            .await;
+        let now = timestamp_now();
+        let (event, delay) = match result {
+            Ok(response) => {
+                failures = 0;
+                let event = WatchEvent::Prices {
+                    site_id: site_id.clone(),
+                    response,
+                };
+                (event, watcher.every)
+            }
+            Err(error) => {
+                failures = failures.saturating_add(1);
+                let retry_in = watcher.backoff(failures);
+                let event = WatchEvent::Failed {
+                    site_id: site_id.clone(),
+                    error,
+                    retry_in,
+                };
+                (event, retry_in)
+            }
+        };
+
+        if let Ok(mut sites) = health.lock() {
+            let site = sites.entry(site_id.clone()).or_default();
+            site.consecutive_failures = failures;
+            site.next_poll_in = delay;
+            match &event {
+                WatchEvent::Prices { .. } => {
+                    site.last_success = Some(now);
+                    site.last_error = None;
+                }
+                WatchEvent::Failed { error, .. } => {
+                    site.last_failure = Some(now);
+                    site.last_error = Some(error.to_string());
+                }
+            }
+        }
+        if events.send(event).is_err() {
+            return;
+        }
+        time::sleep(delay).await;
+    }
+}
+
+/// Handle to the pollers started by [`Watcher::start`].
+///
+/// Dropping the handle stops the pollers.
+#[derive(Debug)]
+pub struct WatcherHandle {
+    /// Events from all pollers.
+    events: UnboundedReceiver<WatchEvent>,
+    /// Health of each site.
+    health: HealthMap,
+    /// The pollers.
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl WatcherHandle {
+    /// Wait for the next event from any site.
+    ///
+    /// Returns `None` once all pollers have stopped.
+    #[inline]
+    pub async fn next(&mut self) -> Option<WatchEvent> {
+        self.events.recv().await
+    }
+
+    /// Returns the health of each watched site.
+    #[must_use]
+    #[inline]
+    pub fn health(&self) -> BTreeMap<SiteId, SiteHealth> {
+        self.health
+            .lock()
+            .map(|sites| sites.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the health of a watched site.
+    #[must_use]
+    #[inline]
+    pub fn site_health(&self, site_id: &SiteId) -> Option<SiteHealth> {
+        let sites = self.health.lock().ok()?;
+        sites.get(site_id).cloned()
+    }
+
+    /// Returns `true` if the last poll of every site succeeded.
+    #[must_use]
+    #[inline]
+    pub fn is_healthy(&self) -> bool {
+        self.health
+            .lock()
+            .is_ok_and(|sites| sites.values().all(SiteHealth::is_healthy))
+    }
+
+    /// Stop all pollers.
+    #[inline]
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for WatcherHandle {
+    #[inline]
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Yields the current interval of each channel of a site whenever it
+/// changes.
+///
+/// Created by [`Amber::watch_current_prices()`]. The site is polled on a fixed
+/// schedule, and an interval is yielded when it differs from the last one
+/// yielded for its channel: either a new interval started, or the price (or
+/// any other detail, such as whether it is an estimate) was revised. The first
+/// poll yields the current interval of every channel.
+///
+/// This behaves as a stream: call [`next()`](Self::next) in a loop.
+#[derive(Debug)]
+pub struct PriceWatch {
+    /// Client used to fetch prices.
+    client: Amber,
+    /// ID of the watched site.
+    site_id: SiteId,
+    /// Time between polls.
+    every: Duration,
+    /// Schedule of the polls, created on the first poll.
+    ticker: Option<time::Interval>,
+    /// Changes found by the polls.
+    changes: Changes,
+}
+
+impl PriceWatch {
+    /// Create a watch of the given site.
+    pub(crate) fn new(client: Amber, site_id: SiteId, every: Duration) -> Self {
+        Self {
+            client,
+            site_id,
+            every: every.max(Duration::from_secs(1)),
+            ticker: None,
+            changes: Changes::default(),
+        }
+    }
+
+    /// Returns the ID of the watched site.
+    #[must_use]
+    #[inline]
+    pub fn site_id(&self) -> &SiteId {
+        &self.site_id
+    }
+
+    /// Wait for the next change of the current interval of any channel.
+    ///
+    /// Polls which find no change are not reported, so this may wait for
+    /// several polls. If a poll takes longer than the configured period, the
+    /// next poll is delayed rather than made in a burst.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a poll fails, under the same conditions as
+    /// [`Amber::current_prices()`]. The watch can be used again afterwards,
+    /// resuming on the same schedule.
+    #[inline]
+    pub async fn next(&mut self) -> Result<CurrentInterval> {
+        loop {
+            if let Some(interval) = self.changes.pending.pop_front() {
+                return Ok(interval);
+            }
+            let every = self.every;
+            self.ticker
+                .get_or_insert_with(|| {
+                    let mut ticker = time::interval(every);
+                    ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+                    ticker
+                })
+                .tick()
+                .await;
+
+            let prices = self
+                .client
+                .current_prices()
+                .site_id(&self.site_id)
+                .call()
+                .await?;
+            self.changes.observe_all(&prices);
+        }
+    }
+}
+
+/// Replays archived forecast snapshots as if they were being watched live.
+///
+/// Snapshots recorded by a [`Recorder`](crate::Recorder) are replayed in
+/// chronological order, yielding exactly the intervals a [`PriceWatch`]
+/// polling at the same times would have yielded. This allows automation
+/// rules to be checked against past data before going live.
+///
+/// Replay can be accelerated: with a `speedup` of 60, an hour of snapshots is
+/// replayed in a minute. A `speedup` of zero replays the snapshots without
+/// waiting.
+///
+/// # Example
+///
+/// ```no_run
+/// use amber_api::PriceReplay;
+/// use amber_api::history::ForecastSnapshot;
+///
+/// # async fn example(archive: Vec<ForecastSnapshot>) {
+/// let mut replay = PriceReplay::new(archive, 0);
+/// while let Some(interval) = replay.next().await {
+///     let now = replay.now().expect("replay started");
+///     println!("{now}: {interval}");
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PriceReplay {
+    /// Snapshots not yet replayed, in chronological order.
+    snapshots: VecDeque<ForecastSnapshot>,
+    /// Factor by which the replay is accelerated, or zero to not wait.
+    speedup: u32,
+    /// Recording time of the last replayed snapshot.
+    now: Option<Timestamp>,
+    /// Changes found in the replayed snapshots.
+    changes: Changes,
+}
+
+impl PriceReplay {
+    /// Create a replay of the given snapshots, accelerated by `speedup`.
+    #[must_use]
+    #[inline]
+    pub fn new(snapshots: impl IntoIterator<Item = ForecastSnapshot>, speedup: u32) -> Self {
+        let mut sorted: Vec<ForecastSnapshot> = snapshots.into_iter().collect();
+        sorted.sort_by_key(|snapshot| snapshot.recorded_at);
+        Self {
+            snapshots: sorted.into(),
+            speedup,
+            now: None,
+            changes: Changes::default(),
+        }
+    }
+
+    /// Returns the recording time of the snapshot being replayed, which
+    /// stands in for the current time when evaluating rules.
+    ///
+    /// Returns `None` before the first snapshot is replayed.
+    #[must_use]
+    #[inline]
+    pub fn now(&self) -> Option<Timestamp> {
+        self.now
+    }
+
+    /// Returns the number of snapshots not yet replayed.
+    #[must_use]
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Wait for the next change of the current interval of any channel.
+    ///
+    /// Returns `None` once all snapshots have been replayed.
+    #[inline]
+    pub async fn next(&mut self) -> Option<CurrentInterval> {
+        loop {
+            if let Some(interval) = self.changes.pending.pop_front() {
+                return Some(interval);
+            }
+            let snapshot = self.snapshots.pop_front()?;
+            if let Some(previous) = self.now
+                && self.speedup > 0
+            {
+                let elapsed = Duration::try_from(snapshot.recorded_at.duration_since(previous))
+                    .unwrap_or_default();
+                time::sleep(elapsed.checked_div(self.speedup).unwrap_or_default()).await;
+            }
+            self.now = Some(snapshot.recorded_at);
+            let intervals: Vec<Interval> = snapshot.intervals.iter().collect();
+            self.changes.observe_all(&intervals);
+        }
+    }
+}
+
+/// Changes of the current interval of each channel.
+#[derive(Debug, Clone, Default)]
+struct Changes {
+    /// Last interval seen for each channel.
+    last: Vec<CurrentInterval>,
+    /// Changed intervals not yet yielded.
+    pending: VecDeque<CurrentInterval>,
+}
+
+impl Changes {
+    /// Queue the current intervals which changed since they were last seen.
+    fn observe_all(&mut self, intervals: &[Interval]) {
+        for current in intervals.iter().filter_map(Interval::as_current_interval) {
+            self.observe(current);
+        }
+    }
+
+    /// Queue an interval if it differs from the last one of its channel.
+    fn observe(&mut self, current: &CurrentInterval) {
+        match self
+            .last
+            .iter_mut()
+            .find(|last| last.channel_type == current.channel_type)
+        {
+            Some(last) if last == current => return,
+            Some(last) => last.clone_from(current),
+            None => self.last.push(current.clone()),
+        }
+        self.pending.push_back(current.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        HttpBackend,
+        client::backend::{BoxFuture, HttpRequest, HttpResponse},
+    };
+
+    /// ID of the site whose requests always fail.
+    const FAILING: &str = "01F5A5CRKMZ5BCX9P1S4V990AM";
+    /// ID of the site whose requests succeed.
+    const WORKING: &str = "01G7A5CRKMZ5BCX9P1S4V990BN";
+
+    /// Backend failing every request for the [`FAILING`] site.
+    #[derive(Debug)]
+    struct OneFailingSite;
+
+    impl HttpBackend for OneFailingSite {
+        fn get(&self, request: HttpRequest) -> BoxFuture<'_, crate::Result<HttpResponse>> {
+            let failing = request.url.contains(FAILING);
+            Box::pin(async move {
+                Ok(if failing {
+                    HttpResponse::new(500, "internal error")
+                } else {
+                    HttpResponse::new(200, "[]")
+                })
+            })
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_maximum() {
+        let watcher = Watcher::builder()
+            .client(Amber::default())
+            .sites(vec![])
+            .every(Duration::from_mins(1))
+            .max_backoff(Duration::from_mins(5))
+            .build();
+        let delays: Vec<u64> = (1..=5)
+            .map(|failures| watcher.backoff(failures).as_secs())
+            .collect();
+        assert_eq!(delays, vec![60, 120, 240, 300, 300]);
+    }
+
+    #[tokio::test]
+    async fn failing_site_does_not_stall_others() {
+        let client = Amber::builder()
+            .api_key("psk_client")
+            .backend(Box::new(OneFailingSite))
+            .retry_policy(crate::RetryPolicy::none())
+            .build()
+            .expect("valid client");
+        let failing: SiteId = FAILING.parse().expect("valid ID");
+        let working: SiteId = WORKING.parse().expect("valid ID");
+        let mut handle = Watcher::builder()
+            .client(client)
+            .sites(vec![failing.clone(), working.clone()])
+            .every(Duration::from_millis(5))
+            .max_backoff(Duration::from_mins(1))
+            .build()
+            .start();
+
+        // The working site keeps being polled while the failing one backs off
+        let mut prices = 0_u32;
+        let mut failures = 0_u32;
+        while prices < 3 {
+            match handle.next().await.expect("pollers running") {
+                WatchEvent::Prices { site_id, .. } => {
+                    assert_eq!(site_id, working);
+                    prices = prices.saturating_add(1);
+                }
+                WatchEvent::Failed { site_id, .. } => {
+                    assert_eq!(site_id, failing);
+                    failures = failures.saturating_add(1);
+                }
+            }
+        }
+        assert!(failures >= 1);
+
+        let health = handle.site_health(&failing).expect("watched site");
+        assert!(!health.is_healthy());
+        assert!(health.consecutive_failures >= 1);
+        assert!(health.last_error.is_some());
+        assert!(
+            handle
+                .site_health(&working)
+                .is_some_and(|site| site.is_healthy())
+        );
+        assert!(!handle.is_healthy());
+        handle.stop();
+    }
+
+    /// Parse a current interval of the general channel.
+    fn current(start: &str, per_kwh: f64, estimate: bool) -> CurrentInterval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": "CurrentInterval",
+            "perKwh": per_kwh,
+            "nemTime": start,
+            "startTime": start,
+            "endTime": start,
+            "estimate": estimate
+        }))
+        .into_current()
+        .expect("current interval")
+    }
+
+    #[test]
+    fn price_watch_yields_changes() {
+        let site_id: SiteId = WORKING.parse().expect("valid ID");
+        let mut watch =
+            Amber::default().watch_current_prices(site_id.clone(), Duration::from_secs(30));
+        assert_eq!(watch.site_id(), &site_id);
+
+        let first = current("2021-05-05T02:00:00Z", 20.0, true);
+        let revised = current("2021-05-05T02:00:00Z", 22.0, false);
+        let next = current("2021-05-05T02:30:00Z", 22.0, true);
+        for interval in [&first, &first, &revised, &revised, &next] {
+            watch.changes.observe(interval);
+        }
+        let yielded: Vec<CurrentInterval> = watch.changes.pending.drain(..).collect();
+        assert_eq!(yielded, vec![first, revised, next]);
+    }
+
+    #[tokio::test]
+    async fn replays_snapshots_in_order() {
+        let site_id: SiteId = WORKING.parse().expect("valid ID");
+        let snapshot = |recorded_at: &str, interval: &CurrentInterval| {
+            ForecastSnapshot::new(
+                site_id.clone(),
+                recorded_at.parse().expect("valid timestamp"),
+                &[Interval::CurrentInterval(interval.clone())],
+            )
+        };
+        let first = current("2021-05-05T02:00:00Z", 20.0, true);
+        let next = current("2021-05-05T02:30:00Z", 25.0, true);
+        let mut replay = PriceReplay::new(
+            vec![
+                snapshot("2021-05-05T02:35:00Z", &next),
+                snapshot("2021-05-05T02:05:00Z", &first),
+                snapshot("2021-05-05T02:10:00Z", &first),
+            ],
+            0,
+        );
+        assert_eq!(replay.now(), None);
+        assert_eq!(replay.remaining(), 3);
+
+        assert_eq!(replay.next().await, Some(first));
+        assert_eq!(
+            replay.now(),
+            Some("2021-05-05T02:05:00Z".parse().expect("valid timestamp"))
+        );
+        assert_eq!(replay.next().await, Some(next));
+        assert_eq!(replay.remaining(), 0);
+        assert_eq!(replay.next().await, None);
+    }
+}
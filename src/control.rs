@@ -0,0 +1,377 @@
+//! # Control Plans
+//!
+//! Scheduling decisions made from price data ultimately need to be handed to
+//! something which switches a relay, sets an inverter's export limit or
+//! changes an EVSE's charge current. A [`ControlPlan`] is the format for this
+//! handoff: a chronological list of [`ControlAction`]s, each applying a
+//! [`Command`] to a named device at a given time.
+//!
+//! Plans are serializable, so they can be written to disk, published over MQTT
+//! or served over HTTP. Controllers typically only care about changes, so
+//! plans can be reduced to the actions which change a device's state
+//! ([`ControlPlan::transitions`]), and a freshly computed plan can be compared
+//! with the previously published one to emit only what differs
+//! ([`ControlPlan::diff`]).
+//!
+//! ```
+//! use amber_api::control::{Command, ControlPlan};
+//! use jiff::Timestamp;
+//!
+//! # fn main() -> Result<(), jiff::Error> {
+//! let noon: Timestamp = "2021-05-05T02:00:00Z".parse()?;
+//! let half_past: Timestamp = "2021-05-05T02:30:00Z".parse()?;
+//!
+//! let mut previous = ControlPlan::new();
+//! previous.push(noon, "pool-pump", Command::On);
+//! previous.push(half_past, "pool-pump", Command::Off);
+//!
+//! let mut current = ControlPlan::new();
+//! current.push(noon, "pool-pump", Command::On);
+//! current.push(half_past, "pool-pump", Command::On);
+//!
+//! // Only the changed action needs to be sent
+//! let changes = current.diff(&previous);
+//! assert_eq!(changes.len(), 1);
+//! assert_eq!(changes.command_at("pool-pump", half_past), Some(&Command::On));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Decisions should only be made from recent data. A [`FreshnessGuard`]
+//! enforces the maximum age of each kind of data set in a
+//! [`FreshnessPolicy`], such as never acting on prices more than 10 minutes
+//! old, and reports when data goes stale or recovers as [`FreshnessEvent`]s.
+//!
+//! A [`BatteryPlanner`] schedules a home battery to charge when import prices
+//! are low and discharge when feed-in prices are high, producing a
+//! [`BatterySchedule`] which converts into a plan of power setpoints.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use compact_str::CompactString;
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+
+mod battery;
+mod freshness;
+
+pub use battery::{BatteryAction, BatteryPlanner, BatterySchedule, BatteryStep};
+pub use freshness::{DataKind, FreshnessEvent, FreshnessGuard, FreshnessPolicy, StaleData};
+
+/// Instruction sent to a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "command", content = "value", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Command {
+    /// Switch the device on.
+    On,
+    /// Switch the device off.
+    Off,
+    /// Set the device to the given setpoint.
+    ///
+    /// The unit depends on the device, such as watts for an inverter's export
+    /// limit or amps for an EVSE's charge current.
+    Setpoint(i32),
+}
+
+impl fmt::Display for Command {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::On => write!(f, "on"),
+            Self::Off => write!(f, "off"),
+            Self::Setpoint(value) => write!(f, "setpoint {value}"),
+        }
+    }
+}
+
+/// A command to apply to a device at a given time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ControlAction {
+    /// When the command takes effect.
+    pub at: Timestamp,
+    /// Name of the device the command applies to.
+    pub device: CompactString,
+    /// The command to apply.
+    #[serde(flatten)]
+    pub command: Command,
+}
+
+impl fmt::Display for ControlAction {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.at, self.device, self.command)
+    }
+}
+
+/// Chronological list of actions for one or more devices.
+///
+/// Actions are kept sorted by time, and then by device name. At most one
+/// action is kept per device and time, with later pushes replacing earlier
+/// ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "Vec<ControlAction>", into = "Vec<ControlAction>")]
+pub struct ControlPlan {
+    /// Sorted actions.
+    actions: Vec<ControlAction>,
+}
+
+impl ControlPlan {
+    /// Create an empty plan.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an action to the plan, replacing any existing action for the same
+    /// device and time.
+    #[inline]
+    pub fn push(&mut self, at: Timestamp, device: impl Into<CompactString>, command: Command) {
+        self.insert(ControlAction {
+            at,
+            device: device.into(),
+            command,
+        });
+    }
+
+    /// Find the position of the action for a device and time.
+    fn search(&self, at: Timestamp, device: &str) -> Result<usize, usize> {
+        self.actions
+            .binary_search_by(|probe| (probe.at, probe.device.as_str()).cmp(&(at, device)))
+    }
+
+    /// Insert an action at its sorted position.
+    fn insert(&mut self, action: ControlAction) {
+        match self.search(action.at, &action.device) {
+            Ok(index) => {
+                if let Some(existing) = self.actions.get_mut(index) {
+                    *existing = action;
+                }
+            }
+            Err(index) => self.actions.insert(index, action),
+        }
+    }
+
+    /// Returns the actions in chronological order.
+    #[must_use]
+    #[inline]
+    pub fn actions(&self) -> &[ControlAction] {
+        &self.actions
+    }
+
+    /// Returns an iterator over the actions in chronological order.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, ControlAction> {
+        self.actions.iter()
+    }
+
+    /// Returns the number of actions.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns `true` if the plan contains no actions.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Returns the command in effect for a device at the given time.
+    ///
+    /// This is the command of the device's latest action at or before `at`,
+    /// or `None` if the device has no action by then.
+    #[must_use]
+    #[inline]
+    pub fn command_at(&self, device: &str, at: Timestamp) -> Option<&Command> {
+        self.actions
+            .iter()
+            .take_while(|action| action.at <= at)
+            .filter(|action| action.device == device)
+            .last()
+            .map(|action| &action.command)
+    }
+
+    /// Returns the plan without actions which repeat the device's previous
+    /// command.
+    ///
+    /// For example, a pump planned to be on for six consecutive intervals is
+    /// reduced to a single `On` action followed by the eventual `Off`.
+    #[must_use]
+    #[inline]
+    pub fn transitions(&self) -> Self {
+        let mut current: Vec<(&str, Command)> = Vec::new();
+        let actions = self
+            .actions
+            .iter()
+            .filter(|action| {
+                if let Some(state) = current
+                    .iter_mut()
+                    .find(|(device, _)| action.device.as_str() == *device)
+                {
+                    let changed = state.1 != action.command;
+                    state.1 = action.command;
+                    changed
+                } else {
+                    current.push((action.device.as_str(), action.command));
+                    true
+                }
+            })
+            .cloned()
+            .collect();
+        Self { actions }
+    }
+
+    /// Returns the actions of this plan which are not in the `previous` plan.
+    ///
+    /// This includes actions at new times or for new devices, and actions
+    /// whose command has changed. Actions of the previous plan which no longer
+    /// appear are not included; controllers should treat each plan as
+    /// superseding the last.
+    #[must_use]
+    #[inline]
+    pub fn diff(&self, previous: &Self) -> Self {
+        let actions = self
+            .actions
+            .iter()
+            .filter(|action| {
+                previous
+                    .search(action.at, &action.device)
+                    .ok()
+                    .and_then(|index| previous.actions.get(index))
+                    .is_none_or(|old| old.command != action.command)
+            })
+            .cloned()
+            .collect();
+        Self { actions }
+    }
+}
+
+impl From<Vec<ControlAction>> for ControlPlan {
+    #[inline]
+    fn from(actions: Vec<ControlAction>) -> Self {
+        actions.into_iter().collect()
+    }
+}
+
+impl From<ControlPlan> for Vec<ControlAction> {
+    #[inline]
+    fn from(plan: ControlPlan) -> Self {
+        plan.actions
+    }
+}
+
+impl FromIterator<ControlAction> for ControlPlan {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = ControlAction>>(iter: I) -> Self {
+        let mut plan = Self::new();
+        plan.extend(iter);
+        plan
+    }
+}
+
+impl Extend<ControlAction> for ControlPlan {
+    #[inline]
+    fn extend<I: IntoIterator<Item = ControlAction>>(&mut self, iter: I) {
+        for action in iter {
+            self.insert(action);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ControlPlan {
+    type Item = &'a ControlAction;
+    type IntoIter = core::slice::Iter<'a, ControlAction>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.actions.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Returns the timestamp `minutes` after a fixed reference time.
+    fn at(minutes: i64) -> Timestamp {
+        Timestamp::from_second(1_620_180_000_i64.saturating_add(minutes.saturating_mul(60)))
+            .expect("valid timestamp")
+    }
+
+    #[test]
+    fn push_sorts_and_replaces() {
+        let mut plan = ControlPlan::new();
+        plan.push(at(30), "pump", Command::Off);
+        plan.push(at(0), "pump", Command::On);
+        plan.push(at(0), "battery", Command::Setpoint(5000));
+        plan.push(at(30), "pump", Command::On);
+
+        let devices: Vec<_> = plan
+            .actions()
+            .iter()
+            .map(|action| (action.at, action.device.as_str(), action.command))
+            .collect();
+        assert_eq!(
+            devices,
+            vec![
+                (at(0), "battery", Command::Setpoint(5000)),
+                (at(0), "pump", Command::On),
+                (at(30), "pump", Command::On),
+            ]
+        );
+        assert_eq!(plan.command_at("pump", at(29)), Some(&Command::On));
+        assert_eq!(plan.command_at("pump", at(-1)), None);
+        assert_eq!(plan.command_at("heater", at(60)), None);
+    }
+
+    #[test]
+    fn transitions_and_diff() {
+        let mut previous = ControlPlan::new();
+        for minutes in [0, 5, 10] {
+            previous.push(at(minutes), "pump", Command::On);
+        }
+        previous.push(at(15), "pump", Command::Off);
+
+        let transitions = previous.transitions();
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions.command_at("pump", at(10)), Some(&Command::On));
+        assert_eq!(transitions.command_at("pump", at(15)), Some(&Command::Off));
+
+        let mut current = previous.clone();
+        current.push(at(15), "pump", Command::On);
+        current.push(at(20), "pump", Command::Off);
+        let changes = current.diff(&previous);
+        let summary: Vec<_> = changes.actions().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            summary,
+            vec![
+                "2021-05-05T02:15:00Z pump: on",
+                "2021-05-05T02:20:00Z pump: off",
+            ]
+        );
+        assert!(previous.diff(&previous).is_empty());
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut plan = ControlPlan::new();
+        plan.push(at(0), "evse", Command::Setpoint(16));
+        plan.push(at(30), "evse", Command::Off);
+
+        let json = serde_json::to_string(&plan).expect("serializes");
+        insta::assert_snapshot!(json, @r#"[{"at":"2021-05-05T02:00:00Z","device":"evse","command":"setpoint","value":16},{"at":"2021-05-05T02:30:00Z","device":"evse","command":"off"}]"#);
+        let restored: ControlPlan = serde_json::from_str(&json).expect("deserializes");
+        assert_eq!(restored, plan);
+    }
+}
@@ -0,0 +1,483 @@
+//! Arbitrage scheduling of a home battery.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::time::Duration;
+
+use jiff::Timestamp;
+
+use super::{Command, ControlPlan};
+use crate::{
+    analysis::sqrt,
+    models::{ChannelType, Interval},
+    units::{CentsPerKwh, KilowattHours, Kilowatts},
+};
+
+/// Number of watts (or watt-hours) in a kilowatt (or kilowatt-hour).
+const PER_KILO: f64 = 1000.0;
+
+/// Energy by which the battery's rates may be exceeded, absorbing rounding
+/// errors.
+const TOLERANCE: KilowattHours = KilowattHours::new(1e-9);
+
+/// Plans when a battery should charge from and discharge to the grid.
+///
+/// The planner finds the schedule maximising the value of trading energy with
+/// the grid at the forecast prices: charging costs the general channel's
+/// price, and discharging earns the feed-in channel's price. As in the API,
+/// feed-in prices are negative when exports are paid.
+///
+/// The state of charge is tracked in steps of [`step_wh`](Self::step_wh), and
+/// the best schedule over these steps is found exactly by dynamic
+/// programming. Household consumption and solar generation are not modelled:
+/// the schedule only describes energy traded through the battery.
+///
+/// Energy left in the battery at the end of the forecast is given no value,
+/// so the schedule discharges whatever is worth selling before then.
+///
+/// # Example
+///
+/// ```
+/// use amber_api::control::BatteryPlanner;
+/// use amber_api::models::Interval;
+///
+/// # fn example(forecast: &[Interval]) {
+/// let planner = BatteryPlanner::builder()
+///     .capacity_wh(13_500)
+///     .max_charge_w(5_000)
+///     .max_discharge_w(5_000)
+///     .round_trip_efficiency(0.9)
+///     .initial_wh(4_000)
+///     .build();
+///
+/// let schedule = planner.plan(forecast);
+/// println!("Expected value: {:.0}c", schedule.value);
+/// let plan = schedule.to_control_plan("battery");
+/// for action in plan.transitions().actions() {
+///     println!("{action}");
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, bon::Builder)]
+#[non_exhaustive]
+pub struct BatteryPlanner {
+    /// Usable capacity of the battery (Wh).
+    pub capacity_wh: u32,
+    /// Maximum power drawn from the grid while charging (W).
+    pub max_charge_w: u32,
+    /// Maximum power delivered to the grid while discharging (W).
+    pub max_discharge_w: u32,
+    /// Fraction of the energy charged which can be discharged again.
+    ///
+    /// Losses are split evenly between charging and discharging. Defaults to
+    /// 0.9.
+    #[builder(default = 0.9_f64)]
+    pub round_trip_efficiency: f64,
+    /// Energy stored at the start of the forecast (Wh).
+    ///
+    /// Defaults to 0.
+    #[builder(default)]
+    pub initial_wh: u32,
+    /// Energy which must always remain stored, such as a backup reserve (Wh).
+    ///
+    /// Defaults to 0.
+    #[builder(default)]
+    pub reserve_wh: u32,
+    /// Granularity of the state of charge (Wh).
+    ///
+    /// Smaller steps find slightly better schedules at a higher cost, which
+    /// grows with the square of the number of steps. Defaults to 100 Wh.
+    #[builder(default = 100)]
+    pub step_wh: u32,
+}
+
+/// What a battery does during an interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BatteryAction {
+    /// Charge from the grid.
+    Charge,
+    /// Discharge to the grid.
+    Discharge,
+    /// Neither charge nor discharge.
+    Idle,
+}
+
+/// The planned operation of a battery during an interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct BatteryStep {
+    /// Start of the interval.
+    pub start: Timestamp,
+    /// End of the interval.
+    pub end: Timestamp,
+    /// What the battery does.
+    pub action: BatteryAction,
+    /// Average power flowing into the battery (W), negative when discharging.
+    pub power_w: i32,
+    /// Energy stored at the end of the interval (Wh).
+    pub stored_wh: u32,
+    /// Value of the energy traded (c), negative when charging.
+    pub value: f64,
+}
+
+/// A charge and discharge schedule produced by a [`BatteryPlanner`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct BatterySchedule {
+    /// The planned operation during each interval with both an import and a
+    /// feed-in price, in chronological order.
+    pub steps: Vec<BatteryStep>,
+    /// Total value of the schedule (c): feed-in earnings less the cost of
+    /// charging.
+    pub value: f64,
+}
+
+impl BatteryStep {
+    /// Returns the average power flowing into the battery, negative when
+    /// discharging.
+    #[must_use]
+    #[inline]
+    pub fn power(&self) -> Kilowatts {
+        Kilowatts::new(f64::from(self.power_w)) / PER_KILO
+    }
+
+    /// Returns the energy stored at the end of the interval.
+    #[must_use]
+    #[inline]
+    pub fn stored(&self) -> KilowattHours {
+        kilowatt_hours(self.stored_wh)
+    }
+}
+
+impl BatterySchedule {
+    /// Returns the schedule as a control plan for the named device.
+    ///
+    /// Each interval becomes a [`Command::Setpoint`] of the power flowing into
+    /// the battery (W), negative when discharging.
+    #[must_use]
+    #[inline]
+    pub fn to_control_plan(&self, device: &str) -> ControlPlan {
+        let mut plan = ControlPlan::new();
+        for step in &self.steps {
+            plan.push(step.start, device, Command::Setpoint(step.power_w));
+        }
+        plan
+    }
+}
+
+/// Prices of an interval.
+#[derive(Debug, Clone, Copy, Default)]
+struct Prices {
+    /// End of the interval.
+    end: Option<Timestamp>,
+    /// Price of importing from the grid.
+    import: Option<CentsPerKwh>,
+    /// Price of exporting to the grid, negative when paid.
+    feed_in: Option<CentsPerKwh>,
+}
+
+/// An interval in which the battery can trade.
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    /// Start of the interval.
+    start: Timestamp,
+    /// End of the interval.
+    end: Timestamp,
+    /// Price of importing from the grid.
+    import: CentsPerKwh,
+    /// Price of exporting to the grid, negative when paid.
+    feed_in: CentsPerKwh,
+}
+
+impl BatteryPlanner {
+    /// Returns the usable capacity of the battery.
+    #[must_use]
+    #[inline]
+    pub fn capacity(&self) -> KilowattHours {
+        kilowatt_hours(self.capacity_wh)
+    }
+
+    /// Returns the maximum power drawn from the grid while charging.
+    #[must_use]
+    #[inline]
+    pub fn max_charge(&self) -> Kilowatts {
+        Kilowatts::new(f64::from(self.max_charge_w)) / PER_KILO
+    }
+
+    /// Returns the maximum power delivered to the grid while discharging.
+    #[must_use]
+    #[inline]
+    pub fn max_discharge(&self) -> Kilowatts {
+        Kilowatts::new(f64::from(self.max_discharge_w)) / PER_KILO
+    }
+
+    /// Plan the battery's operation over the current and forecast intervals.
+    ///
+    /// Intervals without both a general and a feed-in price are skipped.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "energy and price calculations")]
+    pub fn plan(&self, intervals: &[Interval]) -> BatterySchedule {
+        let slots = slots(intervals);
+        let step_wh = self.step_wh.max(1);
+        let levels = usize::try_from(self.capacity_wh.checked_div(step_wh).unwrap_or(0))
+            .unwrap_or(0)
+            .saturating_add(1);
+        let reserve = usize::try_from(self.reserve_wh.div_ceil(step_wh))
+            .unwrap_or(levels)
+            .min(levels.saturating_sub(1));
+        let initial = usize::try_from(self.initial_wh.checked_div(step_wh).unwrap_or(0))
+            .unwrap_or(0)
+            .min(levels.saturating_sub(1));
+
+        // Best value of reaching each level, and the level it was reached from
+        let mut best = vec![f64::NEG_INFINITY; levels];
+        if let Some(start) = best.get_mut(initial) {
+            *start = 0.0_f64;
+        }
+        let mut parents: Vec<Vec<usize>> = Vec::with_capacity(slots.len());
+        for slot in &slots {
+            let mut next = vec![f64::NEG_INFINITY; levels];
+            let mut parent = vec![0; levels];
+            for (from, value) in best.iter().enumerate() {
+                if value.is_infinite() {
+                    continue;
+                }
+                for (to, (candidate, source)) in next.iter_mut().zip(&mut parent).enumerate() {
+                    // Below the reserve, the battery may only charge
+                    if to < reserve && to < from {
+                        continue;
+                    }
+                    let Some(trade) = self.trade(slot, from, to) else {
+                        continue;
+                    };
+                    if value + trade > *candidate {
+                        *candidate = value + trade;
+                        *source = from;
+                    }
+                }
+            }
+            best = next;
+            parents.push(parent);
+        }
+
+        let Some((mut level, value)) = best
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, value)| value.is_finite())
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            return BatterySchedule::default();
+        };
+        let mut steps = Vec::with_capacity(slots.len());
+        for (slot, parent) in slots.iter().zip(&parents).rev() {
+            let from = parent.get(level).copied().unwrap_or(level);
+            steps.push(self.step(slot, from, level));
+            level = from;
+        }
+        steps.reverse();
+        BatterySchedule { steps, value }
+    }
+
+    /// Returns the value (c) of moving from one level to another during an
+    /// interval, or `None` if the power required exceeds the battery's rates.
+    #[expect(clippy::float_arithmetic, reason = "price calculations")]
+    fn trade(&self, slot: &Slot, from: usize, to: usize) -> Option<f64> {
+        let duration = Duration::try_from(slot.end.duration_since(slot.start)).unwrap_or_default();
+        let one_way = sqrt(self.round_trip_efficiency.clamp(0.0, 1.0));
+        let stored = level_energy(to.abs_diff(from), self.step_wh.max(1));
+        if to > from {
+            let grid = stored / one_way;
+            (grid <= self.max_charge().energy_over(duration) + TOLERANCE)
+                .then(|| -(grid * slot.import))
+        } else {
+            let grid = stored * one_way;
+            (grid <= self.max_discharge().energy_over(duration) + TOLERANCE)
+                .then(|| -(grid * slot.feed_in))
+        }
+    }
+
+    /// Describe moving from one level to another during an interval.
+    fn step(&self, slot: &Slot, from: usize, to: usize) -> BatteryStep {
+        let step_wh = i64::from(self.step_wh.max(1));
+        let delta_wh = i64::try_from(to)
+            .unwrap_or(0)
+            .saturating_sub(i64::try_from(from).unwrap_or(0))
+            .saturating_mul(step_wh);
+        let seconds = slot.end.duration_since(slot.start).as_secs().max(1);
+        let power_w = delta_wh
+            .saturating_mul(3600)
+            .checked_div(seconds)
+            .unwrap_or(0);
+        BatteryStep {
+            start: slot.start,
+            end: slot.end,
+            action: match to.cmp(&from) {
+                core::cmp::Ordering::Greater => BatteryAction::Charge,
+                core::cmp::Ordering::Less => BatteryAction::Discharge,
+                core::cmp::Ordering::Equal => BatteryAction::Idle,
+            },
+            power_w: i32::try_from(power_w).unwrap_or(if power_w < 0 {
+                i32::MIN
+            } else {
+                i32::MAX
+            }),
+            stored_wh: u32::try_from(
+                u64::try_from(to)
+                    .unwrap_or(0)
+                    .saturating_mul(u64::from(self.step_wh.max(1))),
+            )
+            .unwrap_or(u32::MAX),
+            value: self.trade(slot, from, to).unwrap_or(0.0),
+        }
+    }
+}
+
+/// Pair the general and feed-in prices of each interval.
+fn slots(intervals: &[Interval]) -> Vec<Slot> {
+    let mut prices: BTreeMap<Timestamp, Prices> = BTreeMap::new();
+    for base in intervals
+        .iter()
+        .filter(|interval| !interval.is_actual_interval())
+        .filter_map(Interval::as_base_interval)
+    {
+        let entry = prices.entry(base.start_time).or_default();
+        entry.end = Some(base.end_time);
+        match base.channel_type {
+            ChannelType::General => entry.import = Some(base.price()),
+            ChannelType::FeedIn => entry.feed_in = Some(base.price()),
+            ChannelType::ControlledLoad | ChannelType::Other(_) => {}
+        }
+    }
+    prices
+        .into_iter()
+        .filter_map(|(start, slot)| {
+            Some(Slot {
+                start,
+                end: slot.end.filter(|end| *end > start)?,
+                import: slot.import?,
+                feed_in: slot.feed_in?,
+            })
+        })
+        .collect()
+}
+
+/// Convert an energy in watt-hours.
+fn kilowatt_hours(wh: u32) -> KilowattHours {
+    KilowattHours::new(f64::from(wh)) / PER_KILO
+}
+
+/// Energy of a number of levels.
+#[expect(clippy::float_arithmetic, reason = "energy calculation")]
+fn level_energy(levels: usize, step_wh: u32) -> KilowattHours {
+    KilowattHours::new(u32::try_from(levels).map_or(f64::MAX, f64::from) * f64::from(step_wh))
+        / PER_KILO
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a forecast interval of the given channel starting at the given
+    /// hour (UTC), lasting an hour.
+    fn interval(channel: &str, hour: u8, per_kwh: f64) -> Interval {
+        let start = alloc::format!("2021-05-05T{hour:02}:00:00Z");
+        let end = alloc::format!("2021-05-05T{:02}:00:00Z", hour.saturating_add(1));
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": "ForecastInterval",
+            "duration": 60,
+            "perKwh": per_kwh,
+            "nemTime": end,
+            "startTime": start,
+            "endTime": end,
+            "channelType": channel
+        }))
+    }
+
+    #[test]
+    fn charges_cheap_and_discharges_expensive() {
+        let forecast = vec![
+            interval("general", 0, 10.0),
+            interval("feedIn", 0, -5.0),
+            interval("general", 1, 30.0),
+            interval("feedIn", 1, -8.0),
+            interval("general", 2, 60.0),
+            interval("feedIn", 2, -50.0),
+            // Without a feed-in price, the interval is skipped
+            interval("general", 3, 1.0),
+        ];
+        let planner = BatteryPlanner::builder()
+            .capacity_wh(10_000)
+            .max_charge_w(5_000)
+            .max_discharge_w(5_000)
+            .round_trip_efficiency(1.0)
+            .build();
+        let schedule = planner.plan(&forecast);
+
+        let actions: Vec<(BatteryAction, i32, u32)> = schedule
+            .steps
+            .iter()
+            .map(|step| (step.action, step.power_w, step.stored_wh))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (BatteryAction::Charge, 5_000_i32, 5_000),
+                (BatteryAction::Idle, 0_i32, 5_000),
+                (BatteryAction::Discharge, -5_000_i32, 0),
+            ]
+        );
+        // 5 kWh bought at 10c and sold at 50c
+        assert!((199.9_f64..200.1_f64).contains(&schedule.value));
+        assert_eq!(planner.capacity(), KilowattHours::new(10.0));
+        assert_eq!(planner.max_charge(), Kilowatts::new(5.0));
+        assert_eq!(
+            schedule
+                .steps
+                .first()
+                .map(|step| (step.power(), step.stored())),
+            Some((Kilowatts::new(5.0), KilowattHours::new(5.0)))
+        );
+
+        let plan = schedule.to_control_plan("battery");
+        assert_eq!(plan.len(), 3);
+        let last = schedule.steps.last().expect("a step");
+        assert_eq!(
+            plan.command_at("battery", last.start),
+            Some(&Command::Setpoint(-5_000))
+        );
+    }
+
+    #[test]
+    fn respects_reserve_and_efficiency() {
+        let forecast = vec![interval("general", 0, 10.0), interval("feedIn", 0, -11.0)];
+        // Selling stored energy for 11c is worth it
+        let full = BatteryPlanner::builder()
+            .capacity_wh(2_000)
+            .max_charge_w(5_000)
+            .max_discharge_w(5_000)
+            .round_trip_efficiency(0.81)
+            .initial_wh(2_000)
+            .reserve_wh(1_000)
+            .build();
+        let schedule = full.plan(&forecast);
+        assert_eq!(
+            schedule.steps.first().map(|step| step.stored_wh),
+            Some(1_000)
+        );
+        // 1 kWh stored delivers 0.9 kWh, earning 9.9c
+        assert!((9.89_f64..9.91_f64).contains(&schedule.value));
+
+        // Buying at 10c to sell at 11c loses energy to inefficiency
+        let empty = BatteryPlanner {
+            initial_wh: 0,
+            reserve_wh: 0,
+            ..full
+        };
+        let value = empty.plan(&forecast).value;
+        assert!((-0.01_f64..0.01_f64).contains(&value));
+    }
+}
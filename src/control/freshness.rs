@@ -0,0 +1,304 @@
+//! Maximum data age for control decisions.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{fmt, time::Duration};
+
+use jiff::Timestamp;
+
+/// Kind of data on which a control decision is based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum DataKind {
+    /// Current prices.
+    Prices,
+    /// Price forecasts.
+    Forecast,
+    /// Usage data.
+    Usage,
+    /// Renewables in the grid.
+    Renewables,
+}
+
+impl fmt::Display for DataKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Prices => write!(f, "prices"),
+            Self::Forecast => write!(f, "forecast"),
+            Self::Usage => write!(f, "usage"),
+            Self::Renewables => write!(f, "renewables"),
+        }
+    }
+}
+
+/// Maximum acceptable age of each kind of data.
+///
+/// Kinds without a maximum age are always considered fresh.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FreshnessPolicy {
+    /// Maximum age by kind of data.
+    max_ages: BTreeMap<DataKind, Duration>,
+}
+
+impl FreshnessPolicy {
+    /// Create a policy without any limits.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy suited to acting on prices: prices may be at most 10 minutes
+    /// old and forecasts at most 30 minutes old.
+    #[must_use]
+    #[inline]
+    pub fn recommended() -> Self {
+        Self::new()
+            .with_max_age(DataKind::Prices, Duration::from_mins(10))
+            .with_max_age(DataKind::Forecast, Duration::from_mins(30))
+    }
+
+    /// Set the maximum age of a kind of data, returning the policy.
+    #[must_use]
+    #[inline]
+    pub fn with_max_age(mut self, kind: DataKind, max_age: Duration) -> Self {
+        self.max_ages.insert(kind, max_age);
+        self
+    }
+
+    /// Returns the maximum age of a kind of data, if limited.
+    #[must_use]
+    #[inline]
+    pub fn max_age(&self, kind: DataKind) -> Option<Duration> {
+        self.max_ages.get(&kind).copied()
+    }
+}
+
+/// Data too old to act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct StaleData {
+    /// Kind of data.
+    pub kind: DataKind,
+    /// Age of the data, or `None` if none has been received.
+    pub age: Option<Duration>,
+    /// Maximum age allowed by the policy.
+    pub max_age: Duration,
+}
+
+impl fmt::Display for StaleData {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.age {
+            Some(age) => write!(
+                f,
+                "Stale {} data: {}s old, exceeding the maximum of {}s",
+                self.kind,
+                age.as_secs(),
+                self.max_age.as_secs()
+            ),
+            None => write!(f, "No {} data received", self.kind),
+        }
+    }
+}
+
+impl core::error::Error for StaleData {}
+
+/// Change in the freshness of a kind of data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FreshnessEvent {
+    /// The data exceeded its maximum age, so decisions based on it are
+    /// refused until fresh data is received.
+    Stale(StaleData),
+    /// Fresh data was received after the data had gone stale.
+    Fresh(DataKind),
+}
+
+/// Enforces a [`FreshnessPolicy`] for all control decisions.
+///
+/// Each time data is received, its time is recorded. Before acting, a
+/// decision checks that the data it is based on is fresh enough with
+/// [`check`](Self::check). Periodically calling [`poll`](Self::poll) reports
+/// when data goes stale (and [`record`](Self::record) when it recovers), so
+/// that the outage can be surfaced even when no decision is being made.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use amber_api::control::{DataKind, FreshnessEvent, FreshnessGuard, FreshnessPolicy};
+/// use jiff::Timestamp;
+///
+/// # fn main() -> Result<(), jiff::Error> {
+/// let mut guard = FreshnessGuard::new(FreshnessPolicy::recommended());
+/// let fetched_at: Timestamp = "2021-05-05T02:00:00Z".parse()?;
+/// guard.record(DataKind::Prices, fetched_at);
+///
+/// let soon: Timestamp = "2021-05-05T02:05:00Z".parse()?;
+/// assert!(guard.check(DataKind::Prices, soon).is_ok());
+///
+/// let later: Timestamp = "2021-05-05T02:15:00Z".parse()?;
+/// assert!(guard.check(DataKind::Prices, later).is_err());
+/// for event in guard.poll(later) {
+///     if let FreshnessEvent::Stale(stale) = event {
+///         eprintln!("Pausing automation: {stale}");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FreshnessGuard {
+    /// The maximum ages.
+    policy: FreshnessPolicy,
+    /// Time at which each kind of data was last received.
+    updated: BTreeMap<DataKind, Timestamp>,
+    /// Kinds of data reported stale by the last poll.
+    stale: Vec<DataKind>,
+}
+
+impl FreshnessGuard {
+    /// Create a guard enforcing the given policy.
+    #[must_use]
+    #[inline]
+    pub fn new(policy: FreshnessPolicy) -> Self {
+        Self {
+            policy,
+            updated: BTreeMap::new(),
+            stale: Vec::new(),
+        }
+    }
+
+    /// Returns the policy.
+    #[must_use]
+    #[inline]
+    pub fn policy(&self) -> &FreshnessPolicy {
+        &self.policy
+    }
+
+    /// Record that data was received, as of the given time.
+    ///
+    /// For responses served from a cache, this should be the time the data
+    /// was originally fetched (such as
+    /// [`Response::fetched_at`](crate::Response)), rather than the current
+    /// time. Older times than already recorded are ignored.
+    ///
+    /// Returns a [`FreshnessEvent::Fresh`] event if the data had previously
+    /// been reported stale.
+    #[inline]
+    pub fn record(&mut self, kind: DataKind, at: Timestamp) -> Option<FreshnessEvent> {
+        let latest = self.updated.entry(kind).or_insert(at);
+        *latest = (*latest).max(at);
+        let position = self.stale.iter().position(|stale| *stale == kind)?;
+        self.stale.remove(position);
+        Some(FreshnessEvent::Fresh(kind))
+    }
+
+    /// Returns the age of a kind of data, or `None` if none has been received.
+    #[must_use]
+    #[inline]
+    pub fn age(&self, kind: DataKind, now: Timestamp) -> Option<Duration> {
+        self.updated
+            .get(&kind)
+            .map(|updated| Duration::try_from(now.duration_since(*updated)).unwrap_or_default())
+    }
+
+    /// Check that a kind of data is fresh enough to act on.
+    ///
+    /// Returns the age of the data, if any has been received.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StaleData`] if the data exceeds its maximum age, or has
+    /// never been received.
+    #[inline]
+    pub fn check(&self, kind: DataKind, now: Timestamp) -> Result<Option<Duration>, StaleData> {
+        let age = self.age(kind, now);
+        match self.policy.max_age(kind) {
+            Some(max_age) if age.is_none_or(|elapsed| elapsed > max_age) => {
+                Err(StaleData { kind, age, max_age })
+            }
+            Some(_) | None => Ok(age),
+        }
+    }
+
+    /// Returns the kinds of data which newly exceeded their maximum age since
+    /// the last poll.
+    ///
+    /// Kinds of data which have never been received are not reported, though
+    /// [`check`](Self::check) refuses them.
+    #[inline]
+    pub fn poll(&mut self, now: Timestamp) -> Vec<FreshnessEvent> {
+        let mut events = Vec::new();
+        for &kind in self.updated.keys() {
+            if let Err(stale) = self.check(kind, now)
+                && !self.stale.contains(&kind)
+            {
+                events.push(FreshnessEvent::Stale(stale));
+            }
+        }
+        self.stale
+            .extend(events.iter().filter_map(|event| match event {
+                FreshnessEvent::Stale(stale) => Some(stale.kind),
+                FreshnessEvent::Fresh(_) => None,
+            }));
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+    use alloc::vec;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a timestamp.
+    fn at(timestamp: &str) -> Timestamp {
+        timestamp.parse().expect("valid timestamp")
+    }
+
+    #[test]
+    fn reports_stale_data_once() {
+        let mut guard = FreshnessGuard::new(FreshnessPolicy::recommended());
+        let start = at("2021-05-05T02:00:00Z");
+        assert_eq!(
+            guard.check(DataKind::Prices, start),
+            Err(StaleData {
+                kind: DataKind::Prices,
+                age: None,
+                max_age: Duration::from_mins(10),
+            })
+        );
+        assert_eq!(guard.check(DataKind::Usage, start), Ok(None));
+
+        assert_eq!(guard.record(DataKind::Prices, start), None);
+        assert_eq!(guard.poll(at("2021-05-05T02:10:00Z")), vec![]);
+
+        let late = at("2021-05-05T02:20:00Z");
+        let stale = StaleData {
+            kind: DataKind::Prices,
+            age: Some(Duration::from_mins(20)),
+            max_age: Duration::from_mins(10),
+        };
+        assert_eq!(guard.poll(late), vec![FreshnessEvent::Stale(stale)]);
+        assert_eq!(guard.poll(late), vec![]);
+        insta::assert_snapshot!(
+            stale.to_string(),
+            @"Stale prices data: 1200s old, exceeding the maximum of 600s"
+        );
+
+        assert_eq!(
+            guard.record(DataKind::Prices, late),
+            Some(FreshnessEvent::Fresh(DataKind::Prices))
+        );
+        assert_eq!(
+            guard.check(DataKind::Prices, late),
+            Ok(Some(Duration::ZERO))
+        );
+    }
+}
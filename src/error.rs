@@ -9,12 +9,18 @@
 //!
 //! - Automatic retries are disabled via `retry_on_rate_limit(false)`
 //! - The maximum retry attempts are exhausted
+//! - The server asks the client to wait longer than the retry policy's maximum
+//!   sleep
 //!
 //! See [`AmberError::RateLimitExceeded`] and [`AmberError::RateLimitExhausted`]
 //! for more details.
 
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
 use alloc::string::String;
 
+use crate::{rate_limit::RateLimitInfo, storage::Format};
+
 /// Error types that can occur when using the Amber API client.
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -24,12 +30,41 @@ pub enum AmberError {
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
 
-    /// Rate limit exceeded. Contains the number of seconds to wait.
+    /// The response body could not be decoded.
+    #[cfg(feature = "std")]
+    #[error("Failed to decode response: {0}")]
+    Decode(#[from] serde_json::Error),
+
+    /// Transport error reported by a custom
+    /// [`HttpBackend`](crate::HttpBackend).
+    #[cfg(feature = "std")]
+    #[error("HTTP backend error: {0}")]
+    Backend(Box<dyn core::error::Error + Send + Sync>),
+
+    /// The request timed out.
+    ///
+    /// Timeouts are configured when building the client, and can be overridden
+    /// for individual requests.
+    #[error("Request timed out")]
+    Timeout,
+
+    /// I/O error, such as when writing to a file.
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Rate limit exceeded.
     ///
     /// This error is returned when the API rate limit is hit and automatic
-    /// retries are disabled via `retry_on_rate_limit(false)`.
-    #[error("Rate limit exceeded. Retry after {0} seconds")]
-    RateLimitExceeded(u64),
+    /// retries are disabled via `retry_on_rate_limit(false)`, or when the wait
+    /// would exceed the retry policy's maximum sleep.
+    #[error("Rate limit exceeded. Retry after {retry_after} seconds")]
+    RateLimitExceeded {
+        /// Number of seconds to wait before retrying.
+        retry_after: u64,
+        /// Rate limit metadata reported with the response.
+        rate_limit: RateLimitInfo,
+    },
 
     /// Rate limit exceeded and max retries exhausted.
     ///
@@ -46,12 +81,62 @@ pub enum AmberError {
         attempts: u32,
         /// Number of seconds to wait before retrying.
         retry_after: u64,
+        /// Rate limit metadata reported with the last response.
+        rate_limit: RateLimitInfo,
+    },
+
+    /// No API key is configured.
+    ///
+    /// Set the `AMBER_API_KEY` environment variable, or pass the key when
+    /// building the client.
+    #[error("No API key configured")]
+    MissingApiKey,
+
+    /// The API key was rejected by the API (HTTP 401 or 403).
+    ///
+    /// The key may have been revoked, or may have been entered incorrectly.
+    #[error("API key rejected (HTTP {status}): {body}")]
+    Unauthorized {
+        /// HTTP status code.
+        status: u16,
+        /// Response body (may be truncated or empty if unreadable).
+        body: String,
     },
 
+    /// The request violates a constraint of the API, and was not sent.
+    ///
+    /// See [`limits`](crate::limits) for the constraints checked.
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    /// Data could not be encoded or decoded in a storage format.
+    #[error("Failed to encode or decode {format} data: {message}")]
+    Encoding {
+        /// The format being encoded or decoded.
+        format: Format,
+        /// Description of the error.
+        message: String,
+    },
+
+    /// A configuration file or environment variable is invalid.
+    #[cfg(feature = "config")]
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+
+    /// The platform's credential store could not be accessed.
+    #[cfg(feature = "keyring")]
+    #[error("Keyring error: {0}")]
+    Keyring(keyring::Error),
+
+    /// The named format is not supported, or its feature is not enabled.
+    #[error("Unsupported format: {0}")]
+    UnsupportedFormat(String),
+
     /// Unexpected HTTP status code.
     ///
     /// This error is returned when the API returns a non-2xx status code that
-    /// is not specifically handled (e.g., not a rate limit error).
+    /// is not specifically handled (e.g., not a rate limit or authentication
+    /// error).
     #[error("HTTP {status}: {body}")]
     UnexpectedStatus {
         /// HTTP status code.
@@ -61,5 +146,63 @@ pub enum AmberError {
     },
 }
 
+impl AmberError {
+    /// Returns the rate limit metadata attached to a rate limit error.
+    #[must_use]
+    #[inline]
+    pub fn rate_limit(&self) -> Option<&RateLimitInfo> {
+        if let Self::RateLimitExceeded { rate_limit, .. }
+        | Self::RateLimitExhausted { rate_limit, .. } = self
+        {
+            Some(rate_limit)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the error is due to a missing or rejected API key.
+    #[must_use]
+    #[inline]
+    pub fn is_auth(&self) -> bool {
+        matches!(self, Self::MissingApiKey | Self::Unauthorized { .. })
+    }
+
+    /// Returns `true` if the error is likely to be resolved by retrying the
+    /// request later.
+    ///
+    /// This includes timeouts, connection failures, errors from custom
+    /// backends, and server errors (HTTP 5xx). Rate limit errors are not
+    /// included, as the client already waits and retries these according to
+    /// its retry policy.
+    #[must_use]
+    #[inline]
+    pub fn is_transient(&self) -> bool {
+        #[cfg(feature = "std")]
+        if let Self::Http(err) = self {
+            return err.is_timeout() || err.is_connect();
+        }
+        #[cfg(feature = "std")]
+        if let Self::Backend(_) = self {
+            return true;
+        }
+        matches!(self, Self::Timeout)
+            || matches!(self, Self::UnexpectedStatus { status, .. } if *status >= 500)
+    }
+
+    /// Returns the number of seconds to wait before retrying, if this is a
+    /// rate limit error.
+    #[must_use]
+    #[inline]
+    pub fn retry_after(&self) -> Option<u64> {
+        if let Self::RateLimitExceeded { retry_after, .. }
+        | Self::RateLimitExhausted { retry_after, .. } = self
+        {
+            Some(*retry_after)
+        } else {
+            None
+        }
+    }
+}
+
 /// Result type for Amber API operations.
 pub type Result<T> = core::result::Result<T, AmberError>;
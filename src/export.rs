@@ -0,0 +1,13 @@
+//! # Data Export
+//!
+//! Exporters rendering retrieved data into text formats for use in other
+//! tools. Numeric columns are rendered according to a
+//! [`PrecisionPolicy`](crate::precision::PrecisionPolicy), except in
+//! [`jsonl`], which serialises the models themselves in full precision.
+
+pub mod compact;
+#[cfg(feature = "csv")]
+pub mod csv;
+pub mod grafana;
+pub mod jsonl;
+pub mod tsv;
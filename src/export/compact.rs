@@ -0,0 +1,302 @@
+//! # Compact JSON Payloads
+//!
+//! Renders current prices and renewables as small JSON objects with
+//! single-letter keys, designed for microcontroller consumers (such as ESPHome
+//! or Tasmota devices) which parse payloads received over MQTT or HTTP with
+//! tight buffer limits.
+//!
+//! ## Keys
+//!
+//! Price payloads ([`price`]) have the following keys:
+//!
+//! | Key | Description                                                  |
+//! | --- | ------------------------------------------------------------ |
+//! | `t` | Start of the interval (Unix seconds)                         |
+//! | `p` | Retail price (c/kWh), including GST                          |
+//! | `d` | Price descriptor, as named by the API (e.g. `extremelyLow`)  |
+//! | `r` | Percentage of renewables in the grid                         |
+//! | `s` | Spike status, omitted when there is no spike                 |
+//! | `e` | `1` if the price is an estimate, omitted otherwise           |
+//!
+//! Renewables payloads ([`renewable`]) have the keys `t`, `r` and `d` (the
+//! renewables descriptor).
+//!
+//! The current prices of all channels can be combined into a single payload
+//! with [`current`], which maps each channel to its price payload: `g` for
+//! general, `c` for controlled load and `f` for feed-in. Unrecognised channels
+//! use their name as reported by the API.
+//!
+//! Numbers are rendered according to a [`PrecisionPolicy`]; one decimal place
+//! for prices and whole percentages are usually sufficient for display.
+//!
+//! ## Example
+//!
+//! ```
+//! use amber_api::export::compact;
+//! use amber_api::models::Interval;
+//! use amber_api::precision::PrecisionPolicy;
+//!
+//! # fn example(intervals: &[Interval]) {
+//! let precision = PrecisionPolicy::DEFAULT
+//!     .with_prices(Some(1))
+//!     .with_percentages(Some(0));
+//! // {"g":{"t":1620181501,"p":24.3,"d":"low","r":45,"e":1},"f":{...}}
+//! let payload = compact::current(intervals, &precision).to_string();
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    models::{ChannelType, Interval, Renewable, SpikeStatus},
+    precision::PrecisionPolicy,
+};
+
+/// Render an interval's price as a compact JSON object.
+///
+/// Returns `None` for intervals of an unrecognised type. See the
+/// [module documentation](self) for the keys.
+#[must_use]
+#[inline]
+pub fn price<'a>(
+    interval: &'a Interval,
+    precision: &'a PrecisionPolicy,
+) -> Option<CompactPrice<'a>> {
+    interval.as_base_interval().map(|_| CompactPrice {
+        interval,
+        precision,
+    })
+}
+
+/// Render a renewables interval as a compact JSON object.
+///
+/// Returns `None` for intervals of an unrecognised type. See the
+/// [module documentation](self) for the keys.
+#[must_use]
+#[inline]
+pub fn renewable<'a>(
+    renewable: &'a Renewable,
+    precision: &'a PrecisionPolicy,
+) -> Option<CompactRenewable<'a>> {
+    renewable.as_base_renewable().map(|_| CompactRenewable {
+        renewable,
+        precision,
+    })
+}
+
+/// Render the current price of each channel as a single compact JSON object.
+///
+/// Only [`CurrentInterval`](Interval::CurrentInterval)s are included; if a
+/// channel appears more than once, the first interval is used. See the
+/// [module documentation](self) for the keys.
+#[must_use]
+#[inline]
+pub fn current<'a>(
+    intervals: &'a [Interval],
+    precision: &'a PrecisionPolicy,
+) -> CompactCurrent<'a> {
+    CompactCurrent {
+        intervals,
+        precision,
+    }
+}
+
+/// An interval's price rendered as compact JSON.
+///
+/// Created by [`price`]. Use [`ToString::to_string`] or [`write!`] to obtain
+/// the output.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactPrice<'a> {
+    /// The interval to render, which is known not to be unrecognised.
+    interval: &'a Interval,
+    /// Precision of the numeric values.
+    precision: &'a PrecisionPolicy,
+}
+
+/// A renewables interval rendered as compact JSON.
+///
+/// Created by [`renewable`]. Use [`ToString::to_string`] or [`write!`] to
+/// obtain the output.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactRenewable<'a> {
+    /// The renewables interval to render, which is known not to be
+    /// unrecognised.
+    renewable: &'a Renewable,
+    /// Precision of the numeric values.
+    precision: &'a PrecisionPolicy,
+}
+
+/// The current price of each channel rendered as compact JSON.
+///
+/// Created by [`current`]. Use [`ToString::to_string`] or [`write!`] to obtain
+/// the output.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactCurrent<'a> {
+    /// The intervals to select the current prices from.
+    intervals: &'a [Interval],
+    /// Precision of the numeric values.
+    precision: &'a PrecisionPolicy,
+}
+
+/// Write a value as a JSON string, using the name it has in the API.
+fn write_name(f: &mut fmt::Formatter<'_>, value: &impl Serialize) -> fmt::Result {
+    f.write_str(&serde_json::to_string(value).map_err(|_err| fmt::Error)?)
+}
+
+impl fmt::Display for CompactPrice<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(base) = self.interval.as_base_interval() else {
+            return f.write_str("null");
+        };
+        write!(
+            f,
+            r#"{{"t":{},"p":{},"d":"#,
+            base.start_time.as_second(),
+            self.precision.price(base.per_kwh)
+        )?;
+        write_name(f, &base.descriptor)?;
+        write!(f, r#","r":{}"#, self.precision.percentage(base.renewables))?;
+        if base.spike_status != SpikeStatus::None {
+            f.write_str(r#","s":"#)?;
+            write_name(f, &base.spike_status)?;
+        }
+        if self
+            .interval
+            .as_current_interval()
+            .is_some_and(|current| current.estimate)
+        {
+            f.write_str(r#","e":1"#)?;
+        }
+        f.write_str("}")
+    }
+}
+
+impl fmt::Display for CompactRenewable<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(base) = self.renewable.as_base_renewable() else {
+            return f.write_str("null");
+        };
+        write!(
+            f,
+            r#"{{"t":{},"r":{},"d":"#,
+            base.start_time.as_second(),
+            self.precision.percentage(base.renewables)
+        )?;
+        write_name(f, &base.descriptor)?;
+        f.write_str("}")
+    }
+}
+
+impl fmt::Display for CompactCurrent<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut seen: Vec<&ChannelType> = Vec::new();
+        f.write_str("{")?;
+        for interval in self.intervals {
+            let Some(current) = interval.as_current_interval() else {
+                continue;
+            };
+            let channel_type = &current.base.channel_type;
+            if seen.contains(&channel_type) {
+                continue;
+            }
+            if !seen.is_empty() {
+                f.write_str(",")?;
+            }
+            seen.push(channel_type);
+
+            match channel_type {
+                ChannelType::General => f.write_str(r#""g""#)?,
+                ChannelType::ControlledLoad => f.write_str(r#""c""#)?,
+                ChannelType::FeedIn => f.write_str(r#""f""#)?,
+                ChannelType::Other(_) => write_name(f, channel_type)?,
+            }
+            write!(
+                f,
+                ":{}",
+                CompactPrice {
+                    interval,
+                    precision: self.precision,
+                }
+            )?;
+        }
+        f.write_str("}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// The precision recommended for compact payloads.
+    const PRECISION: PrecisionPolicy = PrecisionPolicy::DEFAULT
+        .with_prices(Some(1))
+        .with_percentages(Some(0));
+
+    /// Parse a current interval for the given channel.
+    fn current_interval(channel_type: &str, descriptor: &str, spike_status: &str) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": "CurrentInterval",
+            "duration": 5,
+            "spotPerKwh": 6.12,
+            "perKwh": 24.33,
+            "startTime": "2021-05-05T02:25:01Z",
+            "renewables": 45.4,
+            "channelType": channel_type,
+            "spikeStatus": spike_status,
+            "descriptor": descriptor,
+            "estimate": true
+        }))
+    }
+
+    #[test]
+    fn price_payload() {
+        let interval = current_interval("general", "extremelyLow", "none");
+        let payload = price(&interval, &PRECISION).expect("known interval");
+        insta::assert_snapshot!(payload.to_string(), @r#"{"t":1620181501,"p":24.3,"d":"extremelyLow","r":45,"e":1}"#);
+
+        let spiking = current_interval("general", "spike", "spike");
+        let spike = price(&spiking, &PRECISION).expect("known interval");
+        insta::assert_snapshot!(spike.to_string(), @r#"{"t":1620181501,"p":24.3,"d":"spike","r":45,"s":"spike","e":1}"#);
+
+        let unknown = Interval::Unknown(serde_json::json!({"type": "FutureInterval"}));
+        assert!(price(&unknown, &PRECISION).is_none());
+    }
+
+    #[test]
+    fn renewable_payload() {
+        let interval: Renewable = serde_json::from_value(serde_json::json!({
+            "type": "CurrentRenewable",
+            "duration": 5_i32,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-05T12:30:00+10:00",
+            "startTime": "2021-05-05T02:25:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45.4_f64,
+            "descriptor": "notGreat"
+        }))
+        .expect("valid renewable");
+        let payload = renewable(&interval, &PRECISION).expect("known renewable");
+        insta::assert_snapshot!(payload.to_string(), @r#"{"t":1620181501,"r":45,"d":"notGreat"}"#);
+    }
+
+    #[test]
+    fn current_payload() {
+        let intervals: Vec<_> = ["general", "feedIn", "general", "solar"]
+            .into_iter()
+            .map(|channel| current_interval(channel, "low", "none"))
+            .collect();
+        let payload = current(&intervals, &PRECISION).to_string();
+        insta::assert_snapshot!(payload, @r#"{"g":{"t":1620181501,"p":24.3,"d":"low","r":45,"e":1},"f":{"t":1620181501,"p":24.3,"d":"low","r":45,"e":1},"solar":{"t":1620181501,"p":24.3,"d":"low","r":45,"e":1}}"#);
+        assert_eq!(current(&[], &PRECISION).to_string(), "{}");
+    }
+}
@@ -0,0 +1,499 @@
+//! # CSV Export
+//!
+//! Renders price intervals, usage and renewables as comma-separated values
+//! (RFC 4180), for import into spreadsheets, databases or data analysis tools.
+//! Requires the `csv` feature.
+//!
+//! ## Columns
+//!
+//! Column names are stable and match the field names of the API in
+//! `snake_case`. Every export starts with the interval columns:
+//!
+//! | Name         | Description                                         |
+//! | ------------ | --------------------------------------------------- |
+//! | `type`       | `actual`, `forecast` or `current` (not for usage)   |
+//! | `date`       | Date the interval belongs to (NEM time)             |
+//! | `start_time` | Start of the interval                               |
+//! | `end_time`   | End of the interval                                 |
+//! | `nem_time`   | NEM time of the interval                            |
+//! | `duration`   | Length of the interval in minutes                   |
+//!
+//! followed by the columns specific to each export:
+//!
+//! - [`intervals`]: `channel_type`, `per_kwh`, `spot_per_kwh`, `renewables`,
+//!   `spike_status`, `descriptor` and `estimate` (only set for the current
+//!   interval).
+//! - [`usage`]: `channel_identifier`, `channel_type`, `kwh`, `cost`,
+//!   `per_kwh`, `spot_per_kwh`, `renewables`, `spike_status`, `descriptor` and
+//!   `quality`.
+//! - [`renewables`]: `renewables` and `descriptor`.
+//!
+//! Interval and usage exports end with the tariff information, either as a
+//! single `tariff_information` column or, when
+//! [flattened](CsvOptions::flatten_tariff), as the `tariff_period`,
+//! `tariff_season`, `tariff_block` and `demand_window` columns. Missing values
+//! are left empty, and data of an unknown type is skipped.
+//!
+//! Numbers are written in full by default, as rounding is best left to the
+//! tool reading the file; a different
+//! [`PrecisionPolicy`](crate::precision::PrecisionPolicy) can be set in the
+//! options.
+//!
+//! ## Example
+//!
+//! ```
+//! use amber_api::export::csv::{self, CsvOptions, TimestampFormat};
+//! use amber_api::models::Usage;
+//!
+//! # fn example(usage: &[Usage]) {
+//! let options = CsvOptions::builder()
+//!     .timestamps(TimestampFormat::NemLocal)
+//!     .flatten_tariff(true)
+//!     .build();
+//! let text = csv::usage(usage, &options).to_string();
+//! # }
+//! ```
+
+use core::fmt;
+
+use jiff::{Timestamp, civil::Date};
+
+use crate::{
+    analysis::NEM_OFFSET,
+    models::{BaseInterval, BaseRenewable, Interval, Renewable, TariffInformation, Usage},
+    precision::PrecisionPolicy,
+};
+
+/// How timestamps are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TimestampFormat {
+    /// RFC 3339 in UTC, such as `2021-05-05T02:00:00Z`.
+    #[default]
+    Utc,
+    /// RFC 3339 in NEM time, such as `2021-05-05T12:00:00+10:00`.
+    Nem,
+    /// NEM time without an offset, such as `2021-05-05 12:00:00`, which most
+    /// spreadsheets recognise as a date and time.
+    NemLocal,
+    /// Seconds since the Unix epoch.
+    UnixSeconds,
+}
+
+/// Options controlling the CSV output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bon::Builder)]
+#[non_exhaustive]
+pub struct CsvOptions {
+    /// Precision used for numeric columns.
+    ///
+    /// Defaults to [`PrecisionPolicy::FULL`].
+    #[builder(default = PrecisionPolicy::FULL)]
+    pub precision: PrecisionPolicy,
+    /// Whether to write a header row.
+    ///
+    /// Defaults to `true`.
+    #[builder(default = true)]
+    pub header: bool,
+    /// How timestamps are written.
+    #[builder(default)]
+    pub timestamps: TimestampFormat,
+    /// Whether to split the tariff information into one column per field,
+    /// rather than a single column.
+    ///
+    /// Defaults to `false`.
+    #[builder(default)]
+    pub flatten_tariff: bool,
+}
+
+impl Default for CsvOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Render price intervals as CSV.
+///
+/// See the [module documentation](self) for the columns.
+#[must_use]
+#[inline]
+pub fn intervals<'a>(intervals: &'a [Interval], options: &'a CsvOptions) -> Csv<'a, Interval> {
+    Csv {
+        rows: intervals,
+        options,
+    }
+}
+
+/// Render usage as CSV.
+///
+/// See the [module documentation](self) for the columns.
+#[must_use]
+#[inline]
+pub fn usage<'a>(usage: &'a [Usage], options: &'a CsvOptions) -> Csv<'a, Usage> {
+    Csv {
+        rows: usage,
+        options,
+    }
+}
+
+/// Render renewables as CSV.
+///
+/// See the [module documentation](self) for the columns.
+#[must_use]
+#[inline]
+pub fn renewables<'a>(renewables: &'a [Renewable], options: &'a CsvOptions) -> Csv<'a, Renewable> {
+    Csv {
+        rows: renewables,
+        options,
+    }
+}
+
+/// Data rendered as CSV.
+///
+/// Created by [`intervals`], [`usage`] and [`renewables`]. Use
+/// [`ToString::to_string`] or [`write!`] to obtain the output.
+#[derive(Debug)]
+pub struct Csv<'a, T> {
+    /// The rows to render.
+    rows: &'a [T],
+    /// Rendering options.
+    options: &'a CsvOptions,
+}
+
+impl<T> Clone for Csv<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Csv<'_, T> {}
+
+/// Writer of the fields of a row, separating and quoting them.
+struct Fields<'f, 'a> {
+    /// The output.
+    f: &'f mut fmt::Formatter<'a>,
+    /// Rendering options.
+    options: &'f CsvOptions,
+    /// Whether no field has been written yet.
+    first: bool,
+}
+
+impl<'f, 'a> Fields<'f, 'a> {
+    /// Start a new row.
+    fn new(f: &'f mut fmt::Formatter<'a>, options: &'f CsvOptions) -> Self {
+        Self {
+            f,
+            options,
+            first: true,
+        }
+    }
+
+    /// Write a field, quoting it if it contains a separator, a quote or a
+    /// line break.
+    fn field(&mut self, value: impl fmt::Display) -> fmt::Result {
+        if !self.first {
+            self.f.write_str(",")?;
+        }
+        self.first = false;
+        let text = alloc::format!("{value}");
+        if text.contains([',', '"', '\n', '\r']) {
+            write!(self.f, "\"{}\"", text.replace('"', "\"\""))
+        } else {
+            self.f.write_str(&text)
+        }
+    }
+
+    /// Write an optional field, left empty if missing.
+    fn optional(&mut self, value: Option<impl fmt::Display>) -> fmt::Result {
+        match value {
+            Some(present) => self.field(present),
+            None => self.field(""),
+        }
+    }
+
+    /// Write a timestamp field.
+    fn timestamp(&mut self, timestamp: Timestamp) -> fmt::Result {
+        match self.options.timestamps {
+            TimestampFormat::Utc => self.field(timestamp),
+            TimestampFormat::Nem => {
+                self.field(format_args!("{}+10:00", NEM_OFFSET.to_datetime(timestamp)))
+            }
+            TimestampFormat::NemLocal => {
+                let datetime = NEM_OFFSET.to_datetime(timestamp);
+                self.field(format_args!("{} {}", datetime.date(), datetime.time()))
+            }
+            TimestampFormat::UnixSeconds => self.field(timestamp.as_second()),
+        }
+    }
+
+    /// Write the tariff information fields.
+    fn tariff(&mut self, tariff: Option<&TariffInformation>) -> fmt::Result {
+        if self.options.flatten_tariff {
+            self.optional(tariff.and_then(|info| info.period.as_ref()))?;
+            self.optional(tariff.and_then(|info| info.season.as_ref()))?;
+            self.optional(tariff.and_then(|info| info.block))?;
+            self.optional(tariff.and_then(|info| info.demand_window))
+        } else {
+            self.optional(tariff)
+        }
+    }
+
+    /// Write the interval fields shared by all exports.
+    fn times(
+        &mut self,
+        date: Date,
+        (start, end, nem): (Timestamp, Timestamp, Timestamp),
+        duration: u32,
+    ) -> fmt::Result {
+        self.field(date)?;
+        self.timestamp(start)?;
+        self.timestamp(end)?;
+        self.timestamp(nem)?;
+        self.field(duration)
+    }
+
+    /// Write the interval fields of a base interval.
+    fn base(&mut self, base: &BaseInterval) -> fmt::Result {
+        self.times(
+            base.date,
+            (base.start_time, base.end_time, base.nem_time),
+            base.duration,
+        )
+    }
+
+    /// Write the interval fields of a base renewable.
+    fn base_renewable(&mut self, base: &BaseRenewable) -> fmt::Result {
+        self.times(
+            base.date,
+            (base.start_time, base.end_time, base.nem_time),
+            base.duration,
+        )
+    }
+}
+
+/// Header of the tariff information column.
+const TARIFF: &str = "tariff_information";
+
+/// Headers of the flattened tariff information columns.
+const TARIFF_FLAT: &str = "tariff_period,tariff_season,tariff_block,demand_window";
+
+/// A row of a CSV export.
+trait Row {
+    /// Header line, excluding any tariff columns.
+    const HEADER: &'static str;
+    /// Whether the rows end with the tariff information.
+    const TARIFF: bool;
+
+    /// Whether the row is of a known type; unknown rows are skipped.
+    fn is_known(&self) -> bool;
+
+    /// Write the fields of the row.
+    fn write(&self, fields: &mut Fields<'_, '_>) -> fmt::Result;
+}
+
+/// Returns the `type` column of an interval.
+fn kind(actual: bool, forecast: bool) -> &'static str {
+    if actual {
+        "actual"
+    } else if forecast {
+        "forecast"
+    } else {
+        "current"
+    }
+}
+
+impl Row for Interval {
+    const HEADER: &'static str = "type,date,start_time,end_time,nem_time,duration,channel_type,per_kwh,spot_per_kwh,renewables,spike_status,descriptor,estimate";
+    const TARIFF: bool = true;
+
+    fn is_known(&self) -> bool {
+        self.as_base_interval().is_some()
+    }
+
+    fn write(&self, fields: &mut Fields<'_, '_>) -> fmt::Result {
+        let Some(base) = self.as_base_interval() else {
+            return Ok(());
+        };
+        let precision = fields.options.precision;
+        fields.field(kind(self.is_actual_interval(), self.is_forecast_interval()))?;
+        fields.base(base)?;
+        fields.field(&base.channel_type)?;
+        fields.field(precision.price(base.per_kwh))?;
+        fields.field(precision.price(base.spot_per_kwh))?;
+        fields.field(precision.percentage(base.renewables))?;
+        fields.field(&base.spike_status)?;
+        fields.field(&base.descriptor)?;
+        fields.optional(self.as_current_interval().map(|current| current.estimate))?;
+        fields.tariff(base.tariff_information.as_ref())
+    }
+}
+
+impl Row for Usage {
+    const HEADER: &'static str = "date,start_time,end_time,nem_time,duration,channel_identifier,channel_type,kwh,cost,per_kwh,spot_per_kwh,renewables,spike_status,descriptor,quality";
+    const TARIFF: bool = true;
+
+    fn is_known(&self) -> bool {
+        true
+    }
+
+    fn write(&self, fields: &mut Fields<'_, '_>) -> fmt::Result {
+        let precision = fields.options.precision;
+        fields.base(&self.base)?;
+        fields.field(&self.channel_identifier)?;
+        fields.field(&self.base.channel_type)?;
+        fields.field(precision.energy(self.kwh))?;
+        fields.field(precision.cost(self.cost))?;
+        fields.field(precision.price(self.base.per_kwh))?;
+        fields.field(precision.price(self.base.spot_per_kwh))?;
+        fields.field(precision.percentage(self.base.renewables))?;
+        fields.field(&self.base.spike_status)?;
+        fields.field(&self.base.descriptor)?;
+        fields.field(&self.quality)?;
+        fields.tariff(self.base.tariff_information.as_ref())
+    }
+}
+
+impl Row for Renewable {
+    const HEADER: &'static str =
+        "type,date,start_time,end_time,nem_time,duration,renewables,descriptor";
+    const TARIFF: bool = false;
+
+    fn is_known(&self) -> bool {
+        self.as_base_renewable().is_some()
+    }
+
+    fn write(&self, fields: &mut Fields<'_, '_>) -> fmt::Result {
+        let Some(base) = self.as_base_renewable() else {
+            return Ok(());
+        };
+        fields.field(kind(
+            matches!(self, Renewable::ActualRenewable(_)),
+            matches!(self, Renewable::ForecastRenewable(_)),
+        ))?;
+        let precision = fields.options.precision;
+        fields.base_renewable(base)?;
+        fields.field(precision.percentage(base.renewables))?;
+        fields.field(&base.descriptor)
+    }
+}
+
+impl<T: Row> fmt::Display for Csv<'_, T> {
+    /// Renders the rows, each terminated by CRLF as per RFC 4180.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.options.header {
+            f.write_str(T::HEADER)?;
+            if T::TARIFF {
+                f.write_str(",")?;
+                f.write_str(if self.options.flatten_tariff {
+                    TARIFF_FLAT
+                } else {
+                    TARIFF
+                })?;
+            }
+            f.write_str("\r\n")?;
+        }
+        for row in self.rows.iter().filter(|row| row.is_known()) {
+            row.write(&mut Fields::new(f, self.options))?;
+            f.write_str("\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec, vec::Vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse an interval of the given type.
+    fn interval(kind: &str, tariff: &serde_json::Value) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": kind,
+            "spotPerKwh": 6.12,
+            "perKwh": 24.3371,
+            "startTime": "2021-05-05T02:00:01Z",
+            "renewables": 45.5,
+            "tariffInformation": tariff,
+            "estimate": true
+        }))
+    }
+
+    #[test]
+    fn intervals_with_tariff() {
+        let data = vec![
+            interval(
+                "CurrentInterval",
+                &serde_json::json!({"period": "peak", "demandWindow": true}),
+            ),
+            interval("ActualInterval", &serde_json::Value::Null),
+            serde_json::from_value(serde_json::json!({"type": "FutureInterval"}))
+                .expect("unknown interval"),
+        ];
+        insta::assert_snapshot!(intervals(&data, &CsvOptions::default()).to_string().replace("\r\n", "\n"), @r#"
+        type,date,start_time,end_time,nem_time,duration,channel_type,per_kwh,spot_per_kwh,renewables,spike_status,descriptor,estimate,tariff_information
+        current,2021-05-05,2021-05-05T02:00:01Z,2021-05-05T02:30:00Z,2021-05-05T02:30:00Z,30,general,24.3371,6.12,45.5,none,low,true,"period:peak, demand window:true"
+        actual,2021-05-05,2021-05-05T02:00:01Z,2021-05-05T02:30:00Z,2021-05-05T02:30:00Z,30,general,24.3371,6.12,45.5,none,low,,
+        "#);
+
+        let flat = CsvOptions::builder()
+            .header(false)
+            .timestamps(TimestampFormat::NemLocal)
+            .flatten_tariff(true)
+            .precision(PrecisionPolicy::DEFAULT)
+            .build();
+        let output = intervals(&data, &flat).to_string();
+        let lines: Vec<_> = output.split("\r\n").collect();
+        assert_eq!(
+            lines.first(),
+            Some(
+                &"current,2021-05-05,2021-05-05 12:00:01,2021-05-05 12:30:00,2021-05-05 12:30:00,30,general,24.34,6.12,45.5,none,low,true,peak,,,true"
+            )
+        );
+    }
+
+    #[test]
+    fn usage_and_renewables() {
+        let record = crate::test_fixtures::usage(serde_json::json!({
+            "spotPerKwh": 6.12_f64,
+            "perKwh": 24.33_f64,
+            "startTime": "2021-05-05T02:00:01Z",
+            "kwh": 1.5_f64,
+            "cost": 36.5_f64
+        }));
+        let options = CsvOptions::builder()
+            .timestamps(TimestampFormat::UnixSeconds)
+            .flatten_tariff(true)
+            .build();
+        let output = usage(&[record], &options).to_string();
+        assert_eq!(
+            output,
+            "date,start_time,end_time,nem_time,duration,channel_identifier,channel_type,kwh,cost,per_kwh,spot_per_kwh,renewables,spike_status,descriptor,quality,tariff_period,tariff_season,tariff_block,demand_window\r\n\
+             2021-05-05,1620180001,1620181800,1620181800,30,E1,general,1.5,36.5,24.33,6.12,45,none,low,billable,,,,\r\n"
+        );
+
+        let renewable: Renewable = serde_json::from_value(serde_json::json!({
+            "type": "ForecastRenewable",
+            "duration": 30_i32,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-05T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45.5_f64,
+            "descriptor": "ok"
+        }))
+        .expect("valid renewable");
+        let nem = CsvOptions::builder()
+            .timestamps(TimestampFormat::Nem)
+            .build();
+        assert_eq!(
+            renewables(&[renewable], &nem).to_string(),
+            "type,date,start_time,end_time,nem_time,duration,renewables,descriptor\r\n\
+             forecast,2021-05-05,2021-05-05T12:00:01+10:00,2021-05-05T12:30:00+10:00,2021-05-05T12:30:00+10:00,30,45.5,ok\r\n"
+        );
+    }
+}
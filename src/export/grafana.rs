@@ -0,0 +1,433 @@
+//! # Grafana JSON Datasource
+//!
+//! Implements the request and response types of Grafana's simple JSON
+//! datasource contract, answering queries from retrieved intervals and usage
+//! so that Grafana can chart Amber data without an intermediate database.
+//!
+//! The crate does not include an HTTP server. Instead, [`GrafanaSource`]
+//! answers the three requests of the contract, and can be mounted in any web
+//! framework by routing:
+//!
+//! - `POST /search` to [`GrafanaSource::search`],
+//! - `POST /query` to [`GrafanaSource::query`], and
+//! - `POST /annotations` to [`GrafanaSource::annotations`],
+//!
+//! deserializing the request body and serializing the result as JSON. The
+//! root path (`GET /`) only needs to return `200 OK`.
+//!
+//! ## Metrics
+//!
+//! Metrics are named `<channel>.<field>`, where the channel is named as in the
+//! API (`general`, `controlledLoad` or `feedIn`):
+//!
+//! - `per_kwh` and `spot_per_kwh` are the retail and spot prices (c/kWh) of
+//!   the price intervals;
+//! - `kwh` and `cost` are the energy and cost of the usage records.
+//!
+//! The `renewables` metric is the percentage of renewables in the grid, taken
+//! from the general channel's price intervals.
+//!
+//! Annotations mark the price intervals with a spike status other than `none`.
+//!
+//! ## Example
+//!
+//! ```
+//! use amber_api::export::grafana::{GrafanaSource, QueryRequest};
+//! use amber_api::models::{Interval, Usage};
+//!
+//! # fn example(intervals: &[Interval], usage: &[Usage], body: &str) -> serde_json::Result<()> {
+//! let source = GrafanaSource::new(intervals, usage);
+//! let request: QueryRequest = serde_json::from_str(body)?;
+//! let response = serde_json::to_string(&source.query(&request))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    analysis::channel_order,
+    models::{BaseInterval, ChannelType, Interval, SpikeStatus, Usage},
+};
+
+/// Fields of price intervals exposed as metrics.
+const PRICE_FIELDS: [&str; 2] = ["per_kwh", "spot_per_kwh"];
+
+/// Fields of usage records exposed as metrics.
+const USAGE_FIELDS: [&str; 2] = ["kwh", "cost"];
+
+/// Name of the grid renewables metric.
+const RENEWABLES: &str = "renewables";
+
+/// Time range of a query, as sent by Grafana.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TimeRange {
+    /// Start of the range (inclusive).
+    pub from: Timestamp,
+    /// End of the range (inclusive).
+    pub to: Timestamp,
+}
+
+impl TimeRange {
+    /// Returns `true` if the timestamp lies within the range.
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, timestamp: Timestamp) -> bool {
+        self.from <= timestamp && timestamp <= self.to
+    }
+}
+
+/// Body of a `/search` request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SearchRequest {
+    /// Text typed into the metric selector, used to filter the metrics.
+    #[serde(default)]
+    pub target: String,
+}
+
+/// A metric requested in a `/query` request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Target {
+    /// Name of the metric.
+    pub target: String,
+    /// Identifier of the query within the panel.
+    #[serde(default)]
+    pub ref_id: String,
+}
+
+/// Body of a `/query` request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct QueryRequest {
+    /// Time range of the panel.
+    pub range: TimeRange,
+    /// Metrics to return.
+    pub targets: Vec<Target>,
+    /// Maximum number of points to return per metric, if limited.
+    ///
+    /// When exceeded, the most recent points are returned.
+    #[serde(default)]
+    pub max_data_points: Option<usize>,
+}
+
+/// A time series returned by a `/query` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TimeSeries {
+    /// Name of the metric.
+    pub target: String,
+    /// Points of the series as `[value, unix_milliseconds]` pairs, in
+    /// chronological order.
+    pub datapoints: Vec<(f64, i64)>,
+}
+
+/// Annotation query configured in Grafana.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct AnnotationQuery {
+    /// Name of the annotation query.
+    #[serde(default)]
+    pub name: String,
+    /// Free-form query text.
+    ///
+    /// If set to a channel name, only spikes of that channel are returned.
+    #[serde(default)]
+    pub query: Option<String>,
+}
+
+/// Body of an `/annotations` request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct AnnotationRequest {
+    /// Time range of the dashboard.
+    pub range: TimeRange,
+    /// The annotation query.
+    #[serde(default)]
+    pub annotation: AnnotationQuery,
+}
+
+/// An annotation returned by an `/annotations` request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Annotation {
+    /// The annotation query this answers, echoed back to Grafana.
+    pub annotation: AnnotationQuery,
+    /// Time of the annotation (Unix milliseconds).
+    pub time: i64,
+    /// Title of the annotation.
+    pub title: String,
+    /// Description of the annotation.
+    pub text: String,
+    /// Tags of the annotation.
+    pub tags: Vec<String>,
+}
+
+/// Answers Grafana datasource requests from retrieved data.
+///
+/// See the [module documentation](self) for the metrics exposed.
+#[derive(Debug, Clone, Copy)]
+pub struct GrafanaSource<'a> {
+    /// Price intervals.
+    intervals: &'a [Interval],
+    /// Usage records.
+    usage: &'a [Usage],
+}
+
+impl<'a> GrafanaSource<'a> {
+    /// Create a source over the given price intervals and usage records.
+    #[must_use]
+    #[inline]
+    pub fn new(intervals: &'a [Interval], usage: &'a [Usage]) -> Self {
+        Self { intervals, usage }
+    }
+
+    /// Answer a `/search` request with the names of the available metrics
+    /// containing the search text.
+    #[must_use]
+    #[inline]
+    pub fn search(&self, request: &SearchRequest) -> Vec<String> {
+        let mut channels: Vec<&ChannelType> = Vec::new();
+        let bases = self
+            .intervals
+            .iter()
+            .filter_map(Interval::as_base_interval)
+            .chain(self.usage.iter().map(|usage| &usage.base));
+        for base in bases {
+            if !channels.contains(&&base.channel_type) {
+                channels.push(&base.channel_type);
+            }
+        }
+        channels.sort_by_key(|channel_type| channel_order(channel_type));
+
+        let mut metrics = Vec::new();
+        if !self.intervals.is_empty() {
+            metrics.push(String::from(RENEWABLES));
+        }
+        for channel_type in channels {
+            let name = channel_name(channel_type);
+            let has_prices = self.prices(channel_type).next().is_some();
+            let has_usage = self
+                .usage
+                .iter()
+                .any(|usage| usage.base.channel_type == *channel_type);
+            let fields = PRICE_FIELDS
+                .iter()
+                .filter(|_| has_prices)
+                .chain(USAGE_FIELDS.iter().filter(|_| has_usage));
+            metrics.extend(fields.map(|field| format!("{name}.{field}")));
+        }
+        metrics.retain(|metric| metric.contains(request.target.as_str()));
+        metrics
+    }
+
+    /// Answer a `/query` request with a time series for each target.
+    ///
+    /// Unknown metrics result in an empty series.
+    #[must_use]
+    #[inline]
+    pub fn query(&self, request: &QueryRequest) -> Vec<TimeSeries> {
+        request
+            .targets
+            .iter()
+            .map(|target| {
+                let mut datapoints = self.series(&target.target, &request.range);
+                datapoints.sort_by_key(|&(_, time)| time);
+                if let Some(limit) = request.max_data_points {
+                    let excess = datapoints.len().saturating_sub(limit);
+                    datapoints.drain(..excess);
+                }
+                TimeSeries {
+                    target: target.target.clone(),
+                    datapoints,
+                }
+            })
+            .collect()
+    }
+
+    /// Answer an `/annotations` request with the price spikes in the range.
+    #[must_use]
+    #[inline]
+    pub fn annotations(&self, request: &AnnotationRequest) -> Vec<Annotation> {
+        let channel_filter = request
+            .annotation
+            .query
+            .as_deref()
+            .filter(|q| !q.is_empty());
+        self.intervals
+            .iter()
+            .filter_map(Interval::as_base_interval)
+            .filter(|base| base.spike_status != SpikeStatus::None)
+            .filter(|base| request.range.contains(base.start_time))
+            .filter_map(|base| {
+                let channel = channel_name(&base.channel_type);
+                if channel_filter.is_some_and(|filter| filter != channel) {
+                    return None;
+                }
+                Some(Annotation {
+                    annotation: request.annotation.clone(),
+                    time: base.start_time.as_millisecond(),
+                    title: format!("Price {}", base.spike_status),
+                    text: format!("{channel}: {} c/kWh", base.per_kwh),
+                    tags: vec![String::from("spike"), String::from(channel)],
+                })
+            })
+            .collect()
+    }
+
+    /// Price intervals of the given channel.
+    fn prices(&self, channel_type: &ChannelType) -> impl Iterator<Item = &'a BaseInterval> {
+        self.intervals
+            .iter()
+            .filter_map(Interval::as_base_interval)
+            .filter(move |base| base.channel_type == *channel_type)
+    }
+
+    /// Points of the named metric within the range, in no particular order.
+    fn series(&self, metric: &str, range: &TimeRange) -> Vec<(f64, i64)> {
+        let point = |value: f64, base: &BaseInterval| (value, base.start_time.as_millisecond());
+        if metric == RENEWABLES {
+            return self
+                .prices(&ChannelType::General)
+                .filter(|base| range.contains(base.start_time))
+                .map(|base| point(base.renewables, base))
+                .collect();
+        }
+
+        let Some((channel, field)) = metric.split_once('.') else {
+            return Vec::new();
+        };
+        let in_channel = |base: &BaseInterval| {
+            channel_name(&base.channel_type) == channel && range.contains(base.start_time)
+        };
+        let prices = self
+            .intervals
+            .iter()
+            .filter_map(Interval::as_base_interval)
+            .filter(|base| in_channel(base));
+        let records = self.usage.iter().filter(|record| in_channel(&record.base));
+        match field {
+            "per_kwh" => prices.map(|base| point(base.per_kwh, base)).collect(),
+            "spot_per_kwh" => prices.map(|base| point(base.spot_per_kwh, base)).collect(),
+            "kwh" => records
+                .map(|record| point(record.kwh, &record.base))
+                .collect(),
+            "cost" => records
+                .map(|record| point(record.cost, &record.base))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Name of a channel type, as used by the API.
+fn channel_name(channel_type: &ChannelType) -> &str {
+    match channel_type {
+        ChannelType::General => "general",
+        ChannelType::ControlledLoad => "controlledLoad",
+        ChannelType::FeedIn => "feedIn",
+        ChannelType::Other(other) => other.as_str(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse an interval for the given channel and start time.
+    fn interval(channel_type: &str, start_time: &str, spike_status: &str) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "perKwh": 24.5,
+            "startTime": start_time,
+            "endTime": start_time,
+            "channelType": channel_type,
+            "spikeStatus": spike_status
+        }))
+    }
+
+    /// A range covering the first hour of 2021-05-05 (UTC).
+    fn range() -> TimeRange {
+        TimeRange {
+            from: "2021-05-05T00:00:00Z".parse().expect("valid timestamp"),
+            to: "2021-05-05T01:00:00Z".parse().expect("valid timestamp"),
+        }
+    }
+
+    #[test]
+    fn search_lists_metrics() {
+        let intervals = vec![
+            interval("feedIn", "2021-05-05T00:00:00Z", "none"),
+            interval("general", "2021-05-05T00:00:00Z", "none"),
+        ];
+        let source = GrafanaSource::new(&intervals, &[]);
+        assert_eq!(
+            source.search(&SearchRequest::default()),
+            vec![
+                "renewables",
+                "general.per_kwh",
+                "general.spot_per_kwh",
+                "feedIn.per_kwh",
+                "feedIn.spot_per_kwh",
+            ]
+        );
+        let request: SearchRequest =
+            serde_json::from_str(r#"{"target": "spot"}"#).expect("valid request");
+        assert_eq!(
+            source.search(&request),
+            vec!["general.spot_per_kwh", "feedIn.spot_per_kwh"]
+        );
+    }
+
+    #[test]
+    fn query_returns_series() {
+        let intervals = vec![
+            interval("general", "2021-05-05T00:30:00Z", "none"),
+            interval("general", "2021-05-05T00:00:00Z", "none"),
+            interval("general", "2021-05-05T02:00:00Z", "none"),
+        ];
+        let source = GrafanaSource::new(&intervals, &[]);
+        let request: QueryRequest = serde_json::from_value(serde_json::json!({
+            "range": {"from": "2021-05-05T00:00:00Z", "to": "2021-05-05T01:00:00Z"},
+            "targets": [
+                {"target": "general.per_kwh", "refId": "A"},
+                {"target": "bogus", "refId": "B"}
+            ],
+            "maxDataPoints": 1_i32
+        }))
+        .expect("valid request");
+        let response = serde_json::to_string(&source.query(&request)).expect("serializes");
+        insta::assert_snapshot!(response, @r#"[{"target":"general.per_kwh","datapoints":[[24.5,1620174600000]]},{"target":"bogus","datapoints":[]}]"#);
+    }
+
+    #[test]
+    fn annotations_mark_spikes() {
+        let intervals = vec![
+            interval("general", "2021-05-05T00:00:00Z", "spike"),
+            interval("feedIn", "2021-05-05T00:00:00Z", "potential"),
+            interval("general", "2021-05-05T00:30:00Z", "none"),
+        ];
+        let source = GrafanaSource::new(&intervals, &[]);
+        let request = AnnotationRequest {
+            range: range(),
+            annotation: AnnotationQuery {
+                name: String::from("Spikes"),
+                query: Some(String::from("general")),
+            },
+        };
+        let annotations = source.annotations(&request);
+        assert_eq!(annotations.len(), 1);
+        let spike = annotations.first().expect("one annotation");
+        assert_eq!(spike.title, "Price spike");
+        assert_eq!(spike.text, "general: 24.5 c/kWh");
+        assert_eq!(spike.tags, vec!["spike", "general"]);
+    }
+}
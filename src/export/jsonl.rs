@@ -0,0 +1,178 @@
+//! # JSON Lines Export
+//!
+//! Renders collections of models as [JSON Lines](https://jsonlines.org): one
+//! JSON object per line, using the same field names as the API. This is the
+//! format expected by tools such as `jq --slurp`, `BigQuery` and most log
+//! pipelines, and each line can be parsed back into its model.
+//!
+//! Unlike the other exporters, values are not rounded according to a
+//! [`PrecisionPolicy`](crate::precision::PrecisionPolicy): each line is the
+//! serialised model in full precision, so that parsing it back yields the
+//! same model. Round values for display with the policy instead.
+//!
+//! [`lines`] renders a slice, and [`pretty`] renders it as a single indented
+//! JSON array for reading. With the `std` feature, [`write_lines`] streams any
+//! iterator of models to an [`io::Write`](std::io::Write), such as a file or
+//! standard output, without collecting them first.
+//!
+//! ```
+//! use amber_api::export::jsonl;
+//! use amber_api::models::Usage;
+//!
+//! # fn example(usage: &[Usage]) {
+//! for line in jsonl::lines(usage).to_string().lines() {
+//!     let record: Usage = serde_json::from_str(line).expect("valid usage");
+//! }
+//! # }
+//! ```
+
+use core::fmt;
+
+use serde::Serialize;
+
+/// Render models as JSON Lines, one object per line.
+#[must_use]
+#[inline]
+pub fn lines<T: Serialize>(items: &[T]) -> Lines<'_, T> {
+    Lines { items }
+}
+
+/// Render models as a single indented JSON array.
+#[must_use]
+#[inline]
+pub fn pretty<T: Serialize>(items: &[T]) -> Pretty<'_, T> {
+    Pretty { items }
+}
+
+/// Write models as JSON Lines, one object per line, as they are produced by
+/// the iterator.
+///
+/// # Errors
+///
+/// Returns an error if a model cannot be serialised or writing fails.
+#[cfg(feature = "std")]
+#[inline]
+pub fn write_lines<W, T>(mut writer: W, items: impl IntoIterator<Item = T>) -> std::io::Result<()>
+where
+    W: std::io::Write,
+    T: Serialize,
+{
+    for item in items {
+        serde_json::to_writer(&mut writer, &item)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+/// Models rendered as JSON Lines.
+///
+/// Created by [`lines`]. Use [`ToString::to_string`] or [`write!`] to obtain
+/// the output.
+#[derive(Debug)]
+pub struct Lines<'a, T> {
+    /// The models to render.
+    items: &'a [T],
+}
+
+/// Models rendered as an indented JSON array.
+///
+/// Created by [`pretty`]. Use [`ToString::to_string`] or [`write!`] to obtain
+/// the output.
+#[derive(Debug)]
+pub struct Pretty<'a, T> {
+    /// The models to render.
+    items: &'a [T],
+}
+
+impl<T> Clone for Lines<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Lines<'_, T> {}
+
+impl<T> Clone for Pretty<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Pretty<'_, T> {}
+
+impl<T: Serialize> fmt::Display for Lines<'_, T> {
+    /// Renders each model followed by a line break.
+    ///
+    /// Fails if a model cannot be serialised.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in self.items {
+            f.write_str(&serde_json::to_string(item).map_err(|_err| fmt::Error)?)?;
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Serialize> fmt::Display for Pretty<'_, T> {
+    /// Renders the models as a JSON array indented by two spaces.
+    ///
+    /// Fails if a model cannot be serialised.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_json::to_string_pretty(self.items).map_err(|_err| fmt::Error)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec, vec::Vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::Renewable;
+
+    /// Parse a renewables interval of the given type.
+    fn renewable(kind: &str) -> Renewable {
+        serde_json::from_value(serde_json::json!({
+            "type": kind,
+            "duration": 30,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-05T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45.5,
+            "descriptor": "ok"
+        }))
+        .expect("valid renewable")
+    }
+
+    #[test]
+    fn round_trips_lines() {
+        let data = vec![renewable("ActualRenewable"), renewable("ForecastRenewable")];
+        let output = lines(&data).to_string();
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.ends_with('\n'));
+        let parsed: Vec<Renewable> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("valid line"))
+            .collect();
+        assert_eq!(parsed, data);
+
+        let array: Vec<Renewable> =
+            serde_json::from_str(&pretty(&data).to_string()).expect("valid array");
+        assert_eq!(array, data);
+        assert_eq!(lines::<Renewable>(&[]).to_string(), "");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn writes_lines() {
+        let mut buffer = Vec::new();
+        write_lines(&mut buffer, [1_u8, 2, 3].iter()).expect("written");
+        assert_eq!(buffer, b"1\n2\n3\n");
+    }
+}
@@ -0,0 +1,660 @@
+//! # Spreadsheet-Friendly TSV Export
+//!
+//! Renders usage and price data as tab-separated values, designed to be pasted
+//! directly into a spreadsheet such as Google Sheets or Excel.
+//!
+//! ## Column Layout
+//!
+//! Usage exports ([`usage`]) have the following columns:
+//!
+//! | Column | Name          | Description                                   |
+//! | ------ | ------------- | --------------------------------------------- |
+//! | A      | `date`        | Date the interval belongs to (NEM time)       |
+//! | B      | `start_time`  | Start of the interval (RFC 3339, UTC)         |
+//! | C      | `end_time`    | End of the interval (RFC 3339, UTC)           |
+//! | D      | `channel`     | Meter channel identifier                      |
+//! | E      | `channel_type`| Channel type                                  |
+//! | F      | `kwh`         | Energy consumed (negative when generated)     |
+//! | G      | `cost`        | Cost of the interval, including GST           |
+//! | H      | `per_kwh`     | Retail price (c/kWh), including GST           |
+//! | I      | `spot_per_kwh`| Spot price (c/kWh), including GST             |
+//! | J      | `quality`     | Data quality (estimated or billable)          |
+//!
+//! Price exports ([`prices`]) have the following columns:
+//!
+//! | Column | Name          | Description                                   |
+//! | ------ | ------------- | --------------------------------------------- |
+//! | A      | `date`        | Date the interval belongs to (NEM time)       |
+//! | B      | `start_time`  | Start of the interval (RFC 3339, UTC)         |
+//! | C      | `end_time`    | End of the interval (RFC 3339, UTC)           |
+//! | D      | `kind`        | `actual`, `forecast` or `current`             |
+//! | E      | `channel_type`| Channel type                                  |
+//! | F      | `per_kwh`     | Retail price (c/kWh), including GST           |
+//! | G      | `spot_per_kwh`| Spot price (c/kWh), including GST             |
+//! | H      | `renewables`  | Percentage of renewables in the grid          |
+//! | I      | `descriptor`  | Price descriptor                              |
+//!
+//! ## Annotations
+//!
+//! Either export can include a final `notes` column listing the
+//! [annotations](crate::annotations) applying to each interval, separated by
+//! `; `, by calling `with_annotations` on the export.
+//!
+//! ## Daily Subtotals
+//!
+//! Optionally, a subtotal row can be inserted after the last row of each day.
+//! For usage, the subtotal contains the total `kwh` and `cost`; for prices, it
+//! contains the average `per_kwh` and `spot_per_kwh`. Subtotals can either be
+//! precomputed values, or spreadsheet formulas (such as `=SUM(F2:F49)`) which
+//! stay correct if individual cells are edited after pasting.
+//!
+//! Rows are written in the order given, and a new day is detected whenever
+//! the date changes between consecutive rows, so data should be sorted
+//! chronologically.
+//!
+//! ## Example
+//!
+//! ```
+//! use amber_api::export::tsv::{self, Subtotals, TsvOptions};
+//! use amber_api::models::Usage;
+//!
+//! # fn example(usage: &[Usage]) {
+//! let options = TsvOptions::builder()
+//!     .subtotals(Subtotals::Formulas)
+//!     .build();
+//! let text = tsv::usage(usage, &options).to_string();
+//! # }
+//! ```
+
+use core::fmt;
+
+use jiff::civil::Date;
+
+use crate::{
+    annotations::AnnotationLog,
+    models::{BaseInterval, Interval, Usage},
+    precision::{Fixed, PrecisionPolicy},
+};
+
+/// How daily subtotal rows are rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Subtotals {
+    /// No subtotal rows are written.
+    #[default]
+    None,
+    /// Subtotal rows contain precomputed values.
+    Values,
+    /// Subtotal rows contain spreadsheet formulas referencing the rows of the
+    /// day.
+    Formulas,
+}
+
+/// Options controlling the TSV output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bon::Builder)]
+#[non_exhaustive]
+pub struct TsvOptions {
+    /// Precision used for numeric columns.
+    #[builder(default)]
+    pub precision: PrecisionPolicy,
+    /// Whether to write a header row.
+    ///
+    /// Defaults to `true`. Formula subtotals assume that the first data row is
+    /// the row immediately after the header (if any) on a fresh sheet.
+    #[builder(default = true)]
+    pub header: bool,
+    /// How daily subtotal rows are rendered.
+    #[builder(default)]
+    pub subtotals: Subtotals,
+}
+
+impl Default for TsvOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Render usage data as TSV.
+///
+/// See the [module documentation](self) for the column layout.
+#[must_use]
+#[inline]
+pub fn usage<'a>(usage: &'a [Usage], options: &'a TsvOptions) -> UsageTsv<'a> {
+    UsageTsv {
+        usage,
+        options,
+        annotations: None,
+    }
+}
+
+/// Render price intervals as TSV.
+///
+/// See the [module documentation](self) for the column layout.
+#[must_use]
+#[inline]
+pub fn prices<'a>(intervals: &'a [Interval], options: &'a TsvOptions) -> PricesTsv<'a> {
+    PricesTsv {
+        intervals,
+        options,
+        annotations: None,
+    }
+}
+
+/// Usage data rendered as TSV.
+///
+/// Created by [`usage`]. Use [`ToString::to_string`] or [`write!`] to obtain
+/// the output.
+#[derive(Debug, Clone, Copy)]
+pub struct UsageTsv<'a> {
+    /// The usage data to render.
+    usage: &'a [Usage],
+    /// Rendering options.
+    options: &'a TsvOptions,
+    /// Annotations to list in a notes column.
+    annotations: Option<&'a AnnotationLog>,
+}
+
+impl<'a> UsageTsv<'a> {
+    /// Add a `notes` column listing the annotations applying to each row.
+    #[must_use]
+    #[inline]
+    pub fn with_annotations(self, annotations: &'a AnnotationLog) -> Self {
+        Self {
+            annotations: Some(annotations),
+            ..self
+        }
+    }
+}
+
+/// Price intervals rendered as TSV.
+///
+/// Created by [`prices`]. Use [`ToString::to_string`] or [`write!`] to obtain
+/// the output.
+#[derive(Debug, Clone, Copy)]
+pub struct PricesTsv<'a> {
+    /// The intervals to render.
+    intervals: &'a [Interval],
+    /// Rendering options.
+    options: &'a TsvOptions,
+    /// Annotations to list in a notes column.
+    annotations: Option<&'a AnnotationLog>,
+}
+
+impl<'a> PricesTsv<'a> {
+    /// Add a `notes` column listing the annotations applying to each row.
+    #[must_use]
+    #[inline]
+    pub fn with_annotations(self, annotations: &'a AnnotationLog) -> Self {
+        Self {
+            annotations: Some(annotations),
+            ..self
+        }
+    }
+}
+
+/// Running state of a day's rows.
+#[derive(Debug, Clone, Copy)]
+struct Day {
+    /// The date of the rows.
+    date: Date,
+    /// Spreadsheet row number of the first row of the day.
+    first_row: usize,
+    /// Number of rows written for the day.
+    rows: u32,
+    /// Running total of the first subtotal column.
+    first: f64,
+    /// Running total of the second subtotal column.
+    second: f64,
+}
+
+impl Day {
+    /// Start a new day at the given spreadsheet row.
+    fn new(date: Date, first_row: usize) -> Self {
+        Self {
+            date,
+            first_row,
+            rows: 0,
+            first: 0.0,
+            second: 0.0,
+        }
+    }
+
+    /// Add a row's values to the running totals.
+    #[expect(clippy::float_arithmetic, reason = "Accumulating subtotals")]
+    fn add(&mut self, first: f64, second: f64) {
+        self.rows = self.rows.saturating_add(1);
+        self.first += first;
+        self.second += second;
+    }
+
+    /// Returns the spreadsheet row number of the last row of the day.
+    fn last_row(&self) -> usize {
+        self.first_row
+            .saturating_add(usize::try_from(self.rows).unwrap_or(usize::MAX))
+            .saturating_sub(1)
+    }
+
+    /// Returns the running totals averaged over the number of rows.
+    #[expect(clippy::float_arithmetic, reason = "Averaging subtotals")]
+    fn averages(&self) -> (f64, f64) {
+        if self.rows == 0 {
+            return (0.0, 0.0);
+        }
+        let rows = f64::from(self.rows);
+        (self.first / rows, self.second / rows)
+    }
+}
+
+/// A row of an export table.
+trait Row {
+    /// Header line of the table.
+    const HEADER: &'static str;
+    /// Number of columns in the table.
+    const COLUMNS: usize;
+    /// Zero-based column indices and spreadsheet letters of the two subtotal
+    /// columns.
+    const SUBTOTALS: [(usize, char); 2];
+    /// Whether subtotals are averages rather than sums.
+    const AVERAGE: bool;
+
+    /// Interval of the row.
+    fn base(&self) -> &BaseInterval;
+
+    /// Date of the row, and the values of the two subtotal columns.
+    fn subtotal_values(&self) -> (Date, f64, f64);
+
+    /// Render a subtotal value for the given zero-based column.
+    fn subtotal(precision: &PrecisionPolicy, column: usize, value: f64) -> Fixed;
+
+    /// Write the row, excluding the trailing newline.
+    fn write(&self, f: &mut fmt::Formatter<'_>, precision: &PrecisionPolicy) -> fmt::Result;
+}
+
+impl Row for &Usage {
+    const HEADER: &'static str = "date\tstart_time\tend_time\tchannel\tchannel_type\tkwh\tcost\tper_kwh\tspot_per_kwh\tquality";
+    const COLUMNS: usize = 10;
+    const SUBTOTALS: [(usize, char); 2] = [(5, 'F'), (6, 'G')];
+    const AVERAGE: bool = false;
+
+    fn base(&self) -> &BaseInterval {
+        &self.base
+    }
+
+    fn subtotal_values(&self) -> (Date, f64, f64) {
+        (self.base.date, self.kwh, self.cost)
+    }
+
+    fn subtotal(precision: &PrecisionPolicy, column: usize, value: f64) -> Fixed {
+        if column == 5 {
+            precision.energy(value)
+        } else {
+            precision.cost(value)
+        }
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, precision: &PrecisionPolicy) -> fmt::Result {
+        write_times(f, &self.base)?;
+        f.write_str("\t")?;
+        field(f, &self.channel_identifier)?;
+        write!(
+            f,
+            "\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.base.channel_type,
+            precision.energy(self.kwh),
+            precision.cost(self.cost),
+            precision.price(self.base.per_kwh),
+            precision.price(self.base.spot_per_kwh),
+            self.quality
+        )
+    }
+}
+
+impl Row for (&Interval, &BaseInterval) {
+    const HEADER: &'static str = "date\tstart_time\tend_time\tkind\tchannel_type\tper_kwh\tspot_per_kwh\trenewables\tdescriptor";
+    const COLUMNS: usize = 9;
+    const SUBTOTALS: [(usize, char); 2] = [(5, 'F'), (6, 'G')];
+    const AVERAGE: bool = true;
+
+    fn base(&self) -> &BaseInterval {
+        self.1
+    }
+
+    fn subtotal_values(&self) -> (Date, f64, f64) {
+        (self.1.date, self.1.per_kwh, self.1.spot_per_kwh)
+    }
+
+    fn subtotal(precision: &PrecisionPolicy, _column: usize, value: f64) -> Fixed {
+        precision.price(value)
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, precision: &PrecisionPolicy) -> fmt::Result {
+        let (interval, base) = *self;
+        let kind = match interval {
+            Interval::ActualInterval(_) => "actual",
+            Interval::ForecastInterval(_) => "forecast",
+            Interval::CurrentInterval(_) => "current",
+            Interval::Unknown(_) => "unknown",
+        };
+        write_times(f, base)?;
+        write!(
+            f,
+            "\t{kind}\t{}\t{}\t{}\t{}\t{}",
+            base.channel_type,
+            precision.price(base.per_kwh),
+            precision.price(base.spot_per_kwh),
+            precision.percentage(base.renewables),
+            base.descriptor
+        )
+    }
+}
+
+/// Write a subtotal row for the given day.
+fn write_subtotal<R: Row>(
+    f: &mut fmt::Formatter<'_>,
+    day: &Day,
+    options: &TsvOptions,
+    notes: bool,
+) -> fmt::Result {
+    let (first, second) = if R::AVERAGE {
+        day.averages()
+    } else {
+        (day.first, day.second)
+    };
+    let function = if R::AVERAGE { "AVERAGE" } else { "SUM" };
+
+    write!(f, "{} subtotal", day.date)?;
+    for column in 1..R::COLUMNS {
+        f.write_str("\t")?;
+        let value = match R::SUBTOTALS {
+            [(index, letter), _] if index == column => (letter, first),
+            [_, (index, letter)] if index == column => (letter, second),
+            _ => continue,
+        };
+        let (letter, total) = value;
+        match options.subtotals {
+            Subtotals::Formulas => write!(
+                f,
+                "={function}({letter}{}:{letter}{})",
+                day.first_row,
+                day.last_row()
+            )?,
+            Subtotals::Values | Subtotals::None => {
+                write!(f, "{}", R::subtotal(&options.precision, column, total))?;
+            }
+        }
+    }
+    if notes {
+        f.write_str("\t")?;
+    }
+    f.write_str("\n")
+}
+
+/// Write the table, inserting subtotal rows when the date changes.
+fn write_rows<R: Row>(
+    f: &mut fmt::Formatter<'_>,
+    options: &TsvOptions,
+    annotations: Option<&AnnotationLog>,
+    rows: impl Iterator<Item = R>,
+) -> fmt::Result {
+    let notes = annotations.is_some();
+    let mut next_row: usize = 1;
+    if options.header {
+        f.write_str(R::HEADER)?;
+        if notes {
+            f.write_str("\tnotes")?;
+        }
+        f.write_str("\n")?;
+        next_row = next_row.saturating_add(1);
+    }
+
+    let mut current: Option<Day> = None;
+    for row in rows {
+        if options.subtotals != Subtotals::None {
+            let (date, first, second) = row.subtotal_values();
+            if let Some(day) = current.filter(|day| day.date != date) {
+                write_subtotal::<R>(f, &day, options, notes)?;
+                next_row = next_row.saturating_add(1);
+                current = None;
+            }
+            current
+                .get_or_insert_with(|| Day::new(date, next_row))
+                .add(first, second);
+        }
+        row.write(f, &options.precision)?;
+        if let Some(log) = annotations {
+            f.write_str("\t")?;
+            write_notes(f, log, row.base())?;
+        }
+        f.write_str("\n")?;
+        next_row = next_row.saturating_add(1);
+    }
+
+    if let Some(day) = current {
+        write_subtotal::<R>(f, &day, options, notes)?;
+    }
+    Ok(())
+}
+
+/// Write a single field, replacing characters which would break the TSV
+/// structure.
+fn field(f: &mut fmt::Formatter<'_>, value: impl fmt::Display) -> fmt::Result {
+    let text = alloc::format!("{value}");
+    if text.contains(['\t', '\n', '\r']) {
+        f.write_str(&text.replace(['\t', '\n', '\r'], " "))
+    } else {
+        f.write_str(&text)
+    }
+}
+
+/// Write the annotations applying to an interval, separated by `; `.
+fn write_notes(
+    f: &mut fmt::Formatter<'_>,
+    annotations: &AnnotationLog,
+    base: &BaseInterval,
+) -> fmt::Result {
+    for (index, annotation) in annotations.for_interval(base).enumerate() {
+        if index > 0 {
+            f.write_str("; ")?;
+        }
+        field(f, annotation)?;
+    }
+    Ok(())
+}
+
+/// Write the date and time columns shared by both exports.
+fn write_times(f: &mut fmt::Formatter<'_>, base: &BaseInterval) -> fmt::Result {
+    write!(f, "{}\t{}\t{}", base.date, base.start_time, base.end_time)
+}
+
+impl fmt::Display for UsageTsv<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rows(f, self.options, self.annotations, self.usage.iter())
+    }
+}
+
+impl fmt::Display for PricesTsv<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rows(
+            f,
+            self.options,
+            self.annotations,
+            self.intervals
+                .iter()
+                .filter_map(|interval| interval.as_base_interval().map(|base| (interval, base))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec, vec::Vec};
+
+    use jiff::Timestamp;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::{ActualInterval, ChannelType, PriceDescriptor, SpikeStatus, UsageQuality};
+
+    /// Create a base interval starting at the given time.
+    fn base(date: Date, start: &str, per_kwh: f64) -> BaseInterval {
+        let start_time: Timestamp = start.parse().expect("valid timestamp");
+        BaseInterval {
+            duration: 30,
+            spot_per_kwh: 10.0,
+            per_kwh,
+            date,
+            nem_time: start_time,
+            start_time,
+            end_time: start_time,
+            renewables: 45.5,
+            channel_type: ChannelType::General,
+            tariff_information: None,
+            spike_status: SpikeStatus::None,
+            descriptor: PriceDescriptor::Neutral,
+        }
+    }
+
+    /// Sample usage spanning two days.
+    fn sample_usage() -> Vec<Usage> {
+        [
+            (
+                Date::constant(2025, 1, 1),
+                "2025-01-01T00:00:00Z",
+                1.5,
+                30.0,
+            ),
+            (
+                Date::constant(2025, 1, 1),
+                "2025-01-01T00:30:00Z",
+                0.5,
+                12.5,
+            ),
+            (
+                Date::constant(2025, 1, 2),
+                "2025-01-02T00:00:00Z",
+                2.0,
+                40.0,
+            ),
+        ]
+        .into_iter()
+        .map(|(date, start, kwh, cost)| Usage {
+            base: base(date, start, 20.0),
+            channel_identifier: "E1".parse().expect("valid channel identifier"),
+            kwh,
+            quality: UsageQuality::Billable,
+            cost,
+        })
+        .collect()
+    }
+
+    #[test]
+    fn usage_without_subtotals() {
+        let usage = sample_usage();
+        insta::assert_snapshot!(tsv_usage(&usage, &TsvOptions::default()), @"
+        date	start_time	end_time	channel	channel_type	kwh	cost	per_kwh	spot_per_kwh	quality
+        2025-01-01	2025-01-01T00:00:00Z	2025-01-01T00:00:00Z	E1	general	1.50	30.00	20.00	10.00	billable
+        2025-01-01	2025-01-01T00:30:00Z	2025-01-01T00:30:00Z	E1	general	0.50	12.50	20.00	10.00	billable
+        2025-01-02	2025-01-02T00:00:00Z	2025-01-02T00:00:00Z	E1	general	2.00	40.00	20.00	10.00	billable
+        ");
+    }
+
+    #[test]
+    fn usage_with_subtotals() {
+        let usage = sample_usage();
+        let values = TsvOptions::builder().subtotals(Subtotals::Values).build();
+        let output = tsv_usage(&usage, &values);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines.get(3),
+            Some(&"2025-01-01 subtotal\t\t\t\t\t2.00\t42.50\t\t\t")
+        );
+        assert_eq!(
+            lines.get(5),
+            Some(&"2025-01-02 subtotal\t\t\t\t\t2.00\t40.00\t\t\t")
+        );
+
+        let formulas = TsvOptions::builder().subtotals(Subtotals::Formulas).build();
+        let formula_output = tsv_usage(&usage, &formulas);
+        let formula_lines: Vec<_> = formula_output.lines().collect();
+        assert_eq!(
+            formula_lines.get(3),
+            Some(&"2025-01-01 subtotal\t\t\t\t\t=SUM(F2:F3)\t=SUM(G2:G3)\t\t\t")
+        );
+        assert_eq!(
+            formula_lines.get(5),
+            Some(&"2025-01-02 subtotal\t\t\t\t\t=SUM(F5:F5)\t=SUM(G5:G5)\t\t\t")
+        );
+    }
+
+    #[test]
+    fn prices_with_average_subtotals() {
+        let date = Date::constant(2025, 1, 1);
+        let intervals = vec![
+            Interval::ActualInterval(ActualInterval {
+                base: base(date, "2025-01-01T00:00:00Z", 20.0),
+            }),
+            Interval::ActualInterval(ActualInterval {
+                base: base(date, "2025-01-01T00:30:00Z", 30.0),
+            }),
+        ];
+        let options = TsvOptions::builder()
+            .header(false)
+            .subtotals(Subtotals::Values)
+            .build();
+        let output = prices(&intervals, &options).to_string();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(
+            lines.first(),
+            Some(
+                &"2025-01-01\t2025-01-01T00:00:00Z\t2025-01-01T00:00:00Z\tactual\tgeneral\t20.00\t10.00\t45.5\tneutral"
+            )
+        );
+        assert_eq!(
+            lines.get(2),
+            Some(&"2025-01-01 subtotal\t\t\t\t\t25.00\t10.00\t\t")
+        );
+    }
+
+    #[test]
+    fn usage_with_annotations() {
+        let data = sample_usage();
+        let log: AnnotationLog = [crate::annotations::Annotation::builder()
+            .start_time("2025-01-01T00:00:00Z".parse().expect("valid timestamp"))
+            .end_time("2025-01-01T00:15:00Z".parse().expect("valid timestamp"))
+            .note("Dryer\trunning")
+            .tags(vec!["laundry".into()])
+            .build()]
+        .into_iter()
+        .collect();
+        let options = TsvOptions::builder().subtotals(Subtotals::Values).build();
+        let output = usage(&data, &options).with_annotations(&log).to_string();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(
+            lines.first().map(|line| line.ends_with("\tquality\tnotes")),
+            Some(true)
+        );
+        assert_eq!(
+            lines
+                .get(1)
+                .map(|line| line.ends_with("\tbillable\tDryer running #laundry")),
+            Some(true)
+        );
+        assert_eq!(
+            lines.get(2).map(|line| line.ends_with("\tbillable\t")),
+            Some(true)
+        );
+        assert_eq!(
+            lines.get(3),
+            Some(&"2025-01-01 subtotal\t\t\t\t\t2.00\t42.50\t\t\t\t")
+        );
+    }
+
+    /// Render usage to a string.
+    fn tsv_usage(data: &[Usage], options: &TsvOptions) -> alloc::string::String {
+        usage(data, options).to_string()
+    }
+}
@@ -0,0 +1,754 @@
+//! # Compact Interval History
+//!
+//! Holding long histories of 5-minute intervals as a `Vec<Interval>` is
+//! wasteful: every entry carries three full timestamps, a date, several enums
+//! and a number of optional structures which are almost always identical
+//! between neighbouring intervals.
+//!
+//! [`IntervalHistory`] stores the same information in a compact byte stream:
+//!
+//! - Timestamps are delta-encoded against the previous entry and written as
+//!   variable-length integers, so a regular 5-minute series costs only a couple
+//!   of bytes per timestamp.
+//! - Channel types, price descriptors, spike statuses and tariff information
+//!   are dictionary-encoded, with each entry only storing a small index into a
+//!   table of the distinct values seen so far.
+//! - Optional range and advanced price information only takes up space when
+//!   present.
+//!
+//! Entries are decoded on demand when iterating, yielding owned [`Interval`]
+//! values which can be used anywhere a regular interval is expected. Analyses
+//! accepting iterators of intervals, such as
+//! [`Heatmap::from_prices`](crate::analysis::heatmap::Heatmap::from_prices),
+//! can be given a history directly.
+//!
+//! ## Precision
+//!
+//! Timestamps are stored with a resolution of one second. The Amber API only
+//! ever returns whole-second timestamps, so this is lossless for data obtained
+//! from the API.
+//!
+//! ## Example
+//!
+//! ```
+//! use amber_api::history::IntervalHistory;
+//! use amber_api::models::Interval;
+//!
+//! # fn load() -> Vec<Interval> { Vec::new() }
+//! let intervals: Vec<Interval> = load();
+//! let history: IntervalHistory = intervals.iter().collect();
+//!
+//! assert_eq!(history.len(), intervals.len());
+//! for interval in &history {
+//!     println!("{interval}");
+//! }
+//! ```
+
+use alloc::vec::Vec;
+use core::{fmt, iter::FusedIterator};
+
+use jiff::{Timestamp, civil::Date};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{SeqAccess, Visitor},
+};
+
+use crate::models::{
+    ActualInterval, AdvancedPrice, BaseInterval, ChannelType, CurrentInterval, ForecastInterval,
+    Interval, PriceDescriptor, Range, SiteId, SpikeStatus, TariffInformation,
+};
+
+/// Flag marking an [`Interval::ForecastInterval`] entry.
+const KIND_FORECAST: u8 = 0b0000_0001;
+/// Flag marking an [`Interval::CurrentInterval`] entry.
+const KIND_CURRENT: u8 = 0b0000_0010;
+/// Flag marking a current interval whose price is an estimate.
+const FLAG_ESTIMATE: u8 = 0b0000_0100;
+/// Flag marking an entry with a price [`Range`].
+const FLAG_RANGE: u8 = 0b0000_1000;
+/// Flag marking an entry with an [`AdvancedPrice`].
+const FLAG_ADVANCED_PRICE: u8 = 0b0001_0000;
+
+/// Memory-efficient, append-only store of [`Interval`]s.
+///
+/// See the [module documentation](self) for details on the encoding.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntervalHistory {
+    /// Encoded entries.
+    bytes: Vec<u8>,
+    /// Number of entries stored.
+    len: usize,
+    /// Start time (in seconds) of the most recently pushed entry.
+    last_start: i64,
+    /// Packed date of the most recently pushed entry.
+    last_date: i64,
+    /// Distinct channel types.
+    channel_types: Dictionary<ChannelType>,
+    /// Distinct price descriptors.
+    descriptors: Dictionary<PriceDescriptor>,
+    /// Distinct spike statuses.
+    spike_statuses: Dictionary<SpikeStatus>,
+    /// Distinct tariff information.
+    tariffs: Dictionary<TariffInformation>,
+}
+
+impl IntervalHistory {
+    /// Create a new, empty history.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of intervals stored.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no intervals are stored.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the approximate number of bytes used by the encoded entries.
+    ///
+    /// This excludes the (small) dictionaries of distinct enum values and can
+    /// be used to compare against `len() * size_of::<Interval>()`.
+    #[must_use]
+    #[inline]
+    pub fn encoded_size(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Shrink the internal buffers to fit the stored data.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.bytes.shrink_to_fit();
+    }
+
+    /// Append an interval to the history.
+    ///
+    /// Intervals of an [unknown type](Interval::Unknown) cannot be encoded and
+    /// are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The interval to store.
+    #[inline]
+    pub fn push(&mut self, interval: &Interval) {
+        let (mut flags, base, range, estimate, advanced_price) = match interval {
+            Interval::ActualInterval(actual) => (0, &actual.base, None, false, None),
+            Interval::ForecastInterval(forecast) => (
+                KIND_FORECAST,
+                &forecast.base,
+                forecast.range.as_ref(),
+                false,
+                forecast.advanced_price.as_ref(),
+            ),
+            Interval::CurrentInterval(current) => (
+                KIND_CURRENT,
+                &current.base,
+                current.range.as_ref(),
+                current.estimate,
+                current.advanced_price.as_ref(),
+            ),
+            Interval::Unknown(_) => return,
+        };
+        if estimate {
+            flags |= FLAG_ESTIMATE;
+        }
+        if range.is_some() {
+            flags |= FLAG_RANGE;
+        }
+        if advanced_price.is_some() {
+            flags |= FLAG_ADVANCED_PRICE;
+        }
+
+        let start = base.start_time.as_second();
+        let end = base.end_time.as_second();
+        let nem = base.nem_time.as_second();
+        let date = pack_date(base.date);
+
+        self.bytes.push(flags);
+        write_signed(&mut self.bytes, start.saturating_sub(self.last_start));
+        write_signed(&mut self.bytes, end.saturating_sub(start));
+        write_signed(&mut self.bytes, nem.saturating_sub(end));
+        write_signed(&mut self.bytes, date.saturating_sub(self.last_date));
+        write_varint(&mut self.bytes, u64::from(base.duration));
+        write_varint(
+            &mut self.bytes,
+            self.channel_types.index(&base.channel_type),
+        );
+        write_varint(&mut self.bytes, self.descriptors.index(&base.descriptor));
+        write_varint(
+            &mut self.bytes,
+            self.spike_statuses.index(&base.spike_status),
+        );
+        write_varint(
+            &mut self.bytes,
+            base.tariff_information
+                .as_ref()
+                .map_or(0, |tariff| self.tariffs.index(tariff).saturating_add(1)),
+        );
+        write_f64(&mut self.bytes, base.spot_per_kwh);
+        write_f64(&mut self.bytes, base.per_kwh);
+        write_f64(&mut self.bytes, base.renewables);
+        if let Some(bounds) = range {
+            write_f64(&mut self.bytes, bounds.min);
+            write_f64(&mut self.bytes, bounds.max);
+        }
+        if let Some(advanced) = advanced_price {
+            write_f64(&mut self.bytes, advanced.low);
+            write_f64(&mut self.bytes, advanced.predicted);
+            write_f64(&mut self.bytes, advanced.high);
+        }
+
+        self.last_start = start;
+        self.last_date = date;
+        self.len = self.len.saturating_add(1);
+    }
+
+    /// Returns an iterator decoding the stored intervals in insertion order.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            history: self,
+            pos: 0,
+            remaining: self.len,
+            last_start: 0,
+            last_date: 0,
+        }
+    }
+}
+
+impl<'a> Extend<&'a Interval> for IntervalHistory {
+    #[inline]
+    fn extend<T: IntoIterator<Item = &'a Interval>>(&mut self, iter: T) {
+        for interval in iter {
+            self.push(interval);
+        }
+    }
+}
+
+impl Extend<Interval> for IntervalHistory {
+    #[inline]
+    fn extend<T: IntoIterator<Item = Interval>>(&mut self, iter: T) {
+        for interval in iter {
+            self.push(&interval);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a Interval> for IntervalHistory {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = &'a Interval>>(iter: T) -> Self {
+        let mut history = Self::new();
+        history.extend(iter);
+        history
+    }
+}
+
+impl FromIterator<Interval> for IntervalHistory {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        let mut history = Self::new();
+        history.extend(iter);
+        history
+    }
+}
+
+impl<'a> IntoIterator for &'a IntervalHistory {
+    type Item = Interval;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Histories are serialised as a plain sequence of intervals, so that the
+/// stored form does not depend on the in-memory encoding.
+impl Serialize for IntervalHistory {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for IntervalHistory {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(HistoryVisitor)
+    }
+}
+
+/// Visitor encoding a sequence of intervals directly into a history.
+struct HistoryVisitor;
+
+impl<'de> Visitor<'de> for HistoryVisitor {
+    type Value = IntervalHistory;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a sequence of intervals")
+    }
+
+    #[inline]
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<IntervalHistory, A::Error> {
+        let mut history = IntervalHistory::new();
+        while let Some(interval) = seq.next_element::<Interval>()? {
+            history.push(&interval);
+        }
+        Ok(history)
+    }
+}
+
+/// The prices and forecasts of a site as seen at a point in time.
+///
+/// Snapshots taken at regular intervals record how the forecast for each
+/// interval evolved, which is needed to assess forecast accuracy or to replay
+/// what was known at a given time. The intervals are held in an
+/// [`IntervalHistory`] to keep long archives compact.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ForecastSnapshot {
+    /// ID of the site the snapshot was taken for.
+    pub site_id: SiteId,
+    /// When the snapshot was recorded.
+    pub recorded_at: Timestamp,
+    /// Current, forecast (and any previous) intervals at the time.
+    pub intervals: IntervalHistory,
+}
+
+impl ForecastSnapshot {
+    /// Create a new snapshot from the intervals returned at `recorded_at`.
+    #[inline]
+    pub fn new<'a, I>(site_id: SiteId, recorded_at: Timestamp, intervals: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Interval>,
+    {
+        Self {
+            site_id,
+            recorded_at,
+            intervals: intervals.into_iter().collect(),
+        }
+    }
+
+    /// Returns the forecast intervals of the snapshot.
+    #[inline]
+    pub fn forecasts(&self) -> impl Iterator<Item = ForecastInterval> + '_ {
+        self.intervals.iter().filter_map(|interval| match interval {
+            Interval::ForecastInterval(forecast) => Some(forecast),
+            Interval::ActualInterval(_) | Interval::CurrentInterval(_) | Interval::Unknown(_) => {
+                None
+            }
+        })
+    }
+}
+
+/// Iterator over the intervals of an [`IntervalHistory`].
+///
+/// Created by [`IntervalHistory::iter`].
+#[derive(Debug, Clone)]
+pub struct Iter<'a> {
+    /// The history being decoded.
+    history: &'a IntervalHistory,
+    /// Position of the next entry in the byte stream.
+    pos: usize,
+    /// Number of entries left to decode.
+    remaining: usize,
+    /// Start time (in seconds) of the previously decoded entry.
+    last_start: i64,
+    /// Packed date of the previously decoded entry.
+    last_date: i64,
+}
+
+impl Iter<'_> {
+    /// Decode the entry at the current position.
+    ///
+    /// Returns `None` if the byte stream is malformed, which cannot happen for
+    /// streams produced by [`IntervalHistory::push`].
+    fn decode(&mut self) -> Option<Interval> {
+        let history = self.history;
+        let mut reader = Reader {
+            bytes: &history.bytes,
+            pos: self.pos,
+        };
+
+        let flags = reader.byte()?;
+        let start = self.last_start.checked_add(reader.signed()?)?;
+        let end = start.checked_add(reader.signed()?)?;
+        let nem = end.checked_add(reader.signed()?)?;
+        let date = self.last_date.checked_add(reader.signed()?)?;
+        let duration = u32::try_from(reader.varint()?).ok()?;
+        let channel_type = history.channel_types.get(reader.varint()?)?.clone();
+        let descriptor = history.descriptors.get(reader.varint()?)?.clone();
+        let spike_status = history.spike_statuses.get(reader.varint()?)?.clone();
+        let tariff_information = match reader.varint()? {
+            0 => None,
+            index => Some(history.tariffs.get(index.checked_sub(1)?)?.clone()),
+        };
+        let spot_per_kwh = reader.f64()?;
+        let per_kwh = reader.f64()?;
+        let renewables = reader.f64()?;
+        let range = if flags & FLAG_RANGE == 0 {
+            None
+        } else {
+            Some(Range {
+                min: reader.f64()?,
+                max: reader.f64()?,
+            })
+        };
+        let advanced_price = if flags & FLAG_ADVANCED_PRICE == 0 {
+            None
+        } else {
+            Some(AdvancedPrice {
+                low: reader.f64()?,
+                predicted: reader.f64()?,
+                high: reader.f64()?,
+            })
+        };
+
+        let base = BaseInterval {
+            duration,
+            spot_per_kwh,
+            per_kwh,
+            date: unpack_date(date)?,
+            nem_time: Timestamp::from_second(nem).ok()?,
+            start_time: Timestamp::from_second(start).ok()?,
+            end_time: Timestamp::from_second(end).ok()?,
+            renewables,
+            channel_type,
+            tariff_information,
+            spike_status,
+            descriptor,
+        };
+
+        let interval = if flags & KIND_CURRENT != 0 {
+            Interval::CurrentInterval(CurrentInterval {
+                base,
+                range,
+                estimate: flags & FLAG_ESTIMATE != 0,
+                advanced_price,
+            })
+        } else if flags & KIND_FORECAST != 0 {
+            Interval::ForecastInterval(ForecastInterval {
+                base,
+                range,
+                advanced_price,
+            })
+        } else {
+            Interval::ActualInterval(ActualInterval { base })
+        };
+
+        self.pos = reader.pos;
+        self.last_start = start;
+        self.last_date = date;
+        Some(interval)
+    }
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Interval;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let interval = self.decode();
+        self.remaining = if interval.is_some() {
+            self.remaining.saturating_sub(1)
+        } else {
+            0
+        };
+        interval
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+/// Table of distinct values, referenced by index.
+///
+/// The number of distinct values of each dictionary-encoded field is tiny, so
+/// a linear scan is faster than hashing.
+#[derive(Debug, Clone, PartialEq)]
+struct Dictionary<T> {
+    /// The distinct values, in order of first appearance.
+    values: Vec<T>,
+}
+
+impl<T> Default for Dictionary<T> {
+    #[inline]
+    fn default() -> Self {
+        Self { values: Vec::new() }
+    }
+}
+
+impl<T: Clone + PartialEq> Dictionary<T> {
+    /// Returns the index of `value`, inserting it if not already present.
+    fn index(&mut self, value: &T) -> u64 {
+        let position = self
+            .values
+            .iter()
+            .position(|existing| existing == value)
+            .unwrap_or_else(|| {
+                self.values.push(value.clone());
+                self.values.len().saturating_sub(1)
+            });
+        u64::try_from(position).unwrap_or(u64::MAX)
+    }
+
+    /// Returns the value at `index`.
+    fn get(&self, index: u64) -> Option<&T> {
+        self.values.get(usize::try_from(index).ok()?)
+    }
+}
+
+/// Pack a date into a single integer which preserves ordering.
+fn pack_date(date: Date) -> i64 {
+    i64::from(date.year())
+        .saturating_mul(512)
+        .saturating_add(i64::from(date.month()).saturating_mul(32))
+        .saturating_add(i64::from(date.day()))
+}
+
+/// Inverse of [`pack_date`].
+fn unpack_date(packed: i64) -> Option<Date> {
+    let year = i16::try_from(packed >> 9_i32).ok()?;
+    let month = i8::try_from((packed >> 5_i32) & 0x0F).ok()?;
+    let day = i8::try_from(packed & 0x1F).ok()?;
+    Date::new(year, month, day).ok()
+}
+
+/// Write an unsigned LEB128 variable-length integer.
+#[expect(clippy::little_endian_bytes, reason = "Taking the lowest byte")]
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        let [low, ..] = (value & 0x7F).to_le_bytes();
+        bytes.push(low | 0x80);
+        value >>= 7_i32;
+    }
+    let [low, ..] = value.to_le_bytes();
+    bytes.push(low);
+}
+
+/// Write a zigzag-encoded signed variable-length integer.
+fn write_signed(bytes: &mut Vec<u8>, value: i64) {
+    write_varint(
+        bytes,
+        (value.wrapping_shl(1) ^ (value >> 63)).cast_unsigned(),
+    );
+}
+
+/// Write a little-endian `f64`.
+#[expect(clippy::little_endian_bytes, reason = "The encoding is little-endian")]
+fn write_f64(bytes: &mut Vec<u8>, value: f64) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Cursor over an encoded byte stream.
+struct Reader<'a> {
+    /// The encoded bytes.
+    bytes: &'a [u8],
+    /// Current position within `bytes`.
+    pos: usize,
+}
+
+impl Reader<'_> {
+    /// Read a single byte.
+    fn byte(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.pos)?;
+        self.pos = self.pos.checked_add(1)?;
+        Some(byte)
+    }
+
+    /// Read an unsigned LEB128 variable-length integer.
+    fn varint(&mut self) -> Option<u64> {
+        let mut value: u64 = 0;
+        let mut shift: u32 = 0;
+        loop {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7F).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+            shift = shift.checked_add(7)?;
+        }
+    }
+
+    /// Read a zigzag-encoded signed variable-length integer.
+    fn signed(&mut self) -> Option<i64> {
+        let value = self.varint()?;
+        Some((value >> 1).cast_signed() ^ (value & 1).cast_signed().wrapping_neg())
+    }
+
+    /// Read a little-endian `f64`.
+    #[expect(clippy::little_endian_bytes, reason = "The encoding is little-endian")]
+    fn f64(&mut self) -> Option<f64> {
+        let end = self.pos.checked_add(8)?;
+        let bytes = self.bytes.get(self.pos..end)?.try_into().ok()?;
+        self.pos = end;
+        Some(f64::from_le_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn base(start: &str, end: &str, descriptor: PriceDescriptor) -> BaseInterval {
+        BaseInterval {
+            duration: 5,
+            spot_per_kwh: 6.12,
+            per_kwh: 24.33,
+            date: Date::constant(2021, 5, 5),
+            nem_time: end.parse().expect("valid timestamp"),
+            start_time: start.parse().expect("valid timestamp"),
+            end_time: end.parse().expect("valid timestamp"),
+            renewables: 45.5,
+            channel_type: ChannelType::General,
+            tariff_information: None,
+            spike_status: SpikeStatus::None,
+            descriptor,
+        }
+    }
+
+    fn sample() -> Vec<Interval> {
+        let mut feed_in = base(
+            "2021-05-05T02:05:01Z",
+            "2021-05-05T02:10:00Z",
+            PriceDescriptor::Low,
+        );
+        feed_in.channel_type = ChannelType::FeedIn;
+        feed_in.per_kwh = -8.5_f64;
+        feed_in.tariff_information = Some(TariffInformation {
+            period: None,
+            season: None,
+            block: Some(2),
+            demand_window: Some(true),
+        });
+
+        let mut next_day = base(
+            "2021-05-05T14:00:01Z",
+            "2021-05-05T14:05:00Z",
+            PriceDescriptor::Spike,
+        );
+        next_day.date = Date::constant(2021, 5, 6);
+        next_day.spike_status = SpikeStatus::Spike;
+
+        vec![
+            Interval::ActualInterval(ActualInterval {
+                base: base(
+                    "2021-05-05T02:00:01Z",
+                    "2021-05-05T02:05:00Z",
+                    PriceDescriptor::Neutral,
+                ),
+            }),
+            Interval::CurrentInterval(CurrentInterval {
+                base: base(
+                    "2021-05-05T02:05:01Z",
+                    "2021-05-05T02:10:00Z",
+                    PriceDescriptor::Neutral,
+                ),
+                range: Some(Range { min: 1.0, max: 2.0 }),
+                estimate: true,
+                advanced_price: None,
+            }),
+            Interval::CurrentInterval(CurrentInterval {
+                base: feed_in,
+                range: None,
+                estimate: false,
+                advanced_price: Some(AdvancedPrice {
+                    low: 1.0,
+                    predicted: 2.0,
+                    high: 3.0,
+                }),
+            }),
+            Interval::ForecastInterval(ForecastInterval {
+                base: next_day,
+                range: None,
+                advanced_price: None,
+            }),
+            // Going back in time must also round-trip.
+            Interval::ActualInterval(ActualInterval {
+                base: base(
+                    "2021-05-04T02:00:01Z",
+                    "2021-05-04T02:05:00Z",
+                    PriceDescriptor::VeryLow,
+                ),
+            }),
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        let intervals = sample();
+        let history: IntervalHistory = intervals.iter().collect();
+
+        assert_eq!(history.len(), intervals.len());
+        assert_eq!(history.iter().len(), intervals.len());
+        assert_eq!(history.iter().collect::<Vec<_>>(), intervals);
+    }
+
+    #[test]
+    fn empty() {
+        let history = IntervalHistory::new();
+        assert!(history.is_empty());
+        assert_eq!(history.iter().next(), None);
+    }
+
+    #[test]
+    fn smaller_than_vec() {
+        let intervals: Vec<Interval> = sample().into_iter().cycle().take(1000).collect();
+        let history: IntervalHistory = intervals.iter().collect();
+
+        assert!(history.encoded_size() * 3 < intervals.len() * size_of::<Interval>());
+    }
+
+    #[test]
+    fn snapshot_forecasts() {
+        let recorded_at: Timestamp = "2021-05-05T02:06:00Z".parse().expect("valid timestamp");
+        let site_id = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid site ID");
+        let snapshot = ForecastSnapshot::new(site_id, recorded_at, &sample());
+
+        assert_eq!(snapshot.intervals.len(), 5);
+        let forecasts: Vec<_> = snapshot.forecasts().collect();
+        assert_eq!(forecasts.len(), 1);
+        assert_eq!(
+            forecasts.first().map(|forecast| forecast.base.date),
+            Some(Date::constant(2021, 5, 6))
+        );
+    }
+
+    #[test]
+    fn varint_round_trip() {
+        for value in [0, 1, -1, 300, -300, i64::MAX, i64::MIN] {
+            let mut bytes = Vec::new();
+            write_signed(&mut bytes, value);
+            let mut reader = Reader {
+                bytes: &bytes,
+                pos: 0,
+            };
+            assert_eq!(reader.signed(), Some(value));
+            assert_eq!(reader.pos, bytes.len());
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! # Home Assistant Payloads
+//!
+//! Converts intervals into the states and attributes of the sensors created by
+//! Home Assistant's Amber Electric integration, so that custom bridges (such
+//! as those publishing over MQTT or through the REST API) produce entities
+//! that behave like the official ones in dashboards, automations and
+//! templates.
+//!
+//! The integration follows these conventions, which the helpers reproduce:
+//!
+//! - Prices are in dollars per kWh, rounded to the cent. Feed-in prices are
+//!   negated so that a positive price means exports are paid.
+//! - Times are ISO 8601 with an explicit offset: `+00:00` for the start and
+//!   end times, and `+10:00` for the NEM time (the `nem_date` attribute).
+//! - Descriptors are in `snake_case` (such as `extremely_low`), and spike
+//!   statuses and channel types are as named by the API.
+//! - Renewables are rounded to a whole percentage.
+//!
+//! ```
+//! use amber_api::homeassistant;
+//! use amber_api::models::Interval;
+//!
+//! # fn example(intervals: &[Interval]) {
+//! for current in intervals.iter().filter_map(Interval::as_current_interval) {
+//!     let state = homeassistant::price_state(current);
+//!     let attributes = homeassistant::price_attributes(current);
+//!     println!("{state} {}", serde_json::Value::Object(attributes));
+//! }
+//! # }
+//! ```
+
+use alloc::{format, string::String, vec::Vec};
+
+use jiff::{Timestamp, tz::Offset};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    analysis::{NEM_OFFSET, round},
+    models::{
+        AdvancedPrice, BaseInterval, BaseRenewable, ChannelType, CurrentInterval, Interval,
+        PriceDescriptor, Range, Renewable, SpikeStatus,
+    },
+};
+
+/// Attributes of a Home Assistant entity.
+pub type Attributes = Map<String, Value>;
+
+/// Convert cents to dollars, rounded to the cent.
+#[expect(clippy::float_arithmetic, reason = "Converting cents to dollars")]
+fn dollars(cents: f64) -> f64 {
+    round(cents) / 100.0
+}
+
+/// Convert a price to dollars, negating feed-in prices so that a positive
+/// price means exports are paid.
+#[expect(clippy::float_arithmetic, reason = "Negating feed-in prices")]
+fn price(channel_type: &ChannelType, cents: f64) -> f64 {
+    if *channel_type == ChannelType::FeedIn {
+        -dollars(cents)
+    } else {
+        dollars(cents)
+    }
+}
+
+/// Format a timestamp as ISO 8601 in UTC, with an explicit offset.
+fn utc(timestamp: Timestamp) -> String {
+    format!("{}+00:00", Offset::UTC.to_datetime(timestamp))
+}
+
+/// Format a timestamp as ISO 8601 in NEM time, with an explicit offset.
+fn nem(timestamp: Timestamp) -> String {
+    format!("{}+10:00", NEM_OFFSET.to_datetime(timestamp))
+}
+
+/// Returns the value as named by the API.
+fn api_name(value: &impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+/// Returns the descriptor of a price as used for the state of the
+/// descriptor sensor, such as `extremely_low`.
+///
+/// Returns `None` for descriptors not known to this version of the library.
+#[must_use]
+#[inline]
+#[expect(
+    deprecated,
+    reason = "The integration still reports negative descriptors"
+)]
+pub fn descriptor_state(descriptor: &PriceDescriptor) -> Option<&'static str> {
+    match descriptor {
+        PriceDescriptor::Negative => Some("negative"),
+        PriceDescriptor::ExtremelyLow => Some("extremely_low"),
+        PriceDescriptor::VeryLow => Some("very_low"),
+        PriceDescriptor::Low => Some("low"),
+        PriceDescriptor::Neutral => Some("neutral"),
+        PriceDescriptor::High => Some("high"),
+        PriceDescriptor::Spike => Some("spike"),
+        PriceDescriptor::Other(_) => None,
+    }
+}
+
+/// Returns the state of the price sensor: the current price in $/kWh.
+#[must_use]
+#[inline]
+pub fn price_state(current: &CurrentInterval) -> f64 {
+    price(&current.channel_type, current.per_kwh)
+}
+
+/// Insert the attributes shared by current and forecast prices.
+fn interval_attributes(
+    attributes: &mut Attributes,
+    base: &BaseInterval,
+    range: Option<&Range>,
+    advanced_price: Option<&AdvancedPrice>,
+) {
+    let channel_type = &base.channel_type;
+    attributes.insert("duration".into(), base.duration.into());
+    attributes.insert("date".into(), format!("{}", base.date).into());
+    attributes.insert("per_kwh".into(), price(channel_type, base.per_kwh).into());
+    attributes.insert("nem_date".into(), nem(base.nem_time).into());
+    attributes.insert("spot_per_kwh".into(), dollars(base.spot_per_kwh).into());
+    attributes.insert("start_time".into(), utc(base.start_time).into());
+    attributes.insert("end_time".into(), utc(base.end_time).into());
+    attributes.insert("renewables".into(), round(base.renewables).into());
+    attributes.insert("spike_status".into(), api_name(&base.spike_status));
+    attributes.insert(
+        "descriptor".into(),
+        descriptor_state(&base.descriptor).into(),
+    );
+    if let Some(bounds) = range {
+        attributes.insert("range_min".into(), price(channel_type, bounds.min).into());
+        attributes.insert("range_max".into(), price(channel_type, bounds.max).into());
+    }
+    if let Some(advanced) = advanced_price {
+        attributes.insert(
+            "advanced_price_predicted".into(),
+            price(channel_type, advanced.predicted).into(),
+        );
+    }
+}
+
+/// Returns the attributes of the price sensor of the current interval's
+/// channel.
+#[must_use]
+#[inline]
+pub fn price_attributes(current: &CurrentInterval) -> Attributes {
+    let mut attributes = Attributes::new();
+    interval_attributes(
+        &mut attributes,
+        &current.base,
+        current.range.as_ref(),
+        current.advanced_price.as_ref(),
+    );
+    attributes.insert("estimate".into(), current.estimate.into());
+    attributes.insert("channel_type".into(), api_name(&current.channel_type));
+    attributes
+}
+
+/// Returns the state of the forecast sensor of a channel: the price of the
+/// first forecast interval in $/kWh.
+#[must_use]
+#[inline]
+pub fn forecast_state(intervals: &[Interval], channel_type: &ChannelType) -> Option<f64> {
+    intervals
+        .iter()
+        .filter_map(Interval::as_forecast_interval)
+        .find(|forecast| forecast.channel_type == *channel_type)
+        .map(|forecast| price(channel_type, forecast.per_kwh))
+}
+
+/// Returns the attributes of the forecast sensor of a channel, listing the
+/// forecast intervals of the channel under `forecasts`.
+#[must_use]
+#[inline]
+pub fn forecast_attributes(intervals: &[Interval], channel_type: &ChannelType) -> Attributes {
+    let forecasts: Vec<Value> = intervals
+        .iter()
+        .filter_map(Interval::as_forecast_interval)
+        .filter(|forecast| forecast.channel_type == *channel_type)
+        .map(|forecast| {
+            let mut attributes = Attributes::new();
+            interval_attributes(
+                &mut attributes,
+                &forecast.base,
+                forecast.range.as_ref(),
+                forecast.advanced_price.as_ref(),
+            );
+            Value::Object(attributes)
+        })
+        .collect();
+    let mut attributes = Attributes::new();
+    attributes.insert("channel_type".into(), api_name(channel_type));
+    attributes.insert("forecasts".into(), forecasts.into());
+    attributes
+}
+
+/// Returns the state of the price spike binary sensor: `true` during a spike.
+#[must_use]
+#[inline]
+pub fn spike_state(current: &CurrentInterval) -> bool {
+    current.spike_status == SpikeStatus::Spike
+}
+
+/// Returns the attributes of the price spike binary sensor.
+#[must_use]
+#[inline]
+pub fn spike_attributes(current: &CurrentInterval) -> Attributes {
+    let mut attributes = Attributes::new();
+    attributes.insert("spike_status".into(), api_name(&current.spike_status));
+    attributes
+}
+
+/// Returns the state of the renewables sensor: the whole percentage of
+/// renewables in the grid.
+///
+/// Returns `None` for renewables of an unknown type.
+#[must_use]
+#[inline]
+pub fn renewables_state(renewable: &Renewable) -> Option<f64> {
+    renewable
+        .as_base_renewable()
+        .map(|base| round(base.renewables))
+}
+
+/// Returns the attributes of the renewables sensor.
+///
+/// Returns `None` for renewables of an unknown type.
+#[must_use]
+#[inline]
+pub fn renewables_attributes(renewable: &Renewable) -> Option<Attributes> {
+    let base: &BaseRenewable = renewable.as_base_renewable()?;
+    let mut attributes = Attributes::new();
+    attributes.insert("duration".into(), base.duration.into());
+    attributes.insert("date".into(), format!("{}", base.date).into());
+    attributes.insert("nem_date".into(), nem(base.nem_time).into());
+    attributes.insert("start_time".into(), utc(base.start_time).into());
+    attributes.insert("end_time".into(), utc(base.end_time).into());
+    attributes.insert("descriptor".into(), api_name(&base.descriptor));
+    Some(attributes)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse an interval of the given type and channel.
+    fn interval(kind: &str, channel_type: &str, per_kwh: f64) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": kind,
+            "spotPerKwh": 6.12,
+            "perKwh": per_kwh,
+            "startTime": "2021-05-05T02:00:01Z",
+            "renewables": 45.6,
+            "channelType": channel_type,
+            "spikeStatus": "spike",
+            "descriptor": "extremelyLow",
+            "range": {"min": 20.0, "max": 30.0},
+            "estimate": true
+        }))
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "The state is rounded to two places")]
+    fn price_sensor() {
+        let data = interval("CurrentInterval", "feedIn", -8.456);
+        let current = data.as_current_interval().expect("current interval");
+        assert_eq!(price_state(current), 0.08_f64);
+        assert!(spike_state(current));
+        assert_eq!(
+            Value::Object(price_attributes(current)),
+            serde_json::json!({
+                "duration": 30_i32,
+                "date": "2021-05-05",
+                "per_kwh": 0.08_f64,
+                "nem_date": "2021-05-05T12:30:00+10:00",
+                "spot_per_kwh": 0.06_f64,
+                "start_time": "2021-05-05T02:00:01+00:00",
+                "end_time": "2021-05-05T02:30:00+00:00",
+                "renewables": 46.0_f64,
+                "spike_status": "spike",
+                "descriptor": "extremely_low",
+                "range_min": -0.2_f64,
+                "range_max": -0.3_f64,
+                "estimate": true,
+                "channel_type": "feedIn"
+            })
+        );
+    }
+
+    #[test]
+    fn forecast_sensor() {
+        let data = [
+            interval("CurrentInterval", "general", 24.0),
+            interval("ForecastInterval", "general", 31.5),
+            interval("ForecastInterval", "feedIn", -5.0),
+            interval("ForecastInterval", "general", 28.0),
+        ];
+        assert_eq!(forecast_state(&data, &ChannelType::General), Some(0.32_f64));
+        assert_eq!(forecast_state(&data, &ChannelType::ControlledLoad), None);
+
+        let attributes = forecast_attributes(&data, &ChannelType::General);
+        assert_eq!(
+            attributes.get("channel_type"),
+            Some(&Value::from("general"))
+        );
+        let prices: Vec<_> = attributes
+            .get("forecasts")
+            .and_then(Value::as_array)
+            .expect("forecasts")
+            .iter()
+            .map(|forecast| forecast.get("per_kwh").cloned())
+            .collect();
+        assert_eq!(
+            prices,
+            [Some(Value::from(0.32_f64)), Some(Value::from(0.28_f64))]
+        );
+    }
+
+    #[test]
+    fn renewables_sensor() {
+        let renewable: Renewable = serde_json::from_value(serde_json::json!({
+            "type": "CurrentRenewable",
+            "duration": 30_i32,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-05T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45.5_f64,
+            "descriptor": "ok"
+        }))
+        .expect("valid renewable");
+        assert_eq!(renewables_state(&renewable), Some(46.0_f64));
+        assert_eq!(
+            renewables_attributes(&renewable)
+                .and_then(|attributes| attributes.get("descriptor").cloned()),
+            Some(Value::from("ok"))
+        );
+    }
+}
@@ -8,11 +8,54 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod analysis;
+pub mod annotations;
+pub mod calendar;
 #[cfg(feature = "std")]
 mod client;
+pub mod control;
 mod error;
+pub mod export;
+pub mod history;
+pub mod homeassistant;
+pub mod limits;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod models;
+#[cfg(feature = "fixed-point")]
+pub mod money;
+pub mod notify;
+pub mod precision;
+pub mod rate_limit;
+pub mod requests;
+pub mod simulator;
+pub mod storage;
+#[cfg(test)]
+mod test_fixtures;
+pub mod units;
 
+#[cfg(feature = "config")]
+pub use client::config::{Config, RetryConfig};
 #[cfg(feature = "std")]
-pub use client::{Amber, AmberBuilder};
+pub use client::{
+    Amber, AmberBuilder,
+    api::AmberApi,
+    backend::{BoxFuture, HttpBackend, HttpRequest, HttpResponse, ReqwestBackend},
+    backfill::BackfillProgress,
+    cache::{CachePolicy, CacheStats},
+    cassette::{Body, Cassette, Interaction, RecordingBackend, ReplayBackend},
+    feed::{PriceFeed, PriceFeedHandle, PriceSnapshot},
+    fixtures::FixtureBackend,
+    nightly::{FileSink, NightlySummary, SummarySink},
+    pages::{PricesPages, UsagePages},
+    pool::AmberPool,
+    proxy::{ProxyConfig, ProxyScope},
+    recorder::Recorder,
+    response::{RequestInfo, Response, Stale},
+    retry::{JitterSource, RetryPolicy},
+    secret::ApiKey,
+    startup::{CLOCK_SKEW_TOLERANCE, StartupReport, StartupWarning},
+    watcher::{PriceReplay, PriceWatch, SiteHealth, WatchEvent, Watcher, WatcherHandle},
+};
 pub use error::{AmberError, Result};
+pub use rate_limit::RateLimitInfo;
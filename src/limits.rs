@@ -0,0 +1,443 @@
+//! # Interval Limits
+//!
+//! The API caps the number of intervals returned by a single `current_prices`
+//! request at [`MAX_INTERVALS`], counted across all channels of the site. A
+//! request for `previous` and `next` intervals returns `previous + 1 + next`
+//! intervals per channel, so a site with multiple channels reaches the cap
+//! sooner, at which point results are truncated per channel without any
+//! error.
+//!
+//! The `next` and `previous` parameters are expressed as an [`IntervalCount`],
+//! which makes explicit whether a count applies to each channel (the API's
+//! native behaviour) or to the total across all channels.
+//!
+//! This module also provides a planning check, [`check_request`], which can be used
+//! before making a request, and [`ChannelCoverage::analyze`] which inspects a
+//! response to determine how many intervals were actually returned for each
+//! channel.
+//!
+//! Requests which the API would reject outright, such as date ranges longer
+//! than [`MAX_DATE_RANGE_DAYS`], are caught by [`validate_date_range`] and
+//! [`validate_interval_counts`]. The client runs these checks before sending
+//! a request, returning [`AmberError::InvalidRequest`] rather than an opaque
+//! HTTP error.
+//!
+//! ```
+//! use amber_api::limits;
+//!
+//! // Three channels, with a week of 5-minute history and a day of forecasts
+//! if let Some(warning) = limits::check_request(3, 288, 2016) {
+//!     println!("{warning}");
+//! }
+//! ```
+
+use alloc::{format, vec::Vec};
+use core::fmt;
+
+use jiff::civil::Date;
+
+use crate::{
+    error::{AmberError, Result},
+    models::{ChannelType, Interval},
+};
+
+/// The maximum number of intervals returned by a single request, across all
+/// channels.
+pub const MAX_INTERVALS: u32 = 2048;
+
+/// The maximum number of days covered by a `prices` or `usage` request,
+/// counting both the start and end dates.
+pub const MAX_DATE_RANGE_DAYS: i32 = 7;
+
+/// The number of days of usage history served by the API.
+pub const MAX_HISTORY_DAYS: i32 = 90;
+
+/// Number of `next` or `previous` intervals to request.
+///
+/// The API applies `next` and `previous` to each channel of a site: requesting
+/// three `next` intervals for a site with general and feed-in channels returns
+/// six forecast intervals. A plain `u32` converts into
+/// [`PerChannel`](Self::PerChannel), matching the API. Use
+/// [`Total`](Self::Total) to instead specify the number of intervals across
+/// all channels.
+///
+/// ```
+/// use amber_api::limits::IntervalCount;
+///
+/// assert_eq!(IntervalCount::from(3).per_channel_count(), 3);
+/// assert_eq!(IntervalCount::from(3).total_count(2), 6);
+///
+/// // Six intervals in total across two channels, three per channel
+/// assert_eq!(IntervalCount::total(6, 2).per_channel_count(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "A count is either per channel or a total across channels"
+)]
+pub enum IntervalCount {
+    /// Number of intervals for each channel.
+    PerChannel(u32),
+    /// Number of intervals in total across all channels.
+    ///
+    /// The count is divided evenly between the channels, rounding down.
+    Total {
+        /// Total number of intervals.
+        intervals: u32,
+        /// Number of channels of the site.
+        channels: u32,
+    },
+}
+
+impl IntervalCount {
+    /// Create a count of intervals for each channel.
+    #[must_use]
+    #[inline]
+    pub const fn per_channel(intervals: u32) -> Self {
+        Self::PerChannel(intervals)
+    }
+
+    /// Create a count of intervals in total across the given number of
+    /// channels.
+    #[must_use]
+    #[inline]
+    pub const fn total(intervals: u32, channels: u32) -> Self {
+        Self::Total {
+            intervals,
+            channels,
+        }
+    }
+
+    /// Returns the number of intervals for each channel, as sent to the API.
+    #[must_use]
+    #[inline]
+    pub fn per_channel_count(self) -> u32 {
+        match self {
+            Self::PerChannel(intervals) => intervals,
+            Self::Total {
+                intervals,
+                channels,
+            } => intervals.checked_div(channels).unwrap_or(intervals),
+        }
+    }
+
+    /// Returns the number of intervals across the given number of channels.
+    #[must_use]
+    #[inline]
+    pub fn total_count(self, channels: u32) -> u32 {
+        self.per_channel_count().saturating_mul(channels)
+    }
+}
+
+impl From<u32> for IntervalCount {
+    #[inline]
+    fn from(intervals: u32) -> Self {
+        Self::PerChannel(intervals)
+    }
+}
+
+/// Warning raised when a request approaches or exceeds [`MAX_INTERVALS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CapWarning {
+    /// Number of channels the request covers.
+    pub channels: u32,
+    /// Number of intervals requested per channel (`previous + 1 + next`).
+    pub requested_per_channel: u32,
+    /// Total number of intervals requested across all channels.
+    pub requested_total: u32,
+    /// Number of intervals per channel which fit within the cap.
+    pub effective_per_channel: u32,
+}
+
+impl CapWarning {
+    /// Returns `true` if the request exceeds the cap, so that results will be
+    /// truncated.
+    #[must_use]
+    #[inline]
+    pub fn exceeds_cap(&self) -> bool {
+        self.requested_total > MAX_INTERVALS
+    }
+}
+
+impl fmt::Display for CapWarning {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.exceeds_cap() {
+            write!(
+                f,
+                "Requested {} intervals across {} channels exceeds the cap of {}; only {} of {} \
+                 intervals per channel will be returned",
+                self.requested_total,
+                self.channels,
+                MAX_INTERVALS,
+                self.effective_per_channel,
+                self.requested_per_channel
+            )
+        } else {
+            write!(
+                f,
+                "Requested {} intervals across {} channels is close to the cap of {}",
+                self.requested_total, self.channels, MAX_INTERVALS
+            )
+        }
+    }
+}
+
+/// Check whether a request is near (within 10%) or above the interval cap.
+///
+/// Returns `None` if the request comfortably fits within the cap.
+#[must_use]
+#[inline]
+pub fn check_request(channels: u32, next: u32, previous: u32) -> Option<CapWarning> {
+    cap_warning(channels, next, previous)
+}
+
+/// Implementation of [`check_request`], kept out of line so that it can be
+/// checked with the `no-panic` feature.
+#[cfg_attr(
+    feature = "no-panic",
+    inline(never),
+    no_panic::no_panic,
+    expect(clippy::mem_forget, reason = "Expanded by no_panic")
+)]
+fn cap_warning(channels: u32, next: u32, previous: u32) -> Option<CapWarning> {
+    let channel_count = channels.max(1);
+    let requested_per_channel = previous.saturating_add(1).saturating_add(next);
+    let requested_total = requested_per_channel.saturating_mul(channel_count);
+    // Warn at 90% of the cap.
+    let threshold = MAX_INTERVALS.saturating_mul(9).checked_div(10).unwrap_or(0);
+
+    (requested_total >= threshold).then(|| CapWarning {
+        channels: channel_count,
+        requested_per_channel,
+        requested_total,
+        effective_per_channel: MAX_INTERVALS
+            .checked_div(channel_count)
+            .unwrap_or(0)
+            .min(requested_per_channel),
+    })
+}
+
+/// Check that a date range is accepted by the API.
+///
+/// # Errors
+///
+/// Returns [`AmberError::InvalidRequest`] if `end` is before `start`, or the
+/// range covers more than [`MAX_DATE_RANGE_DAYS`] days, counting both the
+/// start and end dates.
+///
+/// ```
+/// use amber_api::limits;
+/// use jiff::civil::date;
+///
+/// assert!(limits::validate_date_range(date(2021, 5, 1), date(2021, 5, 7)).is_ok());
+/// assert!(limits::validate_date_range(date(2021, 5, 1), date(2021, 5, 8)).is_err());
+/// ```
+#[inline]
+pub fn validate_date_range(start: Date, end: Date) -> Result<()> {
+    if end < start {
+        return Err(AmberError::InvalidRequest(format!(
+            "end date {end} is before start date {start}"
+        )));
+    }
+    let days = start
+        .until(end)
+        .map_or(i32::MAX, |span| span.get_days().saturating_add(1));
+    if days > MAX_DATE_RANGE_DAYS {
+        return Err(AmberError::InvalidRequest(format!(
+            "date range from {start} to {end} spans {days} days, exceeding the maximum of \
+             {MAX_DATE_RANGE_DAYS}"
+        )));
+    }
+    Ok(())
+}
+
+/// Check that the `next` and `previous` counts of a request are accepted by
+/// the API.
+///
+/// The counts are per channel, as sent to the API.
+///
+/// # Errors
+///
+/// Returns [`AmberError::InvalidRequest`] if `next + previous` exceeds
+/// [`MAX_INTERVALS`].
+#[inline]
+pub fn validate_interval_counts(next: u32, previous: u32) -> Result<()> {
+    let requested = next.saturating_add(previous);
+    if requested > MAX_INTERVALS {
+        return Err(AmberError::InvalidRequest(format!(
+            "{next} next and {previous} previous intervals requested, exceeding the maximum of \
+             {MAX_INTERVALS}"
+        )));
+    }
+    Ok(())
+}
+
+/// Number of intervals returned for a single channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ChannelCoverage {
+    /// The channel.
+    pub channel_type: ChannelType,
+    /// Number of actual (past) intervals returned.
+    pub previous: u32,
+    /// Number of current intervals returned.
+    pub current: u32,
+    /// Number of forecast intervals returned.
+    pub next: u32,
+}
+
+impl ChannelCoverage {
+    /// Count the intervals returned for each channel.
+    ///
+    /// Channels are listed in the order they first appear in the response.
+    #[must_use]
+    #[inline]
+    pub fn analyze(intervals: &[Interval]) -> Vec<Self> {
+        let mut coverage: Vec<Self> = Vec::new();
+        for interval in intervals {
+            let Some(base) = interval.as_base_interval() else {
+                continue;
+            };
+            let index = coverage
+                .iter()
+                .position(|entry| entry.channel_type == base.channel_type)
+                .unwrap_or_else(|| {
+                    coverage.push(Self {
+                        channel_type: base.channel_type.clone(),
+                        previous: 0,
+                        current: 0,
+                        next: 0,
+                    });
+                    coverage.len().saturating_sub(1)
+                });
+            if let Some(entry) = coverage.get_mut(index) {
+                let count = match interval {
+                    Interval::ActualInterval(_) => &mut entry.previous,
+                    Interval::CurrentInterval(_) => &mut entry.current,
+                    Interval::ForecastInterval(_) => &mut entry.next,
+                    Interval::Unknown(_) => continue,
+                };
+                *count = count.saturating_add(1);
+            }
+        }
+        coverage
+    }
+
+    /// Returns `true` if fewer intervals were returned than requested.
+    #[must_use]
+    #[inline]
+    pub fn is_truncated(&self, next: u32, previous: u32) -> bool {
+        self.next < next || self.previous < previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec};
+
+    use jiff::Timestamp;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::{
+        ActualInterval, BaseInterval, CurrentInterval, ForecastInterval, PriceDescriptor,
+        SpikeStatus,
+    };
+
+    /// Create a base interval for the given channel.
+    fn base(channel_type: ChannelType) -> BaseInterval {
+        BaseInterval {
+            duration: 5,
+            spot_per_kwh: 10.0,
+            per_kwh: 20.0,
+            date: Date::constant(2025, 1, 1),
+            nem_time: Timestamp::UNIX_EPOCH,
+            start_time: Timestamp::UNIX_EPOCH,
+            end_time: Timestamp::UNIX_EPOCH,
+            renewables: 50.0,
+            channel_type,
+            tariff_information: None,
+            spike_status: SpikeStatus::None,
+            descriptor: PriceDescriptor::Neutral,
+        }
+    }
+
+    #[test]
+    fn interval_count() {
+        assert_eq!(IntervalCount::per_channel(3).total_count(2), 6);
+        assert_eq!(IntervalCount::total(7, 2).per_channel_count(), 3);
+        assert_eq!(IntervalCount::total(7, 0).per_channel_count(), 7);
+        assert_eq!(IntervalCount::from(4), IntervalCount::PerChannel(4));
+    }
+
+    #[test]
+    fn check_request_thresholds() {
+        assert_eq!(check_request(1, 288, 288), None);
+
+        let near = check_request(1, 1000, 900).expect("near the cap");
+        assert!(!near.exceeds_cap());
+
+        let over = check_request(3, 288, 2016).expect("over the cap");
+        assert!(over.exceeds_cap());
+        assert_eq!(over.requested_per_channel, 2305);
+        assert_eq!(over.effective_per_channel, 682);
+        insta::assert_snapshot!(over.to_string(), @"Requested 6915 intervals across 3 channels exceeds the cap of 2048; only 682 of 2305 intervals per channel will be returned");
+    }
+
+    #[test]
+    fn date_range_validation() {
+        let start = Date::constant(2021, 5, 1);
+        validate_date_range(start, start).expect("single day");
+        validate_date_range(start, Date::constant(2021, 5, 7)).expect("seven days");
+
+        let too_long =
+            validate_date_range(start, Date::constant(2021, 5, 8)).expect_err("range is too long");
+        insta::assert_snapshot!(too_long.to_string(), @"Invalid request: date range from 2021-05-01 to 2021-05-08 spans 8 days, exceeding the maximum of 7");
+
+        let reversed =
+            validate_date_range(Date::constant(2021, 5, 2), start).expect_err("range is reversed");
+        insta::assert_snapshot!(reversed.to_string(), @"Invalid request: end date 2021-05-01 is before start date 2021-05-02");
+    }
+
+    #[test]
+    fn interval_count_validation() {
+        validate_interval_counts(1024, 1024).expect("maximum intervals");
+        let err = validate_interval_counts(2048, 1).expect_err("too many intervals");
+        insta::assert_snapshot!(err.to_string(), @"Invalid request: 2048 next and 1 previous intervals requested, exceeding the maximum of 2048");
+    }
+
+    #[test]
+    fn coverage_per_channel() {
+        let intervals = vec![
+            Interval::ActualInterval(ActualInterval {
+                base: base(ChannelType::General),
+            }),
+            Interval::CurrentInterval(CurrentInterval {
+                base: base(ChannelType::General),
+                range: None,
+                estimate: true,
+                advanced_price: None,
+            }),
+            Interval::ForecastInterval(ForecastInterval {
+                base: base(ChannelType::General),
+                range: None,
+                advanced_price: None,
+            }),
+            Interval::CurrentInterval(CurrentInterval {
+                base: base(ChannelType::FeedIn),
+                range: None,
+                estimate: true,
+                advanced_price: None,
+            }),
+        ];
+        let coverage = ChannelCoverage::analyze(&intervals);
+        assert_eq!(coverage.len(), 2);
+        let general = coverage.first().expect("general channel");
+        assert_eq!((general.previous, general.current, general.next), (1, 1, 1));
+        assert!(!general.is_truncated(1, 1));
+        let feed_in = coverage.get(1).expect("feed-in channel");
+        assert!(feed_in.is_truncated(1, 1));
+    }
+}
@@ -0,0 +1,373 @@
+//! # Prometheus Metrics
+//!
+//! Exposes the latest prices of each site as Prometheus gauges, rendered in
+//! the [text exposition format] for scraping. Requires the `metrics` feature.
+//!
+//! [`PriceMetrics`] holds the gauges of any number of sites. It is fed with
+//! the intervals of each site through [`record`](PriceMetrics::record) or,
+//! with the `std` feature, straight from the snapshots of a running
+//! [`PriceFeed`](crate::PriceFeed) through
+//! [`from_feed`](PriceMetrics::from_feed). Reading the feed is lock-free, so
+//! the metrics can be rebuilt on every scrape.
+//!
+//! ## Gauges
+//!
+//! All gauges are labelled with the `site`, and all except
+//! `amber_prices_fetched_timestamp_seconds` with the `channel` type as named by
+//! the API (such as `general` or `feedIn`):
+//!
+//! | Name                                     | Description                      |
+//! | ---------------------------------------- | -------------------------------- |
+//! | `amber_price_cents_per_kwh`              | Current retail price, incl. GST  |
+//! | `amber_spot_price_cents_per_kwh`         | Current spot price, incl. GST    |
+//! | `amber_renewables_percent`               | Renewables in the grid           |
+//! | `amber_spike_status`                     | Spike status (see below)         |
+//! | `amber_price_descriptor`                 | Price descriptor (see below)     |
+//! | `amber_price_estimate`                   | `1` if the price is an estimate  |
+//! | `amber_prices_fetched_timestamp_seconds` | When the prices were fetched     |
+//!
+//! The spike status is `0` for none, `1` for a potential spike and `2` for a
+//! spike. The price descriptor ranges from `0` (extremely low) through `1`
+//! (very low), `2` (low), `3` (neutral) and `4` (high) to `5` (spike). Values
+//! not known to this version of the library are reported as `NaN`.
+//!
+//! [text exposition format]: https://prometheus.io/docs/instrumenting/exposition_formats/
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use amber_api::{Amber, PriceFeed, metrics::PriceMetrics};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Amber::default();
+//! let sites = client.active_sites().await?;
+//! let feed = PriceFeed::builder()
+//!     .client(client)
+//!     .sites(sites.into_iter().map(|site| site.id).collect())
+//!     .build()
+//!     .start();
+//!
+//! // In the handler of the `/metrics` endpoint
+//! let body = PriceMetrics::from_feed(&feed).to_string();
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+use jiff::Timestamp;
+
+use crate::models::{ChannelType, Interval, PriceDescriptor, SiteId, SpikeStatus};
+
+/// Gauges of a channel of a site.
+#[derive(Debug, Clone, PartialEq)]
+struct ChannelGauges {
+    /// Channel type.
+    channel_type: ChannelType,
+    /// Retail price (c/kWh).
+    per_kwh: f64,
+    /// Spot price (c/kWh).
+    spot_per_kwh: f64,
+    /// Renewables in the grid (%).
+    renewables: f64,
+    /// Spike status, as a number.
+    spike_status: f64,
+    /// Price descriptor, as a number.
+    descriptor: f64,
+    /// Whether the price is an estimate.
+    estimate: bool,
+}
+
+/// Gauges of a site.
+#[derive(Debug, Clone, PartialEq)]
+struct SiteGauges {
+    /// When the prices were fetched.
+    fetched_at: Timestamp,
+    /// Gauges of each channel with a current interval.
+    channels: Vec<ChannelGauges>,
+}
+
+/// Prometheus gauges of the current prices of several sites.
+///
+/// Renders in the Prometheus text exposition format through
+/// [`Display`](fmt::Display). See the [module documentation](self) for the
+/// gauges.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PriceMetrics {
+    /// Gauges of each site.
+    sites: BTreeMap<SiteId, SiteGauges>,
+}
+
+impl PriceMetrics {
+    /// Create metrics without any site.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the metrics from the latest snapshots of a price feed.
+    ///
+    /// Sites whose prices have not been fetched yet are omitted.
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn from_feed(feed: &crate::PriceFeedHandle) -> Self {
+        let mut metrics = Self::new();
+        for snapshot in feed.latest_all() {
+            metrics.record(&snapshot.site_id, snapshot.fetched_at, &snapshot.intervals);
+        }
+        metrics
+    }
+
+    /// Set the gauges of a site from its intervals, replacing any previous
+    /// values.
+    ///
+    /// Only [`CurrentInterval`](Interval::CurrentInterval)s are used; if a
+    /// channel appears more than once, the first interval is used.
+    #[inline]
+    pub fn record(&mut self, site_id: &SiteId, fetched_at: Timestamp, intervals: &[Interval]) {
+        let mut channels: Vec<ChannelGauges> = Vec::new();
+        for current in intervals.iter().filter_map(Interval::as_current_interval) {
+            let base = &current.base;
+            if channels
+                .iter()
+                .any(|channel| channel.channel_type == base.channel_type)
+            {
+                continue;
+            }
+            channels.push(ChannelGauges {
+                channel_type: base.channel_type.clone(),
+                per_kwh: base.per_kwh,
+                spot_per_kwh: base.spot_per_kwh,
+                renewables: base.renewables,
+                spike_status: spike_status_value(&base.spike_status),
+                descriptor: descriptor_value(&base.descriptor),
+                estimate: current.estimate,
+            });
+        }
+        self.sites.insert(
+            site_id.clone(),
+            SiteGauges {
+                fetched_at,
+                channels,
+            },
+        );
+    }
+
+    /// Remove the gauges of a site.
+    #[inline]
+    pub fn remove(&mut self, site_id: &SiteId) {
+        self.sites.remove(site_id);
+    }
+
+    /// Returns `true` if no site has been recorded.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sites.is_empty()
+    }
+}
+
+/// Numeric value of a spike status.
+fn spike_status_value(status: &SpikeStatus) -> f64 {
+    match status {
+        SpikeStatus::None => 0.0,
+        SpikeStatus::Potential => 1.0,
+        SpikeStatus::Spike => 2.0,
+        SpikeStatus::Other(_) => f64::NAN,
+    }
+}
+
+/// Numeric value of a price descriptor, from cheapest to most expensive.
+#[expect(deprecated, reason = "Negative prices are reported as extremely low")]
+fn descriptor_value(descriptor: &PriceDescriptor) -> f64 {
+    match descriptor {
+        PriceDescriptor::Negative | PriceDescriptor::ExtremelyLow => 0.0,
+        PriceDescriptor::VeryLow => 1.0,
+        PriceDescriptor::Low => 2.0,
+        PriceDescriptor::Neutral => 3.0,
+        PriceDescriptor::High => 4.0,
+        PriceDescriptor::Spike => 5.0,
+        PriceDescriptor::Other(_) => f64::NAN,
+    }
+}
+
+/// Name of a channel type, as used by the API.
+fn channel_name(channel_type: &ChannelType) -> &str {
+    match channel_type {
+        ChannelType::General => "general",
+        ChannelType::ControlledLoad => "controlledLoad",
+        ChannelType::FeedIn => "feedIn",
+        ChannelType::Other(other) => other.as_str(),
+    }
+}
+
+/// Write a label value, escaping backslashes, quotes and line breaks.
+fn label(f: &mut fmt::Formatter<'_>, value: impl fmt::Display) -> fmt::Result {
+    let text = alloc::format!("{value}");
+    f.write_str(
+        &text
+            .replace('\\', r"\\")
+            .replace('"', r#"\""#)
+            .replace('\n', r"\n"),
+    )
+}
+
+/// Write a sample value, spelling infinities as Prometheus expects.
+fn sample(f: &mut fmt::Formatter<'_>, value: f64) -> fmt::Result {
+    if value.is_infinite() {
+        f.write_str(if value > 0.0 { "+Inf" } else { "-Inf" })
+    } else {
+        write!(f, "{value}")
+    }
+}
+
+/// A gauge rendered for every channel of every site.
+type ChannelGauge = (&'static str, &'static str, fn(&ChannelGauges) -> f64);
+
+/// The gauges rendered for each channel.
+const CHANNEL_GAUGES: [ChannelGauge; 6] = [
+    (
+        "amber_price_cents_per_kwh",
+        "Current retail price in c/kWh, including GST.",
+        |channel| channel.per_kwh,
+    ),
+    (
+        "amber_spot_price_cents_per_kwh",
+        "Current spot price in c/kWh, including GST.",
+        |channel| channel.spot_per_kwh,
+    ),
+    (
+        "amber_renewables_percent",
+        "Percentage of renewables in the grid.",
+        |channel| channel.renewables,
+    ),
+    (
+        "amber_spike_status",
+        "Spike status: 0 none, 1 potential, 2 spike.",
+        |channel| channel.spike_status,
+    ),
+    (
+        "amber_price_descriptor",
+        "Price descriptor: 0 extremely low, 1 very low, 2 low, 3 neutral, 4 high, 5 spike.",
+        |channel| channel.descriptor,
+    ),
+    (
+        "amber_price_estimate",
+        "Whether the current price is an estimate.",
+        |channel| if channel.estimate { 1.0_f64 } else { 0.0_f64 },
+    ),
+];
+
+impl fmt::Display for PriceMetrics {
+    /// Renders the gauges in the Prometheus text exposition format.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, help, value) in CHANNEL_GAUGES {
+            writeln!(f, "# HELP {name} {help}")?;
+            writeln!(f, "# TYPE {name} gauge")?;
+            for (site_id, site) in &self.sites {
+                for channel in &site.channels {
+                    write!(f, "{name}{{site=\"")?;
+                    label(f, site_id)?;
+                    f.write_str("\",channel=\"")?;
+                    label(f, channel_name(&channel.channel_type))?;
+                    f.write_str("\"} ")?;
+                    sample(f, value(channel))?;
+                    f.write_str("\n")?;
+                }
+            }
+        }
+
+        let name = "amber_prices_fetched_timestamp_seconds";
+        writeln!(
+            f,
+            "# HELP {name} When the prices were fetched from the API."
+        )?;
+        writeln!(f, "# TYPE {name} gauge")?;
+        for (site_id, site) in &self.sites {
+            write!(f, "{name}{{site=\"")?;
+            label(f, site_id)?;
+            writeln!(f, "\"}} {}", site.fetched_at.as_second())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a current interval of the given channel.
+    fn current(channel_type: &str, per_kwh: f64, descriptor: &str) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": "CurrentInterval",
+            "spotPerKwh": 6.5,
+            "perKwh": per_kwh,
+            "startTime": "2021-05-05T02:00:01Z",
+            "channelType": channel_type,
+            "spikeStatus": "potential",
+            "descriptor": descriptor,
+            "estimate": true
+        }))
+    }
+
+    #[test]
+    fn renders_exposition_format() {
+        let site_id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+        let fetched_at: Timestamp = "2021-05-05T02:05:00Z".parse().expect("valid timestamp");
+        let mut metrics = PriceMetrics::new();
+        metrics.record(
+            &site_id,
+            fetched_at,
+            &[
+                current("general", 24.5, "low"),
+                current("feedIn", -8.25, "mystery"),
+                current("general", 99.0, "spike"),
+            ],
+        );
+        insta::assert_snapshot!(metrics.to_string(), @r#"
+        # HELP amber_price_cents_per_kwh Current retail price in c/kWh, including GST.
+        # TYPE amber_price_cents_per_kwh gauge
+        amber_price_cents_per_kwh{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="general"} 24.5
+        amber_price_cents_per_kwh{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="feedIn"} -8.25
+        # HELP amber_spot_price_cents_per_kwh Current spot price in c/kWh, including GST.
+        # TYPE amber_spot_price_cents_per_kwh gauge
+        amber_spot_price_cents_per_kwh{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="general"} 6.5
+        amber_spot_price_cents_per_kwh{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="feedIn"} 6.5
+        # HELP amber_renewables_percent Percentage of renewables in the grid.
+        # TYPE amber_renewables_percent gauge
+        amber_renewables_percent{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="general"} 45
+        amber_renewables_percent{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="feedIn"} 45
+        # HELP amber_spike_status Spike status: 0 none, 1 potential, 2 spike.
+        # TYPE amber_spike_status gauge
+        amber_spike_status{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="general"} 1
+        amber_spike_status{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="feedIn"} 1
+        # HELP amber_price_descriptor Price descriptor: 0 extremely low, 1 very low, 2 low, 3 neutral, 4 high, 5 spike.
+        # TYPE amber_price_descriptor gauge
+        amber_price_descriptor{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="general"} 2
+        amber_price_descriptor{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="feedIn"} NaN
+        # HELP amber_price_estimate Whether the current price is an estimate.
+        # TYPE amber_price_estimate gauge
+        amber_price_estimate{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="general"} 1
+        amber_price_estimate{site="01F5A5CRKMZ5BCX9P1S4V990AM",channel="feedIn"} 1
+        # HELP amber_prices_fetched_timestamp_seconds When the prices were fetched from the API.
+        # TYPE amber_prices_fetched_timestamp_seconds gauge
+        amber_prices_fetched_timestamp_seconds{site="01F5A5CRKMZ5BCX9P1S4V990AM"} 1620180300
+        "#);
+
+        metrics.remove(&site_id);
+        assert!(metrics.is_empty());
+        assert_eq!(
+            metrics.to_string().lines().count(),
+            CHANNEL_GAUGES.len().saturating_add(1).saturating_mul(2)
+        );
+    }
+}
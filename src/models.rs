@@ -28,6 +28,8 @@
 //! - [`SpikeStatus`] - Spike warning indicators
 //! - [`Range`] - Price ranges when volatile
 //! - [`AdvancedPrice`] - Advanced price prediction with confidence bands
+//! - [`IntervalSeries`] - Intervals of every channel, with helpers to split,
+//!   sort and check them
 //!
 //! ## Usage Data
 //!
@@ -49,26 +51,110 @@
 //! - [`TariffInformation`] - Time-of-use and demand tariff details
 //! - [`TariffPeriod`] - Time periods (off peak, shoulder, solar sponge, peak)
 //! - [`TariffSeason`] - Seasonal variations (Summer, Winter, etc.)
+//! - [`PriceView`] - Viewing prices and costs including or excluding GST
+//!
+//! ## Identifiers
+//!
+//! - [`SiteId`] - Unique identifier of a site
+//! - [`Nmi`] - National Metering Identifier of a site
+//! - [`ChannelId`] - Identifier of a meter channel
+//!
+//! Short, frequently repeated identifiers (site IDs, NMIs, channel identifiers,
+//! tariff codes and network names) are stored as [`CompactString`]s. Strings of
+//! up to 24 bytes are kept inline without a heap allocation, which keeps long
+//! histories of [`Usage`] records cheap to hold in memory. A [`CompactString`]
+//! dereferences to [`str`] and can be converted to and from
+//! [`String`](alloc::string::String).
+//!
+//! ## Experimental Endpoints
+//!
+//! With the `experimental` feature, [`experimental`] provides typed requests
+//! for endpoints announced by Amber but not yet covered by the stable client.
+//!
+//! ## Synthetic Data
+//!
+//! Model types are `#[non_exhaustive]`, so they cannot be constructed with
+//! struct literals outside of this crate. With the `test-util` feature, each
+//! model struct has a [`bon`] builder instead, so that applications can test
+//! their own logic against synthetic data:
+//!
+//! ```
+//! # #[cfg(feature = "test-util")]
+//! # {
+//! use amber_api::models::{
+//!     BaseInterval, ChannelType, CurrentInterval, Interval, PriceDescriptor, SpikeStatus,
+//! };
+//! use jiff::civil::date;
+//!
+//! let base = BaseInterval::builder()
+//!     .duration(30)
+//!     .spot_per_kwh(6.1)
+//!     .per_kwh(24.3)
+//!     .date(date(2021, 5, 5))
+//!     .nem_time("2021-05-05T02:00:01Z".parse().expect("valid timestamp"))
+//!     .start_time("2021-05-05T01:30:01Z".parse().expect("valid timestamp"))
+//!     .end_time("2021-05-05T02:00:00Z".parse().expect("valid timestamp"))
+//!     .renewables(45.0)
+//!     .channel_type(ChannelType::General)
+//!     .spike_status(SpikeStatus::None)
+//!     .descriptor(PriceDescriptor::Low)
+//!     .build();
+//! let current = CurrentInterval::builder().base(base).estimate(true).build();
+//! assert!(Interval::CurrentInterval(current).is_current_interval());
+//! # }
+//! ```
+//!
+//! With the `proptest` feature, the models implement [`proptest`](https://docs.rs/proptest)'s
+//! `Arbitrary` trait, generating realistic timestamps, prices and descriptors
+//! for property testing.
 //!
 //! ## Date and Time Handling
 //!
 //! All datetime fields use the [`jiff`] crate for robust datetime handling:
 //! - [`jiff::civil::Date`] for date-only fields (ISO 8601 dates)
 //! - [`jiff::Timestamp`] for datetime fields (ISO 8601 timestamps)
+//!
+//! Intervals can be viewed as [`jiff::Zoned`] datetimes in NEM time (see
+//! [`nem_time_zone`]) or in any other time zone, such as the site's.
 
 #![expect(
     deprecated,
     reason = "Defining deprecated variant for backwards compatibility"
 )]
 
-use alloc::{format, string::String, vec::Vec};
-use core::fmt;
-
-use jiff::{Timestamp, civil::Date};
-use serde::Deserialize;
+#[cfg(feature = "proptest")]
+mod arbitrary;
+mod de;
+#[cfg(feature = "experimental")]
+pub mod experimental;
+mod ids;
+mod series;
+
+use alloc::{format, vec::Vec};
+use core::{
+    fmt,
+    ops::{Bound, Deref, RangeBounds},
+};
+
+use compact_str::CompactString;
+use jiff::{
+    Span, Timestamp, Unit, Zoned,
+    civil::Date,
+    tz::{self, TimeZone},
+};
+use serde::{Deserialize, Serialize};
+
+pub use ids::{ChannelId, Nmi, ParseIdError, SiteId};
+pub use series::{IntervalGap, IntervalSeries};
+
+use crate::{
+    precision::{DisplayPrecision, PrecisionPolicy},
+    units::{CentsPerKwh, KilowattHours},
+};
 
 /// Valid Australian states for renewable energy data.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum State {
     /// New South Wales.
@@ -81,6 +167,11 @@ pub enum State {
     Sa,
 }
 
+impl State {
+    /// Every state covered by the API.
+    pub const ALL: [Self; 4] = [Self::Nsw, Self::Vic, Self::Qld, Self::Sa];
+}
+
 impl fmt::Display for State {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -94,7 +185,9 @@ impl fmt::Display for State {
 }
 
 /// Valid interval resolution options.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Resolutions are serialised as their length in minutes, as in the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum Resolution {
     /// 5-minute intervals.
@@ -113,6 +206,56 @@ impl fmt::Display for Resolution {
     }
 }
 
+impl Serialize for Resolution {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(u32::from(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Resolution {
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let minutes = u32::deserialize(deserializer)?;
+        Self::try_from(minutes).map_err(|_err| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(u64::from(minutes)),
+                &"5 or 30",
+            )
+        })
+    }
+}
+
+/// Error returned when an interval length does not match any [`Resolution`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("invalid resolution: {minutes} minutes (expected 5 or 30)")]
+#[non_exhaustive]
+pub struct InvalidResolution {
+    /// The rejected interval length, in minutes.
+    pub minutes: u32,
+}
+
+impl TryFrom<u32> for Resolution {
+    type Error = InvalidResolution;
+
+    #[inline]
+    fn try_from(minutes: u32) -> Result<Self, Self::Error> {
+        IntervalDuration::from(minutes).try_into()
+    }
+}
+
+impl TryFrom<IntervalDuration> for Resolution {
+    type Error = InvalidResolution;
+
+    #[inline]
+    fn try_from(value: IntervalDuration) -> Result<Self, Self::Error> {
+        match value {
+            IntervalDuration::Known(resolution) => Ok(resolution),
+            IntervalDuration::Irregular(minutes) => Err(InvalidResolution { minutes }),
+        }
+    }
+}
+
 impl From<Resolution> for u32 {
     #[inline]
     fn from(value: Resolution) -> Self {
@@ -123,8 +266,79 @@ impl From<Resolution> for u32 {
     }
 }
 
+/// Length of an interval.
+///
+/// Intervals are almost always 5 or 30 minutes long, matching one of the
+/// [`Resolution`] options. Any other length is preserved as
+/// [`Irregular`](IntervalDuration::Irregular) so that no information is lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "A duration is either a known resolution or not, allowing exhaustive matching"
+)]
+pub enum IntervalDuration {
+    /// The interval length matches a known resolution.
+    Known(Resolution),
+    /// The interval length (in minutes) does not match any known resolution.
+    Irregular(u32),
+}
+
+impl IntervalDuration {
+    /// Returns the matching resolution, if any.
+    #[must_use]
+    #[inline]
+    pub fn resolution(self) -> Option<Resolution> {
+        match self {
+            Self::Known(resolution) => Some(resolution),
+            Self::Irregular(_) => None,
+        }
+    }
+
+    /// Returns the length of the interval in minutes.
+    #[must_use]
+    #[inline]
+    pub fn minutes(self) -> u32 {
+        u32::from(self)
+    }
+}
+
+impl From<u32> for IntervalDuration {
+    #[inline]
+    fn from(minutes: u32) -> Self {
+        match minutes {
+            5 => Self::Known(Resolution::FiveMinute),
+            30 => Self::Known(Resolution::ThirtyMinute),
+            other => Self::Irregular(other),
+        }
+    }
+}
+
+impl From<Resolution> for IntervalDuration {
+    #[inline]
+    fn from(value: Resolution) -> Self {
+        Self::Known(value)
+    }
+}
+
+impl From<IntervalDuration> for u32 {
+    #[inline]
+    fn from(value: IntervalDuration) -> Self {
+        match value {
+            IntervalDuration::Known(resolution) => resolution.into(),
+            IntervalDuration::Irregular(minutes) => minutes,
+        }
+    }
+}
+
+impl fmt::Display for IntervalDuration {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} min", u32::from(*self))
+    }
+}
+
 /// Meter channel type.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum ChannelType {
@@ -138,6 +352,11 @@ pub enum ChannelType {
     /// Feed in channel sends power back to the grid - you will have these types
     /// of channels if you have solar or batteries.
     FeedIn,
+    /// A channel type not known to this version of the library.
+    ///
+    /// The raw value returned by the API is preserved.
+    #[serde(untagged)]
+    Other(CompactString),
 }
 
 impl fmt::Display for ChannelType {
@@ -147,6 +366,7 @@ impl fmt::Display for ChannelType {
             ChannelType::General => write!(f, "general"),
             ChannelType::ControlledLoad => write!(f, "controlled load"),
             ChannelType::FeedIn => write!(f, "feed-in"),
+            ChannelType::Other(other) => write!(f, "{other}"),
         }
     }
 }
@@ -162,17 +382,19 @@ impl fmt::Display for ChannelType {
 ///
 /// The feed in channel sends power back to the grid - you will have these types
 /// of channels if you have solar or batteries.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
+#[cfg_attr(feature = "test-util", builder(on(CompactString, into)))]
 pub struct Channel {
     /// Identifier of the channel.
-    pub identifier: String,
+    pub identifier: ChannelId,
     /// Channel type.
     #[serde(rename = "type")]
     pub channel_type: ChannelType,
     /// The tariff code of the channel.
-    pub tariff: String,
+    pub tariff: CompactString,
 }
 
 impl fmt::Display for Channel {
@@ -197,7 +419,7 @@ impl fmt::Display for Channel {
 /// Active sites are ones that Amber actively supplies electricity to.
 ///
 /// Closed sites are old sites that Amber no longer supplies.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum SiteStatus {
@@ -211,6 +433,11 @@ pub enum SiteStatus {
     Active,
     /// Old site that Amber no longer supplies.
     Closed,
+    /// A site status not known to this version of the library.
+    ///
+    /// The raw value returned by the API is preserved.
+    #[serde(untagged)]
+    Other(CompactString),
 }
 
 impl fmt::Display for SiteStatus {
@@ -220,23 +447,26 @@ impl fmt::Display for SiteStatus {
             SiteStatus::Pending => write!(f, "pending"),
             SiteStatus::Active => write!(f, "active"),
             SiteStatus::Closed => write!(f, "closed"),
+            SiteStatus::Other(other) => write!(f, "{other}"),
         }
     }
 }
 
 /// Site information.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
+#[cfg_attr(feature = "test-util", builder(on(CompactString, into)))]
 pub struct Site {
     /// Unique Site Identifier.
-    pub id: String,
+    pub id: SiteId,
     /// National Metering Identifier (NMI) for the site.
-    pub nmi: String,
+    pub nmi: Nmi,
     /// List of channels that are readable from your meter.
     pub channels: Vec<Channel>,
     /// The name of the site's network.
-    pub network: String,
+    pub network: CompactString,
     /// Site status.
     pub status: SiteStatus,
     /// Date the site became active. This date will be in the future for pending
@@ -249,6 +479,61 @@ pub struct Site {
     pub interval_length: u32,
 }
 
+impl Site {
+    /// Returns the typed length of the intervals the site is billed on.
+    ///
+    /// This should be preferred over the raw
+    /// [`interval_length`](Self::interval_length) field, as it allows
+    /// exhaustive matching on the interval resolution.
+    #[must_use]
+    #[inline]
+    pub fn interval_duration(&self) -> IntervalDuration {
+        IntervalDuration::from(self.interval_length)
+    }
+
+    /// Returns the resolution the site is billed on, or `None` if the
+    /// interval length does not match any [`Resolution`].
+    ///
+    /// This can be passed directly as the `resolution` of the price
+    /// endpoints, to request prices matching the site's billing.
+    #[must_use]
+    #[inline]
+    pub fn resolution(&self) -> Option<Resolution> {
+        self.interval_duration().resolution()
+    }
+
+    /// Returns `true` if the site has a status of [`SiteStatus::Active`].
+    #[must_use]
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.status == SiteStatus::Active
+    }
+
+    /// Returns `true` if the site was active on the given date.
+    ///
+    /// A site is considered active from its [`active_from`](Self::active_from)
+    /// date up to and including its [`closed_on`](Self::closed_on) date. Sites
+    /// without an `active_from` date are never considered active.
+    #[must_use]
+    #[inline]
+    pub fn is_active_on(&self, date: Date) -> bool {
+        self.lifetime()
+            .is_some_and(|lifetime| lifetime.contains(date))
+    }
+
+    /// Returns the period during which the site is (or was) active.
+    ///
+    /// Returns `None` if the site has no `active_from` date.
+    #[must_use]
+    #[inline]
+    pub fn lifetime(&self) -> Option<SiteLifetime> {
+        self.active_from.map(|active_from| SiteLifetime {
+            active_from,
+            closed_on: self.closed_on,
+        })
+    }
+}
+
 impl fmt::Display for Site {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -260,11 +545,70 @@ impl fmt::Display for Site {
     }
 }
 
+/// The period during which a site is active.
+///
+/// The period starts on the `active_from` date and ends on (and includes) the
+/// `closed_on` date if the site has been closed; otherwise it is open-ended.
+/// Note that `active_from` may be in the future for pending sites.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
+pub struct SiteLifetime {
+    /// Date the site became (or will become) active.
+    pub active_from: Date,
+    /// Date the site closed, if it has closed.
+    pub closed_on: Option<Date>,
+}
+
+impl SiteLifetime {
+    /// Returns `true` if the date falls within the site's lifetime.
+    #[must_use]
+    #[inline]
+    #[expect(
+        clippy::same_name_method,
+        reason = "Takes the date by value, unlike `RangeBounds::contains`"
+    )]
+    pub fn contains(&self, date: Date) -> bool {
+        self.active_from <= date && self.closed_on.is_none_or(|closed_on| date <= closed_on)
+    }
+
+    /// Returns `true` if the site has closed.
+    #[must_use]
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.closed_on.is_some()
+    }
+}
+
+impl RangeBounds<Date> for SiteLifetime {
+    #[inline]
+    fn start_bound(&self) -> Bound<&Date> {
+        Bound::Included(&self.active_from)
+    }
+
+    #[inline]
+    fn end_bound(&self) -> Bound<&Date> {
+        self.closed_on
+            .as_ref()
+            .map_or(Bound::Unbounded, Bound::Included)
+    }
+}
+
+impl fmt::Display for SiteLifetime {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.closed_on {
+            Some(closed_on) => write!(f, "{} to {}", self.active_from, closed_on),
+            None => write!(f, "{} onwards", self.active_from),
+        }
+    }
+}
+
 /// Spike status.
 ///
 /// Indicates whether this interval will potentially spike, or is currently in a
 /// spike state.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum SpikeStatus {
@@ -274,6 +618,11 @@ pub enum SpikeStatus {
     Potential,
     /// Spike is currently occurring during this interval.
     Spike,
+    /// A spike status not known to this version of the library.
+    ///
+    /// The raw value returned by the API is preserved.
+    #[serde(untagged)]
+    Other(CompactString),
 }
 
 impl fmt::Display for SpikeStatus {
@@ -283,6 +632,7 @@ impl fmt::Display for SpikeStatus {
             SpikeStatus::None => write!(f, "none"),
             SpikeStatus::Potential => write!(f, "potential"),
             SpikeStatus::Spike => write!(f, "spike"),
+            SpikeStatus::Other(other) => write!(f, "{other}"),
         }
     }
 }
@@ -292,7 +642,7 @@ impl fmt::Display for SpikeStatus {
 /// Gives you an indication of how cheap the price is in relation to the average
 /// VMO and DMO. Note: Negative is no longer used. It has been replaced with
 /// extremelyLow.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum PriceDescriptor {
@@ -311,6 +661,11 @@ pub enum PriceDescriptor {
     High,
     /// Spike pricing - very high costs, avoid high usage.
     Spike,
+    /// A price descriptor not known to this version of the library.
+    ///
+    /// The raw value returned by the API is preserved.
+    #[serde(untagged)]
+    Other(CompactString),
 }
 
 impl fmt::Display for PriceDescriptor {
@@ -324,6 +679,7 @@ impl fmt::Display for PriceDescriptor {
             PriceDescriptor::Neutral => write!(f, "neutral"),
             PriceDescriptor::High => write!(f, "high"),
             PriceDescriptor::Spike => write!(f, "spike"),
+            PriceDescriptor::Other(other) => write!(f, "{other}"),
         }
     }
 }
@@ -331,7 +687,7 @@ impl fmt::Display for PriceDescriptor {
 /// Describes the state of renewables.
 ///
 /// Gives you an indication of how green power is right now.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum RenewableDescriptor {
@@ -345,6 +701,11 @@ pub enum RenewableDescriptor {
     NotGreat,
     /// Worst renewable conditions - lowest percentage of green energy.
     Worst,
+    /// A renewable descriptor not known to this version of the library.
+    ///
+    /// The raw value returned by the API is preserved.
+    #[serde(untagged)]
+    Other(CompactString),
 }
 
 impl fmt::Display for RenewableDescriptor {
@@ -356,15 +717,17 @@ impl fmt::Display for RenewableDescriptor {
             RenewableDescriptor::Ok => write!(f, "ok"),
             RenewableDescriptor::NotGreat => write!(f, "not great"),
             RenewableDescriptor::Worst => write!(f, "worst"),
+            RenewableDescriptor::Other(other) => write!(f, "{other}"),
         }
     }
 }
 
 /// When prices are particularly volatile, the API may return a range of NEM
 /// spot prices (c/kWh) that are possible.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct Range {
     /// Estimated minimum price (c/kWh).
     pub min: f64,
@@ -375,7 +738,19 @@ pub struct Range {
 impl fmt::Display for Range {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:.2}-{:.2}c/kWh", self.min, self.max)
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for Range {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}c/kWh",
+            policy.price(self.min),
+            policy.price(self.max)
+        )
     }
 }
 
@@ -384,9 +759,10 @@ impl fmt::Display for Range {
 /// Amber has created an advanced forecast system, that represents Amber's
 /// confidence in the AEMO forecast. The range indicates where Amber thinks the
 /// price will land for a given interval.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct AdvancedPrice {
     /// The lower bound of Amber's prediction band. Price includes network and
     /// market fees. (c/kWh).
@@ -402,18 +778,28 @@ pub struct AdvancedPrice {
 impl fmt::Display for AdvancedPrice {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for AdvancedPrice {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
         write!(
             f,
-            "L:{:.2} H:{:.2} P:{:.2} c/kWh",
-            self.low, self.predicted, self.high
+            "L:{} H:{} P:{} c/kWh",
+            policy.price(self.low),
+            policy.price(self.predicted),
+            policy.price(self.high)
         )
     }
 }
 
 /// Information about how your tariff affects an interval.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct TariffInformation {
     /// The Time of Use period that is currently active.
     ///
@@ -460,7 +846,7 @@ impl fmt::Display for TariffInformation {
 }
 
 /// Time of Use period.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum TariffPeriod {
@@ -473,6 +859,11 @@ pub enum TariffPeriod {
     SolarSponge,
     /// Peak period with highest electricity rates.
     Peak,
+    /// A tariff period not known to this version of the library.
+    ///
+    /// The raw value returned by the API is preserved.
+    #[serde(untagged)]
+    Other(CompactString),
 }
 
 impl fmt::Display for TariffPeriod {
@@ -483,12 +874,13 @@ impl fmt::Display for TariffPeriod {
             TariffPeriod::Shoulder => write!(f, "shoulder"),
             TariffPeriod::SolarSponge => write!(f, "solar sponge"),
             TariffPeriod::Peak => write!(f, "peak"),
+            TariffPeriod::Other(other) => write!(f, "{other}"),
         }
     }
 }
 
 /// Time of Use season.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum TariffSeason {
@@ -513,6 +905,11 @@ pub enum TariffSeason {
     WeekendHoliday,
     /// Weekday tariff period with standard rates.
     Weekday,
+    /// A tariff season not known to this version of the library.
+    ///
+    /// The raw value returned by the API is preserved.
+    #[serde(untagged)]
+    Other(CompactString),
 }
 
 impl fmt::Display for TariffSeason {
@@ -529,14 +926,26 @@ impl fmt::Display for TariffSeason {
             TariffSeason::Weekend => write!(f, "weekend"),
             TariffSeason::WeekendHoliday => write!(f, "weekend holiday"),
             TariffSeason::Weekday => write!(f, "weekday"),
+            TariffSeason::Other(other) => write!(f, "{other}"),
         }
     }
 }
 
+/// Returns the time zone of NEM time.
+///
+/// NEM time is fixed at UTC+10 all year round, matching
+/// `Australia/Brisbane`, so this is available without a time zone database.
+#[must_use]
+#[inline]
+pub fn nem_time_zone() -> TimeZone {
+    TimeZone::fixed(tz::offset(10))
+}
+
 /// Base interval structure containing common fields.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct BaseInterval {
     /// Length of the interval in minutes.
     pub duration: u32,
@@ -572,18 +981,167 @@ pub struct BaseInterval {
     pub descriptor: PriceDescriptor,
 }
 
+impl BaseInterval {
+    /// Returns the typed length of the interval.
+    ///
+    /// This should be preferred over the raw [`duration`](Self::duration)
+    /// field, as it allows exhaustive matching on the interval resolution.
+    #[must_use]
+    #[inline]
+    pub fn interval_duration(&self) -> IntervalDuration {
+        IntervalDuration::from(self.duration)
+    }
+
+    /// Returns the start of the interval in the given time zone, such as the
+    /// site's local time zone.
+    #[must_use]
+    #[inline]
+    pub fn start_in(&self, time_zone: &TimeZone) -> Zoned {
+        self.start_time.to_zoned(time_zone.clone())
+    }
+
+    /// Returns the end of the interval in the given time zone, such as the
+    /// site's local time zone.
+    #[must_use]
+    #[inline]
+    pub fn end_in(&self, time_zone: &TimeZone) -> Zoned {
+        self.end_time.to_zoned(time_zone.clone())
+    }
+
+    /// Returns the start of the interval in NEM time.
+    #[must_use]
+    #[inline]
+    pub fn start_nem(&self) -> Zoned {
+        self.start_in(&nem_time_zone())
+    }
+
+    /// Returns the end of the interval in NEM time.
+    #[must_use]
+    #[inline]
+    pub fn end_nem(&self) -> Zoned {
+        self.end_in(&nem_time_zone())
+    }
+
+    /// Returns `true` if the timestamp falls within the interval.
+    ///
+    /// Intervals are half-open, so the end of an interval belongs to the
+    /// next one.
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, timestamp: Timestamp) -> bool {
+        self.start_time <= timestamp && timestamp < self.end_time
+    }
+
+    /// Returns the length of the interval as a span of hours and minutes,
+    /// measured from its start and end times.
+    #[must_use]
+    #[inline]
+    pub fn span(&self) -> Span {
+        self.start_time
+            .until((Unit::Hour, self.end_time))
+            .unwrap_or_default()
+    }
+
+    /// Returns the retail price, including GST.
+    #[must_use]
+    #[inline]
+    pub fn price(&self) -> CentsPerKwh {
+        CentsPerKwh::new(self.per_kwh)
+    }
+
+    /// Returns the spot price, including GST.
+    #[must_use]
+    #[inline]
+    pub fn spot_price(&self) -> CentsPerKwh {
+        CentsPerKwh::new(self.spot_per_kwh)
+    }
+
+    /// Returns the retail price excluding GST (c/kWh).
+    #[must_use]
+    #[inline]
+    pub fn per_kwh_ex_gst(&self) -> f64 {
+        PriceView::ExGst.apply(self.per_kwh)
+    }
+
+    /// Returns the spot price excluding GST (c/kWh).
+    #[must_use]
+    #[inline]
+    pub fn spot_per_kwh_ex_gst(&self) -> f64 {
+        PriceView::ExGst.apply(self.spot_per_kwh)
+    }
+
+    /// Returns the retail price (c/kWh) as an exact fixed-point amount, or
+    /// `None` if it cannot be represented.
+    #[cfg(feature = "fixed-point")]
+    #[must_use]
+    #[inline]
+    pub fn per_kwh_cents(&self) -> Option<crate::money::PriceCents> {
+        crate::money::PriceCents::from_cents(self.per_kwh)
+    }
+
+    /// Returns the spot price (c/kWh) as an exact fixed-point amount, or
+    /// `None` if it cannot be represented.
+    #[cfg(feature = "fixed-point")]
+    #[must_use]
+    #[inline]
+    pub fn spot_per_kwh_cents(&self) -> Option<crate::money::PriceCents> {
+        crate::money::PriceCents::from_cents(self.spot_per_kwh)
+    }
+}
+
+/// Rate of the Goods and Services Tax included in all prices and costs
+/// reported by the API.
+pub const GST_RATE: f64 = 0.1;
+
+/// Whether prices and costs are viewed including or excluding GST.
+///
+/// Every price and cost reported by the API includes GST. Businesses which
+/// claim GST back can use [`PriceView::ExGst`] to work with the amounts they
+/// actually bear.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum PriceView {
+    /// Amounts including GST, as reported by the API.
+    #[default]
+    IncGst,
+    /// Amounts excluding GST.
+    ExGst,
+}
+
+impl PriceView {
+    /// Convert an amount including GST, such as a price or cost reported by
+    /// the API, to this view.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Removing GST")]
+    pub fn apply(self, value: f64) -> f64 {
+        match self {
+            Self::IncGst => value,
+            Self::ExGst => value / (1.0 + GST_RATE),
+        }
+    }
+}
+
 impl fmt::Display for BaseInterval {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for BaseInterval {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
         write!(
             f,
-            "{} {} {:.2}c/kWh (spot: {:.2}c/kWh) ({}) {}% renewable",
+            "{} {} {}c/kWh (spot: {}c/kWh) ({}) {}% renewable",
             self.date,
             self.channel_type,
-            self.per_kwh,
-            self.spot_per_kwh,
+            policy.price(self.per_kwh),
+            policy.price(self.spot_per_kwh),
             self.descriptor,
-            self.renewables
+            policy.percentage(self.renewables)
         )?;
 
         if self.spike_status != SpikeStatus::None {
@@ -599,26 +1157,44 @@ impl fmt::Display for BaseInterval {
 }
 
 /// Actual interval with confirmed pricing.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct ActualInterval {
     /// Base interval data with confirmed pricing.
     #[serde(flatten)]
     pub base: BaseInterval,
 }
 
+impl Deref for ActualInterval {
+    type Target = BaseInterval;
+
+    #[inline]
+    fn deref(&self) -> &BaseInterval {
+        &self.base
+    }
+}
+
 impl fmt::Display for ActualInterval {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Actual: {}", self.base)
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for ActualInterval {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        write!(f, "Actual: {}", self.base.display_with(policy))
     }
 }
 
 /// Forecast interval with predicted pricing.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct ForecastInterval {
     /// Base interval data with predicted pricing.
     #[serde(flatten)]
@@ -629,24 +1205,41 @@ pub struct ForecastInterval {
     pub advanced_price: Option<AdvancedPrice>,
 }
 
+impl Deref for ForecastInterval {
+    type Target = BaseInterval;
+
+    #[inline]
+    fn deref(&self) -> &BaseInterval {
+        &self.base
+    }
+}
+
 impl fmt::Display for ForecastInterval {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Forecast: {}", self.base)?;
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for ForecastInterval {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        write!(f, "Forecast: {}", self.base.display_with(policy))?;
         if let Some(ref range) = self.range {
-            write!(f, " Range: {range}")?;
+            write!(f, " Range: {}", range.display_with(policy))?;
         }
         if let Some(ref adv_price) = self.advanced_price {
-            write!(f, " Advanced: {adv_price}")?;
+            write!(f, " Advanced: {}", adv_price.display_with(policy))?;
         }
         Ok(())
     }
 }
 
 /// Current interval with real-time pricing.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct CurrentInterval {
     /// Base interval data with real-time pricing.
     #[serde(flatten)]
@@ -660,25 +1253,49 @@ pub struct CurrentInterval {
     pub advanced_price: Option<AdvancedPrice>,
 }
 
+impl Deref for CurrentInterval {
+    type Target = BaseInterval;
+
+    #[inline]
+    fn deref(&self) -> &BaseInterval {
+        &self.base
+    }
+}
+
 impl fmt::Display for CurrentInterval {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Current: {}", self.base)?;
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for CurrentInterval {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        write!(f, "Current: {}", self.base.display_with(policy))?;
         if self.estimate {
             write!(f, " (estimate)")?;
         }
         if let Some(ref range) = self.range {
-            write!(f, " Range: {range}")?;
+            write!(f, " Range: {}", range.display_with(policy))?;
         }
         if let Some(ref adv_price) = self.advanced_price {
-            write!(f, " Advanced: {adv_price}")?;
+            write!(f, " Advanced: {}", adv_price.display_with(policy))?;
         }
         Ok(())
     }
 }
 
 /// Interval enum that can be any of the interval types.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+///
+/// The variant is selected by the `type` field of the JSON object. The
+/// [`Deserialize`] implementation reads the tag and all fields in a single
+/// pass, avoiding the intermediate buffering of a derived implementation.
+///
+/// Objects with an unrecognised `type` are deserialised as
+/// [`Interval::Unknown`], so that a new kind of interval introduced by the API
+/// does not cause the whole response to fail.
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(tag = "type")]
 #[non_exhaustive]
 pub enum Interval {
@@ -688,6 +1305,12 @@ pub enum Interval {
     ForecastInterval(ForecastInterval),
     /// Current interval with real-time pricing data.
     CurrentInterval(CurrentInterval),
+    /// Interval of a type not known to this version of the library.
+    ///
+    /// The raw JSON object returned by the API is preserved, including the
+    /// `type` field.
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
 }
 
 impl Interval {
@@ -757,7 +1380,79 @@ impl Interval {
         }
     }
 
+    /// Returns `true` if the interval is of an [`Unknown`] type.
+    ///
+    /// [`Unknown`]: Interval::Unknown
+    #[inline]
+    #[must_use]
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown(..))
+    }
+
+    /// Return the raw JSON object of an [`Unknown`] interval.
+    ///
+    /// [`Unknown`]: Interval::Unknown
+    #[inline]
+    #[must_use]
+    pub fn as_unknown(&self) -> Option<&serde_json::Value> {
+        if let Self::Unknown(raw) = self {
+            Some(raw)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `type` tag of an [`Unknown`] interval.
+    ///
+    /// [`Unknown`]: Interval::Unknown
+    #[inline]
+    #[must_use]
+    pub fn unknown_type(&self) -> Option<&str> {
+        self.as_unknown().and_then(raw_type)
+    }
+
+    /// Convert into the [`ActualInterval`] variant if it is one.
+    ///
+    /// [`ActualInterval`]: Interval::ActualInterval
+    #[inline]
+    #[must_use]
+    pub fn into_actual(self) -> Option<ActualInterval> {
+        if let Self::ActualInterval(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Convert into the [`ForecastInterval`] variant if it is one.
+    ///
+    /// [`ForecastInterval`]: Interval::ForecastInterval
+    #[inline]
+    #[must_use]
+    pub fn into_forecast(self) -> Option<ForecastInterval> {
+        if let Self::ForecastInterval(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Convert into the [`CurrentInterval`] variant if it is one.
+    ///
+    /// [`CurrentInterval`]: Interval::CurrentInterval
+    #[inline]
+    #[must_use]
+    pub fn into_current(self) -> Option<CurrentInterval> {
+        if let Self::CurrentInterval(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
     /// Returns the base interval if it exists.
+    ///
+    /// Returns `None` for [`Unknown`](Interval::Unknown) intervals.
     #[inline]
     #[must_use]
     pub fn as_base_interval(&self) -> Option<&BaseInterval> {
@@ -765,31 +1460,91 @@ impl Interval {
             Interval::ActualInterval(actual) => Some(&actual.base),
             Interval::ForecastInterval(forecast) => Some(&forecast.base),
             Interval::CurrentInterval(current) => Some(&current.base),
+            Interval::Unknown(_) => None,
+        }
+    }
+
+    /// Convert into the base interval if it exists, discarding any
+    /// variant-specific fields.
+    #[inline]
+    #[must_use]
+    pub fn into_base_interval(self) -> Option<BaseInterval> {
+        match self {
+            Interval::ActualInterval(actual) => Some(actual.base),
+            Interval::ForecastInterval(forecast) => Some(forecast.base),
+            Interval::CurrentInterval(current) => Some(current.base),
+            Interval::Unknown(_) => None,
         }
     }
+
+    /// Returns the start time of the interval.
+    #[inline]
+    #[must_use]
+    pub fn start_time(&self) -> Option<Timestamp> {
+        self.as_base_interval().map(|base| base.start_time)
+    }
+
+    /// Returns the end time of the interval.
+    #[inline]
+    #[must_use]
+    pub fn end_time(&self) -> Option<Timestamp> {
+        self.as_base_interval().map(|base| base.end_time)
+    }
+
+    /// Returns the channel type of the interval.
+    #[inline]
+    #[must_use]
+    pub fn channel_type(&self) -> Option<&ChannelType> {
+        self.as_base_interval().map(|base| &base.channel_type)
+    }
+
+    /// Returns the price of the interval in c/kWh, including GST.
+    #[inline]
+    #[must_use]
+    pub fn per_kwh(&self) -> Option<f64> {
+        self.as_base_interval().map(|base| base.per_kwh)
+    }
+
+    /// Returns the price descriptor of the interval.
+    #[inline]
+    #[must_use]
+    pub fn descriptor(&self) -> Option<&PriceDescriptor> {
+        self.as_base_interval().map(|base| &base.descriptor)
+    }
 }
 
 impl fmt::Display for Interval {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for Interval {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
         match self {
-            Interval::ActualInterval(actual) => write!(f, "{actual}"),
-            Interval::ForecastInterval(forecast) => write!(f, "{forecast}"),
-            Interval::CurrentInterval(current) => write!(f, "{current}"),
+            Interval::ActualInterval(actual) => actual.fmt_precision(f, policy),
+            Interval::ForecastInterval(forecast) => forecast.fmt_precision(f, policy),
+            Interval::CurrentInterval(current) => current.fmt_precision(f, policy),
+            Interval::Unknown(raw) => {
+                write!(f, "Unknown interval ({})", raw_type(raw).unwrap_or("?"))
+            }
         }
     }
 }
 
 /// Usage data for a specific interval.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct Usage {
     /// Base interval data for usage reporting.
     #[serde(flatten)]
     pub base: BaseInterval,
     /// Meter channel identifier.
-    pub channel_identifier: String,
+    pub channel_identifier: ChannelId,
     /// Number of kWh you consumed or generated.
     ///
     /// Generated numbers will be negative.
@@ -801,19 +1556,54 @@ pub struct Usage {
     pub cost: f64,
 }
 
+impl Usage {
+    /// Returns the energy consumed, negative when generated.
+    #[must_use]
+    #[inline]
+    pub fn energy(&self) -> KilowattHours {
+        KilowattHours::new(self.kwh)
+    }
+
+    /// Returns the cost excluding GST (c).
+    #[must_use]
+    #[inline]
+    pub fn cost_ex_gst(&self) -> f64 {
+        PriceView::ExGst.apply(self.cost)
+    }
+
+    /// Returns the cost (c) as an exact fixed-point amount, or `None` if it
+    /// cannot be represented.
+    #[cfg(feature = "fixed-point")]
+    #[must_use]
+    #[inline]
+    pub fn cost_cents(&self) -> Option<crate::money::PriceCents> {
+        crate::money::PriceCents::from_cents(self.cost)
+    }
+}
+
 impl fmt::Display for Usage {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for Usage {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
         write!(
             f,
-            "Usage {} {:.2}kWh ${:.2} ({})",
-            self.channel_identifier, self.kwh, self.cost, self.quality
+            "Usage {} {}kWh ${} ({})",
+            self.channel_identifier,
+            policy.energy(self.kwh),
+            policy.cost(self.cost),
+            self.quality
         )
     }
 }
 
 /// Usage data quality.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum UsageQuality {
@@ -834,9 +1624,10 @@ impl fmt::Display for UsageQuality {
 }
 
 /// Base renewable data structure.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct BaseRenewable {
     /// Length of the interval in minutes.
     pub duration: u32,
@@ -859,70 +1650,199 @@ pub struct BaseRenewable {
     pub descriptor: RenewableDescriptor,
 }
 
+impl BaseRenewable {
+    /// Returns the typed length of the interval.
+    ///
+    /// This should be preferred over the raw [`duration`](Self::duration)
+    /// field, as it allows exhaustive matching on the interval resolution.
+    #[must_use]
+    #[inline]
+    pub fn interval_duration(&self) -> IntervalDuration {
+        IntervalDuration::from(self.duration)
+    }
+
+    /// Returns the start of the interval in the given time zone, such as the
+    /// site's local time zone.
+    #[must_use]
+    #[inline]
+    pub fn start_in(&self, time_zone: &TimeZone) -> Zoned {
+        self.start_time.to_zoned(time_zone.clone())
+    }
+
+    /// Returns the end of the interval in the given time zone, such as the
+    /// site's local time zone.
+    #[must_use]
+    #[inline]
+    pub fn end_in(&self, time_zone: &TimeZone) -> Zoned {
+        self.end_time.to_zoned(time_zone.clone())
+    }
+
+    /// Returns the start of the interval in NEM time.
+    #[must_use]
+    #[inline]
+    pub fn start_nem(&self) -> Zoned {
+        self.start_in(&nem_time_zone())
+    }
+
+    /// Returns the end of the interval in NEM time.
+    #[must_use]
+    #[inline]
+    pub fn end_nem(&self) -> Zoned {
+        self.end_in(&nem_time_zone())
+    }
+
+    /// Returns `true` if the timestamp falls within the interval.
+    ///
+    /// Intervals are half-open, so the end of an interval belongs to the
+    /// next one.
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, timestamp: Timestamp) -> bool {
+        self.start_time <= timestamp && timestamp < self.end_time
+    }
+
+    /// Returns the length of the interval as a span of hours and minutes,
+    /// measured from its start and end times.
+    #[must_use]
+    #[inline]
+    pub fn span(&self) -> Span {
+        self.start_time
+            .until((Unit::Hour, self.end_time))
+            .unwrap_or_default()
+    }
+}
+
 impl fmt::Display for BaseRenewable {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for BaseRenewable {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
         write!(
             f,
             "{} {}% renewable ({})",
-            self.date, self.renewables, self.descriptor
+            self.date,
+            policy.percentage(self.renewables),
+            self.descriptor
         )
     }
 }
 
 /// Actual renewable data.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct ActualRenewable {
     /// Base renewable data with confirmed historical values.
     #[serde(flatten)]
     pub base: BaseRenewable,
 }
 
+impl Deref for ActualRenewable {
+    type Target = BaseRenewable;
+
+    #[inline]
+    fn deref(&self) -> &BaseRenewable {
+        &self.base
+    }
+}
+
 impl fmt::Display for ActualRenewable {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Actual: {}", self.base)
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for ActualRenewable {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        write!(f, "Actual: {}", self.base.display_with(policy))
     }
 }
 
 /// Forecast renewable data.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct ForecastRenewable {
     /// Base renewable data with predicted future values.
     #[serde(flatten)]
     pub base: BaseRenewable,
 }
 
+impl Deref for ForecastRenewable {
+    type Target = BaseRenewable;
+
+    #[inline]
+    fn deref(&self) -> &BaseRenewable {
+        &self.base
+    }
+}
+
 impl fmt::Display for ForecastRenewable {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Forecast: {}", self.base)
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for ForecastRenewable {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        write!(f, "Forecast: {}", self.base.display_with(policy))
     }
 }
 
 /// Current renewable data.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[cfg_attr(feature = "test-util", derive(bon::Builder))]
 pub struct CurrentRenewable {
     /// Base renewable data with current real-time values.
     #[serde(flatten)]
     pub base: BaseRenewable,
 }
 
+impl Deref for CurrentRenewable {
+    type Target = BaseRenewable;
+
+    #[inline]
+    fn deref(&self) -> &BaseRenewable {
+        &self.base
+    }
+}
+
 impl fmt::Display for CurrentRenewable {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Current: {}", self.base)
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for CurrentRenewable {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
+        write!(f, "Current: {}", self.base.display_with(policy))
     }
 }
 
 /// Renewable enum that can be any of the renewable types.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+///
+/// The variant is selected by the `type` field of the JSON object. The
+/// [`Deserialize`] implementation reads the tag and all fields in a single
+/// pass, avoiding the intermediate buffering of a derived implementation.
+///
+/// Objects with an unrecognised `type` are deserialised as
+/// [`Renewable::Unknown`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(tag = "type")]
 #[non_exhaustive]
 pub enum Renewable {
@@ -932,6 +1852,12 @@ pub enum Renewable {
     ForecastRenewable(ForecastRenewable),
     /// Current renewable data with real-time values.
     CurrentRenewable(CurrentRenewable),
+    /// Renewable data of a type not known to this version of the library.
+    ///
+    /// The raw JSON object returned by the API is preserved, including the
+    /// `type` field.
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
 }
 
 impl Renewable {
@@ -1001,32 +1927,153 @@ impl Renewable {
         }
     }
 
+    /// Returns `true` if the renewable is of an [`Unknown`] type.
+    ///
+    /// [`Unknown`]: Renewable::Unknown
+    #[must_use]
+    #[inline]
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown(..))
+    }
+
+    /// Return the raw JSON object of an [`Unknown`] renewable.
+    ///
+    /// [`Unknown`]: Renewable::Unknown
+    #[must_use]
+    #[inline]
+    pub fn as_unknown(&self) -> Option<&serde_json::Value> {
+        if let Self::Unknown(raw) = self {
+            Some(raw)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `type` tag of an [`Unknown`] renewable.
+    ///
+    /// [`Unknown`]: Renewable::Unknown
+    #[must_use]
+    #[inline]
+    pub fn unknown_type(&self) -> Option<&str> {
+        self.as_unknown().and_then(raw_type)
+    }
+
+    /// Convert into the [`ActualRenewable`] variant if it is one.
+    ///
+    /// [`ActualRenewable`]: Renewable::ActualRenewable
+    #[must_use]
+    #[inline]
+    pub fn into_actual(self) -> Option<ActualRenewable> {
+        if let Self::ActualRenewable(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Convert into the [`ForecastRenewable`] variant if it is one.
+    ///
+    /// [`ForecastRenewable`]: Renewable::ForecastRenewable
+    #[must_use]
+    #[inline]
+    pub fn into_forecast(self) -> Option<ForecastRenewable> {
+        if let Self::ForecastRenewable(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Convert into the [`CurrentRenewable`] variant if it is one.
+    ///
+    /// [`CurrentRenewable`]: Renewable::CurrentRenewable
+    #[must_use]
+    #[inline]
+    pub fn into_current(self) -> Option<CurrentRenewable> {
+        if let Self::CurrentRenewable(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
     /// Returns the base renewable data.
+    ///
+    /// Returns `None` for [`Unknown`](Renewable::Unknown) renewables.
+    #[must_use]
+    #[inline]
+    pub fn as_base_renewable(&self) -> Option<&BaseRenewable> {
+        match self {
+            Self::ActualRenewable(actual) => Some(&actual.base),
+            Self::ForecastRenewable(forecast) => Some(&forecast.base),
+            Self::CurrentRenewable(current) => Some(&current.base),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// Convert into the base renewable data if it exists.
     #[must_use]
     #[inline]
-    pub fn as_base_renewable(&self) -> &BaseRenewable {
+    pub fn into_base_renewable(self) -> Option<BaseRenewable> {
         match self {
-            Self::ActualRenewable(actual) => &actual.base,
-            Self::ForecastRenewable(forecast) => &forecast.base,
-            Self::CurrentRenewable(current) => &current.base,
+            Self::ActualRenewable(actual) => Some(actual.base),
+            Self::ForecastRenewable(forecast) => Some(forecast.base),
+            Self::CurrentRenewable(current) => Some(current.base),
+            Self::Unknown(_) => None,
         }
     }
+
+    /// Returns the start time of the interval.
+    #[must_use]
+    #[inline]
+    pub fn start_time(&self) -> Option<Timestamp> {
+        self.as_base_renewable().map(|base| base.start_time)
+    }
+
+    /// Returns the end time of the interval.
+    #[must_use]
+    #[inline]
+    pub fn end_time(&self) -> Option<Timestamp> {
+        self.as_base_renewable().map(|base| base.end_time)
+    }
+
+    /// Returns the percentage of renewables in the grid.
+    #[must_use]
+    #[inline]
+    pub fn renewables(&self) -> Option<f64> {
+        self.as_base_renewable().map(|base| base.renewables)
+    }
 }
 
 impl fmt::Display for Renewable {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_precision(f, &PrecisionPolicy::DEFAULT)
+    }
+}
+
+impl DisplayPrecision for Renewable {
+    #[inline]
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result {
         match self {
-            Renewable::ActualRenewable(actual) => write!(f, "{actual}"),
-            Renewable::ForecastRenewable(forecast) => write!(f, "{forecast}"),
-            Renewable::CurrentRenewable(current) => write!(f, "{current}"),
+            Renewable::ActualRenewable(actual) => actual.fmt_precision(f, policy),
+            Renewable::ForecastRenewable(forecast) => forecast.fmt_precision(f, policy),
+            Renewable::CurrentRenewable(current) => current.fmt_precision(f, policy),
+            Renewable::Unknown(raw) => {
+                write!(f, "Unknown renewable ({})", raw_type(raw).unwrap_or("?"))
+            }
         }
     }
 }
 
+/// Returns the `type` tag of a raw JSON object.
+fn raw_type(raw: &serde_json::Value) -> Option<&str> {
+    raw.get("type").and_then(serde_json::Value::as_str)
+}
+
 #[cfg(test)]
 mod tests {
-    use alloc::{borrow::ToOwned as _, string::ToString as _, vec};
+    use alloc::{string::ToString as _, vec};
 
     use super::*;
     use anyhow::Result;
@@ -1047,6 +2094,10 @@ mod tests {
 
         let actual: ActualRenewable = serde_json::from_str(json)?;
         assert_eq!(actual.base.duration, 5);
+        assert_eq!(
+            actual.base.interval_duration(),
+            IntervalDuration::Known(Resolution::FiveMinute)
+        );
         assert_eq!(actual.base.date.to_string(), "2021-05-05");
         assert!(44.0_f64 < actual.base.renewables && actual.base.renewables < 46.0_f64);
         assert_eq!(actual.base.descriptor, RenewableDescriptor::Best);
@@ -1227,6 +2278,11 @@ mod tests {
             "2022-05-01"
         );
         assert_eq!(site.interval_length, 30);
+        assert_eq!(
+            site.interval_duration(),
+            IntervalDuration::Known(Resolution::ThirtyMinute)
+        );
+        assert_eq!(site.resolution(), Some(Resolution::ThirtyMinute));
 
         Ok(())
     }
@@ -1584,6 +2640,44 @@ mod tests {
         insta::assert_snapshot!(Resolution::ThirtyMinute.to_string(), @"30");
     }
 
+    #[test]
+    fn interval_duration() {
+        assert_eq!(
+            IntervalDuration::from(5),
+            IntervalDuration::Known(Resolution::FiveMinute)
+        );
+        assert_eq!(
+            IntervalDuration::from(30),
+            IntervalDuration::Known(Resolution::ThirtyMinute)
+        );
+        assert_eq!(IntervalDuration::from(15), IntervalDuration::Irregular(15));
+        assert_eq!(IntervalDuration::from(15).resolution(), None);
+        assert_eq!(
+            IntervalDuration::from(Resolution::ThirtyMinute).minutes(),
+            30
+        );
+        insta::assert_snapshot!(IntervalDuration::from(5).to_string(), @"5 min");
+        insta::assert_snapshot!(IntervalDuration::Irregular(15).to_string(), @"15 min");
+    }
+
+    #[test]
+    fn resolution_from_minutes() {
+        assert_eq!(Resolution::try_from(5), Ok(Resolution::FiveMinute));
+        assert_eq!(Resolution::try_from(30), Ok(Resolution::ThirtyMinute));
+        assert_eq!(
+            Resolution::try_from(15),
+            Err(InvalidResolution { minutes: 15 })
+        );
+        assert_eq!(
+            Resolution::try_from(IntervalDuration::Irregular(0)),
+            Err(InvalidResolution { minutes: 0 })
+        );
+        insta::assert_snapshot!(
+            InvalidResolution { minutes: 15 }.to_string(),
+            @"invalid resolution: 15 minutes (expected 5 or 30)"
+        );
+    }
+
     #[test]
     fn display_channel_type() {
         insta::assert_snapshot!(ChannelType::General.to_string(), @"general");
@@ -1594,9 +2688,9 @@ mod tests {
     #[test]
     fn display_channel() {
         let channel = Channel {
-            identifier: "E1".to_owned(),
+            identifier: "E1".parse().expect("valid channel identifier"),
             channel_type: ChannelType::General,
-            tariff: "A100".to_owned(),
+            tariff: "A100".into(),
         };
         insta::assert_snapshot!(channel.to_string(), @"E1 (general): A100");
     }
@@ -1612,10 +2706,10 @@ mod tests {
     fn display_site() {
         use jiff::civil::Date;
         let site = Site {
-            id: "01F5A5CRKMZ5BCX9P1S4V990AM".to_owned(),
-            nmi: "3052282872".to_owned(),
+            id: "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid site ID"),
+            nmi: "3052282872".parse().expect("valid NMI"),
             channels: vec![],
-            network: "Jemena".to_owned(),
+            network: "Jemena".into(),
             status: SiteStatus::Active,
             active_from: Some(Date::constant(2022, 1, 1)),
             closed_on: None,
@@ -1624,6 +2718,62 @@ mod tests {
         insta::assert_snapshot!(site.to_string(), @"Site 01F5A5CRKMZ5BCX9P1S4V990AM (NMI: 3052282872) - active on Jemena network");
     }
 
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn site_builder() {
+        use jiff::civil::Date;
+        let site = Site::builder()
+            .id("01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid site ID"))
+            .nmi("3052282872".parse().expect("valid NMI"))
+            .channels(vec![
+                Channel::builder()
+                    .identifier("E1".parse().expect("valid channel ID"))
+                    .channel_type(ChannelType::General)
+                    .tariff("A100")
+                    .build(),
+            ])
+            .network("Jemena")
+            .status(SiteStatus::Active)
+            .active_from(Date::constant(2022, 1, 1))
+            .interval_length(30)
+            .build();
+        assert!(site.is_active());
+        assert_eq!(site.closed_on, None);
+        insta::assert_snapshot!(site.to_string(), @"Site 01F5A5CRKMZ5BCX9P1S4V990AM (NMI: 3052282872) - active on Jemena network");
+    }
+
+    #[test]
+    fn site_lifecycle() {
+        use jiff::civil::Date;
+        let mut site = Site {
+            id: "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid site ID"),
+            nmi: "3052282872".parse().expect("valid NMI"),
+            channels: vec![],
+            network: "Jemena".into(),
+            status: SiteStatus::Closed,
+            active_from: Some(Date::constant(2022, 1, 1)),
+            closed_on: Some(Date::constant(2023, 6, 30)),
+            interval_length: 30,
+        };
+        assert!(!site.is_active());
+        assert!(!site.is_active_on(Date::constant(2021, 12, 31)));
+        assert!(site.is_active_on(Date::constant(2022, 1, 1)));
+        assert!(site.is_active_on(Date::constant(2023, 6, 30)));
+        assert!(!site.is_active_on(Date::constant(2023, 7, 1)));
+        insta::assert_snapshot!(site.lifetime().expect("site has a lifetime").to_string(), @"2022-01-01 to 2023-06-30");
+
+        site.status = SiteStatus::Active;
+        site.closed_on = None;
+        assert!(site.is_active());
+        assert!(site.is_active_on(Date::constant(2030, 1, 1)));
+        insta::assert_snapshot!(site.lifetime().expect("site has a lifetime").to_string(), @"2022-01-01 onwards");
+
+        site.status = SiteStatus::Pending;
+        site.active_from = None;
+        assert_eq!(site.lifetime(), None);
+        assert!(!site.is_active_on(Date::constant(2030, 1, 1)));
+    }
+
     #[test]
     fn display_spike_status() {
         insta::assert_snapshot!(SpikeStatus::None.to_string(), @"none");
@@ -1651,6 +2801,118 @@ mod tests {
         insta::assert_snapshot!(RenewableDescriptor::Worst.to_string(), @"worst");
     }
 
+    #[test]
+    fn unknown_variants() -> Result<()> {
+        let channel_type: ChannelType = serde_json::from_str(r#""batteryStorage""#)?;
+        assert_eq!(channel_type, ChannelType::Other("batteryStorage".into()));
+        insta::assert_snapshot!(channel_type.to_string(), @"batteryStorage");
+
+        let known: ChannelType = serde_json::from_str(r#""feedIn""#)?;
+        assert_eq!(known, ChannelType::FeedIn);
+
+        let status: SiteStatus = serde_json::from_str(r#""suspended""#)?;
+        assert_eq!(status, SiteStatus::Other("suspended".into()));
+
+        let spike_status: SpikeStatus = serde_json::from_str(r#""imminent""#)?;
+        assert_eq!(spike_status, SpikeStatus::Other("imminent".into()));
+
+        let descriptor: PriceDescriptor = serde_json::from_str(r#""free""#)?;
+        assert_eq!(descriptor, PriceDescriptor::Other("free".into()));
+
+        let renewable: RenewableDescriptor = serde_json::from_str(r#""perfect""#)?;
+        assert_eq!(renewable, RenewableDescriptor::Other("perfect".into()));
+
+        let period: TariffPeriod = serde_json::from_str(r#""superOffPeak""#)?;
+        assert_eq!(period, TariffPeriod::Other("superOffPeak".into()));
+
+        let season: TariffSeason = serde_json::from_str(r#""monsoon""#)?;
+        assert_eq!(season, TariffSeason::Other("monsoon".into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn variant_accessors() -> Result<()> {
+        let json = r#"{
+            "type": "CurrentInterval",
+            "duration": 5,
+            "spotPerKwh": 6.12,
+            "perKwh": 24.33,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-06T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45,
+            "channelType": "feedIn",
+            "spikeStatus": "none",
+            "descriptor": "low",
+            "estimate": true
+        }"#;
+        let interval: Interval = serde_json::from_str(json)?;
+        assert_eq!(interval.per_kwh(), Some(24.33_f64));
+        assert_eq!(interval.channel_type(), Some(&ChannelType::FeedIn));
+        assert_eq!(interval.descriptor(), Some(&PriceDescriptor::Low));
+        assert_eq!(interval.start_time(), Some("2021-05-05T02:00:01Z".parse()?));
+        assert_eq!(interval.clone().into_actual(), None);
+        assert_eq!(interval.clone().into_forecast(), None);
+
+        let current = interval.clone().into_current().expect("current interval");
+        assert!(current.estimate);
+        // Common fields are reachable through `Deref`.
+        assert!(24.0_f64 < current.per_kwh && current.per_kwh < 25.0_f64);
+        assert_eq!(current.channel_type, ChannelType::FeedIn);
+        assert_eq!(
+            interval.into_base_interval().map(|base| base.duration),
+            Some(5)
+        );
+
+        let renewable: Renewable = serde_json::from_str(
+            r#"{
+                "type": "ForecastRenewable",
+                "duration": 30,
+                "date": "2021-05-05",
+                "nemTime": "2021-05-06T12:30:00+10:00",
+                "startTime": "2021-05-05T02:00:01Z",
+                "endTime": "2021-05-05T02:30:00Z",
+                "renewables": 45,
+                "descriptor": "great"
+            }"#,
+        )?;
+        assert_eq!(renewable.renewables(), Some(45.0_f64));
+        assert_eq!(renewable.end_time(), Some("2021-05-05T02:30:00Z".parse()?));
+        assert_eq!(renewable.clone().into_current(), None);
+        let forecast = renewable.into_forecast().expect("forecast renewable");
+        assert_eq!(forecast.descriptor, RenewableDescriptor::Great);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_interval_accessors() -> Result<()> {
+        let intervals: Vec<Interval> =
+            serde_json::from_str(r#"[{"type": "ScheduledInterval", "perKwh": 12.5}]"#)?;
+        let interval = intervals.first().expect("one interval");
+        assert!(interval.is_unknown());
+        assert_eq!(interval.unknown_type(), Some("ScheduledInterval"));
+        assert_eq!(interval.as_base_interval(), None);
+        assert_eq!(
+            interval
+                .as_unknown()
+                .and_then(|raw| raw.get("perKwh"))
+                .and_then(serde_json::Value::as_f64),
+            Some(12.5_f64)
+        );
+        insta::assert_snapshot!(interval.to_string(), @"Unknown interval (ScheduledInterval)");
+
+        let renewable: Renewable = serde_json::from_str(r#"{"type": "ModelledRenewable"}"#)?;
+        assert!(renewable.is_unknown());
+        assert_eq!(renewable.unknown_type(), Some("ModelledRenewable"));
+        assert_eq!(renewable.as_base_renewable(), None);
+        insta::assert_snapshot!(renewable.to_string(), @"Unknown renewable (ModelledRenewable)");
+
+        Ok(())
+    }
+
     #[test]
     fn display_range() {
         let range = Range {
@@ -1692,6 +2954,46 @@ mod tests {
         insta::assert_snapshot!(TariffSeason::Weekday.to_string(), @"weekday");
     }
 
+    #[test]
+    fn zoned_interval_times() {
+        let interval: BaseInterval = serde_json::from_value(serde_json::json!({
+            "duration": 30_i32,
+            "spotPerKwh": 6.0_f64,
+            "perKwh": 24.0_f64,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-05T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:00Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45.0_f64,
+            "channelType": "general",
+            "spikeStatus": "none",
+            "descriptor": "low"
+        }))
+        .expect("valid interval");
+
+        assert_eq!(
+            interval.start_nem().datetime(),
+            jiff::civil::datetime(2021, 5, 5, 12, 0, 0, 0)
+        );
+        assert_eq!(interval.end_nem().timestamp(), interval.nem_time,);
+        let adelaide = TimeZone::fixed(tz::offset(9));
+        assert_eq!(
+            interval.start_in(&adelaide).datetime(),
+            jiff::civil::datetime(2021, 5, 5, 11, 0, 0, 0)
+        );
+        assert!(interval.contains("2021-05-05T02:15:00Z".parse().expect("valid time")));
+        assert!(!interval.contains(interval.end_time));
+        assert_eq!(interval.span().get_minutes(), 30);
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "Prices including GST are unchanged")]
+    fn price_views() {
+        assert_eq!(PriceView::default(), PriceView::IncGst);
+        assert_eq!(PriceView::IncGst.apply(22.0), 22.0_f64);
+        assert!((19.99_f64..20.01_f64).contains(&PriceView::ExGst.apply(22.0)));
+    }
+
     #[test]
     fn display_tariff_information() {
         // Test with no information
@@ -2029,7 +3331,7 @@ mod tests {
                 spike_status: SpikeStatus::None,
                 descriptor: PriceDescriptor::Low,
             },
-            channel_identifier: "E1".to_owned(),
+            channel_identifier: "E1".parse().expect("valid channel identifier"),
             kwh: 1.25,
             quality: UsageQuality::Billable,
             cost: 30.41,
@@ -0,0 +1,673 @@
+//! [`Arbitrary`] implementations for property testing.
+//!
+//! Generated values resemble those returned by the API rather than covering
+//! every representable value:
+//!
+//! - Intervals are 5 or 30 minutes long, aligned to NEM time between 2020 and
+//!   2030. The start time is one second after the previous interval ended, and
+//!   the NEM time is the end of the interval, as returned by the API.
+//! - Spot prices are mostly between 0 and 30 c/kWh, with occasional negative
+//!   prices and rarer spikes of up to 1500 c/kWh. Prices of the general and
+//!   controlled load channels include network fees, and feed-in prices are
+//!   the negative of the spot price.
+//! - Price descriptors and spike statuses are consistent with the spot price,
+//!   and renewable descriptors with the share of renewables.
+//! - Deprecated variants and values unknown to this library are never
+//!   generated.
+
+use alloc::format;
+
+use jiff::{Timestamp, civil::Date};
+use proptest::{
+    option,
+    prelude::{Arbitrary, BoxedStrategy, Just, Strategy, any, prop_oneof},
+};
+
+use super::{
+    ActualInterval, ActualRenewable, AdvancedPrice, BaseInterval, BaseRenewable, Channel,
+    ChannelId, ChannelType, CurrentInterval, CurrentRenewable, ForecastInterval, ForecastRenewable,
+    Interval, IntervalDuration, Nmi, PriceDescriptor, Range, Renewable, RenewableDescriptor,
+    Resolution, Site, SiteId, SiteStatus, SpikeStatus, State, TariffInformation, TariffPeriod,
+    TariffSeason, Usage, UsageQuality, nem_time_zone,
+};
+use crate::simulator::{cents, price_descriptor as descriptor, spike_status};
+
+/// Start of the generated intervals: 2020-01-01 00:00 in NEM time.
+const FIRST_SECOND: i64 = 1_577_800_800;
+
+/// Number of 5-minute intervals between 2020 and 2030.
+const FIVE_MINUTE_INTERVALS: i64 = 1_052_064;
+
+/// Times of an interval, as `(date, nem_time, start_time, end_time)`.
+type Times = (Date, Timestamp, Timestamp, Timestamp);
+
+/// Returns the times of an interval of the given length.
+fn times(minutes: u32) -> impl Strategy<Value = Times> {
+    let seconds = i64::from(minutes).saturating_mul(60);
+    let count = FIVE_MINUTE_INTERVALS
+        .saturating_mul(5)
+        .checked_div(i64::from(minutes))
+        .unwrap_or_default();
+    (1..=count).prop_filter_map("interval in range", move |index| {
+        let end =
+            Timestamp::from_second(FIRST_SECOND.saturating_add(index.saturating_mul(seconds)))
+                .ok()?;
+        let start =
+            Timestamp::from_second(end.as_second().saturating_sub(seconds).saturating_add(1))
+                .ok()?;
+        let date = start.to_zoned(nem_time_zone()).date();
+        Some((date, end, start, end))
+    })
+}
+
+/// Returns the renewable descriptor of a share of renewables (%).
+fn renewable_descriptor(renewables: f64) -> RenewableDescriptor {
+    if renewables >= 80.0 {
+        RenewableDescriptor::Best
+    } else if renewables >= 60.0 {
+        RenewableDescriptor::Great
+    } else if renewables >= 40.0 {
+        RenewableDescriptor::Ok
+    } else if renewables >= 20.0 {
+        RenewableDescriptor::NotGreat
+    } else {
+        RenewableDescriptor::Worst
+    }
+}
+
+/// Returns realistic spot prices (c/kWh).
+fn spot_price() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        80 => 0.0..30.0_f64,
+        10 => -20.0..0.0_f64,
+        8 => 30.0..300.0_f64,
+        2 => 300.0..1500.0_f64,
+    ]
+    .prop_map(cents)
+}
+
+/// Returns a price band around a price.
+#[expect(clippy::float_arithmetic, reason = "Widening a price into a band")]
+fn band(per_kwh: f64) -> impl Strategy<Value = (f64, f64)> {
+    (0.0..20.0_f64, 0.0..50.0_f64)
+        .prop_map(move |(below, above)| (cents(per_kwh - below), cents(per_kwh + above)))
+}
+
+impl Arbitrary for State {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            Just(Self::Nsw),
+            Just(Self::Vic),
+            Just(Self::Qld),
+            Just(Self::Sa)
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Resolution {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![Just(Self::FiveMinute), Just(Self::ThirtyMinute)].boxed()
+    }
+}
+
+impl Arbitrary for IntervalDuration {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        any::<Resolution>().prop_map(Self::Known).boxed()
+    }
+}
+
+impl Arbitrary for ChannelType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            6 => Just(Self::General),
+            1 => Just(Self::ControlledLoad),
+            3 => Just(Self::FeedIn),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for SiteStatus {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            1 => Just(Self::Pending),
+            8 => Just(Self::Active),
+            1 => Just(Self::Closed),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for SpikeStatus {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        spot_price().prop_map(spike_status).boxed()
+    }
+}
+
+impl Arbitrary for PriceDescriptor {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        spot_price().prop_map(descriptor).boxed()
+    }
+}
+
+impl Arbitrary for RenewableDescriptor {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (0.0..100.0_f64).prop_map(renewable_descriptor).boxed()
+    }
+}
+
+impl Arbitrary for TariffPeriod {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            Just(Self::OffPeak),
+            Just(Self::Shoulder),
+            Just(Self::SolarSponge),
+            Just(Self::Peak),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for TariffSeason {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            Just(Self::Default),
+            Just(Self::Summer),
+            Just(Self::Autumn),
+            Just(Self::Winter),
+            Just(Self::Spring),
+            Just(Self::NonSummer),
+            Just(Self::Holiday),
+            Just(Self::Weekend),
+            Just(Self::WeekendHoliday),
+            Just(Self::Weekday),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for UsageQuality {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            1 => Just(Self::Estimated),
+            4 => Just(Self::Billable),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for SiteId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        "01[0-9A-HJKMNP-TV-Z]{24}"
+            .prop_filter_map("valid site ID", |id| Self::new(id).ok())
+            .boxed()
+    }
+}
+
+impl Arbitrary for Nmi {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        "[2-7][0-9]{9}[0-9]?"
+            .prop_filter_map("valid NMI", |nmi| Self::new(nmi).ok())
+            .boxed()
+    }
+}
+
+impl Arbitrary for ChannelId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        "[EB][1-3]"
+            .prop_filter_map("valid channel ID", |id| Self::new(id).ok())
+            .boxed()
+    }
+}
+
+impl Arbitrary for Channel {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (any::<ChannelType>(), 1..=3_u8, "[A-Z][0-9]{2,3}")
+            .prop_filter_map("valid channel", |(channel_type, number, tariff)| {
+                let prefix = if channel_type == ChannelType::FeedIn {
+                    'B'
+                } else {
+                    'E'
+                };
+                Some(Self {
+                    identifier: ChannelId::new(format!("{prefix}{number}")).ok()?,
+                    channel_type,
+                    tariff: tariff.into(),
+                })
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Site {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            any::<SiteId>(),
+            any::<Nmi>(),
+            proptest::collection::vec(any::<Channel>(), 1..=3),
+            prop_oneof![
+                Just("Ausgrid"),
+                Just("Endeavour"),
+                Just("Energex"),
+                Just("Jemena"),
+                Just("SA Power Networks"),
+                Just("United"),
+            ],
+            any::<SiteStatus>(),
+            0..3650_i32,
+            0..1825_i32,
+            any::<Resolution>(),
+        )
+            .prop_map(
+                |(id, nmi, channels, network, status, active_days, closed_days, resolution)| {
+                    let start = Date::constant(2019, 1, 1);
+                    let active_from = start.checked_add(jiff::Span::new().days(active_days)).ok();
+                    let closed_on = if status == SiteStatus::Closed {
+                        active_from.and_then(|from| {
+                            from.checked_add(jiff::Span::new().days(closed_days)).ok()
+                        })
+                    } else {
+                        None
+                    };
+                    Self {
+                        id,
+                        nmi,
+                        channels,
+                        network: network.into(),
+                        status,
+                        active_from,
+                        closed_on,
+                        interval_length: resolution.into(),
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Range {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        spot_price()
+            .prop_flat_map(band)
+            .prop_map(|(min, max)| Self { min, max })
+            .boxed()
+    }
+}
+
+impl Arbitrary for AdvancedPrice {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        spot_price()
+            .prop_flat_map(|predicted| {
+                band(predicted).prop_map(move |(low, high)| Self {
+                    low,
+                    predicted,
+                    high,
+                })
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TariffInformation {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            option::of(any::<TariffPeriod>()),
+            option::of(any::<TariffSeason>()),
+            option::of(1..=3_u32),
+            option::of(any::<bool>()),
+        )
+            .prop_map(|(period, season, block, demand_window)| Self {
+                period,
+                season,
+                block,
+                demand_window,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for BaseInterval {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        any::<Resolution>()
+            .prop_flat_map(|resolution| {
+                let duration = u32::from(resolution);
+                (
+                    times(duration),
+                    spot_price(),
+                    5.0_f64..35.0_f64,
+                    0.0_f64..100.0_f64,
+                    any::<ChannelType>(),
+                    option::of(any::<TariffInformation>()),
+                )
+                    .prop_map(
+                        move |(
+                            (date, nem_time, start_time, end_time),
+                            spot_per_kwh,
+                            network,
+                            renewables,
+                            channel_type,
+                            tariff_information,
+                        )| {
+                            #[expect(
+                                clippy::float_arithmetic,
+                                reason = "Adding network fees to the spot price"
+                            )]
+                            let per_kwh = if channel_type == ChannelType::FeedIn {
+                                -spot_per_kwh
+                            } else {
+                                cents(spot_per_kwh + network)
+                            };
+                            Self {
+                                duration,
+                                spot_per_kwh,
+                                per_kwh,
+                                date,
+                                nem_time,
+                                start_time,
+                                end_time,
+                                renewables: cents(renewables),
+                                channel_type,
+                                tariff_information,
+                                spike_status: spike_status(spot_per_kwh),
+                                descriptor: descriptor(spot_per_kwh),
+                            }
+                        },
+                    )
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ActualInterval {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        any::<BaseInterval>().prop_map(|base| Self { base }).boxed()
+    }
+}
+
+impl Arbitrary for ForecastInterval {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            any::<BaseInterval>(),
+            option::weighted(0.2, any::<Range>()),
+            option::of(any::<AdvancedPrice>()),
+        )
+            .prop_map(|(base, range, advanced_price)| Self {
+                base,
+                range,
+                advanced_price,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CurrentInterval {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            any::<BaseInterval>(),
+            option::weighted(0.2, any::<Range>()),
+            any::<bool>(),
+            option::of(any::<AdvancedPrice>()),
+        )
+            .prop_map(|(base, range, estimate, advanced_price)| Self {
+                base,
+                range,
+                estimate,
+                advanced_price,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Interval {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            any::<ActualInterval>().prop_map(Self::ActualInterval),
+            any::<ForecastInterval>().prop_map(Self::ForecastInterval),
+            any::<CurrentInterval>().prop_map(Self::CurrentInterval),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Usage {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (
+            any::<BaseInterval>(),
+            any::<ChannelId>(),
+            0.0..3.0_f64,
+            any::<UsageQuality>(),
+        )
+            .prop_map(|(base, channel_identifier, energy, quality)| {
+                // Generation is reported as negative energy
+                #[expect(clippy::float_arithmetic, reason = "Computing the cost of the energy")]
+                let (kwh, cost) = {
+                    let kwh = if base.channel_type == ChannelType::FeedIn {
+                        -cents(energy)
+                    } else {
+                        cents(energy)
+                    };
+                    (kwh, cents(kwh * base.per_kwh))
+                };
+                Self {
+                    base,
+                    channel_identifier,
+                    kwh,
+                    quality,
+                    cost,
+                }
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for BaseRenewable {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        any::<Resolution>()
+            .prop_flat_map(|resolution| {
+                let duration = u32::from(resolution);
+                (times(duration), 0.0_f64..100.0_f64).prop_map(
+                    move |((date, nem_time, start_time, end_time), renewables)| Self {
+                        duration,
+                        date,
+                        nem_time,
+                        start_time,
+                        end_time,
+                        renewables: cents(renewables),
+                        descriptor: renewable_descriptor(renewables),
+                    },
+                )
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ActualRenewable {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        any::<BaseRenewable>()
+            .prop_map(|base| Self { base })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ForecastRenewable {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        any::<BaseRenewable>()
+            .prop_map(|base| Self { base })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CurrentRenewable {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        any::<BaseRenewable>()
+            .prop_map(|base| Self { base })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Renewable {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    #[inline]
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        prop_oneof![
+            any::<ActualRenewable>().prop_map(Self::ActualRenewable),
+            any::<ForecastRenewable>().prop_map(Self::ForecastRenewable),
+            any::<CurrentRenewable>().prop_map(Self::CurrentRenewable),
+        ]
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{prop_assert, prop_assert_eq, proptest};
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn intervals_are_realistic(interval in any::<Interval>()) {
+            let base = interval.as_base_interval().expect("known interval");
+            prop_assert_eq!(base.nem_time, base.end_time);
+            prop_assert_eq!(
+                base.end_time.as_second().saturating_sub(base.start_time.as_second()).saturating_add(1),
+                i64::from(base.duration).saturating_mul(60)
+            );
+            prop_assert!(matches!(base.duration, 5 | 30));
+            prop_assert_eq!(base.date, base.start_time.to_zoned(nem_time_zone()).date());
+            prop_assert_eq!(&base.descriptor, &descriptor(base.spot_per_kwh));
+        }
+
+        #[test]
+        fn models_round_trip(site in any::<Site>(), usage in any::<Usage>(), renewable in any::<Renewable>()) {
+            let site_json = serde_json::to_string(&site).expect("serializable");
+            prop_assert_eq!(serde_json::from_str::<Site>(&site_json).expect("valid site"), site);
+            let usage_json = serde_json::to_string(&usage).expect("serializable");
+            prop_assert_eq!(serde_json::from_str::<Usage>(&usage_json).expect("valid usage"), usage);
+            let renewable_json = serde_json::to_string(&renewable).expect("serializable");
+            prop_assert_eq!(
+                serde_json::from_str::<Renewable>(&renewable_json).expect("valid renewable"),
+                renewable
+            );
+        }
+    }
+}
@@ -0,0 +1,814 @@
+//! # Single-pass deserialisation of tagged enums
+//!
+//! The [`Interval`] and [`Renewable`] enums are internally tagged by the
+//! `type` field, and each variant flattens a shared base structure. Deriving
+//! [`Deserialize`] for that shape causes serde to buffer the whole object once
+//! to locate the tag, and a second time to resolve the flattened fields.
+//!
+//! The visitors in this module instead read the tag and every field directly
+//! from the underlying map in a single pass, only assembling the appropriate
+//! variant once the whole object has been consumed. Only the fields preceding
+//! the tag need to be buffered; the API sends the tag first, so in practice
+//! nothing is. The resulting behaviour (accepted inputs, ignored unknown fields
+//! and error conditions) matches the derived implementation, except that an
+//! unrecognised tag produces an `Unknown` variant holding the raw object
+//! instead of an error.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use compact_str::CompactString;
+use jiff::{Timestamp, civil::Date};
+use serde::{
+    Deserialize, Deserializer,
+    de::{self, DeserializeSeed, Error as _, IgnoredAny, MapAccess, Visitor},
+};
+use serde_json::{Map, Value};
+
+use super::{
+    ActualInterval, ActualRenewable, AdvancedPrice, BaseInterval, BaseRenewable, ChannelType,
+    CurrentInterval, CurrentRenewable, ForecastInterval, ForecastRenewable, Interval,
+    PriceDescriptor, Range, Renewable, RenewableDescriptor, SpikeStatus, TariffInformation,
+};
+
+/// Field identifiers found across all interval and renewable payloads.
+#[derive(Clone, Copy)]
+enum Field {
+    /// The `type` tag.
+    Type,
+    /// The `duration` field.
+    Duration,
+    /// The `spotPerKwh` field.
+    SpotPerKwh,
+    /// The `perKwh` field.
+    PerKwh,
+    /// The `date` field.
+    Date,
+    /// The `nemTime` field.
+    NemTime,
+    /// The `startTime` field.
+    StartTime,
+    /// The `endTime` field.
+    EndTime,
+    /// The `renewables` field.
+    Renewables,
+    /// The `channelType` field.
+    ChannelType,
+    /// The `tariffInformation` field.
+    TariffInformation,
+    /// The `spikeStatus` field.
+    SpikeStatus,
+    /// The `descriptor` field.
+    Descriptor,
+    /// The `range` field.
+    Range,
+    /// The `estimate` field.
+    Estimate,
+    /// The `advancedPrice` field.
+    AdvancedPrice,
+    /// Any other field, which is ignored.
+    Other,
+}
+
+impl Field {
+    /// Map a field name to its identifier.
+    #[cfg_attr(
+        feature = "no-panic",
+        inline(never),
+        no_panic::no_panic,
+        expect(clippy::mem_forget, reason = "Expanded by no_panic")
+    )]
+    fn from_key(key: &str) -> Self {
+        match key {
+            "type" => Self::Type,
+            "duration" => Self::Duration,
+            "spotPerKwh" => Self::SpotPerKwh,
+            "perKwh" => Self::PerKwh,
+            "date" => Self::Date,
+            "nemTime" => Self::NemTime,
+            "startTime" => Self::StartTime,
+            "endTime" => Self::EndTime,
+            "renewables" => Self::Renewables,
+            "channelType" => Self::ChannelType,
+            "tariffInformation" => Self::TariffInformation,
+            "spikeStatus" => Self::SpikeStatus,
+            "descriptor" => Self::Descriptor,
+            "range" => Self::Range,
+            "estimate" => Self::Estimate,
+            "advancedPrice" => Self::AdvancedPrice,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Field {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+/// Visitor mapping field names to [`Field`] identifiers.
+struct FieldVisitor;
+
+impl Visitor<'_> for FieldVisitor {
+    type Value = Field;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a field identifier")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Field, E> {
+        Ok(Field::from_key(v))
+    }
+}
+
+/// Known values of the `type` tag for [`Interval`].
+#[derive(Clone, Copy)]
+enum IntervalKind {
+    /// Tag for [`Interval::ActualInterval`].
+    Actual,
+    /// Tag for [`Interval::ForecastInterval`].
+    Forecast,
+    /// Tag for [`Interval::CurrentInterval`].
+    Current,
+}
+
+/// Known values of the `type` tag for [`Renewable`].
+#[derive(Clone, Copy)]
+enum RenewableKind {
+    /// Tag for [`Renewable::ActualRenewable`].
+    Actual,
+    /// Tag for [`Renewable::ForecastRenewable`].
+    Forecast,
+    /// Tag for [`Renewable::CurrentRenewable`].
+    Current,
+}
+
+/// Accumulator for the fields of a tagged payload.
+trait Fields: Default {
+    /// Known values of the `type` tag.
+    type Kind;
+
+    /// Parse the `type` tag, returning `None` if it is not recognised.
+    fn kind(tag: &str) -> Option<Self::Kind>;
+
+    /// Deserialise the value of `field` into the matching slot.
+    fn assign<'de, D: Deserializer<'de>>(
+        &mut self,
+        field: Field,
+        deserializer: D,
+    ) -> Result<(), D::Error>;
+}
+
+/// Seed deserialising a single map value into a [`Fields`] accumulator.
+struct Slot<'a, F> {
+    /// The accumulator to fill.
+    fields: &'a mut F,
+    /// The field being deserialised.
+    field: Field,
+}
+
+impl<'de, F: Fields> DeserializeSeed<'de> for Slot<'_, F> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.fields.assign(self.field, deserializer)
+    }
+}
+
+/// Outcome of reading a tagged payload.
+enum Tagged<F: Fields> {
+    /// The tag was recognised and all fields were read.
+    Known(F::Kind, F),
+    /// The tag was not recognised; the raw object is preserved.
+    Unknown(Value),
+}
+
+/// Deserialise the next value into `slot`, rejecting duplicate fields.
+#[inline]
+fn fill<'de, D, T>(
+    slot: &mut Option<T>,
+    name: &'static str,
+    deserializer: D,
+) -> Result<(), D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    if slot.is_some() {
+        return Err(de::Error::duplicate_field(name));
+    }
+    *slot = Some(T::deserialize(deserializer)?);
+    Ok(())
+}
+
+/// Read a tagged payload from `map` in a single pass.
+///
+/// Fields preceding the tag are buffered as raw JSON. Once the tag is known,
+/// buffered fields are replayed into the accumulator and all remaining fields
+/// are deserialised directly. If the tag is not recognised, the remaining
+/// fields are also read as raw JSON and the whole object is returned.
+fn visit_tagged<'de, A, F>(mut map: A) -> Result<Tagged<F>, A::Error>
+where
+    A: MapAccess<'de>,
+    F: Fields,
+{
+    let mut pending: Vec<(CompactString, Value)> = Vec::new();
+    let tag = loop {
+        let Some(key) = map.next_key::<CompactString>()? else {
+            return Err(A::Error::missing_field("type"));
+        };
+        if key == "type" {
+            break map.next_value::<CompactString>()?;
+        }
+        pending.push((key, map.next_value()?));
+    };
+
+    let Some(kind) = F::kind(&tag) else {
+        let mut raw: Map<String, Value> = pending
+            .into_iter()
+            .map(|(key, value)| (String::from(key), value))
+            .collect();
+        raw.insert(String::from("type"), Value::String(String::from(tag)));
+        while let Some(key) = map.next_key::<CompactString>()? {
+            if key == "type" {
+                return Err(A::Error::duplicate_field("type"));
+            }
+            raw.insert(String::from(key), map.next_value()?);
+        }
+        return Ok(Tagged::Unknown(Value::Object(raw)));
+    };
+
+    let mut fields = F::default();
+    for (key, value) in pending {
+        fields
+            .assign(Field::from_key(&key), value)
+            .map_err(A::Error::custom)?;
+    }
+    while let Some(field) = map.next_key::<Field>()? {
+        if matches!(field, Field::Type) {
+            return Err(A::Error::duplicate_field("type"));
+        }
+        map.next_value_seed(Slot {
+            fields: &mut fields,
+            field,
+        })?;
+    }
+    Ok(Tagged::Known(kind, fields))
+}
+
+/// Fields of an [`Interval`] payload.
+#[derive(Default)]
+#[expect(
+    clippy::option_option,
+    reason = "A field set to null is recorded, to reject it if repeated"
+)]
+struct IntervalFields {
+    /// The `duration` field.
+    duration: Option<u32>,
+    /// The `spotPerKwh` field.
+    spot_per_kwh: Option<f64>,
+    /// The `perKwh` field.
+    per_kwh: Option<f64>,
+    /// The `date` field.
+    date: Option<Date>,
+    /// The `nemTime` field.
+    nem_time: Option<Timestamp>,
+    /// The `startTime` field.
+    start_time: Option<Timestamp>,
+    /// The `endTime` field.
+    end_time: Option<Timestamp>,
+    /// The `renewables` field.
+    renewables: Option<f64>,
+    /// The `channelType` field.
+    channel_type: Option<ChannelType>,
+    /// The `tariffInformation` field.
+    tariff_information: Option<Option<TariffInformation>>,
+    /// The `spikeStatus` field.
+    spike_status: Option<SpikeStatus>,
+    /// The `descriptor` field.
+    descriptor: Option<PriceDescriptor>,
+    /// The `range` field.
+    range: Option<Option<Range>>,
+    /// The `estimate` field.
+    estimate: Option<bool>,
+    /// The `advancedPrice` field.
+    advanced_price: Option<Option<AdvancedPrice>>,
+}
+
+impl Fields for IntervalFields {
+    type Kind = IntervalKind;
+
+    #[inline]
+    fn kind(tag: &str) -> Option<IntervalKind> {
+        match tag {
+            "ActualInterval" => Some(IntervalKind::Actual),
+            "ForecastInterval" => Some(IntervalKind::Forecast),
+            "CurrentInterval" => Some(IntervalKind::Current),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn assign<'de, D: Deserializer<'de>>(
+        &mut self,
+        field: Field,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        match field {
+            Field::Duration => fill(&mut self.duration, "duration", deserializer),
+            Field::SpotPerKwh => fill(&mut self.spot_per_kwh, "spotPerKwh", deserializer),
+            Field::PerKwh => fill(&mut self.per_kwh, "perKwh", deserializer),
+            Field::Date => fill(&mut self.date, "date", deserializer),
+            Field::NemTime => fill(&mut self.nem_time, "nemTime", deserializer),
+            Field::StartTime => fill(&mut self.start_time, "startTime", deserializer),
+            Field::EndTime => fill(&mut self.end_time, "endTime", deserializer),
+            Field::Renewables => fill(&mut self.renewables, "renewables", deserializer),
+            Field::ChannelType => fill(&mut self.channel_type, "channelType", deserializer),
+            Field::TariffInformation => fill(
+                &mut self.tariff_information,
+                "tariffInformation",
+                deserializer,
+            ),
+            Field::SpikeStatus => fill(&mut self.spike_status, "spikeStatus", deserializer),
+            Field::Descriptor => fill(&mut self.descriptor, "descriptor", deserializer),
+            Field::Range => fill(&mut self.range, "range", deserializer),
+            Field::Estimate => fill(&mut self.estimate, "estimate", deserializer),
+            Field::AdvancedPrice => fill(&mut self.advanced_price, "advancedPrice", deserializer),
+            Field::Type | Field::Other => IgnoredAny::deserialize(deserializer).map(|_| ()),
+        }
+    }
+}
+
+impl IntervalFields {
+    /// Assemble the interval, reporting any missing fields.
+    fn build<E: de::Error>(self, kind: IntervalKind) -> Result<Interval, E> {
+        let missing = E::missing_field;
+        let base = BaseInterval {
+            duration: self.duration.ok_or_else(|| missing("duration"))?,
+            spot_per_kwh: self.spot_per_kwh.ok_or_else(|| missing("spotPerKwh"))?,
+            per_kwh: self.per_kwh.ok_or_else(|| missing("perKwh"))?,
+            date: self.date.ok_or_else(|| missing("date"))?,
+            nem_time: self.nem_time.ok_or_else(|| missing("nemTime"))?,
+            start_time: self.start_time.ok_or_else(|| missing("startTime"))?,
+            end_time: self.end_time.ok_or_else(|| missing("endTime"))?,
+            renewables: self.renewables.ok_or_else(|| missing("renewables"))?,
+            channel_type: self.channel_type.ok_or_else(|| missing("channelType"))?,
+            tariff_information: self.tariff_information.flatten(),
+            spike_status: self.spike_status.ok_or_else(|| missing("spikeStatus"))?,
+            descriptor: self.descriptor.ok_or_else(|| missing("descriptor"))?,
+        };
+
+        Ok(match kind {
+            IntervalKind::Actual => Interval::ActualInterval(ActualInterval { base }),
+            IntervalKind::Forecast => Interval::ForecastInterval(ForecastInterval {
+                base,
+                range: self.range.flatten(),
+                advanced_price: self.advanced_price.flatten(),
+            }),
+            IntervalKind::Current => Interval::CurrentInterval(CurrentInterval {
+                base,
+                range: self.range.flatten(),
+                estimate: self.estimate.ok_or_else(|| missing("estimate"))?,
+                advanced_price: self.advanced_price.flatten(),
+            }),
+        })
+    }
+}
+
+/// Single-pass visitor for [`Interval`].
+struct IntervalVisitor;
+
+impl<'de> Visitor<'de> for IntervalVisitor {
+    type Value = Interval;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an interval object")
+    }
+
+    #[inline]
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Interval, A::Error> {
+        match visit_tagged::<A, IntervalFields>(map)? {
+            Tagged::Known(kind, fields) => fields.build(kind),
+            Tagged::Unknown(raw) => Ok(Interval::Unknown(raw)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Interval {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(IntervalVisitor)
+    }
+}
+
+/// Fields of a [`Renewable`] payload.
+#[derive(Default)]
+struct RenewableFields {
+    /// The `duration` field.
+    duration: Option<u32>,
+    /// The `date` field.
+    date: Option<Date>,
+    /// The `nemTime` field.
+    nem_time: Option<Timestamp>,
+    /// The `startTime` field.
+    start_time: Option<Timestamp>,
+    /// The `endTime` field.
+    end_time: Option<Timestamp>,
+    /// The `renewables` field.
+    renewables: Option<f64>,
+    /// The `descriptor` field.
+    descriptor: Option<RenewableDescriptor>,
+}
+
+impl Fields for RenewableFields {
+    type Kind = RenewableKind;
+
+    #[inline]
+    fn kind(tag: &str) -> Option<RenewableKind> {
+        match tag {
+            "ActualRenewable" => Some(RenewableKind::Actual),
+            "ForecastRenewable" => Some(RenewableKind::Forecast),
+            "CurrentRenewable" => Some(RenewableKind::Current),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn assign<'de, D: Deserializer<'de>>(
+        &mut self,
+        field: Field,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        match field {
+            Field::Duration => fill(&mut self.duration, "duration", deserializer),
+            Field::Date => fill(&mut self.date, "date", deserializer),
+            Field::NemTime => fill(&mut self.nem_time, "nemTime", deserializer),
+            Field::StartTime => fill(&mut self.start_time, "startTime", deserializer),
+            Field::EndTime => fill(&mut self.end_time, "endTime", deserializer),
+            Field::Renewables => fill(&mut self.renewables, "renewables", deserializer),
+            Field::Descriptor => fill(&mut self.descriptor, "descriptor", deserializer),
+            Field::Type
+            | Field::SpotPerKwh
+            | Field::PerKwh
+            | Field::ChannelType
+            | Field::TariffInformation
+            | Field::SpikeStatus
+            | Field::Range
+            | Field::Estimate
+            | Field::AdvancedPrice
+            | Field::Other => IgnoredAny::deserialize(deserializer).map(|_| ()),
+        }
+    }
+}
+
+impl RenewableFields {
+    /// Assemble the renewable, reporting any missing fields.
+    fn build<E: de::Error>(self, kind: RenewableKind) -> Result<Renewable, E> {
+        let missing = E::missing_field;
+        let base = BaseRenewable {
+            duration: self.duration.ok_or_else(|| missing("duration"))?,
+            date: self.date.ok_or_else(|| missing("date"))?,
+            nem_time: self.nem_time.ok_or_else(|| missing("nemTime"))?,
+            start_time: self.start_time.ok_or_else(|| missing("startTime"))?,
+            end_time: self.end_time.ok_or_else(|| missing("endTime"))?,
+            renewables: self.renewables.ok_or_else(|| missing("renewables"))?,
+            descriptor: self.descriptor.ok_or_else(|| missing("descriptor"))?,
+        };
+
+        Ok(match kind {
+            RenewableKind::Actual => Renewable::ActualRenewable(ActualRenewable { base }),
+            RenewableKind::Forecast => Renewable::ForecastRenewable(ForecastRenewable { base }),
+            RenewableKind::Current => Renewable::CurrentRenewable(CurrentRenewable { base }),
+        })
+    }
+}
+
+/// Single-pass visitor for [`Renewable`].
+struct RenewableVisitor;
+
+impl<'de> Visitor<'de> for RenewableVisitor {
+    type Value = Renewable;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a renewable object")
+    }
+
+    #[inline]
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Renewable, A::Error> {
+        match visit_tagged::<A, RenewableFields>(map)? {
+            Tagged::Known(kind, fields) => fields.build(kind),
+            Tagged::Unknown(raw) => Ok(Renewable::Unknown(raw)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Renewable {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(RenewableVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+    use alloc::vec::Vec;
+
+    use super::*;
+    use anyhow::Result;
+    use pretty_assertions::assert_eq;
+
+    /// Reference implementation using the derived, double-buffering approach.
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(tag = "type")]
+    #[expect(clippy::enum_variant_names, reason = "Named after the tags of the API")]
+    enum DerivedInterval {
+        ActualInterval(ActualInterval),
+        ForecastInterval(ForecastInterval),
+        CurrentInterval(CurrentInterval),
+    }
+
+    impl From<DerivedInterval> for Interval {
+        #[inline]
+        fn from(value: DerivedInterval) -> Self {
+            match value {
+                DerivedInterval::ActualInterval(v) => Self::ActualInterval(v),
+                DerivedInterval::ForecastInterval(v) => Self::ForecastInterval(v),
+                DerivedInterval::CurrentInterval(v) => Self::CurrentInterval(v),
+            }
+        }
+    }
+
+    /// Reference implementation using the derived, double-buffering approach.
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(tag = "type")]
+    #[expect(clippy::enum_variant_names, reason = "Named after the tags of the API")]
+    enum DerivedRenewable {
+        ActualRenewable(ActualRenewable),
+        ForecastRenewable(ForecastRenewable),
+        CurrentRenewable(CurrentRenewable),
+    }
+
+    impl From<DerivedRenewable> for Renewable {
+        #[inline]
+        fn from(value: DerivedRenewable) -> Self {
+            match value {
+                DerivedRenewable::ActualRenewable(v) => Self::ActualRenewable(v),
+                DerivedRenewable::ForecastRenewable(v) => Self::ForecastRenewable(v),
+                DerivedRenewable::CurrentRenewable(v) => Self::CurrentRenewable(v),
+            }
+        }
+    }
+
+    const INTERVALS: &str = r#"[
+        {
+            "type": "ActualInterval",
+            "duration": 5,
+            "spotPerKwh": 6.12,
+            "perKwh": 24.33,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-06T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45,
+            "channelType": "general",
+            "tariffInformation": null,
+            "spikeStatus": "none",
+            "descriptor": "negative"
+        },
+        {
+            "duration": 30,
+            "spotPerKwh": 6.12,
+            "perKwh": 24.33,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-06T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45,
+            "channelType": "feedIn",
+            "tariffInformation": {
+                "period": "peak",
+                "season": "summer",
+                "block": 2,
+                "demandWindow": true
+            },
+            "spikeStatus": "potential",
+            "descriptor": "high",
+            "range": {"min": 0, "max": 10},
+            "estimate": true,
+            "advancedPrice": {"low": 1, "predicted": 3, "high": 10},
+            "type": "CurrentInterval"
+        },
+        {
+            "type": "ForecastInterval",
+            "duration": 5,
+            "spotPerKwh": 6.12,
+            "perKwh": 24.33,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-06T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45,
+            "channelType": "controlledLoad",
+            "spikeStatus": "spike",
+            "descriptor": "spike",
+            "unknownField": {"nested": [1, 2, 3]}
+        }
+    ]"#;
+
+    const RENEWABLES: &str = r#"[
+        {
+            "type": "ActualRenewable",
+            "duration": 5,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-06T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45,
+            "descriptor": "best"
+        },
+        {
+            "duration": 30,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-06T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 12.5,
+            "descriptor": "notGreat",
+            "type": "ForecastRenewable"
+        },
+        {
+            "type": "CurrentRenewable",
+            "duration": 5,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-06T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 99.9,
+            "descriptor": "worst",
+            "extra": null
+        }
+    ]"#;
+
+    #[test]
+    fn interval_matches_derive() -> Result<()> {
+        let manual: Vec<Interval> = serde_json::from_str(INTERVALS)?;
+        let parsed: Vec<DerivedInterval> = serde_json::from_str(INTERVALS)?;
+        let derived: Vec<Interval> = parsed.into_iter().map(Interval::from).collect();
+
+        assert_eq!(manual.len(), 3);
+        assert_eq!(manual, derived);
+        Ok(())
+    }
+
+    #[test]
+    fn renewable_matches_derive() -> Result<()> {
+        let manual: Vec<Renewable> = serde_json::from_str(RENEWABLES)?;
+        let parsed: Vec<DerivedRenewable> = serde_json::from_str(RENEWABLES)?;
+        let derived: Vec<Renewable> = parsed.into_iter().map(Renewable::from).collect();
+
+        assert_eq!(manual.len(), 3);
+        assert_eq!(manual, derived);
+        Ok(())
+    }
+
+    #[test]
+    fn interval_errors_match_derive() {
+        let cases = [
+            // Missing tag
+            r#"{"duration": 5}"#,
+            // Missing required field
+            r#"{
+                "type": "ActualInterval",
+                "duration": 5,
+                "perKwh": 24.33,
+                "date": "2021-05-05",
+                "nemTime": "2021-05-06T12:30:00+10:00",
+                "startTime": "2021-05-05T02:00:01Z",
+                "endTime": "2021-05-05T02:30:00Z",
+                "renewables": 45,
+                "channelType": "general",
+                "spikeStatus": "none",
+                "descriptor": "low"
+            }"#,
+            // Current interval without an estimate
+            r#"{
+                "type": "CurrentInterval",
+                "duration": 5,
+                "spotPerKwh": 6.12,
+                "perKwh": 24.33,
+                "date": "2021-05-05",
+                "nemTime": "2021-05-06T12:30:00+10:00",
+                "startTime": "2021-05-05T02:00:01Z",
+                "endTime": "2021-05-05T02:30:00Z",
+                "renewables": 45,
+                "channelType": "general",
+                "spikeStatus": "none",
+                "descriptor": "low"
+            }"#,
+            // Wrong value type
+            r#"{"type": "ActualInterval", "duration": "five"}"#,
+        ];
+
+        for case in cases {
+            assert!(serde_json::from_str::<Interval>(case).is_err(), "{case}");
+            assert!(
+                serde_json::from_str::<DerivedInterval>(case).is_err(),
+                "{case}"
+            );
+        }
+    }
+
+    #[test]
+    fn renewable_errors_match_derive() {
+        let cases = [
+            r#"{"duration": 5}"#,
+            r#"{
+                "type": "ActualRenewable",
+                "duration": 5,
+                "date": "2021-05-05",
+                "nemTime": "2021-05-06T12:30:00+10:00",
+                "startTime": "2021-05-05T02:00:01Z",
+                "endTime": "2021-05-05T02:30:00Z",
+                "renewables": 45
+            }"#,
+        ];
+
+        for case in cases {
+            assert!(serde_json::from_str::<Renewable>(case).is_err(), "{case}");
+            assert!(
+                serde_json::from_str::<DerivedRenewable>(case).is_err(),
+                "{case}"
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_tags_are_preserved() -> Result<()> {
+        let interval: Interval =
+            serde_json::from_str(r#"{"duration": 5, "type": "FutureInterval", "perKwh": "n/a"}"#)?;
+        assert_eq!(
+            interval,
+            Interval::Unknown(serde_json::json!({
+                "type": "FutureInterval",
+                "duration": 5_i32,
+                "perKwh": "n/a",
+            }))
+        );
+
+        let renewable: Renewable =
+            serde_json::from_str(r#"{"type": "FutureRenewable", "renewables": 45}"#)?;
+        assert_eq!(
+            renewable,
+            Renewable::Unknown(serde_json::json!({
+                "type": "FutureRenewable",
+                "renewables": 45_i32,
+            }))
+        );
+
+        // Derived tagged enums reject unknown tags outright.
+        serde_json::from_str::<DerivedInterval>(r#"{"type": "FutureInterval"}"#)
+            .expect_err("unknown tag rejected");
+        Ok(())
+    }
+
+    #[test]
+    fn fields_before_the_tag_are_replayed() -> Result<()> {
+        let json = r#"{
+            "duration": 5,
+            "spotPerKwh": 6.12,
+            "perKwh": 24.33,
+            "date": "2021-05-05",
+            "nemTime": "2021-05-06T12:30:00+10:00",
+            "startTime": "2021-05-05T02:00:01Z",
+            "endTime": "2021-05-05T02:30:00Z",
+            "renewables": 45,
+            "channelType": "general",
+            "spikeStatus": "none",
+            "descriptor": "low",
+            "type": "ActualInterval"
+        }"#;
+        let manual: Interval = serde_json::from_str(json)?;
+        let derived: DerivedInterval = serde_json::from_str(json)?;
+        assert_eq!(manual, Interval::from(derived));
+
+        let duplicate = r#"{"duration": 5, "duration": 5, "type": "ActualInterval"}"#;
+        serde_json::from_str::<Interval>(duplicate).expect_err("duplicate field rejected");
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_fields_are_rejected() {
+        let json = r#"{"type": "ActualRenewable", "type": "ActualRenewable"}"#;
+        let err = serde_json::from_str::<Renewable>(json)
+            .expect_err("duplicate fields should be rejected");
+        assert!(err.to_string().contains("duplicate field `type`"));
+    }
+}
@@ -0,0 +1,146 @@
+//! # Experimental Endpoints
+//!
+//! Amber occasionally announces endpoints before they are documented well
+//! enough for this crate to support them with stable types. This module,
+//! enabled by the `experimental` feature, lets early adopters call such
+//! endpoints with typed responses rather than raw JSON: an
+//! [`ExperimentalRequest`] names the path, query and response type of an
+//! endpoint and is sent through the generic
+//! [`Amber::send()`](crate::Amber::send) like any other request.
+//!
+//! Every endpoint of the published API is covered by the stable client, as
+//! listed in [`STABLE_ENDPOINTS`]. Types in this module may change in any
+//! release, and are replaced by stable methods once an endpoint is published.
+//!
+//! ```
+//! use amber_api::models::experimental::ExperimentalRequest;
+//! use amber_api::requests::ApiRequest;
+//!
+//! /// Response of an announced endpoint.
+//! #[derive(Debug, serde::Deserialize)]
+//! struct Tariff {
+//!     name: String,
+//! }
+//!
+//! let path = "sites/01F5A5CRKMZ5BCX9P1S4V990AM/tariffs";
+//! let request = ExperimentalRequest::<Vec<Tariff>>::new(path).with_query("startDate", "2021-05-01");
+//! assert_eq!(request.path(), path);
+//! assert_eq!(request.query().len(), 1);
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use serde::de::DeserializeOwned;
+
+use crate::requests::ApiRequest;
+
+/// Paths of the published endpoints, relative to the base URL, all of which
+/// are covered by the stable client.
+///
+/// Parameters are shown in braces.
+pub const STABLE_ENDPOINTS: &[&str] = &[
+    "sites",
+    "sites/{siteId}/prices",
+    "sites/{siteId}/prices/current",
+    "sites/{siteId}/usage",
+    "state/{state}/renewables/current",
+];
+
+/// A request to an endpoint not yet covered by the stable client, whose
+/// response is deserialised as `T`.
+pub struct ExperimentalRequest<T> {
+    /// Path of the endpoint, relative to the base URL.
+    path: String,
+    /// Query parameters of the request.
+    query: Vec<(&'static str, String)>,
+    /// Type of the response body.
+    output: PhantomData<fn() -> T>,
+}
+
+impl<T> ExperimentalRequest<T> {
+    /// Create a request to the endpoint at `path`, relative to the base URL.
+    #[must_use]
+    #[inline]
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            query: Vec::new(),
+            output: PhantomData,
+        }
+    }
+
+    /// Add a query parameter.
+    #[must_use]
+    #[inline]
+    pub fn with_query(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.query.push((key, value.into()));
+        self
+    }
+}
+
+impl<T> fmt::Debug for ExperimentalRequest<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExperimentalRequest")
+            .field("path", &self.path)
+            .field("query", &self.query)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for ExperimentalRequest<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            query: self.query.clone(),
+            output: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for ExperimentalRequest<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.query == other.query
+    }
+}
+
+impl<T> Eq for ExperimentalRequest<T> {}
+
+impl<T: DeserializeOwned> ApiRequest for ExperimentalRequest<T> {
+    type Output = T;
+
+    #[inline]
+    fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    #[inline]
+    fn query(&self) -> Vec<(&'static str, String)> {
+        self.query.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned as _, vec};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::Site;
+
+    #[test]
+    fn typed_request() {
+        let request =
+            ExperimentalRequest::<Vec<Site>>::new("sites/01F5A5CRKMZ5BCX9P1S4V990AM/batteries")
+                .with_query("limit", "1");
+        assert_eq!(request.path(), "sites/01F5A5CRKMZ5BCX9P1S4V990AM/batteries");
+        assert_eq!(request.query(), vec![("limit", "1".to_owned())]);
+        assert_eq!(request.clone(), request);
+        request.validate().expect("valid request");
+        assert_eq!(request.end_date(), None);
+    }
+}
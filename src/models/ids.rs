@@ -0,0 +1,339 @@
+//! # Typed Identifiers
+//!
+//! Site IDs, NMIs and channel identifiers are all short strings, which makes
+//! them easy to mix up. Each is wrapped in its own type so that, for example,
+//! an NMI cannot be passed where a site ID is expected.
+//!
+//! Identifiers parsed with [`FromStr`] or [`TryFrom`] are validated.
+//! Identifiers deserialised from API responses are accepted as-is, so that a
+//! change in format on Amber's side does not break existing responses.
+//!
+//! ## Redaction
+//!
+//! NMIs and site IDs identify a customer's premises. With the `redact-ids`
+//! feature, the `Debug` output of identifiers (and so of every model containing
+//! them) only shows their last four characters, so that logging a value with
+//! `{:?}` does not disclose them. `Display` and serialisation are unaffected.
+
+use alloc::string::String;
+use core::{fmt, ops::Deref, str::FromStr};
+
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+
+/// Error returned when parsing an identifier with an invalid format.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid {kind}: {value:?}")]
+#[non_exhaustive]
+pub struct ParseIdError {
+    /// Kind of identifier which failed to parse.
+    pub kind: &'static str,
+    /// The rejected value.
+    pub value: CompactString,
+}
+
+/// Number of trailing characters of an identifier kept by redaction.
+#[cfg(feature = "redact-ids")]
+const REDACTED_SUFFIX: usize = 4;
+
+/// Debug representation of an identifier, redacted with the `redact-ids`
+/// feature.
+struct Redacted<'a>(&'a str);
+
+impl fmt::Debug for Redacted<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "redact-ids")]
+        {
+            let suffix = self
+                .0
+                .char_indices()
+                .rev()
+                .nth(REDACTED_SUFFIX.saturating_sub(1))
+                .and_then(|(index, _)| self.0.get(index..))
+                .unwrap_or_default();
+            write!(f, "\"...{suffix}\"")
+        }
+        #[cfg(not(feature = "redact-ids"))]
+        {
+            fmt::Debug::fmt(self.0, f)
+        }
+    }
+}
+
+/// Define a validated string identifier.
+macro_rules! identifier {
+    ($(#[$meta:meta])* $name:ident, $kind:literal, $validate:path) => {
+        $(#[$meta])*
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+        #[serde(transparent)]
+        pub struct $name(CompactString);
+
+        impl $name {
+            /// Create a new identifier, validating its format.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the value is not a valid identifier.
+            #[inline]
+            pub fn new(value: impl AsRef<str>) -> Result<Self, ParseIdError> {
+                let value = value.as_ref();
+                if $validate(value) {
+                    Ok(Self(CompactString::from(value)))
+                } else {
+                    Err(ParseIdError {
+                        kind: $kind,
+                        value: CompactString::from(value),
+                    })
+                }
+            }
+
+            /// Returns the identifier as a string slice.
+            #[must_use]
+            #[inline]
+            pub fn as_str(&self) -> &str {
+                self.0.as_str()
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            #[inline]
+            fn deref(&self) -> &str {
+                self.0.as_str()
+            }
+        }
+
+        impl AsRef<str> for $name {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self.0.as_str()
+            }
+        }
+
+        impl fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&Redacted(&self.0))
+                    .finish()
+            }
+        }
+
+        impl fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIdError;
+
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::new(s)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = ParseIdError;
+
+            #[inline]
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                Self::new(value)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = ParseIdError;
+
+            #[inline]
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                Self::new(value)
+            }
+        }
+
+        impl From<$name> for String {
+            #[inline]
+            fn from(value: $name) -> Self {
+                value.0.into()
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            #[inline]
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            #[inline]
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+identifier!(
+    /// Unique identifier of a site.
+    ///
+    /// Site IDs are [ULIDs](https://github.com/ulid/spec): 26 characters of
+    /// upper case Crockford base 32, as returned by the API.
+    SiteId,
+    "site ID",
+    is_site_id
+);
+
+identifier!(
+    /// National Metering Identifier (NMI) of a site.
+    ///
+    /// NMIs are 10 characters of digits and upper case letters (excluding `I`
+    /// and `O`), optionally followed by a checksum digit.
+    Nmi,
+    "NMI",
+    is_nmi
+);
+
+identifier!(
+    /// Identifier of a meter channel, such as `E1` or `B1`.
+    ///
+    /// Channel identifiers are between 1 and 8 ASCII letters or digits.
+    ChannelId,
+    "channel identifier",
+    is_channel_id
+);
+
+/// Returns `true` if the value is an upper case ULID.
+#[cfg_attr(
+    feature = "no-panic",
+    inline(never),
+    no_panic::no_panic,
+    expect(clippy::mem_forget, reason = "Expanded by no_panic")
+)]
+fn is_site_id(value: &str) -> bool {
+    value.len() == 26
+        && value.bytes().all(|b| {
+            b.is_ascii_digit()
+                || (b.is_ascii_uppercase() && !matches!(b, b'I' | b'L' | b'O' | b'U'))
+        })
+}
+
+/// Returns `true` if the value is a 10 character NMI, with optional checksum.
+#[cfg_attr(
+    feature = "no-panic",
+    inline(never),
+    no_panic::no_panic,
+    expect(clippy::mem_forget, reason = "Expanded by no_panic")
+)]
+fn is_nmi(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let Some((body, checksum)) = bytes.split_at_checked(10) else {
+        return false;
+    };
+    body.iter()
+        .copied()
+        .all(|b| b.is_ascii_digit() || (b.is_ascii_uppercase() && !matches!(b, b'I' | b'O')))
+        && matches!(checksum, [] | [b'0'..=b'9'])
+}
+
+/// Returns `true` if the value is a plausible channel identifier.
+#[cfg_attr(
+    feature = "no-panic",
+    inline(never),
+    no_panic::no_panic,
+    expect(clippy::mem_forget, reason = "Expanded by no_panic")
+)]
+fn is_channel_id(value: &str) -> bool {
+    (1..=8).contains(&value.len()) && value.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString as _};
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn site_id() {
+        let id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid site ID");
+        assert_eq!(id, "01F5A5CRKMZ5BCX9P1S4V990AM");
+        assert_eq!(id.to_string(), "01F5A5CRKMZ5BCX9P1S4V990AM");
+
+        SiteId::new("01f5a5crkmz5bcx9p1s4v990am").expect_err("lowercase ID");
+        SiteId::new("01F5A5CRKMZ5BCX9P1S4V990A").expect_err("short ID");
+        SiteId::new("01F5A5CRKMZ5BCX9P1S4V990AU").expect_err("invalid character");
+        insta::assert_snapshot!(
+            SiteId::new("3052282872").expect_err("NMI is not a site ID").to_string(),
+            @r#"invalid site ID: "3052282872""#
+        );
+    }
+
+    #[test]
+    fn nmi() {
+        Nmi::new("3052282872").expect("valid NMI");
+        Nmi::new("QAAAVZZZZZ3").expect("valid alphanumeric NMI");
+        Nmi::new("305228287").expect_err("short NMI");
+        Nmi::new("30522828721X").expect_err("long NMI");
+        Nmi::new("305228287O").expect_err("invalid checksum character");
+        Nmi::new("01F5A5CRKMZ5BCX9P1S4V990AM").expect_err("site ID is not an NMI");
+    }
+
+    #[test]
+    fn channel_id() {
+        ChannelId::new("E1").expect("valid channel ID");
+        ChannelId::new("").expect_err("empty channel ID");
+        ChannelId::new("E 1").expect_err("channel ID with a space");
+    }
+
+    #[test]
+    fn debug() {
+        let site_id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid site ID");
+        let nmi: Nmi = "3052282872".parse().expect("valid NMI");
+        #[cfg(not(feature = "redact-ids"))]
+        {
+            assert_eq!(
+                format!("{site_id:?}"),
+                r#"SiteId("01F5A5CRKMZ5BCX9P1S4V990AM")"#
+            );
+            assert_eq!(format!("{nmi:?}"), r#"Nmi("3052282872")"#);
+        }
+        #[cfg(feature = "redact-ids")]
+        {
+            assert_eq!(format!("{site_id:?}"), r#"SiteId("...90AM")"#);
+            assert_eq!(format!("{nmi:?}"), r#"Nmi("...2872")"#);
+            assert_eq!(
+                format!("{:?}", ChannelId::new("E1").expect("valid channel ID")),
+                r#"ChannelId("...")"#
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        for value in [
+            "",
+            "\0",
+            "01F5A5CRKMZ5BCX9P1S4V990A\u{e9}",
+            "\u{e9}1F5A5CRKMZ5BCX9P1S4V990A",
+            "\u{ff13}\u{ff10}\u{ff15}\u{ff12}\u{ff12}\u{ff18}\u{ff12}\u{ff18}\u{ff17}\u{ff12}",
+            "305228287\u{e9}",
+            "30522828720\u{e9}",
+        ] {
+            assert!(SiteId::new(value).is_err(), "{value:?}");
+            assert!(Nmi::new(value).is_err(), "{value:?}");
+            assert!(ChannelId::new(value).is_err(), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn deserialize_is_lenient() -> anyhow::Result<()> {
+        let id: SiteId = serde_json::from_str(r#""not-a-ulid""#)?;
+        assert_eq!(id.as_str(), "not-a-ulid");
+        Ok(())
+    }
+}
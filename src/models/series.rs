@@ -0,0 +1,356 @@
+//! Collection of price intervals.
+
+use alloc::{
+    collections::BTreeSet,
+    vec::{self, Vec},
+};
+use core::{ops::Deref, slice};
+
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use super::{ActualInterval, ChannelType, CurrentInterval, ForecastInterval, Interval};
+use crate::analysis::channel_order;
+
+/// Whether an interval is of an unknown type, and its channel and start time.
+type IntervalKey<'a> = (bool, Option<((u8, &'a str), Timestamp)>);
+
+/// Sort key of an interval: by channel in the order used by the API, then by
+/// start time, with intervals of an unknown type last.
+fn interval_key(interval: &Interval) -> IntervalKey<'_> {
+    let key = interval
+        .as_base_interval()
+        .map(|base| (channel_order(&base.channel_type), base.start_time));
+    (key.is_none(), key)
+}
+
+/// A missing period between two consecutive intervals of a channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IntervalGap {
+    /// Channel with the missing intervals.
+    pub channel_type: ChannelType,
+    /// End of the interval before the gap.
+    pub start: Timestamp,
+    /// Start of the interval after the gap.
+    pub end: Timestamp,
+}
+
+/// Price intervals, as returned by the API, with helpers to work with them.
+///
+/// The API returns the intervals of every channel in one flat list. A series
+/// wraps that list to split it by channel or by interval type, put it in
+/// order, drop duplicates (such as after stitching together several requests)
+/// and find missing intervals. It dereferences to a slice of intervals.
+///
+/// ```
+/// use amber_api::models::{Interval, IntervalSeries};
+///
+/// # fn example(intervals: Vec<Interval>) {
+/// let series = IntervalSeries::new(intervals).sorted().dedup();
+/// for (channel_type, channel) in series.by_channel() {
+///     println!("{channel_type}: {} intervals", channel.len());
+/// }
+/// for gap in series.gaps() {
+///     println!("{} missing from {} to {}", gap.channel_type, gap.start, gap.end);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct IntervalSeries {
+    /// The intervals.
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSeries {
+    /// Wrap a list of intervals.
+    #[must_use]
+    #[inline]
+    pub const fn new(intervals: Vec<Interval>) -> Self {
+        Self { intervals }
+    }
+
+    /// Returns the wrapped list of intervals.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> Vec<Interval> {
+        self.intervals
+    }
+
+    /// Returns the intervals as a slice.
+    #[must_use]
+    #[inline]
+    pub fn as_slice(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    /// Split the series by channel, in the order used by the API.
+    ///
+    /// Intervals of an unknown type have no channel and are omitted.
+    #[must_use]
+    #[inline]
+    pub fn by_channel(&self) -> Vec<(ChannelType, Self)> {
+        let mut channels: Vec<(ChannelType, Self)> = Vec::new();
+        for interval in &self.intervals {
+            let Some(base) = interval.as_base_interval() else {
+                continue;
+            };
+            let order = channel_order(&base.channel_type);
+            match channels
+                .binary_search_by_key(&order, |(channel_type, _)| channel_order(channel_type))
+            {
+                Ok(index) => {
+                    if let Some((_, series)) = channels.get_mut(index) {
+                        series.intervals.push(interval.clone());
+                    }
+                }
+                Err(index) => channels.insert(
+                    index,
+                    (
+                        base.channel_type.clone(),
+                        Self::new(Vec::from([interval.clone()])),
+                    ),
+                ),
+            }
+        }
+        channels
+    }
+
+    /// Returns the intervals with confirmed prices.
+    #[inline]
+    pub fn actuals(&self) -> impl Iterator<Item = &ActualInterval> {
+        self.intervals
+            .iter()
+            .filter_map(Interval::as_actual_interval)
+    }
+
+    /// Returns the forecast intervals.
+    #[inline]
+    pub fn forecasts(&self) -> impl Iterator<Item = &ForecastInterval> {
+        self.intervals
+            .iter()
+            .filter_map(Interval::as_forecast_interval)
+    }
+
+    /// Returns the current intervals, one for each channel.
+    #[inline]
+    pub fn current(&self) -> impl Iterator<Item = &CurrentInterval> {
+        self.intervals
+            .iter()
+            .filter_map(Interval::as_current_interval)
+    }
+
+    /// Returns the series grouped by channel, in the order used by the API,
+    /// and in chronological order within each channel.
+    ///
+    /// Intervals of an unknown type are placed last.
+    #[must_use]
+    #[inline]
+    pub fn sorted(mut self) -> Self {
+        self.intervals
+            .sort_by(|a, b| interval_key(a).cmp(&interval_key(b)));
+        self
+    }
+
+    /// Returns the series without repeated intervals of the same channel and
+    /// start time, keeping the first of each.
+    ///
+    /// Intervals of an unknown type are kept.
+    #[must_use]
+    #[inline]
+    pub fn dedup(mut self) -> Self {
+        let mut seen = BTreeSet::new();
+        let keep: Vec<bool> = self
+            .intervals
+            .iter()
+            .map(|interval| {
+                interval.as_base_interval().is_none_or(|base| {
+                    seen.insert((channel_order(&base.channel_type), base.start_time))
+                })
+            })
+            .collect();
+        let mut flags = keep.into_iter();
+        self.intervals.retain(|_| flags.next().unwrap_or(true));
+        self
+    }
+
+    /// Returns the periods missing between consecutive intervals of each
+    /// channel, in the order of [`sorted()`](Self::sorted).
+    #[must_use]
+    #[inline]
+    pub fn gaps(&self) -> Vec<IntervalGap> {
+        let mut gaps = Vec::new();
+        for (channel_type, channel) in self.by_channel() {
+            let mut bounds: Vec<(Timestamp, Timestamp)> = channel
+                .intervals
+                .iter()
+                .filter_map(Interval::as_base_interval)
+                .map(|base| (base.start_time, base.end_time))
+                .collect();
+            bounds.sort_unstable();
+            let mut covered_until: Option<Timestamp> = None;
+            for (start, end) in bounds {
+                if let Some(previous_end) = covered_until
+                    && previous_end < start
+                {
+                    gaps.push(IntervalGap {
+                        channel_type: channel_type.clone(),
+                        start: previous_end,
+                        end: start,
+                    });
+                }
+                covered_until = Some(covered_until.map_or(end, |until| until.max(end)));
+            }
+        }
+        gaps
+    }
+}
+
+impl From<Vec<Interval>> for IntervalSeries {
+    #[inline]
+    fn from(intervals: Vec<Interval>) -> Self {
+        Self::new(intervals)
+    }
+}
+
+impl From<IntervalSeries> for Vec<Interval> {
+    #[inline]
+    fn from(series: IntervalSeries) -> Self {
+        series.intervals
+    }
+}
+
+impl Deref for IntervalSeries {
+    type Target = [Interval];
+
+    #[inline]
+    fn deref(&self) -> &[Interval] {
+        &self.intervals
+    }
+}
+
+impl FromIterator<Interval> for IntervalSeries {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for IntervalSeries {
+    type Item = Interval;
+    type IntoIter = vec::IntoIter<Interval>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a IntervalSeries {
+    type Item = &'a Interval;
+    type IntoIter = slice::Iter<'a, Interval>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use jiff::SignedDuration;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a 5-minute interval of the given type and channel starting at
+    /// the given minute past 02:00 UTC.
+    fn interval(kind: &str, channel_type: &str, minute: u8) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": kind,
+            "duration": 5,
+            "nemTime": at(minute.saturating_add(5)),
+            "startTime": at(minute),
+            "endTime": at(minute.saturating_add(5)),
+            "channelType": channel_type,
+            "estimate": true
+        }))
+    }
+
+    /// Start times of the intervals of a series.
+    fn starts(series: &IntervalSeries) -> Vec<(ChannelType, Timestamp)> {
+        series
+            .iter()
+            .filter_map(Interval::as_base_interval)
+            .map(|base| (base.channel_type.clone(), base.start_time))
+            .collect()
+    }
+
+    /// The timestamp the given number of minutes after 02:00 UTC.
+    fn at(minute: u8) -> Timestamp {
+        let base: Timestamp = "2021-05-05T02:00:00Z".parse().expect("valid timestamp");
+        base.checked_add(SignedDuration::from_mins(i64::from(minute)))
+            .expect("timestamp in range")
+    }
+
+    #[test]
+    fn splits_sorts_and_dedups() {
+        let series = IntervalSeries::new(vec![
+            interval("ActualInterval", "feedIn", 5),
+            interval("ActualInterval", "general", 5),
+            interval("ActualInterval", "general", 0),
+            interval("CurrentInterval", "general", 10),
+            interval("ActualInterval", "feedIn", 0),
+            interval("ActualInterval", "general", 5),
+            interval("ForecastInterval", "general", 15),
+        ]);
+        assert_eq!(series.actuals().count(), 5);
+        assert_eq!(series.current().count(), 1);
+        assert_eq!(series.forecasts().count(), 1);
+
+        let tidy = series.sorted().dedup();
+        assert_eq!(
+            starts(&tidy),
+            vec![
+                (ChannelType::General, at(0)),
+                (ChannelType::General, at(5)),
+                (ChannelType::General, at(10)),
+                (ChannelType::General, at(15)),
+                (ChannelType::FeedIn, at(0)),
+                (ChannelType::FeedIn, at(5)),
+            ]
+        );
+
+        let channels: Vec<_> = tidy
+            .by_channel()
+            .into_iter()
+            .map(|(channel_type, channel)| (channel_type, channel.len()))
+            .collect();
+        assert_eq!(
+            channels,
+            vec![(ChannelType::General, 4), (ChannelType::FeedIn, 2)]
+        );
+    }
+
+    #[test]
+    fn finds_gaps() {
+        let series = IntervalSeries::from(vec![
+            interval("ActualInterval", "general", 0),
+            interval("ActualInterval", "general", 20),
+            interval("ActualInterval", "general", 5),
+            interval("ActualInterval", "feedIn", 0),
+            interval("ActualInterval", "feedIn", 5),
+        ]);
+        assert_eq!(
+            series.gaps(),
+            vec![IntervalGap {
+                channel_type: ChannelType::General,
+                start: at(10),
+                end: at(20),
+            }]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! # Fixed-Point Prices
+//!
+//! Prices and costs are reported by the API as floating-point cents. Summing
+//! thousands of intervals as `f64` accumulates rounding errors, so that a
+//! month of costs may not add up to the cent. With the `fixed-point` feature,
+//! [`PriceCents`] stores an amount as an integer number of millionths of a
+//! cent: every amount reported by the API is represented exactly (the API
+//! reports far fewer decimal places), and sums are exact.
+//!
+//! Models convert to this representation through
+//! [`BaseInterval::per_kwh_cents()`](crate::models::BaseInterval::per_kwh_cents)
+//! and [`Usage::cost_cents()`](crate::models::Usage::cost_cents), and
+//! [`total_cost`] sums the cost of usage exactly. The cost totals of the
+//! [`aggregate`](crate::analysis::aggregate),
+//! [`billing`](crate::analysis::billing),
+//! [`summary`](crate::analysis::summary) and
+//! [`budget`](crate::analysis::budget) analyses are also accumulated exactly
+//! with this feature, while still being reported as `f64` cents.
+//!
+//! ```
+//! use amber_api::money::PriceCents;
+//!
+//! let costs = [0.1, 0.2, 0.3].map(|cents| PriceCents::from_cents(cents).unwrap_or_default());
+//! let total: PriceCents = costs.into_iter().sum();
+//! assert_eq!(total, PriceCents::from_cents(0.6).unwrap_or_default());
+//! assert_eq!(total.to_string(), "0.60");
+//! ```
+
+use core::{fmt, iter::Sum, ops};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{analysis::round, models::Usage};
+
+/// Number of units of a [`PriceCents`] in one cent.
+pub const MICROS_PER_CENT: i64 = 1_000_000;
+
+/// An amount in cents (or cents per kWh), stored exactly as millionths of a
+/// cent.
+///
+/// Arithmetic saturates at the bounds of the representation, which are far
+/// beyond any realistic amount.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct PriceCents(i64);
+
+impl PriceCents {
+    /// Zero cents.
+    pub const ZERO: Self = Self(0);
+
+    /// Create an amount from a number of millionths of a cent.
+    #[must_use]
+    #[inline]
+    pub const fn from_micros(micros: i64) -> Self {
+        Self(micros)
+    }
+
+    /// Convert an amount in floating-point cents, as reported by the API,
+    /// rounding to the nearest millionth of a cent.
+    ///
+    /// Returns `None` if the amount is not finite or out of range.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Scaling to millionths")]
+    #[expect(
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        reason = "The value is rounded and checked to be in range"
+    )]
+    pub fn from_cents(cents: f64) -> Option<Self> {
+        /// Largest magnitude (in millionths) converted, well within `i64`.
+        const LIMIT: f64 = 9.0e18;
+        let micros = round(cents * 1_000_000.0);
+        (micros.is_finite() && micros.abs() < LIMIT).then_some(Self(micros as i64))
+    }
+
+    /// Returns the number of millionths of a cent.
+    #[must_use]
+    #[inline]
+    pub const fn as_micros(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the amount in floating-point cents.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Scaling from millionths")]
+    #[expect(
+        clippy::as_conversions,
+        clippy::cast_precision_loss,
+        reason = "Amounts are well within the exact range of f64"
+    )]
+    pub fn to_cents(self) -> f64 {
+        self.0 as f64 / 1_000_000.0
+    }
+
+    /// Returns the amount in floating-point dollars.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Converting cents to dollars")]
+    pub fn to_dollars(self) -> f64 {
+        self.to_cents() / 100.0
+    }
+
+    /// Returns the absolute amount.
+    #[must_use]
+    #[inline]
+    pub const fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    /// Returns `true` if the amount is negative, such as a credit.
+    #[must_use]
+    #[inline]
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+}
+
+impl ops::Add for PriceCents {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl ops::AddAssign for PriceCents {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 = self.0.saturating_add(rhs.0);
+    }
+}
+
+impl ops::Sub for PriceCents {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl ops::SubAssign for PriceCents {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 = self.0.saturating_sub(rhs.0);
+    }
+}
+
+impl ops::Neg for PriceCents {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.saturating_neg())
+    }
+}
+
+impl Sum for PriceCents {
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |total, amount| {
+            Self(total.0.saturating_add(amount.0))
+        })
+    }
+}
+
+impl<'a> Sum<&'a Self> for PriceCents {
+    #[inline]
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl fmt::Display for PriceCents {
+    /// Renders the amount in cents, with at least two and at most six
+    /// decimal places.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let magnitude = self.0.unsigned_abs();
+        let micros_per_cent = MICROS_PER_CENT.unsigned_abs();
+        let whole = magnitude.checked_div(micros_per_cent).unwrap_or(0);
+        let mut fraction = magnitude.checked_rem(micros_per_cent).unwrap_or(0);
+        let mut places: usize = 6;
+        while places > 2 && fraction.checked_rem(10) == Some(0) {
+            fraction = fraction.checked_div(10).unwrap_or(0);
+            places = places.saturating_sub(1);
+        }
+        write!(f, "{sign}{whole}.{fraction:0places$}")
+    }
+}
+
+/// Sum the cost of usage records exactly.
+///
+/// Costs which cannot be represented (such as NaN) are counted as zero.
+#[must_use]
+#[inline]
+pub fn total_cost(usage: &[Usage]) -> PriceCents {
+    usage
+        .iter()
+        .map(|record| record.cost_cents().unwrap_or_default())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    #[expect(clippy::float_cmp, reason = "The sums are exact")]
+    fn exact_sums() {
+        let tenth = PriceCents::from_cents(0.1).expect("in range");
+        assert_eq!(tenth.as_micros(), 100_000);
+        let total: PriceCents = core::iter::repeat_n(tenth, 10_000).sum();
+        assert_eq!(total, PriceCents::from_micros(1_000_000_000));
+        assert_eq!(total.to_cents(), 1000.0_f64);
+        assert_eq!(total.to_dollars(), 10.0_f64);
+
+        assert_eq!(PriceCents::from_cents(f64::NAN), None);
+        assert_eq!(PriceCents::from_cents(1.0e20), None);
+        assert_eq!(-tenth - tenth, PriceCents::from_micros(-200_000));
+    }
+
+    #[test]
+    fn display() {
+        let render = |cents: f64| PriceCents::from_cents(cents).expect("in range").to_string();
+        assert_eq!(render(23.45_f64), "23.45");
+        assert_eq!(render(5.0_f64), "5.00");
+        assert_eq!(render(-0.123_456_f64), "-0.123456");
+        assert_eq!(render(12.345_6_f64), "12.3456");
+    }
+}
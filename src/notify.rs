@@ -0,0 +1,488 @@
+//! # Price Notifications
+//!
+//! An [`EventDetector`] watches the current prices of a site, as returned by
+//! each poll, and reports when something worth notifying about starts: a price
+//! spike, the price dropping under a threshold, or the share of renewables
+//! rising above a threshold. Events are only reported on the transition, so a
+//! spike lasting an hour produces a single [`PriceEvent::SpikeStarted`] no
+//! matter how often the prices are polled.
+//!
+//! Events are serializable, and are delivered by [`Notifier`]s. With the `std`
+//! feature, [`Notifications`] consumes the events of a
+//! [`Watcher`](crate::Watcher) and delivers the detected events to each
+//! notifier. Closures can be used directly as notifiers, and the following are
+//! provided:
+//!
+//! - [`CommandNotifier`](command::CommandNotifier) runs a program with the
+//!   event as JSON on its standard input.
+//! - [`Webhook`](webhook::Webhook) posts the event to an HTTP endpoint
+//!   (requires the `webhook` feature).
+//! - [`MqttNotifier`](mqtt::MqttNotifier) publishes the event to an MQTT
+//!   broker (requires the `mqtt` feature).
+//!
+//! Other services, such as Pushover or Telegram, can be added by implementing
+//! [`Notifier::notify`].
+//!
+//! ```
+//! use amber_api::models::{Interval, SiteId};
+//! use amber_api::notify::EventDetector;
+//!
+//! # fn example(site_id: &SiteId, intervals: &[Interval]) {
+//! let mut detector = EventDetector::builder()
+//!     .price_below(10.0)
+//!     .renewables_above(80.0)
+//!     .build();
+//! for event in detector.detect(site_id, intervals) {
+//!     println!("{event}");
+//! }
+//! # }
+//! ```
+
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use tracing::warn;
+
+use crate::models::{ChannelType, Interval, SiteId, SpikeStatus};
+#[cfg(feature = "std")]
+use crate::{BoxFuture, Result, WatchEvent, WatcherHandle};
+
+#[cfg(feature = "std")]
+pub mod command;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+/// Something worth notifying about, which started in the current interval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    tag = "event",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+#[non_exhaustive]
+pub enum PriceEvent {
+    /// A price spike started.
+    SpikeStarted {
+        /// ID of the site.
+        site_id: SiteId,
+        /// Channel with the spike.
+        channel_type: ChannelType,
+        /// Start of the current interval.
+        start_time: Timestamp,
+        /// Current price (c/kWh).
+        per_kwh: f64,
+    },
+    /// The price dropped under the threshold.
+    PriceBelow {
+        /// ID of the site.
+        site_id: SiteId,
+        /// Channel of the price.
+        channel_type: ChannelType,
+        /// Start of the current interval.
+        start_time: Timestamp,
+        /// Current price (c/kWh).
+        per_kwh: f64,
+        /// The threshold (c/kWh).
+        threshold: f64,
+    },
+    /// The share of renewables rose above the threshold.
+    RenewablesAbove {
+        /// ID of the site.
+        site_id: SiteId,
+        /// Start of the current interval.
+        start_time: Timestamp,
+        /// Current percentage of renewables in the grid.
+        renewables: f64,
+        /// The threshold (%).
+        threshold: f64,
+    },
+}
+
+impl PriceEvent {
+    /// Returns the ID of the site the event is about.
+    #[must_use]
+    #[inline]
+    pub fn site_id(&self) -> &SiteId {
+        match self {
+            Self::SpikeStarted { site_id, .. }
+            | Self::PriceBelow { site_id, .. }
+            | Self::RenewablesAbove { site_id, .. } => site_id,
+        }
+    }
+
+    /// Returns the name of the event, as serialized in the `event` field
+    /// (such as `spikeStarted`).
+    #[must_use]
+    #[inline]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::SpikeStarted { .. } => "spikeStarted",
+            Self::PriceBelow { .. } => "priceBelow",
+            Self::RenewablesAbove { .. } => "renewablesAbove",
+        }
+    }
+}
+
+impl fmt::Display for PriceEvent {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SpikeStarted {
+                site_id,
+                channel_type,
+                per_kwh,
+                ..
+            } => write!(
+                f,
+                "Price spike on the {channel_type} channel of site {site_id}: {per_kwh:.2}c/kWh"
+            ),
+            Self::PriceBelow {
+                site_id,
+                channel_type,
+                per_kwh,
+                threshold,
+                ..
+            } => write!(
+                f,
+                "Price on the {channel_type} channel of site {site_id} is {per_kwh:.2}c/kWh, \
+                 under {threshold:.2}c/kWh"
+            ),
+            Self::RenewablesAbove {
+                site_id,
+                renewables,
+                threshold,
+                ..
+            } => write!(
+                f,
+                "Renewables at site {site_id} are at {renewables}%, above {threshold}%"
+            ),
+        }
+    }
+}
+
+/// Conditions which held at the last poll of a site.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Conditions {
+    /// Whether the watched channel was spiking.
+    spike: bool,
+    /// Whether the price was under the threshold.
+    price_below: bool,
+    /// Whether renewables were above the threshold.
+    renewables_above: bool,
+}
+
+/// Reports [`PriceEvent`]s when their conditions start to hold.
+///
+/// Each site is tracked separately. Conditions are checked against the
+/// current interval of the watched channel; polls without one leave the state
+/// of the site unchanged.
+#[derive(Debug, Clone, PartialEq, bon::Builder)]
+#[non_exhaustive]
+pub struct EventDetector {
+    /// Channel whose price is watched.
+    ///
+    /// Defaults to [`ChannelType::General`].
+    #[builder(default = ChannelType::General)]
+    channel_type: ChannelType,
+    /// Whether to report price spikes.
+    ///
+    /// Defaults to `true`.
+    #[builder(default = true)]
+    spikes: bool,
+    /// Price (c/kWh) under which [`PriceEvent::PriceBelow`] is reported.
+    price_below: Option<f64>,
+    /// Percentage of renewables above which [`PriceEvent::RenewablesAbove`]
+    /// is reported.
+    renewables_above: Option<f64>,
+    /// Conditions at the last poll of each site.
+    #[builder(skip)]
+    state: BTreeMap<SiteId, Conditions>,
+}
+
+impl EventDetector {
+    /// Check the current prices of a site, returning the events which started
+    /// since the last check.
+    ///
+    /// The first check of a site reports every condition which holds.
+    #[inline]
+    pub fn detect(&mut self, site_id: &SiteId, intervals: &[Interval]) -> Vec<PriceEvent> {
+        let mut events = Vec::new();
+        let Some(current) = intervals
+            .iter()
+            .filter_map(Interval::as_current_interval)
+            .find(|current| current.channel_type == self.channel_type)
+        else {
+            return events;
+        };
+
+        let now = Conditions {
+            spike: self.spikes && current.spike_status == SpikeStatus::Spike,
+            price_below: self
+                .price_below
+                .is_some_and(|threshold| current.per_kwh < threshold),
+            renewables_above: self
+                .renewables_above
+                .is_some_and(|threshold| current.renewables > threshold),
+        };
+        let before = self.state.insert(site_id.clone(), now).unwrap_or_default();
+
+        if now.spike && !before.spike {
+            events.push(PriceEvent::SpikeStarted {
+                site_id: site_id.clone(),
+                channel_type: current.channel_type.clone(),
+                start_time: current.start_time,
+                per_kwh: current.per_kwh,
+            });
+        }
+        if let Some(threshold) = self.price_below
+            && now.price_below
+            && !before.price_below
+        {
+            events.push(PriceEvent::PriceBelow {
+                site_id: site_id.clone(),
+                channel_type: current.channel_type.clone(),
+                start_time: current.start_time,
+                per_kwh: current.per_kwh,
+                threshold,
+            });
+        }
+        if let Some(threshold) = self.renewables_above
+            && now.renewables_above
+            && !before.renewables_above
+        {
+            events.push(PriceEvent::RenewablesAbove {
+                site_id: site_id.clone(),
+                start_time: current.start_time,
+                renewables: current.renewables,
+                threshold,
+            });
+        }
+        events
+    }
+}
+
+/// Destination for price events.
+#[cfg(feature = "std")]
+pub trait Notifier: Send {
+    /// Deliver an event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event could not be delivered. Errors are logged
+    /// by [`Notifications`], and do not prevent delivery to other notifiers.
+    fn notify<'a>(&'a mut self, event: &'a PriceEvent) -> BoxFuture<'a, Result<()>>;
+}
+
+#[cfg(feature = "std")]
+impl<F: FnMut(&PriceEvent) + Send> Notifier for F {
+    #[inline]
+    fn notify<'a>(&'a mut self, event: &'a PriceEvent) -> BoxFuture<'a, Result<()>> {
+        self(event);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Detects events in the prices reported by a [`Watcher`](crate::Watcher),
+/// and delivers them to a set of [`Notifier`]s.
+///
+/// # Example
+///
+/// ```no_run
+/// use amber_api::notify::{EventDetector, Notifications, PriceEvent};
+/// use amber_api::{Amber, Watcher};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Amber::default();
+/// let sites = client.active_sites().await?;
+/// let handle = Watcher::builder()
+///     .client(client)
+///     .sites(sites.into_iter().map(|site| site.id).collect())
+///     .build()
+///     .start();
+///
+/// Notifications::builder()
+///     .detector(EventDetector::builder().price_below(10.0).build())
+///     .notifiers(vec![Box::new(|event: &PriceEvent| println!("{event}"))])
+///     .build()
+///     .run(handle)
+///     .await;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+#[derive(bon::Builder)]
+#[non_exhaustive]
+pub struct Notifications {
+    /// Detector of the events.
+    ///
+    /// Defaults to reporting price spikes on the general channel.
+    #[builder(default = EventDetector::builder().build())]
+    detector: EventDetector,
+    /// Destinations of the events.
+    #[builder(default)]
+    notifiers: Vec<Box<dyn Notifier>>,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for Notifications {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Notifications")
+            .field("detector", &self.detector)
+            .field("notifiers", &self.notifiers.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Notifications {
+    /// Deliver an event to every notifier, logging failures.
+    #[inline]
+    pub async fn deliver(&mut self, event: &PriceEvent) {
+        for notifier in &mut self.notifiers {
+            if let Err(err) = notifier.notify(event).await {
+                warn!(site_id = %event.site_id(), "Failed to deliver {} event: {err}", event.kind());
+            }
+        }
+    }
+
+    /// Detect the events in an update from a watcher and deliver them,
+    /// returning the events detected.
+    ///
+    /// Failed polls are ignored.
+    #[inline]
+    pub async fn handle(&mut self, event: &WatchEvent) -> Vec<PriceEvent> {
+        let WatchEvent::Prices { site_id, response } = event else {
+            return Vec::new();
+        };
+        let events = self.detector.detect(site_id, &response.data);
+        for detected in &events {
+            self.deliver(detected).await;
+        }
+        events
+    }
+
+    /// Handle the updates of a watcher until it stops.
+    #[inline]
+    pub async fn run(mut self, mut handle: WatcherHandle) {
+        while let Some(event) = handle.next().await {
+            self.handle(&event).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse a current general interval.
+    fn current(per_kwh: f64, renewables: f64, spike_status: &str) -> Interval {
+        crate::test_fixtures::interval(serde_json::json!({
+            "type": "CurrentInterval",
+            "perKwh": per_kwh,
+            "startTime": "2021-05-05T02:00:01Z",
+            "renewables": renewables,
+            "spikeStatus": spike_status,
+            "estimate": true
+        }))
+    }
+
+    /// Returns the names of the events.
+    fn names(events: &[PriceEvent]) -> Vec<&'static str> {
+        events
+            .iter()
+            .map(|event| match event {
+                PriceEvent::SpikeStarted { .. } => "spike",
+                PriceEvent::PriceBelow { .. } => "price",
+                PriceEvent::RenewablesAbove { .. } => "renewables",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_transitions() {
+        let site_id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+        let mut detector = EventDetector::builder()
+            .price_below(10.0)
+            .renewables_above(80.0)
+            .build();
+
+        let events = detector.detect(&site_id, &[current(5.0, 50.0, "none")]);
+        assert_eq!(names(&events), vec!["price"]);
+        assert_eq!(
+            serde_json::to_value(events.first()).expect("serializable"),
+            serde_json::json!({
+                "event": "priceBelow",
+                "siteId": "01F5A5CRKMZ5BCX9P1S4V990AM",
+                "channelType": "general",
+                "startTime": "2021-05-05T02:00:01Z",
+                "perKwh": 5.0_f64,
+                "threshold": 10.0_f64
+            })
+        );
+
+        // Conditions which still hold are not reported again
+        let renewables = detector.detect(&site_id, &[current(6.0, 85.0, "none")]);
+        assert_eq!(names(&renewables), vec!["renewables"]);
+        let spike = detector.detect(&site_id, &[current(150.0, 85.0, "spike")]);
+        assert_eq!(names(&spike), vec!["spike"]);
+        assert!(detector.detect(&site_id, &[]).is_empty());
+        let price = detector.detect(&site_id, &[current(8.0, 85.0, "none")]);
+        assert_eq!(names(&price), vec!["price"]);
+    }
+
+    #[cfg(feature = "std")]
+    #[tokio::test]
+    async fn delivers_watched_events() {
+        use alloc::{string::String, sync::Arc};
+        use std::sync::Mutex;
+
+        use crate::{RequestInfo, Response};
+
+        let site_id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID");
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        let mut notifications = Notifications::builder()
+            .notifiers(vec![Box::new(move |event: &PriceEvent| {
+                sink.lock().expect("not poisoned").push(event.kind());
+            })])
+            .build();
+
+        let prices = |spike_status: &str| WatchEvent::Prices {
+            site_id: site_id.clone(),
+            response: Response {
+                data: vec![current(150.0, 50.0, spike_status)],
+                request: RequestInfo {
+                    url: String::new(),
+                    query: vec![],
+                },
+                fetched_at: Timestamp::UNIX_EPOCH,
+                server_time: None,
+                status: 200,
+                rate_limit: None,
+                stale: None,
+            },
+        };
+        assert_eq!(
+            names(&notifications.handle(&prices("spike")).await),
+            vec!["spike"]
+        );
+        assert!(notifications.handle(&prices("spike")).await.is_empty());
+        assert_eq!(
+            *received.lock().expect("not poisoned"),
+            vec!["spikeStarted"]
+        );
+    }
+}
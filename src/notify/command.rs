@@ -0,0 +1,127 @@
+//! Delivery of events to external programs.
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use std::{
+    io::{self, Write as _},
+    process::{Command, Stdio},
+};
+
+use super::{Notifier, PriceEvent};
+use crate::{BoxFuture, Result};
+
+/// Runs a program for each event, with the event as JSON on its standard
+/// input.
+///
+/// The program also receives the name of the event (such as `spikeStarted`)
+/// in the `AMBER_EVENT` environment variable, the site ID in `AMBER_SITE_ID`,
+/// and the event's message in `AMBER_MESSAGE`, so that simple scripts need
+/// not parse the JSON.
+///
+/// # Example
+///
+/// ```
+/// use amber_api::notify::command::CommandNotifier;
+///
+/// let notifier = CommandNotifier::builder()
+///     .program("notify-send")
+///     .args(vec!["Amber".to_owned()])
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, bon::Builder)]
+#[non_exhaustive]
+pub struct CommandNotifier {
+    /// Program to run, looked up in the `PATH` if not a path.
+    #[builder(into)]
+    program: String,
+    /// Arguments passed to the program.
+    #[builder(default)]
+    args: Vec<String>,
+}
+
+impl CommandNotifier {
+    /// Returns the program run for each event.
+    #[must_use]
+    #[inline]
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Run the program for an event, waiting for it to exit.
+    ///
+    /// The program is waited for even if writing its input fails, in which
+    /// case the write error is returned once it has exited.
+    fn run(&self, event: &PriceEvent, input: &[u8]) -> io::Result<()> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .env("AMBER_EVENT", event.kind())
+            .env("AMBER_SITE_ID", event.site_id().as_str())
+            .env("AMBER_MESSAGE", format!("{event}"))
+            .stdin(Stdio::piped())
+            .spawn()?;
+        let written = child
+            .stdin
+            .take()
+            .map_or(Ok(()), |mut stdin| stdin.write_all(input));
+        let status = child.wait()?;
+        written?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!(
+                "{} exited with {status}",
+                self.program
+            )))
+        }
+    }
+}
+
+impl Notifier for CommandNotifier {
+    #[inline]
+    fn notify<'a>(&'a mut self, event: &'a PriceEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let input = serde_json::to_vec(event)?;
+            let notifier = self.clone();
+            let owned = event.clone();
+            tokio::task::spawn_blocking(move || notifier.run(&owned, &input))
+                .await
+                .map_err(io::Error::other)??;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use jiff::Timestamp;
+
+    use super::*;
+    use crate::models::ChannelType;
+
+    /// Returns a spike event.
+    fn event() -> PriceEvent {
+        PriceEvent::SpikeStarted {
+            site_id: "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID"),
+            channel_type: ChannelType::General,
+            start_time: Timestamp::UNIX_EPOCH,
+            per_kwh: 150.0,
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_program() {
+        let mut success = CommandNotifier::builder()
+            .program("sh")
+            .args(vec![
+                "-c".into(),
+                r#"grep -q '"event":"spikeStarted"' && test "$AMBER_EVENT" = spikeStarted"#.into(),
+            ])
+            .build();
+        success.notify(&event()).await.expect("program succeeds");
+
+        let mut failure = CommandNotifier::builder().program("false").build();
+        assert!(failure.notify(&event()).await.is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! Delivery of events to MQTT brokers.
+
+use alloc::{borrow::ToOwned as _, boxed::Box, format, string::String};
+use core::fmt;
+use std::io;
+
+pub use rumqttc::{AsyncClient, MqttOptions, QoS};
+
+use super::{Notifier, PriceEvent};
+use crate::{BoxFuture, Result};
+
+/// Publishes each event as JSON to an MQTT broker.
+///
+/// Events are published to `<prefix>/<site ID>/<event>`, such as
+/// `amber/01F5A5CRKMZ5BCX9P1S4V990AM/spikeStarted`, so that subscribers can
+/// select the sites and events they are interested in with wildcards.
+///
+/// The notifier only queues messages on the client: its event loop must be
+/// polled for them to be sent.
+///
+/// # Example
+///
+/// ```no_run
+/// use amber_api::notify::mqtt::{AsyncClient, MqttNotifier, MqttOptions};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let options = MqttOptions::new("amber", "localhost", 1883);
+/// let (client, mut event_loop) = AsyncClient::new(options, 16);
+/// tokio::spawn(async move {
+///     while event_loop.poll().await.is_ok() {}
+/// });
+///
+/// let notifier = MqttNotifier::builder().client(client).build();
+/// # }
+/// ```
+#[derive(Clone, bon::Builder)]
+#[non_exhaustive]
+pub struct MqttNotifier {
+    /// Client used to publish the events.
+    client: AsyncClient,
+    /// Prefix of the topics.
+    ///
+    /// Defaults to `amber`.
+    #[builder(into, default = "amber".to_owned())]
+    prefix: String,
+    /// Quality of service of the messages.
+    ///
+    /// Defaults to [`QoS::AtLeastOnce`].
+    #[builder(default = QoS::AtLeastOnce)]
+    qos: QoS,
+    /// Whether the broker retains the last message of each topic.
+    ///
+    /// Defaults to `false`.
+    #[builder(default)]
+    retain: bool,
+}
+
+impl fmt::Debug for MqttNotifier {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MqttNotifier")
+            .field("prefix", &self.prefix)
+            .field("qos", &self.qos)
+            .field("retain", &self.retain)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MqttNotifier {
+    /// Returns the topic an event is published to.
+    #[must_use]
+    #[inline]
+    pub fn topic(&self, event: &PriceEvent) -> String {
+        format!("{}/{}/{}", self.prefix, event.site_id(), event.kind())
+    }
+}
+
+impl Notifier for MqttNotifier {
+    #[inline]
+    fn notify<'a>(&'a mut self, event: &'a PriceEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let payload = serde_json::to_vec(event)?;
+            self.client
+                .publish(self.topic(event), self.qos, self.retain, payload)
+                .await
+                .map_err(io::Error::other)?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::Timestamp;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn publishes_by_site_and_event() {
+        let (client, _event_loop) =
+            AsyncClient::new(MqttOptions::new("test", "localhost", 1883), 1);
+        let notifier = MqttNotifier::builder()
+            .client(client)
+            .prefix("home/amber")
+            .build();
+        let event = PriceEvent::RenewablesAbove {
+            site_id: "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID"),
+            start_time: Timestamp::UNIX_EPOCH,
+            renewables: 90.0_f64,
+            threshold: 80.0_f64,
+        };
+        assert_eq!(
+            notifier.topic(&event),
+            "home/amber/01F5A5CRKMZ5BCX9P1S4V990AM/renewablesAbove"
+        );
+    }
+}
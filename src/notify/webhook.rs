@@ -0,0 +1,236 @@
+//! Delivery of events to HTTP endpoints.
+
+use alloc::{borrow::ToOwned as _, boxed::Box, format, string::String, vec::Vec};
+use core::{fmt, time::Duration};
+
+use hmac::{Hmac, Mac as _};
+use serde::Serialize;
+use sha2::Sha256;
+use tracing::warn;
+
+use super::{Notifier, PriceEvent};
+use crate::{
+    BoxFuture,
+    error::{AmberError, Result},
+};
+
+/// Header carrying the signature of the payload.
+pub const SIGNATURE_HEADER: &str = "X-Amber-Signature";
+
+/// Compute the signature of a payload: the hex-encoded HMAC-SHA256 of the
+/// body, prefixed with `sha256=`.
+///
+/// Receivers recompute the signature of the raw body with the shared secret,
+/// and compare it with the [`SIGNATURE_HEADER`] in constant time.
+#[must_use]
+#[inline]
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret) else {
+        // HMAC accepts keys of any length
+        return String::new();
+    };
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let mut signature = String::from("sha256=");
+    signature.extend(digest.into_iter().map(|byte| format!("{byte:02x}")));
+    signature
+}
+
+/// An HTTP endpoint receiving events as JSON `POST` requests.
+///
+/// When a secret is set, each request carries the signature of its body in
+/// the [`SIGNATURE_HEADER`] (see [`sign`]), so that the receiver can check
+/// that the payload is genuine.
+///
+/// Requests failing with a transport error, a server error (5xx) or a rate
+/// limit (429) are retried with exponential backoff. Other responses outside
+/// the 2xx range are not retried.
+///
+/// # Example
+///
+/// ```no_run
+/// use amber_api::models::{Interval, SiteId};
+/// use amber_api::notify::{EventDetector, webhook::Webhook};
+///
+/// # async fn example(site_id: &SiteId, intervals: &[Interval]) -> amber_api::Result<()> {
+/// let webhook = Webhook::builder()
+///     .url("https://example.com/hooks/amber")
+///     .secret("shared secret")
+///     .build();
+/// let mut detector = EventDetector::builder().build();
+/// for event in detector.detect(site_id, intervals) {
+///     webhook.send(&event).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, bon::Builder)]
+#[non_exhaustive]
+pub struct Webhook {
+    /// URL the events are posted to.
+    #[builder(into)]
+    url: String,
+    /// Secret used to sign the payloads, if any.
+    #[builder(into)]
+    secret: Option<String>,
+    /// Maximum number of retries after the initial attempt.
+    ///
+    /// Defaults to 3.
+    #[builder(default = 3)]
+    max_retries: u32,
+    /// Delay before the first retry, doubled for each subsequent retry.
+    ///
+    /// Defaults to 1 second.
+    #[builder(default = Duration::from_secs(1))]
+    backoff: Duration,
+    /// Timeout of each attempt.
+    ///
+    /// Defaults to 10 seconds.
+    #[builder(default = Duration::from_secs(10))]
+    timeout: Duration,
+    /// HTTP client used to send the requests.
+    #[builder(default)]
+    client: reqwest::Client,
+}
+
+impl fmt::Debug for Webhook {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Webhook")
+            .field("url", &self.url)
+            .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
+            .field("max_retries", &self.max_retries)
+            .field("backoff", &self.backoff)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Webhook {
+    /// Returns the URL the events are posted to.
+    #[must_use]
+    #[inline]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the headers sent with a body, including its signature if a
+    /// secret is set.
+    #[must_use]
+    #[inline]
+    pub fn headers(&self, body: &[u8]) -> Vec<(String, String)> {
+        let mut headers = Vec::from([("Content-Type".to_owned(), "application/json".to_owned())]);
+        if let Some(secret) = &self.secret {
+            headers.push((SIGNATURE_HEADER.to_owned(), sign(secret.as_bytes(), body)));
+        }
+        headers
+    }
+
+    /// Post a payload, retrying failed attempts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload cannot be serialised, or if the last
+    /// attempt fails with a transport error or a non-2xx response.
+    #[inline]
+    pub async fn send(&self, payload: &impl Serialize) -> Result<()> {
+        let body = serde_json::to_vec(payload)?;
+        let headers = self.headers(&body);
+        let mut delay = self.backoff;
+        let mut attempt: u32 = 0;
+        loop {
+            let error = match self.attempt(&body, &headers).await {
+                Ok(()) => return Ok(()),
+                Err(error) if attempt < self.max_retries && is_retryable(&error) => error,
+                Err(error) => return Err(error),
+            };
+            attempt = attempt.saturating_add(1);
+            warn!(
+                "Webhook delivery to {} failed, retrying in {}s: {error}",
+                self.url,
+                delay.as_secs_f32()
+            );
+            tokio::time::sleep(delay).await;
+            delay = delay.saturating_mul(2);
+        }
+    }
+
+    /// Make a single attempt at posting a body.
+    async fn attempt(&self, body: &[u8], headers: &[(String, String)]) -> Result<()> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .timeout(self.timeout)
+            .body(body.to_vec());
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        if (200..300).contains(&status) {
+            return Ok(());
+        }
+        Err(AmberError::UnexpectedStatus {
+            status,
+            body: response.text().await.unwrap_or_default(),
+        })
+    }
+}
+
+impl Notifier for Webhook {
+    #[inline]
+    fn notify<'a>(&'a mut self, event: &'a PriceEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(self.send(event))
+    }
+}
+
+/// Returns `true` if a failed delivery may succeed when retried.
+fn is_retryable(error: &AmberError) -> bool {
+    matches!(error, AmberError::Http(_) | AmberError::Timeout)
+        || matches!(
+            error,
+            AmberError::UnexpectedStatus { status, .. } if *status == 429 || *status >= 500
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn signs_payloads() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let webhook = Webhook::builder()
+            .url("https://example.com/hook")
+            .secret("Jefe")
+            .build();
+        let headers = webhook.headers(b"what do ya want for nothing?");
+        assert_eq!(
+            headers.get(1).map(|(name, _)| name.as_str()),
+            Some(SIGNATURE_HEADER)
+        );
+        assert!(!format!("{webhook:?}").contains("Jefe"));
+
+        let unsigned = Webhook::builder().url("https://example.com/hook").build();
+        assert_eq!(unsigned.headers(b"{}").len(), 1);
+    }
+
+    #[test]
+    fn retries_transient_failures() {
+        let status = |status| AmberError::UnexpectedStatus {
+            status,
+            body: String::new(),
+        };
+        assert!(is_retryable(&status(503)));
+        assert!(is_retryable(&status(429)));
+        assert!(!is_retryable(&status(404)));
+        assert!(is_retryable(&AmberError::Timeout));
+    }
+}
@@ -0,0 +1,280 @@
+//! # Numeric Precision Policy
+//!
+//! Prices, costs, energy, power and percentages are all represented as `f64`
+//! values.
+//! When rendered for humans (or exported to text formats), the number of
+//! decimal places used for each kind of quantity is controlled by a
+//! [`PrecisionPolicy`]. Serialised models, including the
+//! [JSON Lines export](crate::export::jsonl), always keep full precision.
+//!
+//! The [`Display`](core::fmt::Display) implementations of the models use
+//! [`PrecisionPolicy::default()`]. A different policy can be applied to any
+//! model implementing [`DisplayPrecision`]:
+//!
+//! ```
+//! use amber_api::models::Range;
+//! use amber_api::precision::{DisplayPrecision as _, PrecisionPolicy};
+//!
+//! # fn example(range: &Range) {
+//! let policy = PrecisionPolicy::DEFAULT.with_prices(Some(4));
+//! println!("{}", range.display_with(&policy));
+//! # }
+//! ```
+
+use core::fmt;
+
+/// Number of decimal places used when rendering each kind of quantity.
+///
+/// A value of `None` renders the number with the shortest representation that
+/// round-trips, which is the default for percentages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PrecisionPolicy {
+    /// Decimal places for prices in c/kWh.
+    ///
+    /// Defaults to 2.
+    pub prices: Option<usize>,
+    /// Decimal places for costs.
+    ///
+    /// Defaults to 2.
+    pub costs: Option<usize>,
+    /// Decimal places for energy in kWh.
+    ///
+    /// Defaults to 2.
+    pub energy: Option<usize>,
+    /// Decimal places for power in kW.
+    ///
+    /// Defaults to 2.
+    pub power: Option<usize>,
+    /// Decimal places for percentages (such as the renewables percentage).
+    ///
+    /// Defaults to `None` (shortest representation).
+    pub percentages: Option<usize>,
+}
+
+impl PrecisionPolicy {
+    /// The default policy: two decimal places for prices, costs, energy and
+    /// power, and the shortest representation for percentages.
+    pub const DEFAULT: Self = Self {
+        prices: Some(2),
+        costs: Some(2),
+        energy: Some(2),
+        power: Some(2),
+        percentages: None,
+    };
+
+    /// A policy which never rounds, rendering every value in full.
+    pub const FULL: Self = Self {
+        prices: None,
+        costs: None,
+        energy: None,
+        power: None,
+        percentages: None,
+    };
+
+    /// Returns the policy with the number of decimal places for prices set.
+    #[must_use]
+    #[inline]
+    pub const fn with_prices(mut self, places: Option<usize>) -> Self {
+        self.prices = places;
+        self
+    }
+
+    /// Returns the policy with the number of decimal places for costs set.
+    #[must_use]
+    #[inline]
+    pub const fn with_costs(mut self, places: Option<usize>) -> Self {
+        self.costs = places;
+        self
+    }
+
+    /// Returns the policy with the number of decimal places for energy quantities set.
+    #[must_use]
+    #[inline]
+    pub const fn with_energy(mut self, places: Option<usize>) -> Self {
+        self.energy = places;
+        self
+    }
+
+    /// Returns the policy with the number of decimal places for power set.
+    #[must_use]
+    #[inline]
+    pub const fn with_power(mut self, places: Option<usize>) -> Self {
+        self.power = places;
+        self
+    }
+
+    /// Returns the policy with the number of decimal places for percentages set.
+    #[must_use]
+    #[inline]
+    pub const fn with_percentages(mut self, places: Option<usize>) -> Self {
+        self.percentages = places;
+        self
+    }
+
+    /// Format a price (c/kWh) according to this policy.
+    #[must_use]
+    #[inline]
+    pub fn price(&self, value: f64) -> Fixed {
+        Fixed::new(value, self.prices)
+    }
+
+    /// Format a cost according to this policy.
+    #[must_use]
+    #[inline]
+    pub fn cost(&self, value: f64) -> Fixed {
+        Fixed::new(value, self.costs)
+    }
+
+    /// Format an energy quantity (kWh) according to this policy.
+    #[must_use]
+    #[inline]
+    pub fn energy(&self, value: f64) -> Fixed {
+        Fixed::new(value, self.energy)
+    }
+
+    /// Format a power (kW) according to this policy.
+    #[must_use]
+    #[inline]
+    pub fn power(&self, value: f64) -> Fixed {
+        Fixed::new(value, self.power)
+    }
+
+    /// Format a percentage according to this policy.
+    #[must_use]
+    #[inline]
+    pub fn percentage(&self, value: f64) -> Fixed {
+        Fixed::new(value, self.percentages)
+    }
+}
+
+impl Default for PrecisionPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A number rendered with an optional fixed number of decimal places.
+///
+/// Created by the formatting methods of [`PrecisionPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fixed {
+    /// The value to render.
+    value: f64,
+    /// Number of decimal places, or `None` for the shortest representation.
+    places: Option<usize>,
+}
+
+impl Fixed {
+    /// Create a new fixed-precision value.
+    #[must_use]
+    #[inline]
+    pub const fn new(value: f64, places: Option<usize>) -> Self {
+        Self { value, places }
+    }
+}
+
+impl fmt::Display for Fixed {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.places {
+            Some(places) => write!(f, "{:.*}", places, self.value),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+/// Human-readable rendering under a configurable [`PrecisionPolicy`].
+///
+/// All models with a [`Display`](fmt::Display) implementation containing
+/// numeric quantities implement this trait, and their `Display`
+/// implementation is equivalent to rendering with the default policy.
+pub trait DisplayPrecision {
+    /// Format the value using the given precision policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the formatter fails.
+    fn fmt_precision(&self, f: &mut fmt::Formatter<'_>, policy: &PrecisionPolicy) -> fmt::Result;
+
+    /// Returns a wrapper implementing [`Display`](fmt::Display) using the
+    /// given precision policy.
+    #[inline]
+    fn display_with<'a>(&'a self, policy: &'a PrecisionPolicy) -> WithPrecision<'a, Self> {
+        WithPrecision {
+            value: self,
+            policy,
+        }
+    }
+}
+
+/// Wrapper rendering a value with a specific [`PrecisionPolicy`].
+///
+/// Created by [`DisplayPrecision::display_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct WithPrecision<'a, T: ?Sized> {
+    /// The value to render.
+    value: &'a T,
+    /// The policy to render with.
+    policy: &'a PrecisionPolicy,
+}
+
+impl<T: DisplayPrecision + ?Sized> fmt::Display for WithPrecision<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_precision(f, self.policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::{AdvancedPrice, Range};
+
+    #[test]
+    fn fixed() {
+        insta::assert_snapshot!(Fixed::new(1.23456, Some(2)).to_string(), @"1.23");
+        insta::assert_snapshot!(Fixed::new(1.23456, Some(0)).to_string(), @"1");
+        insta::assert_snapshot!(Fixed::new(1.23456, None).to_string(), @"1.23456");
+    }
+
+    #[test]
+    fn setters() {
+        let policy = PrecisionPolicy::DEFAULT
+            .with_prices(Some(4))
+            .with_power(Some(1))
+            .with_percentages(Some(1));
+        assert_eq!(policy.prices, Some(4));
+        assert_eq!(policy.costs, Some(2));
+        assert_eq!(policy.power, Some(1));
+        assert_eq!(policy.percentages, Some(1));
+        assert_eq!(PrecisionPolicy::default(), PrecisionPolicy::DEFAULT);
+    }
+
+    #[test]
+    fn display_with_policy() {
+        let range = Range {
+            min: 12.3456,
+            max: 56.7891,
+        };
+        let advanced_price = AdvancedPrice {
+            low: 1.23456,
+            predicted: 4.56789,
+            high: 7.89123,
+        };
+        let policy = PrecisionPolicy::DEFAULT.with_prices(Some(3));
+
+        insta::assert_snapshot!(range.display_with(&policy).to_string(), @"12.346-56.789c/kWh");
+        insta::assert_snapshot!(range.display_with(&PrecisionPolicy::FULL).to_string(), @"12.3456-56.7891c/kWh");
+        insta::assert_snapshot!(advanced_price.display_with(&policy).to_string(), @"L:1.235 H:4.568 P:7.891 c/kWh");
+        assert_eq!(
+            range.to_string(),
+            range.display_with(&PrecisionPolicy::DEFAULT).to_string()
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! # Rate Limit Metadata
+//!
+//! The Amber API reports its rate limits through the `RateLimit-Limit`,
+//! `RateLimit-Remaining`, `RateLimit-Reset` and `RateLimit-Policy` response
+//! headers. These are captured in a [`RateLimitInfo`], which is available from
+//! the client after each request and attached to rate limit errors, allowing
+//! callers to throttle themselves.
+
+use core::fmt;
+
+/// Rate limit metadata reported by the API.
+///
+/// Each field is `None` if the corresponding header was absent or could not be
+/// parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct RateLimitInfo {
+    /// Maximum number of requests allowed in the current window.
+    pub limit: Option<u64>,
+    /// Number of requests remaining in the current window.
+    pub remaining: Option<u64>,
+    /// Number of seconds until the current window resets.
+    pub reset: Option<u64>,
+    /// Length of the rate limit window in seconds, from the `RateLimit-Policy`
+    /// header.
+    pub window: Option<u64>,
+}
+
+impl RateLimitInfo {
+    /// Parse rate limit metadata from the values of the `RateLimit-Limit`,
+    /// `RateLimit-Remaining`, `RateLimit-Reset` and `RateLimit-Policy`
+    /// headers.
+    ///
+    /// Values may either be a plain number, or a number followed by parameters
+    /// (such as `50;w=300`), in which case only the leading number is used.
+    /// The window is read from the `w` parameter of the policy.
+    #[must_use]
+    #[inline]
+    pub fn from_headers(
+        limit: Option<&str>,
+        remaining: Option<&str>,
+        reset: Option<&str>,
+        policy: Option<&str>,
+    ) -> Self {
+        Self {
+            limit: limit.and_then(leading_number),
+            remaining: remaining.and_then(leading_number),
+            reset: reset.and_then(leading_number),
+            window: policy.and_then(window_parameter),
+        }
+    }
+
+    /// Returns `true` if no rate limit information was reported.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns `true` if the API reported that no requests remain in the
+    /// current window.
+    #[must_use]
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+}
+
+impl fmt::Display for RateLimitInfo {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.remaining, self.limit) {
+            (Some(remaining), Some(limit)) => write!(f, "{remaining}/{limit} requests remaining")?,
+            (Some(remaining), None) => write!(f, "{remaining} requests remaining")?,
+            (None, Some(limit)) => write!(f, "limit of {limit} requests")?,
+            (None, None) => write!(f, "unknown rate limit")?,
+        }
+        if let Some(reset) = self.reset {
+            write!(f, ", resets in {reset}s")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the leading number of a header value, ignoring any parameters.
+#[cfg_attr(
+    feature = "no-panic",
+    inline(never),
+    no_panic::no_panic,
+    expect(clippy::mem_forget, reason = "Expanded by no_panic")
+)]
+fn leading_number(value: &str) -> Option<u64> {
+    // Deliberate panic, to check that the `no-panic` build detects it.
+    #[cfg(no_panic_canary)]
+    assert!(value.len() < 100, "header value too long");
+    value
+        .split([';', ','])
+        .next()
+        .and_then(|number| number.trim().parse().ok())
+}
+
+/// Parse the `w` parameter of a `RateLimit-Policy` header value.
+#[cfg_attr(
+    feature = "no-panic",
+    inline(never),
+    no_panic::no_panic,
+    expect(clippy::mem_forget, reason = "Expanded by no_panic")
+)]
+fn window_parameter(value: &str) -> Option<u64> {
+    value
+        .split([';', ','])
+        .filter_map(|param| param.trim().strip_prefix("w="))
+        .find_map(|window| window.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn parse_headers() {
+        let info =
+            RateLimitInfo::from_headers(Some("50"), Some("12"), Some("120"), Some("50;w=300"));
+        assert_eq!(
+            info,
+            RateLimitInfo {
+                limit: Some(50),
+                remaining: Some(12),
+                reset: Some(120),
+                window: Some(300),
+            }
+        );
+        assert!(!info.is_exhausted());
+        insta::assert_snapshot!(info.to_string(), @"12/50 requests remaining, resets in 120s");
+    }
+
+    #[test]
+    fn parse_missing_and_invalid() {
+        let info = RateLimitInfo::from_headers(Some("50, 50;w=300"), Some("0"), Some("soon"), None);
+        assert_eq!(info.limit, Some(50));
+        assert_eq!(info.reset, None);
+        assert_eq!(info.window, None);
+        assert!(info.is_exhausted());
+        assert!(RateLimitInfo::from_headers(None, None, None, None).is_empty());
+    }
+
+    #[test]
+    fn parse_malformed() {
+        for value in [
+            "",
+            ";",
+            ",,",
+            "-1",
+            "1e3",
+            "18446744073709551616",
+            "\u{e9};w=\u{e9}",
+            "w=;w",
+        ] {
+            let info =
+                RateLimitInfo::from_headers(Some(value), Some(value), Some(value), Some(value));
+            assert!(info.is_empty(), "{value:?}");
+        }
+    }
+}
@@ -0,0 +1,381 @@
+//! # Typed Requests
+//!
+//! Each parameterised endpoint of the API has a request type holding its
+//! parameters: [`PricesRequest`], [`CurrentPricesRequest`], [`UsageRequest`]
+//! and [`CurrentRenewablesRequest`]. The endpoint methods of the [`Amber`](crate::Amber)
+//! client build these internally, and they can also be constructed directly
+//! and sent with [`Amber::send()`](crate::Amber::send).
+//!
+//! Requests are plain values: they can be compared and hashed (for example, to
+//! key a cache of responses), serialised (to store a list of queries to run)
+//! and reused. The [`ApiRequest`] trait describes how each maps to the path and
+//! query string of the API.
+//!
+//! ```
+//! use amber_api::models::{Resolution, SiteId};
+//! use amber_api::requests::{ApiRequest, PricesRequest};
+//! use jiff::civil::date;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let site_id: SiteId = "01F5A5CRKMZ5BCX9P1S4V990AM".parse()?;
+//! let request = PricesRequest::builder()
+//!     .site_id(site_id)
+//!     .start_date(date(2021, 5, 1))
+//!     .end_date(date(2021, 5, 7))
+//!     .resolution(Resolution::ThirtyMinute)
+//!     .build();
+//!
+//! assert_eq!(request.path(), "sites/01F5A5CRKMZ5BCX9P1S4V990AM/prices");
+//! assert_eq!(request.query().len(), 3);
+//! request.validate()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write as _};
+
+use jiff::civil::Date;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    error::Result,
+    limits,
+    models::{Interval, Renewable, Resolution, SiteId, State, Usage},
+};
+
+/// A request to a parameterised endpoint of the API.
+pub trait ApiRequest {
+    /// Type of the response body.
+    type Output: DeserializeOwned;
+
+    /// Returns the path of the endpoint, relative to the base URL.
+    fn path(&self) -> String;
+
+    /// Returns the query parameters of the request.
+    fn query(&self) -> Vec<(&'static str, String)>;
+
+    /// Check that the parameters are accepted by the API, before sending the
+    /// request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmberError::InvalidRequest`](crate::AmberError::InvalidRequest)
+    /// if the API would reject the request.
+    #[inline]
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns the last date covered by the request, if it is bounded.
+    ///
+    /// Requests ending before today cover data which no longer changes.
+    #[inline]
+    fn end_date(&self) -> Option<Date> {
+        None
+    }
+}
+
+/// Request for the prices of a site between two dates.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, bon::Builder)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PricesRequest {
+    /// ID of the site.
+    pub site_id: SiteId,
+    /// First date of the prices, defaulting to today.
+    pub start_date: Option<Date>,
+    /// Last date of the prices, defaulting to today.
+    pub end_date: Option<Date>,
+    /// Resolution of the intervals, defaulting to the site's.
+    pub resolution: Option<Resolution>,
+}
+
+impl ApiRequest for PricesRequest {
+    type Output = Vec<Interval>;
+
+    #[inline]
+    fn path(&self) -> String {
+        display(format_args!("sites/{}/prices", self.site_id))
+    }
+
+    #[inline]
+    fn query(&self) -> Vec<(&'static str, String)> {
+        [
+            ("startDate", self.start_date.map(display)),
+            ("endDate", self.end_date.map(display)),
+            ("resolution", self.resolution.map(display)),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .collect()
+    }
+
+    #[inline]
+    fn validate(&self) -> Result<()> {
+        if let (Some(start), Some(end)) = (self.start_date, self.end_date) {
+            limits::validate_date_range(start, end)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn end_date(&self) -> Option<Date> {
+        self.end_date
+    }
+}
+
+/// Request for the current prices of a site, along with neighbouring
+/// intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, bon::Builder)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CurrentPricesRequest {
+    /// ID of the site.
+    pub site_id: SiteId,
+    /// Number of forecast intervals to return for each channel.
+    pub next: Option<u32>,
+    /// Number of past intervals to return for each channel.
+    pub previous: Option<u32>,
+    /// Resolution of the intervals, defaulting to the site's.
+    pub resolution: Option<Resolution>,
+}
+
+impl ApiRequest for CurrentPricesRequest {
+    type Output = Vec<Interval>;
+
+    #[inline]
+    fn path(&self) -> String {
+        display(format_args!("sites/{}/prices/current", self.site_id))
+    }
+
+    #[inline]
+    fn query(&self) -> Vec<(&'static str, String)> {
+        [
+            ("next", self.next.map(display)),
+            ("previous", self.previous.map(display)),
+            ("resolution", self.resolution.map(display)),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .collect()
+    }
+
+    #[inline]
+    fn validate(&self) -> Result<()> {
+        limits::validate_interval_counts(self.next.unwrap_or(0), self.previous.unwrap_or(0))
+    }
+}
+
+/// Request for the usage of a site between two dates.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, bon::Builder)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct UsageRequest {
+    /// ID of the site.
+    pub site_id: SiteId,
+    /// First date of the usage.
+    pub start_date: Date,
+    /// Last date of the usage.
+    pub end_date: Date,
+}
+
+impl ApiRequest for UsageRequest {
+    type Output = Vec<Usage>;
+
+    #[inline]
+    fn path(&self) -> String {
+        display(format_args!("sites/{}/usage", self.site_id))
+    }
+
+    #[inline]
+    fn query(&self) -> Vec<(&'static str, String)> {
+        Vec::from([
+            ("startDate", display(self.start_date)),
+            ("endDate", display(self.end_date)),
+        ])
+    }
+
+    #[inline]
+    fn validate(&self) -> Result<()> {
+        limits::validate_date_range(self.start_date, self.end_date)
+    }
+
+    #[inline]
+    fn end_date(&self) -> Option<Date> {
+        Some(self.end_date)
+    }
+}
+
+/// Request for the current percentage of renewables in the grid of a state,
+/// along with neighbouring intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, bon::Builder)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CurrentRenewablesRequest {
+    /// State of the grid.
+    pub state: State,
+    /// Number of forecast intervals to return.
+    pub next: Option<u32>,
+    /// Number of past intervals to return.
+    pub previous: Option<u32>,
+    /// Resolution of the intervals, defaulting to 30 minutes.
+    pub resolution: Option<Resolution>,
+}
+
+impl ApiRequest for CurrentRenewablesRequest {
+    type Output = Vec<Renewable>;
+
+    #[inline]
+    fn path(&self) -> String {
+        display(format_args!("state/{}/renewables/current", self.state))
+    }
+
+    #[inline]
+    fn query(&self) -> Vec<(&'static str, String)> {
+        [
+            ("next", self.next.map(display)),
+            ("previous", self.previous.map(display)),
+            ("resolution", self.resolution.map(display)),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .collect()
+    }
+
+    #[inline]
+    fn validate(&self) -> Result<()> {
+        limits::validate_interval_counts(self.next.unwrap_or(0), self.previous.unwrap_or(0))
+    }
+}
+
+/// Format a value as a path segment or query parameter.
+///
+/// Unlike `to_string()` and `format!`, this does not panic should a `Display`
+/// implementation fail.
+fn display(value: impl fmt::Display) -> String {
+    let mut formatted = String::new();
+    if write!(formatted, "{value}").is_err() {
+        formatted.clear();
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned as _, vec};
+
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Parse the test site ID.
+    fn site_id() -> SiteId {
+        "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid ID")
+    }
+
+    #[test]
+    fn prices_request() {
+        let request = PricesRequest::builder()
+            .site_id(site_id())
+            .end_date(date(2021, 5, 7))
+            .resolution(Resolution::FiveMinute)
+            .build();
+        assert_eq!(request.path(), "sites/01F5A5CRKMZ5BCX9P1S4V990AM/prices");
+        assert_eq!(
+            request.query(),
+            vec![
+                ("endDate", "2021-05-07".to_owned()),
+                ("resolution", "5".to_owned())
+            ]
+        );
+        assert_eq!(ApiRequest::end_date(&request), Some(date(2021, 5, 7)));
+        request.validate().expect("valid request");
+
+        let json = serde_json::to_value(&request).expect("serialises");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "siteId": "01F5A5CRKMZ5BCX9P1S4V990AM",
+                "startDate": null,
+                "endDate": "2021-05-07",
+                "resolution": 5_i32
+            })
+        );
+        let parsed: PricesRequest = serde_json::from_value(json).expect("deserialises");
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn current_prices_request() {
+        let request = CurrentPricesRequest::builder()
+            .site_id(site_id())
+            .next(12)
+            .build();
+        assert_eq!(
+            request.path(),
+            "sites/01F5A5CRKMZ5BCX9P1S4V990AM/prices/current"
+        );
+        assert_eq!(request.query(), vec![("next", "12".to_owned())]);
+        request.validate().expect("valid request");
+        assert!(
+            CurrentPricesRequest::builder()
+                .site_id(site_id())
+                .next(limits::MAX_INTERVALS)
+                .previous(1)
+                .build()
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn current_renewables_request() {
+        let request = CurrentRenewablesRequest::builder()
+            .state(State::Vic)
+            .previous(48)
+            .resolution(Resolution::ThirtyMinute)
+            .build();
+        assert_eq!(request.path(), "state/vic/renewables/current");
+        assert_eq!(
+            request.query(),
+            vec![
+                ("previous", "48".to_owned()),
+                ("resolution", "30".to_owned())
+            ]
+        );
+        request.validate().expect("valid request");
+
+        let json = serde_json::to_value(&request).expect("serialises");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "state": "vic",
+                "next": null,
+                "previous": 48_i32,
+                "resolution": 30_i32
+            })
+        );
+        let parsed: CurrentRenewablesRequest = serde_json::from_value(json).expect("deserialises");
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn usage_request() {
+        let request = UsageRequest::builder()
+            .site_id(site_id())
+            .start_date(date(2021, 5, 1))
+            .end_date(date(2021, 5, 9))
+            .build();
+        assert_eq!(request.path(), "sites/01F5A5CRKMZ5BCX9P1S4V990AM/usage");
+        assert_eq!(
+            request.query(),
+            vec![
+                ("startDate", "2021-05-01".to_owned()),
+                ("endDate", "2021-05-09".to_owned())
+            ]
+        );
+        assert!(request.validate().is_err());
+    }
+}
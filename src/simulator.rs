@@ -0,0 +1,470 @@
+//! # Synthetic Prices
+//!
+//! A [`Simulator`] generates streams of realistic-looking [`Interval`]s, for
+//! developing and load-testing automations when no API key or price history is
+//! available. Prices follow a daily "duck curve":
+//!
+//! - low prices overnight, with a small rise on the morning peak;
+//! - a dip around midday, when rooftop solar floods the grid, often below zero
+//!   during the solar sponge;
+//! - the highest prices on the evening peak, as solar generation falls away;
+//!
+//! with some noise, and occasional price spikes lasting a few intervals. The
+//! share of renewables follows the sun. Streams are deterministic: the same
+//! simulator with the same seed always generates the same prices.
+//!
+//! ```
+//! use amber_api::{models::Resolution, simulator::Simulator};
+//!
+//! let simulator = Simulator::builder()
+//!     .start("2025-01-01T00:00:00+10:00".parse().expect("valid timestamp"))
+//!     .resolution(Resolution::FiveMinute)
+//!     .seed(42)
+//!     .build();
+//! for interval in simulator.intervals().take(288) {
+//!     println!("{interval}");
+//! }
+//!
+//! // Or mimic a response of the current prices endpoint
+//! let now = "2025-01-01T18:12:00+10:00".parse().expect("valid timestamp");
+//! let prices = simulator.current_prices(now, 12, 48);
+//! assert_eq!(prices.len(), 61);
+//! ```
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use jiff::{SignedDuration, Timestamp};
+
+use crate::{
+    analysis::round,
+    models::{
+        ActualInterval, AdvancedPrice, BaseInterval, ChannelType, CurrentInterval,
+        ForecastInterval, Interval, PriceDescriptor, Resolution, SpikeStatus, nem_time_zone,
+    },
+};
+
+/// Shape of the spot price over the day, by hour in NEM time.
+///
+/// Values range from -1 (midday, when solar generation is highest) to 1 (the
+/// evening peak), and are scaled by [`Simulator::amplitude`].
+const DUCK_CURVE: [f64; 24] = [
+    -0.15, -0.2, -0.25, -0.25, -0.2, 0.0, 0.25, 0.4, 0.2, -0.15, -0.5, -0.8, -1.0, -0.95, -0.7,
+    -0.25, 0.25, 0.7, 1.0, 0.85, 0.55, 0.3, 0.1, -0.05,
+];
+
+/// Returns the price descriptor of a spot price (c/kWh).
+pub(crate) fn price_descriptor(spot_per_kwh: f64) -> PriceDescriptor {
+    if spot_per_kwh < 0.0 {
+        PriceDescriptor::ExtremelyLow
+    } else if spot_per_kwh < 5.0 {
+        PriceDescriptor::VeryLow
+    } else if spot_per_kwh < 15.0 {
+        PriceDescriptor::Low
+    } else if spot_per_kwh < 30.0 {
+        PriceDescriptor::Neutral
+    } else if spot_per_kwh < 300.0 {
+        PriceDescriptor::High
+    } else {
+        PriceDescriptor::Spike
+    }
+}
+
+/// Returns the spike status of a spot price (c/kWh).
+pub(crate) fn spike_status(spot_per_kwh: f64) -> SpikeStatus {
+    if spot_per_kwh >= 300.0 {
+        SpikeStatus::Spike
+    } else if spot_per_kwh >= 100.0 {
+        SpikeStatus::Potential
+    } else {
+        SpikeStatus::None
+    }
+}
+
+/// Round a value to two decimal places.
+#[expect(clippy::float_arithmetic, reason = "Rounding to two decimal places")]
+pub(crate) fn cents(value: f64) -> f64 {
+    round(value * 100.0) / 100.0
+}
+
+/// A small deterministic pseudo-random number generator (`SplitMix64`).
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    /// Returns the next 64 random bits.
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in `[0, 1)`.
+    #[expect(clippy::float_arithmetic, reason = "Scaling random bits")]
+    fn next_f64(&mut self) -> f64 {
+        let bits = u32::try_from(self.next_u64() >> 32_i32).unwrap_or_default();
+        f64::from(bits) / 4_294_967_296.0
+    }
+
+    /// Returns a random number in `[low, high)`.
+    #[expect(clippy::float_arithmetic, reason = "Scaling a random number")]
+    fn range(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+}
+
+/// Generates synthetic price intervals.
+///
+/// See the [module documentation](self) for the shape of the prices.
+#[derive(Debug, Clone, PartialEq, bon::Builder)]
+#[non_exhaustive]
+pub struct Simulator {
+    /// Time from which intervals are generated.
+    ///
+    /// The first interval is the one containing this time.
+    pub start: Timestamp,
+    /// Length of the intervals.
+    ///
+    /// Defaults to 30 minutes.
+    #[builder(default = Resolution::ThirtyMinute)]
+    pub resolution: Resolution,
+    /// Channel whose prices are generated.
+    ///
+    /// Feed-in prices are the negative of the spot price, and prices of other
+    /// channels include [`network_per_kwh`](Self::network_per_kwh). Defaults to
+    /// [`ChannelType::General`].
+    #[builder(default = ChannelType::General)]
+    pub channel_type: ChannelType,
+    /// Seed of the random noise and spikes.
+    ///
+    /// Defaults to 0.
+    #[builder(default)]
+    pub seed: u64,
+    /// Average spot price over the day (c/kWh).
+    ///
+    /// Defaults to 10 c/kWh.
+    #[builder(default = 10.0_f64)]
+    pub mean_spot_per_kwh: f64,
+    /// Difference between the average spot price and the highest (evening) or
+    /// lowest (midday) point of the daily curve (c/kWh).
+    ///
+    /// Midday prices are negative whenever this exceeds the average spot
+    /// price. Defaults to 15 c/kWh.
+    #[builder(default = 15.0_f64)]
+    pub amplitude: f64,
+    /// Maximum random deviation of the spot price from the curve (c/kWh).
+    ///
+    /// Defaults to 2 c/kWh.
+    #[builder(default = 2.0_f64)]
+    pub noise: f64,
+    /// Network and market fees added to the spot price (c/kWh).
+    ///
+    /// Defaults to 15 c/kWh.
+    #[builder(default = 15.0_f64)]
+    pub network_per_kwh: f64,
+    /// Probability of a price spike starting in each interval.
+    ///
+    /// Defaults to 0.005, which is one or two spikes a week with 30-minute
+    /// intervals.
+    #[builder(default = 0.005_f64)]
+    pub spike_probability: f64,
+    /// Typical spot price during a spike (c/kWh).
+    ///
+    /// Spikes range from half to one and a half times this price. Defaults to
+    /// 500 c/kWh.
+    #[builder(default = 500.0_f64)]
+    pub spike_per_kwh: f64,
+}
+
+impl Simulator {
+    /// Returns the length of the intervals.
+    fn interval_length(&self) -> SignedDuration {
+        SignedDuration::from_mins(i64::from(u32::from(self.resolution)))
+    }
+
+    /// Returns the end of the interval containing a time.
+    fn interval_end(&self, time: Timestamp) -> Timestamp {
+        let length = self.interval_length().as_secs();
+        let second = time.as_second();
+        let past = second.checked_rem_euclid(length).unwrap_or_default();
+        let end = if past == 0 {
+            second
+        } else {
+            second.saturating_add(length.saturating_sub(past))
+        };
+        Timestamp::from_second(end).unwrap_or(time)
+    }
+
+    /// Returns the infinite stream of intervals from the
+    /// [start](Self::start).
+    ///
+    /// All intervals are [actual intervals](Interval::ActualInterval); use
+    /// [`current_prices`](Self::current_prices) for a mix of actual, current
+    /// and forecast intervals.
+    #[must_use]
+    #[inline]
+    pub fn intervals(&self) -> SimulatedIntervals<'_> {
+        SimulatedIntervals {
+            simulator: self,
+            rng: Rng(self.seed),
+            end: self.interval_end(self.start),
+            spike: 0,
+        }
+    }
+
+    /// Returns the intervals around a time, as returned by the current prices
+    /// endpoint.
+    ///
+    /// The interval containing `now` is a [current
+    /// interval](Interval::CurrentInterval), preceded by `previous` actual
+    /// intervals and followed by `next` forecast intervals. `now` must not be
+    /// before the [start](Self::start) plus the `previous` intervals, as
+    /// intervals before the start are not generated.
+    #[must_use]
+    #[inline]
+    pub fn current_prices(&self, now: Timestamp, previous: u32, next: u32) -> Vec<Interval> {
+        let current = self.interval_end(now);
+        let skip = current
+            .duration_since(self.interval_end(self.start))
+            .as_secs()
+            .checked_div(self.interval_length().as_secs())
+            .and_then(|count| usize::try_from(count).ok())
+            .unwrap_or_default()
+            .saturating_sub(usize::try_from(previous).unwrap_or(usize::MAX));
+        let mut rng = Rng(self.seed);
+        self.intervals()
+            .skip(skip)
+            .take(
+                usize::try_from(previous.saturating_add(next).saturating_add(1))
+                    .unwrap_or(usize::MAX),
+            )
+            .filter_map(Interval::into_base_interval)
+            .map(|base| match base.end_time.cmp(&current) {
+                Ordering::Less => Interval::ActualInterval(ActualInterval { base }),
+                Ordering::Equal => Interval::CurrentInterval(CurrentInterval {
+                    base,
+                    range: None,
+                    estimate: true,
+                    advanced_price: None,
+                }),
+                Ordering::Greater => {
+                    let advanced_price = self.advanced_price(&mut rng, base.per_kwh);
+                    Interval::ForecastInterval(ForecastInterval {
+                        base,
+                        range: None,
+                        advanced_price: Some(advanced_price),
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a prediction band around a forecast price.
+    #[expect(clippy::float_arithmetic, reason = "Widening a price into a band")]
+    fn advanced_price(&self, rng: &mut Rng, predicted: f64) -> AdvancedPrice {
+        let width = self.noise.abs().max(1.0);
+        AdvancedPrice {
+            low: cents(predicted - rng.range(width, width * 3.0)),
+            predicted,
+            high: cents(predicted + rng.range(width, width * 6.0)),
+        }
+    }
+
+    /// Returns the spot price on the daily curve at a fractional hour of the
+    /// day, in NEM time.
+    #[expect(clippy::float_arithmetic, reason = "Interpolating the daily curve")]
+    fn curve(&self, hour: usize, fraction: f64) -> f64 {
+        let here = DUCK_CURVE.get(hour).copied().unwrap_or_default();
+        let next = DUCK_CURVE
+            .get(hour.saturating_add(1).checked_rem(24).unwrap_or_default())
+            .copied()
+            .unwrap_or_default();
+        self.mean_spot_per_kwh + self.amplitude * (here + (next - here) * fraction)
+    }
+}
+
+/// The stream of intervals generated by a [`Simulator`].
+///
+/// Returned by [`Simulator::intervals`]. The stream never ends.
+#[derive(Debug, Clone)]
+pub struct SimulatedIntervals<'a> {
+    /// The simulator generating the intervals.
+    simulator: &'a Simulator,
+    /// Source of the noise and spikes.
+    rng: Rng,
+    /// End of the next interval.
+    end: Timestamp,
+    /// Number of intervals remaining in the current spike.
+    spike: u32,
+}
+
+impl Iterator for SimulatedIntervals<'_> {
+    type Item = Interval;
+
+    #[inline]
+    #[expect(
+        clippy::float_arithmetic,
+        reason = "Prices and renewables are generated from the daily curve"
+    )]
+    fn next(&mut self) -> Option<Interval> {
+        let simulator = self.simulator;
+        let length = simulator.interval_length();
+        let end_time = self.end;
+        let start_time = end_time
+            .checked_sub(length)
+            .and_then(|start| start.checked_add(SignedDuration::from_secs(1)))
+            .ok()?;
+        self.end = end_time.checked_add(length).ok()?;
+
+        // Position the interval on the daily curve by its midpoint
+        let midpoint = start_time
+            .checked_add(length.checked_div(2)?)
+            .ok()?
+            .to_zoned(nem_time_zone());
+        let hour = usize::try_from(midpoint.hour()).unwrap_or_default();
+        let fraction = f64::from(midpoint.minute()) / 60.0_f64;
+
+        let noise = self.rng.range(-simulator.noise, simulator.noise);
+        if self.spike == 0 && self.rng.next_f64() < simulator.spike_probability {
+            let intervals = self.rng.next_u64().checked_rem(6).unwrap_or_default();
+            self.spike = u32::try_from(intervals)
+                .unwrap_or_default()
+                .saturating_add(1);
+        }
+        let spot_per_kwh = if self.spike > 0 {
+            self.spike = self.spike.saturating_sub(1);
+            cents(simulator.spike_per_kwh * self.rng.range(0.5, 1.5))
+        } else {
+            cents(simulator.curve(hour, fraction) + noise)
+        };
+        let per_kwh = if simulator.channel_type == ChannelType::FeedIn {
+            -spot_per_kwh
+        } else {
+            cents(spot_per_kwh + simulator.network_per_kwh)
+        };
+
+        // Renewables peak at midday with solar generation
+        let hours = f64::from(midpoint.hour()) + fraction;
+        let solar = (1.0 - (hours - 12.5).abs() / 6.0).max(0.0);
+        let renewables = cents((25.0 + 55.0 * solar + self.rng.range(-5.0, 5.0)).clamp(0.0, 100.0));
+
+        Some(Interval::ActualInterval(ActualInterval {
+            base: BaseInterval {
+                duration: simulator.resolution.into(),
+                spot_per_kwh,
+                per_kwh,
+                date: start_time.to_zoned(nem_time_zone()).date(),
+                nem_time: end_time,
+                start_time,
+                end_time,
+                renewables,
+                channel_type: simulator.channel_type.clone(),
+                tariff_information: None,
+                spike_status: spike_status(spot_per_kwh),
+                descriptor: price_descriptor(spot_per_kwh),
+            },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Returns a simulator starting at midnight on 1 January 2025.
+    fn simulator() -> Simulator {
+        Simulator::builder()
+            .start(
+                "2025-01-01T00:00:00+10:00"
+                    .parse()
+                    .expect("valid timestamp"),
+            )
+            .seed(7)
+            .build()
+    }
+
+    #[test]
+    fn follows_the_duck_curve() {
+        let simulator = Simulator {
+            noise: 0.0,
+            spike_probability: 0.0,
+            ..simulator()
+        };
+        let day: Vec<BaseInterval> = simulator
+            .intervals()
+            .take(48)
+            .filter_map(Interval::into_base_interval)
+            .collect();
+        assert_eq!(day.len(), 48);
+        let at = |index: usize| day.get(index).expect("interval of the day");
+
+        // Intervals are contiguous and aligned to NEM time
+        assert_eq!(
+            at(0).start_time,
+            "2024-12-31T13:30:01Z".parse::<Timestamp>().expect("valid")
+        );
+        assert!(day.windows(2).all(
+            |pair| matches!(pair, [a, b] if b.start_time.as_second() == a.end_time.as_second() + 1)
+        ));
+
+        // Negative solar sponge at midday, highest prices in the evening
+        let midday = at(24);
+        let evening = at(36);
+        assert!(midday.spot_per_kwh < 0.0_f64, "{midday:?}");
+        assert_eq!(midday.descriptor, PriceDescriptor::ExtremelyLow);
+        assert!(evening.spot_per_kwh > 20.0_f64, "{evening:?}");
+        assert!(midday.renewables > evening.renewables);
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let first: Vec<Interval> = simulator().intervals().take(500).collect();
+        let second: Vec<Interval> = simulator().intervals().take(500).collect();
+        assert_eq!(first, second);
+
+        let spiky = Simulator {
+            spike_probability: 0.1,
+            ..simulator()
+        };
+        assert!(
+            spiky
+                .intervals()
+                .take(500)
+                .any(|interval| interval.descriptor() == Some(&PriceDescriptor::Spike))
+        );
+    }
+
+    #[test]
+    fn mimics_current_prices() {
+        let simulator = simulator();
+        let now = "2025-01-02T18:12:00+10:00"
+            .parse()
+            .expect("valid timestamp");
+        let prices = simulator.current_prices(now, 2, 3);
+        assert_eq!(prices.len(), 6);
+        let kinds: Vec<&str> = prices
+            .iter()
+            .map(|interval| match interval {
+                Interval::ActualInterval(_) => "actual",
+                Interval::CurrentInterval(_) => "current",
+                Interval::ForecastInterval(_) => "forecast",
+                Interval::Unknown(_) => "unknown",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "actual", "actual", "current", "forecast", "forecast", "forecast"
+            ]
+        );
+        let current = prices
+            .iter()
+            .find_map(Interval::as_current_interval)
+            .expect("current interval");
+        assert!(current.start_time <= now && now <= current.end_time);
+    }
+}
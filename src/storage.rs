@@ -0,0 +1,257 @@
+//! # Storage Formats
+//!
+//! Data archived for later use, such as
+//! [`ForecastSnapshot`](crate::history::ForecastSnapshot)s and
+//! [`IntervalHistory`](crate::history::IntervalHistory)s, can be encoded in
+//! one of several [`Format`]s:
+//!
+//! - JSON, which is always available and easy to inspect.
+//! - CBOR, enabled by the `cbor` feature.
+//! - MessagePack, enabled by the `msgpack` feature.
+//!
+//! The binary formats take roughly half the space of JSON, which adds up for
+//! year-long archives of 5-minute intervals on small devices.
+//!
+//! ```
+//! use amber_api::history::IntervalHistory;
+//! use amber_api::storage::Format;
+//!
+//! # fn main() -> amber_api::Result<()> {
+//! let history = IntervalHistory::new();
+//! let bytes = Format::Json.encode(&history)?;
+//! let restored: IntervalHistory = Format::Json.decode(&bytes)?;
+//! assert_eq!(restored, history);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::{borrow::ToOwned as _, string::ToString as _, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::error::{AmberError, Result};
+
+/// Encoding used to store data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    /// JSON.
+    #[default]
+    Json,
+    /// CBOR ([RFC 8949](https://www.rfc-editor.org/rfc/rfc8949)).
+    #[cfg(feature = "cbor")]
+    Cbor,
+    /// [MessagePack](https://msgpack.org).
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+}
+
+impl Format {
+    /// Encode a value in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be represented in this format.
+    #[inline]
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => serde_json::to_vec(value).map_err(|err| self.error(err)),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|err| self.error(err))?;
+                Ok(bytes)
+            }
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => rmp_serde::to_vec_named(value).map_err(|err| self.error(err)),
+        }
+    }
+
+    /// Decode a value from this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is malformed or does not match the
+    /// expected type.
+    #[inline]
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T> {
+        match self {
+            Self::Json => serde_json::from_slice(bytes).map_err(|err| self.error(err)),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => ciborium::from_reader(bytes).map_err(|err| self.error(err)),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => rmp_serde::from_slice(bytes).map_err(|err| self.error(err)),
+        }
+    }
+
+    /// Returns the conventional file extension for this format.
+    #[must_use]
+    #[inline]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            #[cfg(feature = "cbor")]
+            Self::Cbor => "cbor",
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => "msgpack",
+        }
+    }
+
+    /// Returns the media type of this format.
+    #[must_use]
+    #[inline]
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            #[cfg(feature = "cbor")]
+            Self::Cbor => "application/cbor",
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => "application/msgpack",
+        }
+    }
+
+    /// Returns the format matching a media type, such as the value of a
+    /// `Content-Type` header.
+    ///
+    /// Parameters (such as `charset`) are ignored, and the comparison is
+    /// case-insensitive. Returns `None` if the media type is not recognised or
+    /// the corresponding feature is disabled.
+    #[must_use]
+    #[inline]
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match essence.as_str() {
+            "application/json" => Some(Self::Json),
+            #[cfg(feature = "cbor")]
+            "application/cbor" => Some(Self::Cbor),
+            #[cfg(feature = "msgpack")]
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(Self::MessagePack)
+            }
+            _ => None,
+        }
+    }
+
+    /// Wrap an encoding error.
+    fn error(self, err: impl fmt::Display) -> AmberError {
+        AmberError::Encoding {
+            format: self,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "JSON"),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => write!(f, "CBOR"),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => write!(f, "MessagePack"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = AmberError;
+
+    /// Parse a format from its name or file extension, ignoring case.
+    #[inline]
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            #[cfg(feature = "cbor")]
+            "cbor" => Ok(Self::Cbor),
+            #[cfg(feature = "msgpack")]
+            "msgpack" | "messagepack" => Ok(Self::MessagePack),
+            _ => Err(AmberError::UnsupportedFormat(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::history::ForecastSnapshot;
+
+    /// A snapshot containing a single current interval.
+    fn snapshot() -> ForecastSnapshot {
+        let interval = crate::test_fixtures::interval(serde_json::json!({
+            "type": "CurrentInterval",
+            "duration": 5_i32,
+            "spotPerKwh": 6.12_f64,
+            "perKwh": 24.33_f64,
+            "startTime": "2021-05-05T02:25:01Z",
+            "tariffInformation": {"period": "peak", "demandWindow": true},
+            "range": {"min": 1.5_f64, "max": 30_i32},
+            "estimate": true
+        }));
+        ForecastSnapshot::new(
+            "01F5A5CRKMZ5BCX9P1S4V990AM".parse().expect("valid site ID"),
+            "2021-05-05T02:26:00Z".parse().expect("valid timestamp"),
+            &[interval],
+        )
+    }
+
+    /// Encode and decode the sample snapshot in the given format.
+    fn round_trip(format: Format) {
+        let original = snapshot();
+        let bytes = format.encode(&original).expect("encodes");
+        let decoded: ForecastSnapshot = format.decode(&bytes).expect("decodes");
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn json() {
+        round_trip(Format::Json);
+        Format::Json
+            .decode::<ForecastSnapshot>(b"{")
+            .expect_err("truncated body");
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn cbor() {
+        round_trip(Format::Cbor);
+        let json = Format::Json.encode(&snapshot()).expect("encodes");
+        let cbor = Format::Cbor.encode(&snapshot()).expect("encodes");
+        assert!(cbor.len() < json.len());
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack() {
+        round_trip(Format::MessagePack);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("JSON".parse::<Format>().ok(), Some(Format::Json));
+        "yaml".parse::<Format>().expect_err("unknown format");
+        assert_eq!(Format::default().extension(), "json");
+    }
+
+    #[test]
+    fn media_types() {
+        assert_eq!(
+            Format::from_media_type("Application/JSON; charset=utf-8"),
+            Some(Format::Json)
+        );
+        assert_eq!(Format::from_media_type("text/html"), None);
+        assert_eq!(Format::from_media_type(""), None);
+        assert_eq!(
+            Format::from_media_type(Format::Json.media_type()),
+            Some(Format::Json)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! # Test Fixtures
+//!
+//! Factories for the models used throughout the unit tests. Each starts from
+//! a typical 30-minute general interval on 2021-05-05 and replaces the fields
+//! given, so a test only spells out what it varies.
+
+use serde_json::{Value, json};
+
+use crate::models::{Interval, Usage};
+
+/// Returns the fields shared by every interval.
+fn base() -> Value {
+    json!({
+        "duration": 30,
+        "spotPerKwh": 6.0,
+        "perKwh": 24.0,
+        "date": "2021-05-05",
+        "nemTime": "2021-05-05T12:30:00+10:00",
+        "startTime": "2021-05-05T02:00:00Z",
+        "endTime": "2021-05-05T02:30:00Z",
+        "renewables": 45.0,
+        "channelType": "general",
+        "spikeStatus": "none",
+        "descriptor": "low"
+    })
+}
+
+/// Replace the fields of `value` with those of `fields`.
+fn merge(mut value: Value, fields: Value) -> Value {
+    if let (Some(object), Value::Object(extra)) = (value.as_object_mut(), fields) {
+        object.extend(extra);
+    }
+    value
+}
+
+/// Parse an interval, an `ActualInterval` unless `fields` sets the `type`.
+pub(crate) fn interval(fields: Value) -> Interval {
+    let value = merge(base(), json!({ "type": "ActualInterval" }));
+    serde_json::from_value(merge(value, fields)).expect("valid interval")
+}
+
+/// Parse a usage record of 1 kWh on channel `E1`.
+pub(crate) fn usage(fields: Value) -> Usage {
+    let value = merge(
+        base(),
+        json!({
+            "type": "Usage",
+            "channelIdentifier": "E1",
+            "kwh": 1.0_f64,
+            "quality": "billable",
+            "cost": 24.0_f64
+        }),
+    );
+    serde_json::from_value(merge(value, fields)).expect("valid usage")
+}
@@ -0,0 +1,300 @@
+//! # Units
+//!
+//! Energy, power and prices are all plain `f64`s in the API, which makes it
+//! easy to multiply a price by a power instead of an energy. The newtypes in
+//! this module carry their unit in the type: [`KilowattHours`],
+//! [`Kilowatts`] and [`CentsPerKwh`]. Quantities of the same unit can be added
+//! and scaled, and only meaningful combinations of units are defined, such as
+//! an energy multiplied by a price giving a cost in cents.
+//!
+//! Models and analyses expose typed accessors alongside their raw fields,
+//! such as [`Usage::energy()`](crate::models::Usage::energy) and
+//! [`BaseInterval::price()`](crate::models::BaseInterval::price).
+//!
+//! ```
+//! use core::time::Duration;
+//!
+//! use amber_api::units::{CentsPerKwh, Kilowatts};
+//!
+//! let energy = Kilowatts::new(7.2).energy_over(Duration::from_secs(30 * 60));
+//! let cost = energy * CentsPerKwh::new(25.0);
+//! assert_eq!(format!("{energy:.1}"), "3.6 kWh");
+//! assert_eq!(cost, 90.0);
+//! ```
+
+use core::{fmt, iter::Sum, ops, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::precision::{DisplayPrecision, PrecisionPolicy};
+
+/// Number of seconds in an hour.
+const SECONDS_PER_HOUR: f64 = 3600.0;
+
+/// Define a quantity with a unit.
+macro_rules! unit {
+    ($(#[$meta:meta])* $name:ident, $suffix:literal, $policy:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(f64);
+
+        impl $name {
+            /// Zero of the unit.
+            pub const ZERO: Self = Self(0.0);
+
+            /// Create a quantity from its value in this unit.
+            #[must_use]
+            #[inline]
+            pub const fn new(value: f64) -> Self {
+                Self(value)
+            }
+
+            /// Returns the value in this unit.
+            #[must_use]
+            #[inline]
+            pub const fn value(self) -> f64 {
+                self.0
+            }
+
+            /// Returns the absolute quantity.
+            #[must_use]
+            #[inline]
+            pub fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+        }
+
+        impl From<$name> for f64 {
+            #[inline]
+            fn from(quantity: $name) -> Self {
+                quantity.0
+            }
+        }
+
+        impl ops::Add for $name {
+            type Output = Self;
+
+            #[inline]
+            #[expect(clippy::float_arithmetic, reason = "Adding quantities")]
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl ops::AddAssign for $name {
+            #[inline]
+            #[expect(clippy::float_arithmetic, reason = "Adding quantities")]
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl ops::Sub for $name {
+            type Output = Self;
+
+            #[inline]
+            #[expect(clippy::float_arithmetic, reason = "Subtracting quantities")]
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl ops::SubAssign for $name {
+            #[inline]
+            #[expect(clippy::float_arithmetic, reason = "Subtracting quantities")]
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl ops::Neg for $name {
+            type Output = Self;
+
+            #[inline]
+            #[expect(clippy::float_arithmetic, reason = "Negating a quantity")]
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl ops::Mul<f64> for $name {
+            type Output = Self;
+
+            #[inline]
+            #[expect(clippy::float_arithmetic, reason = "Scaling a quantity")]
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl ops::Div<f64> for $name {
+            type Output = Self;
+
+            #[inline]
+            #[expect(clippy::float_arithmetic, reason = "Scaling a quantity")]
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        impl Sum for $name {
+            #[inline]
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(Self::value).sum())
+            }
+        }
+
+        impl<'a> Sum<&'a Self> for $name {
+            #[inline]
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+
+        impl fmt::Display for $name {
+            /// Renders the value followed by the unit, honouring the
+            /// formatter's precision.
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)?;
+                f.write_str($suffix)
+            }
+        }
+
+        impl DisplayPrecision for $name {
+            #[inline]
+            fn fmt_precision(
+                &self,
+                f: &mut fmt::Formatter<'_>,
+                policy: &PrecisionPolicy,
+            ) -> fmt::Result {
+                write!(f, "{}{}", policy.$policy(self.0), $suffix)
+            }
+        }
+    };
+}
+
+unit!(
+    /// An amount of energy, in kilowatt-hours.
+    KilowattHours,
+    " kWh",
+    energy
+);
+
+unit!(
+    /// A power, in kilowatts.
+    Kilowatts,
+    " kW",
+    power
+);
+
+unit!(
+    /// A price of energy, in cents per kilowatt-hour.
+    CentsPerKwh,
+    "c/kWh",
+    price
+);
+
+impl KilowattHours {
+    /// Returns the average power needed to use the energy over a duration,
+    /// or `None` if the duration is zero.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Converting energy to power")]
+    pub fn average_power(self, duration: Duration) -> Option<Kilowatts> {
+        let hours = duration.as_secs_f64() / SECONDS_PER_HOUR;
+        (hours > 0.0).then(|| Kilowatts(self.0 / hours))
+    }
+}
+
+impl Kilowatts {
+    /// Returns the energy used at this power over a duration.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Converting power to energy")]
+    pub fn energy_over(self, duration: Duration) -> KilowattHours {
+        KilowattHours(self.0 * duration.as_secs_f64() / SECONDS_PER_HOUR)
+    }
+}
+
+impl CentsPerKwh {
+    /// Returns the average price paid for an energy at a cost in cents, or
+    /// `None` if the energy is zero.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Averaging a price")]
+    pub fn from_cost(cost: f64, energy: KilowattHours) -> Option<Self> {
+        (energy.0 != 0.0_f64).then(|| Self(cost / energy.0))
+    }
+}
+
+impl ops::Mul<CentsPerKwh> for KilowattHours {
+    /// Cost of the energy, in cents.
+    type Output = f64;
+
+    #[inline]
+    #[expect(clippy::float_arithmetic, reason = "Computing a cost")]
+    fn mul(self, rhs: CentsPerKwh) -> f64 {
+        self.0 * rhs.0
+    }
+}
+
+impl ops::Mul<KilowattHours> for CentsPerKwh {
+    /// Cost of the energy, in cents.
+    type Output = f64;
+
+    #[inline]
+    fn mul(self, rhs: KilowattHours) -> f64 {
+        rhs * self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString as _};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let half_hour = Duration::from_mins(30);
+        let energy = Kilowatts::new(3.0).energy_over(half_hour);
+        assert_eq!(energy, KilowattHours::new(1.5));
+        assert_eq!(energy.average_power(half_hour), Some(Kilowatts::new(3.0)));
+        assert_eq!(energy.average_power(Duration::ZERO), None);
+        assert!((CentsPerKwh::new(20.0) * energy - 30.0_f64).abs() < f64::EPSILON);
+        assert_eq!(
+            CentsPerKwh::from_cost(30.0, energy),
+            Some(CentsPerKwh::new(20.0))
+        );
+        assert_eq!(CentsPerKwh::from_cost(30.0, KilowattHours::ZERO), None);
+
+        let total: KilowattHours = [energy, -energy * 2.0_f64].into_iter().sum();
+        assert_eq!(total, KilowattHours::new(-1.5));
+        assert_eq!(total.abs(), KilowattHours::new(1.5));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(KilowattHours::new(1.5).to_string(), "1.5 kWh");
+        assert_eq!(format!("{:.2}", Kilowatts::new(7.2)), "7.20 kW");
+        assert_eq!(
+            CentsPerKwh::new(23.456)
+                .display_with(&PrecisionPolicy::DEFAULT)
+                .to_string(),
+            "23.46c/kWh"
+        );
+        let policy = PrecisionPolicy::DEFAULT.with_power(Some(1));
+        assert_eq!(
+            Kilowatts::new(7.25).display_with(&policy).to_string(),
+            "7.2 kW"
+        );
+        assert_eq!(
+            KilowattHours::new(7.25).display_with(&policy).to_string(),
+            "7.25 kWh"
+        );
+    }
+}
@@ -55,7 +55,9 @@ async fn current_renewables_default(amber_client: Amber) -> Result<()> {
         .first()
         .ok_or_else(|| anyhow!("Expect at least one entry"))?;
     assert!(entry.is_current_renewable());
-    let base = entry.as_base_renewable();
+    let base = entry
+        .as_base_renewable()
+        .ok_or_else(|| anyhow!("Expected a known renewable type"))?;
     assert!(base.start_time < base.end_time);
     assert_eq!(base.duration, 30);
 
@@ -95,7 +97,9 @@ async fn current_renewables_optional(amber_client: Amber) -> Result<()> {
     assert_eq!(forecast_count, 3);
 
     for entry in renewables {
-        let base = entry.as_base_renewable();
+        let base = entry
+            .as_base_renewable()
+            .ok_or_else(|| anyhow!("Expected a known renewable type"))?;
         assert!(base.start_time < base.end_time);
         assert_eq!(base.duration, 5);
     }
@@ -124,9 +128,9 @@ async fn sites_retrieval(amber_client: Amber) -> Result<()> {
 
     // Test the Display implementation
     let display_string = format!("{site}");
-    assert!(display_string.contains(&site.id));
-    assert!(display_string.contains(&site.nmi));
-    assert!(display_string.contains(&site.network));
+    assert!(display_string.contains(site.id.as_str()));
+    assert!(display_string.contains(site.nmi.as_str()));
+    assert!(display_string.contains(site.network.as_str()));
 
     Ok(())
 }
@@ -135,7 +139,7 @@ async fn sites_retrieval(amber_client: Amber) -> Result<()> {
 ///
 /// This is required for additional tests.
 #[fixture]
-async fn site_id(amber_client: Amber) -> String {
+async fn site_id(amber_client: Amber) -> models::SiteId {
     amber_client
         .sites()
         .await
@@ -149,7 +153,7 @@ async fn site_id(amber_client: Amber) -> String {
 /// Test the `prices()` method to ensure it works with default arguments.
 #[rstest]
 #[tokio::test]
-async fn prices_default(amber_client: Amber, #[future] site_id: String) -> Result<()> {
+async fn prices_default(amber_client: Amber, #[future] site_id: models::SiteId) -> Result<()> {
     let site_id = site_id.await;
     let intervals = amber_client.prices().site_id(&site_id).call().await?;
 
@@ -170,7 +174,7 @@ async fn prices_default(amber_client: Amber, #[future] site_id: String) -> Resul
 #[tokio::test]
 async fn prices_optional(
     amber_client: Amber,
-    #[future] site_id: String,
+    #[future] site_id: models::SiteId,
     seven_days_ago: jiff::civil::Date,
 ) -> Result<()> {
     let site_id = site_id.await;
@@ -211,7 +215,10 @@ async fn prices_optional(
 /// arguments.
 #[rstest]
 #[tokio::test]
-async fn current_prices_default(amber_client: Amber, #[future] site_id: String) -> Result<()> {
+async fn current_prices_default(
+    amber_client: Amber,
+    #[future] site_id: models::SiteId,
+) -> Result<()> {
     let site_id = site_id.await;
     let intervals = amber_client
         .current_prices()
@@ -241,7 +248,10 @@ async fn current_prices_default(amber_client: Amber, #[future] site_id: String)
 /// arguments.
 #[rstest]
 #[tokio::test]
-async fn current_prices_optional(amber_client: Amber, #[future] site_id: String) -> Result<()> {
+async fn current_prices_optional(
+    amber_client: Amber,
+    #[future] site_id: models::SiteId,
+) -> Result<()> {
     let site_id = site_id.await;
     let intervals = amber_client
         .current_prices()
@@ -285,7 +295,7 @@ async fn current_prices_optional(amber_client: Amber, #[future] site_id: String)
 #[tokio::test]
 async fn usage_default(
     amber_client: Amber,
-    #[future] site_id: String,
+    #[future] site_id: models::SiteId,
     seven_days_ago: jiff::civil::Date,
 ) -> Result<()> {
     let site_id = site_id.await;
@@ -321,7 +331,7 @@ async fn usage_default(
 #[tokio::test]
 async fn usage_multi_day(
     amber_client: Amber,
-    #[future] site_id: String,
+    #[future] site_id: models::SiteId,
     seven_days_ago: jiff::civil::Date,
 ) -> Result<()> {
     let site_id = site_id.await;